@@ -476,6 +476,8 @@ impl TempCatalog {
                 },
                 options: TableOptionsInternal {
                     columns: columns.clone(),
+                    partition_by: Vec::new(),
+                    view_sql: None,
                 }
                 .into(),
                 tunnel_id: None,
@@ -521,6 +523,8 @@ impl TempCatalog {
                 },
                 options: TableOptionsInternal {
                     columns: Vec::new(),
+                    partition_by: Vec::new(),
+                    view_sql: None,
                 }
                 .into(),
                 tunnel_id: None,
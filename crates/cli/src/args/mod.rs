@@ -38,6 +38,14 @@ pub struct MetastoreArgs {
     /// store).
     #[clap(short = 'f', long, value_parser)]
     pub local_file_path: Option<PathBuf>,
+
+    /// Path to a file containing a hex encoded 256 bit key used to encrypt
+    /// database catalogs.
+    ///
+    /// Previous keys may follow on separate lines to read catalogs written
+    /// before the key was rotated.
+    #[clap(long, value_parser)]
+    pub encryption_key_file: Option<PathBuf>,
 }
 
 #[derive(Parser)]
@@ -1,4 +1,5 @@
 use clap::Args;
+use datasources::common::secrets::SecretRule;
 
 use crate::args::{PathBuf, StorageConfigArgs};
 
@@ -58,6 +59,32 @@ pub struct ServerArgs {
     #[arg(long, value_parser)]
     pub spill_path: Option<PathBuf>,
 
+    /// Directory client certificates and keys for data sources can be read
+    /// from.
+    ///
+    /// Paths provided in the `ca_cert`, `client_cert` and `client_key`
+    /// options are resolved relative to this directory, and files outside of
+    /// it can't be read. If unset, these options can't be used.
+    #[arg(long, value_parser)]
+    pub tls_cert_dir: Option<PathBuf>,
+
+    /// File containing the passphrase to encrypt SSH tunnel private keys
+    /// stored in the catalog with.
+    ///
+    /// Private keys provided by users when creating tunnels can only be used
+    /// if set.
+    #[arg(long, value_parser)]
+    pub tunnel_key_passphrase_file: Option<PathBuf>,
+
+    /// Secrets that can be referenced in options, as `<store>:<pattern>`.
+    ///
+    /// The store is one of `env`, `file` or `vault`, and the pattern is a glob
+    /// matched against the variable name, absolute file path or Vault secret
+    /// path, e.g. `env:GLAREDB_*` or `file:/run/secrets/*`. May be provided
+    /// multiple times. If unset, secret references can't be used.
+    #[arg(long = "allow-secret", value_parser)]
+    pub allow_secrets: Vec<SecretRule>,
+
     /// Ignore authentication messages.
     ///
     /// (Internal)
@@ -6,12 +6,23 @@ use std::sync::Arc;
 use anyhow::{anyhow, Result};
 use atty::Stream;
 use clap::Subcommand;
+use datasources::common::secrets::{set_secret_access, SecretAccess};
+use datasources::common::ssh::key::set_catalog_key_passphrase;
+use datasources::common::tls::{set_cert_file_access, CertFileAccess};
 use ioutil::ensure_dir;
 use object_store_util::conf::StorageConfig;
+use object_store_util::encryption::local::LocalKeyProvider;
+use object_store_util::encryption::KeyProvider;
 use pgsrv::auth::{LocalAuthenticator, PasswordlessAuthenticator, SingleUserAuthenticator};
 use slt::discovery::SltDiscovery;
-use slt::hooks::{AllTestsHook, IcebergFormatVersionHook, SqliteTestsHook, SshTunnelHook};
-use slt::tests::{PgBinaryEncoding, SshKeysTest};
+use slt::hooks::{
+    AllTestsHook,
+    DuckDbTestsHook,
+    IcebergFormatVersionHook,
+    SqliteTestsHook,
+    SshTunnelHook,
+};
+use slt::tests::{ParquetPruningMetrics, PgBinaryEncoding, SshKeysTest};
 use tokio::net::TcpListener;
 use tokio::runtime::{Builder, Runtime};
 use tracing::info;
@@ -145,6 +156,9 @@ impl RunCommand for ServerArgs {
             service_account_path,
             storage_config,
             spill_path,
+            tls_cert_dir,
+            tunnel_key_passphrase_file,
+            allow_secrets,
             ignore_pg_auth,
             disable_rpc_auth,
             segment_key,
@@ -153,6 +167,26 @@ impl RunCommand for ServerArgs {
             disable_postgres_api,
         } = self;
 
+        // Options are provided by any user over SQL, so files can't be read
+        // from arbitrary locations.
+        set_cert_file_access(match tls_cert_dir {
+            Some(dir) => CertFileAccess::Directory(dir),
+            None => CertFileAccess::Disabled,
+        })?;
+
+        // Same for secrets referenced in options, only the configured ones
+        // can be read.
+        set_secret_access(SecretAccess::Allowlist(allow_secrets))?;
+
+        if let Some(path) = tunnel_key_passphrase_file {
+            let passphrase = std::fs::read_to_string(path)?;
+            let passphrase = passphrase.trim();
+            if passphrase.is_empty() {
+                return Err(anyhow!("tunnel key passphrase file is empty"));
+            }
+            set_catalog_key_passphrase(passphrase.to_string())?;
+        }
+
         // Map an empty string to None. Makes writing the terraform easier.
         let segment_key = segment_key.and_then(|s| if s.is_empty() { None } else { Some(s) });
 
@@ -254,6 +288,7 @@ impl RunCommand for MetastoreArgs {
             bucket,
             service_account_path,
             local_file_path,
+            encryption_key_file,
         } = self;
         let conf = match (bucket, service_account_path, local_file_path) {
             (Some(bucket), Some(service_account_path), None) => {
@@ -281,7 +316,12 @@ impl RunCommand for MetastoreArgs {
 
         runtime.block_on(async move {
             let store = conf.new_object_store()?;
-            let metastore = Metastore::new(store)?;
+            let key_provider = encryption_key_file
+                .map(|path| {
+                    LocalKeyProvider::from_file(path).map(|p| Arc::new(p) as Arc<dyn KeyProvider>)
+                })
+                .transpose()?;
+            let metastore = Metastore::new(store, key_provider)?;
             metastore.serve(addr).await
         })
     }
@@ -294,10 +334,16 @@ impl RunCommand for SltArgs {
             // Rust tests
             .test("sqllogictests/ssh_keys", Box::new(SshKeysTest))?
             .test("pgproto/binary_encoding", Box::new(PgBinaryEncoding))?
+            .test(
+                "sqllogictests/parquet_pruning_metrics",
+                Box::new(ParquetPruningMetrics),
+            )?
             // Add hooks
             .hook("*", Arc::new(AllTestsHook))?
             // Sqlite tests
             .hook("sqllogictests_sqlite/*", Arc::new(SqliteTestsHook))?
+            // DuckDB tests
+            .hook("sqllogictests_duckdb/*", Arc::new(DuckDbTestsHook))?
             // Iceberg format version tests
             .hook(
                 "sqllogictests_iceberg/local_v1",
@@ -4,6 +4,7 @@ use std::sync::Arc;
 use anyhow::Result;
 use metastore::srv::Service;
 use object_store::ObjectStore;
+use object_store_util::encryption::KeyProvider;
 use protogen::gen::metastore::service::metastore_service_server::MetastoreServiceServer;
 use tonic::transport::Server;
 use tracing::{debug_span, info};
@@ -13,9 +14,12 @@ pub struct Metastore {
 }
 
 impl Metastore {
-    pub fn new(store: Arc<dyn ObjectStore>) -> Result<Self> {
+    pub fn new(
+        store: Arc<dyn ObjectStore>,
+        key_provider: Option<Arc<dyn KeyProvider>>,
+    ) -> Result<Self> {
         Ok(Metastore {
-            service: Service::new(store),
+            service: Service::new(store, key_provider),
         })
     }
 
@@ -13,6 +13,7 @@ use datafusion::execution::TaskContext;
 use datafusion::physical_expr::{PhysicalSortExpr, PhysicalSortRequirement};
 use datafusion::physical_plan::metrics::{
     BaselineMetrics,
+    Count,
     ExecutionPlanMetricsSet,
     Gauge,
     MetricBuilder,
@@ -31,11 +32,28 @@ use futures::{Stream, StreamExt};
 
 const BYTES_READ_GAUGE_NAME: &str = "bytes_read";
 const BYTES_WRITTEN_GAUGE_NAME: &str = "bytes_written";
+const ROW_GROUPS_PRUNED_COUNT_NAME: &str = "row_groups_pruned";
+const PAGES_PRUNED_COUNT_NAME: &str = "pages_pruned";
+
+/// Metrics reported by datafusion's parquet exec for row groups that were
+/// skipped using either row group statistics or bloom filters.
+const PARQUET_ROW_GROUPS_PRUNED_METRICS: &[&str] = &[
+    "row_groups_pruned_statistics",
+    "row_groups_pruned_bloom_filter",
+];
+
+/// Metric recorded on parquet execs for pages skipped using the page index.
+///
+/// Datafusion only reports the number of rows skipped, so this is recorded by
+/// the file reader used for parquet scans.
+pub const PARQUET_PAGES_PRUNED_METRIC: &str = "page_index_pages_pruned";
 
 #[derive(Debug, Default)]
 pub struct DataSourceMetricsOpts {
     pub track_reads: bool,
     pub track_writes: bool,
+    /// Track the number of row groups and pages skipped by the source.
+    pub track_pruning: bool,
 }
 
 impl DataSourceMetricsOpts {
@@ -43,6 +61,7 @@ impl DataSourceMetricsOpts {
         Self {
             track_reads: true,
             track_writes: true,
+            track_pruning: false,
         }
     }
 
@@ -50,6 +69,7 @@ impl DataSourceMetricsOpts {
         Self {
             track_reads: true,
             track_writes: false,
+            track_pruning: true,
         }
     }
 
@@ -57,10 +77,21 @@ impl DataSourceMetricsOpts {
         Self {
             track_reads: false,
             track_writes: true,
+            track_pruning: false,
         }
     }
 }
 
+/// Counters for data that a source was able to skip reading entirely.
+#[derive(Debug, Clone)]
+struct PruningMetrics {
+    /// Number of row groups (or equivalent unit of the source) skipped.
+    row_groups_pruned: Count,
+
+    /// Number of pages skipped using page indexes.
+    pages_pruned: Count,
+}
+
 /// Standard metrics we should be collecting for all data sources during
 /// queries.
 #[derive(Debug, Clone)]
@@ -71,6 +102,9 @@ struct DataSourceMetrics {
     /// Track bytes written by the plan.
     bytes_written: Option<Gauge>,
 
+    /// Track row groups and pages pruned by source plans.
+    pruning: Option<PruningMetrics>,
+
     /// Baseline metrics like output rows and elapsed time.
     baseline: BaselineMetrics,
 }
@@ -95,13 +129,64 @@ impl DataSourceMetrics {
             None
         };
 
+        let pruning = if opts.track_pruning {
+            Some(PruningMetrics {
+                row_groups_pruned: MetricBuilder::new(metrics)
+                    .counter(ROW_GROUPS_PRUNED_COUNT_NAME, partition),
+                pages_pruned: MetricBuilder::new(metrics)
+                    .counter(PAGES_PRUNED_COUNT_NAME, partition),
+            })
+        } else {
+            None
+        };
+
         Self {
             bytes_read,
             bytes_written,
+            pruning,
             baseline,
         }
     }
 
+    /// Record row groups and pages that were skipped by the source.
+    fn record_pruned(&self, row_groups: usize, pages: usize) {
+        if let Some(pruning) = self.pruning.as_ref() {
+            pruning.row_groups_pruned.add(row_groups);
+            pruning.pages_pruned.add(pages);
+        }
+    }
+
+    /// Record pruning reported by a wrapped plan that we can't modify to
+    /// record metrics directly (e.g. parquet).
+    ///
+    /// Should only be called once the partition has finished executing.
+    fn record_pruned_from_plan(&self, plan: &dyn ExecutionPlan, partition: usize) {
+        if self.pruning.is_none() {
+            return;
+        }
+
+        let metrics = match plan.metrics() {
+            Some(metrics) => metrics,
+            None => return,
+        };
+
+        let mut row_groups = 0;
+        let mut pages = 0;
+        for metric in metrics
+            .iter()
+            .filter(|metric| metric.partition() == Some(partition))
+        {
+            let value = metric.value();
+            if PARQUET_ROW_GROUPS_PRUNED_METRICS.contains(&value.name()) {
+                row_groups += value.as_usize();
+            } else if value.name() == PARQUET_PAGES_PRUNED_METRIC {
+                pages += value.as_usize();
+            }
+        }
+
+        self.record_pruned(row_groups, pages);
+    }
+
     /// Track metrics based on the poll result from an async stream.
     pub fn record_poll(
         &self,
@@ -149,6 +234,15 @@ impl<S> DataSourceMetricsStreamAdapter<S> {
             metrics: DataSourceMetrics::new(partition, metrics, DataSourceMetricsOpts::read_only()),
         }
     }
+
+    /// Record the number of row groups and pages the source skipped reading
+    /// for this partition.
+    ///
+    /// Sources should call this whenever they're able to prune part of the
+    /// data using statistics, indexes or filters.
+    pub fn record_pruned(&self, row_groups: usize, pages: usize) {
+        self.metrics.record_pruned(row_groups, pages)
+    }
 }
 
 impl<S: RecordBatchStream + Unpin> Stream for DataSourceMetricsStreamAdapter<S> {
@@ -334,12 +428,15 @@ impl ExecutionPlan for ReadOnlyDataSourceMetricsExecAdapter {
         context: Arc<TaskContext>,
     ) -> Result<SendableRecordBatchStream> {
         let stream = self.child.execute(partition, context)?;
-        Ok(Box::pin(BoxedStreamAdapater::new(
-            stream,
-            partition,
-            &self.metrics,
-            ReadOnlyDataSourceMetricsOptsType::OPTS,
-        )))
+        Ok(Box::pin(
+            BoxedStreamAdapater::new(
+                stream,
+                partition,
+                &self.metrics,
+                ReadOnlyDataSourceMetricsOptsType::OPTS,
+            )
+            .with_pruning_source(self.child.clone()),
+        ))
     }
 
     fn statistics(&self) -> Result<Statistics> {
@@ -360,6 +457,9 @@ impl<T: DataSourceMetricsOptsType> DisplayAs for DataSourceMetricsExecAdapter<T>
 struct BoxedStreamAdapater {
     stream: SendableRecordBatchStream,
     metrics: DataSourceMetrics,
+    partition: usize,
+    /// Plan to pull pruning metrics from once the stream completes.
+    pruning_source: Option<Arc<dyn ExecutionPlan>>,
 }
 
 impl BoxedStreamAdapater {
@@ -372,8 +472,15 @@ impl BoxedStreamAdapater {
         Self {
             stream,
             metrics: DataSourceMetrics::new(partition, metrics, opts),
+            partition,
+            pruning_source: None,
         }
     }
+
+    fn with_pruning_source(mut self, plan: Arc<dyn ExecutionPlan>) -> Self {
+        self.pruning_source = Some(plan);
+        self
+    }
 }
 
 impl Stream for BoxedStreamAdapater {
@@ -381,6 +488,13 @@ impl Stream for BoxedStreamAdapater {
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         let poll = self.stream.poll_next_unpin(cx);
+        if let Poll::Ready(None) = &poll {
+            // Take the plan so pruning is only recorded once.
+            if let Some(plan) = self.pruning_source.take() {
+                self.metrics
+                    .record_pruned_from_plan(plan.as_ref(), self.partition);
+            }
+        }
         self.metrics.record_poll(poll)
     }
 }
@@ -472,6 +586,10 @@ pub struct AggregatedMetrics {
     pub bytes_read: u64,
     /// Total bytes written.
     pub bytes_written: Option<u64>,
+    /// Total row groups skipped by data sources.
+    pub row_groups_pruned: u64,
+    /// Total pages skipped by data sources using page indexes.
+    pub pages_pruned: u64,
 }
 
 impl AggregatedMetrics {
@@ -484,6 +602,8 @@ impl AggregatedMetrics {
             elapsed_compute_ns: 0,
             bytes_read: 0,
             bytes_written: None,
+            row_groups_pruned: 0,
+            pages_pruned: 0,
         };
         agg.aggregate_recurse(plan);
         agg
@@ -496,6 +616,14 @@ impl AggregatedMetrics {
                 .sum_by_name(BYTES_READ_GAUGE_NAME)
                 .map(|m| m.as_usize() as u64)
                 .unwrap_or_default();
+            self.row_groups_pruned += metrics
+                .sum_by_name(ROW_GROUPS_PRUNED_COUNT_NAME)
+                .map(|m| m.as_usize() as u64)
+                .unwrap_or_default();
+            self.pages_pruned += metrics
+                .sum_by_name(PAGES_PRUNED_COUNT_NAME)
+                .map(|m| m.as_usize() as u64)
+                .unwrap_or_default();
 
             if self.bytes_written.is_none() {
                 // Only count bytes written if they were not counted before.
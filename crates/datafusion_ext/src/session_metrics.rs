@@ -61,6 +61,8 @@ impl SessionMetricsHandler {
                 "output_rows": metric.output_rows,
                 "bytes_read": metric.bytes_read,
                 "bytes_written": metric.bytes_written,
+                "row_groups_pruned": metric.row_groups_pruned,
+                "pages_pruned": metric.pages_pruned,
             }),
         );
     }
@@ -104,6 +106,10 @@ pub struct QueryMetrics {
     pub bytes_read: Option<u64>,
     /// Number of bytes written during the execution of write operation.
     pub bytes_written: Option<u64>,
+    /// Number of row groups data sources were able to skip.
+    pub row_groups_pruned: Option<u64>,
+    /// Number of pages data sources were able to skip using page indexes.
+    pub pages_pruned: Option<u64>,
 }
 
 impl Default for QueryMetrics {
@@ -117,6 +123,8 @@ impl Default for QueryMetrics {
             output_rows: None,
             bytes_read: None,
             bytes_written: None,
+            row_groups_pruned: None,
+            pages_pruned: None,
         }
     }
 }
@@ -159,6 +167,8 @@ impl BatchStreamWithMetricSender {
         metrics.bytes_read = Some(agg_metrics.bytes_read);
         metrics.bytes_written = agg_metrics.bytes_written;
         metrics.elapsed_compute_ns = Some(agg_metrics.elapsed_compute_ns);
+        metrics.row_groups_pruned = Some(agg_metrics.row_groups_pruned);
+        metrics.pages_pruned = Some(agg_metrics.pages_pruned);
     }
 }
 
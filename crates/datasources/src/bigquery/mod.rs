@@ -23,11 +23,12 @@ use datafusion::arrow::datatypes::{
     TimeUnit,
 };
 use datafusion::arrow::ipc::reader::StreamReader as ArrowStreamReader;
-use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::arrow::record_batch::{RecordBatch, RecordBatchOptions};
 use datafusion::datasource::TableProvider;
 use datafusion::error::{DataFusionError, Result as DatafusionResult};
 use datafusion::execution::context::{SessionState, TaskContext};
-use datafusion::logical_expr::{Expr, TableProviderFilterPushDown, TableType};
+use datafusion::logical_expr::expr::{Between, InList, Like};
+use datafusion::logical_expr::{Expr, Operator, TableProviderFilterPushDown, TableType};
 use datafusion::physical_expr::PhysicalSortExpr;
 use datafusion::physical_plan::memory::MemoryExec;
 use datafusion::physical_plan::metrics::{ExecutionPlanMetricsSet, MetricsSet};
@@ -40,6 +41,7 @@ use datafusion::physical_plan::{
     SendableRecordBatchStream,
     Statistics,
 };
+use datafusion::scalar::ScalarValue;
 use datafusion_ext::errors::ExtensionError;
 use datafusion_ext::functions::VirtualLister;
 use datafusion_ext::metrics::DataSourceMetricsStreamAdapter;
@@ -312,11 +314,24 @@ impl TableProvider for BigQueryTableProvider {
         };
 
         // Select fields based off of what's in our projected schema.
-        let selected: Vec<_> = projected_schema
-            .fields
-            .iter()
-            .map(|field| field.name().clone())
-            .collect();
+        //
+        // BigQuery returns every column if no fields are selected, so select
+        // the first column when we only care about the number of rows. The
+        // column is dropped when reading the stream.
+        let selected: Vec<_> = if projected_schema.fields().is_empty() {
+            self.arrow_schema
+                .fields()
+                .iter()
+                .take(1)
+                .map(|field| field.name().clone())
+                .collect()
+        } else {
+            projected_schema
+                .fields
+                .iter()
+                .map(|field| field.name().clone())
+                .collect()
+        };
         builder = builder.selected_fields(selected);
 
         let mut sess = builder
@@ -469,6 +484,7 @@ impl BufferedIpcStream {
         receiver: Receiver<BufferedArrowIpcReader>,
         partition: usize,
     ) -> Self {
+        let output_schema = schema.clone();
         let stream = stream! {
             let reader = match receiver.recv().await {
                 Ok(r) => r,
@@ -487,6 +503,12 @@ impl BufferedIpcStream {
             let reader = ArrowStreamReader::try_new(Cursor::new(buf), None)?;
             for batch in reader {
                 let batch = batch?;
+                if output_schema.fields().is_empty() {
+                    // Column was only selected for getting the row count.
+                    let options = RecordBatchOptions::new().with_row_count(Some(batch.num_rows()));
+                    yield Ok(RecordBatch::try_new_with_options(output_schema.clone(), Vec::new(), &options)?);
+                    continue;
+                }
                 let batch = util::normalize_batch(&batch)?;
                 yield Ok(batch);
             }
@@ -580,13 +602,15 @@ fn handle_nullable_fields(field: &BigQuerySchema) -> Result<Field> {
 
 /// Convert filtering expressions to a predicate string usable with BigQuery's
 /// row restriction.
+///
+/// Expressions that can't be converted are skipped, DataFusion will still
+/// apply them after reading.
 fn exprs_to_predicate_string(exprs: &[Expr]) -> Result<String> {
     let mut ss = Vec::new();
-    let mut buf = String::new();
     for expr in exprs {
+        let mut buf = String::new();
         if write_expr(expr, &mut buf)? {
             ss.push(buf);
-            buf = String::new();
         }
     }
 
@@ -594,50 +618,108 @@ fn exprs_to_predicate_string(exprs: &[Expr]) -> Result<String> {
 }
 
 /// Try to write the expression to the string, returning true if it was written.
+///
+/// The buffer may contain a partially written expression if this returns
+/// false.
 fn write_expr(expr: &Expr, buf: &mut String) -> Result<bool> {
     match expr {
         Expr::Column(col) => {
-            write!(buf, "{}", col)?;
-        }
-        Expr::Literal(val) => {
-            util::encode_literal_to_text(util::Datasource::BigQuery, buf, val)?;
+            write!(buf, "`{}`", col.name)?;
         }
-        Expr::IsNull(expr) => {
-            if write_expr(expr, buf)? {
-                write!(buf, " IS NULL")?;
-            } else {
+        Expr::Literal(val) => return write_literal(val, buf),
+        Expr::IsNull(expr) => return write_postfix(expr, " IS NULL", buf),
+        Expr::IsNotNull(expr) => return write_postfix(expr, " IS NOT NULL", buf),
+        Expr::IsTrue(expr) => return write_postfix(expr, " IS TRUE", buf),
+        Expr::IsFalse(expr) => return write_postfix(expr, " IS FALSE", buf),
+        Expr::Not(expr) => {
+            write!(buf, "(NOT ")?;
+            if !write_expr(expr, buf)? {
                 return Ok(false);
             }
+            write!(buf, ")")?;
         }
-        Expr::IsNotNull(expr) => {
-            if write_expr(expr, buf)? {
-                write!(buf, " IS NOT NULL")?;
-            } else {
+        Expr::BinaryExpr(binary) => {
+            // Division is left out since integer division semantics differ.
+            match binary.op {
+                Operator::Eq
+                | Operator::NotEq
+                | Operator::Lt
+                | Operator::LtEq
+                | Operator::Gt
+                | Operator::GtEq
+                | Operator::Plus
+                | Operator::Minus
+                | Operator::Multiply
+                | Operator::And
+                | Operator::Or => (),
+                _ => return Ok(false),
+            }
+            write!(buf, "(")?;
+            if !write_expr(binary.left.as_ref(), buf)? {
+                return Ok(false);
+            }
+            write!(buf, " {} ", binary.op)?;
+            if !write_expr(binary.right.as_ref(), buf)? {
                 return Ok(false);
             }
+            write!(buf, ")")?;
         }
-        Expr::IsTrue(expr) => {
-            if write_expr(expr, buf)? {
-                write!(buf, " IS TRUE")?;
-            } else {
+        Expr::InList(InList {
+            expr,
+            list,
+            negated,
+        }) => {
+            write!(buf, "(")?;
+            if !write_expr(expr, buf)? {
                 return Ok(false);
             }
+            write!(buf, "{} IN (", if *negated { " NOT" } else { "" })?;
+            for (idx, item) in list.iter().enumerate() {
+                if idx > 0 {
+                    write!(buf, ", ")?;
+                }
+                if !write_expr(item, buf)? {
+                    return Ok(false);
+                }
+            }
+            write!(buf, "))")?;
         }
-        Expr::IsFalse(expr) => {
-            if write_expr(expr, buf)? {
-                write!(buf, " IS FALSE")?;
-            } else {
+        Expr::Between(Between {
+            expr,
+            negated,
+            low,
+            high,
+        }) => {
+            write!(buf, "(")?;
+            if !write_expr(expr, buf)? {
+                return Ok(false);
+            }
+            write!(buf, "{} BETWEEN ", if *negated { " NOT" } else { "" })?;
+            if !write_expr(low, buf)? {
+                return Ok(false);
+            }
+            write!(buf, " AND ")?;
+            if !write_expr(high, buf)? {
                 return Ok(false);
             }
+            write!(buf, ")")?;
         }
-        Expr::BinaryExpr(binary) => {
-            if !write_expr(binary.left.as_ref(), buf)? {
+        Expr::Like(Like {
+            negated,
+            expr,
+            pattern,
+            escape_char: None,
+            case_insensitive: false,
+        }) => {
+            write!(buf, "(")?;
+            if !write_expr(expr, buf)? {
                 return Ok(false);
             }
-            write!(buf, " {} ", binary.op)?;
-            if !write_expr(binary.right.as_ref(), buf)? {
+            write!(buf, "{} LIKE ", if *negated { " NOT" } else { "" })?;
+            if !write_expr(pattern, buf)? {
                 return Ok(false);
             }
+            write!(buf, ")")?;
         }
         _ => {
             // Unsupported.
@@ -648,11 +730,55 @@ fn write_expr(expr: &Expr, buf: &mut String) -> Result<bool> {
     Ok(true)
 }
 
+/// Write `expr` followed by `postfix`, e.g. " IS NULL".
+fn write_postfix(expr: &Expr, postfix: &str, buf: &mut String) -> Result<bool> {
+    write!(buf, "(")?;
+    if !write_expr(expr, buf)? {
+        return Ok(false);
+    }
+    write!(buf, "{postfix})")?;
+    Ok(true)
+}
+
+/// Write a literal, returning false if the literal can't be represented.
+///
+/// Dates and times are written as typed literals so that they can be compared
+/// against columns without relying on coercion from strings.
+fn write_literal(val: &ScalarValue, buf: &mut String) -> Result<bool> {
+    if val.is_null() {
+        write!(buf, "NULL")?;
+        return Ok(true);
+    }
+
+    match val {
+        ScalarValue::Boolean(Some(v)) => {
+            write!(buf, "{}", if *v { "TRUE" } else { "FALSE" })?;
+            return Ok(true);
+        }
+        ScalarValue::Date32(_) => write!(buf, "DATE ")?,
+        ScalarValue::TimestampNanosecond(_, Some(_))
+        | ScalarValue::TimestampMicrosecond(_, Some(_))
+        | ScalarValue::TimestampMillisecond(_, Some(_))
+        | ScalarValue::TimestampSecond(_, Some(_)) => write!(buf, "TIMESTAMP ")?,
+        ScalarValue::TimestampNanosecond(_, None)
+        | ScalarValue::TimestampMicrosecond(_, None)
+        | ScalarValue::TimestampMillisecond(_, None)
+        | ScalarValue::TimestampSecond(_, None) => write!(buf, "DATETIME ")?,
+        ScalarValue::Time64Nanosecond(_) | ScalarValue::Time64Microsecond(_) => {
+            write!(buf, "TIME ")?
+        }
+        _ => (),
+    }
+
+    Ok(util::encode_literal_to_text(util::Datasource::BigQuery, buf, val).is_ok())
+}
+
 #[cfg(test)]
 mod tests {
     use datafusion::common::Column;
     use datafusion::logical_expr::expr::Sort;
-    use datafusion::logical_expr::{BinaryExpr, Operator};
+    use datafusion::logical_expr::BinaryExpr;
+    use datafusion::prelude::{col, lit};
 
     use super::*;
 
@@ -684,7 +810,7 @@ mod tests {
         ];
 
         let out = exprs_to_predicate_string(&exprs).unwrap();
-        assert_eq!(out, "a < b AND c < d")
+        assert_eq!(out, "(`a` < `b`) AND (`c` < `d`)")
     }
 
     #[test]
@@ -714,6 +840,35 @@ mod tests {
         ];
 
         let out = exprs_to_predicate_string(&exprs).unwrap();
-        assert_eq!(out, "a < b")
+        assert_eq!(out, "(`a` < `b`)")
+    }
+
+    #[test]
+    fn skip_partially_supported_expr_string() {
+        let exprs = vec![
+            // Unsupported literal on the right side, nothing from this
+            // expression should end up in the output.
+            col("a").eq(lit(ScalarValue::UInt64(Some(1)))),
+            col("b").gt_eq(lit(ScalarValue::Date32(Some(19358)))),
+        ];
+
+        let out = exprs_to_predicate_string(&exprs).unwrap();
+        assert_eq!(out, "(`b` >= DATE '2023-01-01')")
+    }
+
+    #[test]
+    fn nested_expr_string() {
+        let exprs = vec![
+            col("a").eq(lit(1_i64)).or(col("b").is_null()),
+            col("c").in_list(vec![lit("x"), lit("y")], false),
+            col("d").between(lit(1_i64), lit(5_i64)),
+            lit(true).eq(col("e")),
+        ];
+
+        let out = exprs_to_predicate_string(&exprs).unwrap();
+        assert_eq!(
+            out,
+            "((`a` = 1) OR (`b` IS NULL)) AND (`c` IN ('x', 'y')) AND (`d` BETWEEN 1 AND 5) AND (TRUE = `e`)"
+        )
     }
 }
@@ -4,13 +4,17 @@ use std::sync::Arc;
 use bson::RawDocumentBuf;
 use bytes::BytesMut;
 use datafusion::arrow::datatypes::Schema;
+use datafusion::datasource::file_format::file_compression_type::FileCompressionType;
 use datafusion::datasource::streaming::StreamingTable;
 use datafusion::datasource::TableProvider;
+use datafusion::error::DataFusionError;
 use datafusion::parquet::data_type::AsBytes;
 use datafusion::physical_plan::streaming::PartitionStream;
-use futures::StreamExt;
+use futures::{StreamExt, TryStreamExt};
 use object_store::{ObjectMeta, ObjectStore};
+use tokio::io::AsyncRead;
 use tokio_util::codec::LengthDelimitedCodec;
+use tokio_util::io::StreamReader;
 
 use crate::bson::errors::BsonError;
 use crate::bson::schema::{merge_schemas, schema_from_document};
@@ -18,11 +22,20 @@ use crate::bson::stream::BsonPartitionStream;
 use crate::common::url::DatasourceUrl;
 use crate::object_store::{ObjStoreAccess, ObjStoreAccessor};
 
+/// Create a streaming table for the bson files matching `source_url`.
+///
+/// If `union_by_name` is set, the schema inference sample is taken from every
+/// file rather than only from the first file(s).
+///
+/// If `compression` isn't provided, it's inferred from the extension of each
+/// file.
 pub async fn bson_streaming_table(
     store_access: Arc<dyn ObjStoreAccess>,
     source_url: DatasourceUrl,
     schema: Option<Schema>,
     schema_inference_sample_size: Option<i64>,
+    union_by_name: bool,
+    compression: Option<FileCompressionType>,
 ) -> Result<Arc<dyn TableProvider>, BsonError> {
     let accessor = ObjStoreAccessor::new(store_access)?;
 
@@ -37,14 +50,22 @@ pub async fn bson_streaming_table(
 
     let store = accessor.into_object_store();
 
-    bson_streaming_table_inner(store, list, schema, schema_inference_sample_size).await
+    bson_streaming_table_inner(
+        store,
+        list,
+        schema,
+        schema_inference_sample_size,
+        union_by_name,
+        compression,
+    )
+    .await
 }
 
 pub async fn bson_streaming_table_from_object(
     store: Arc<dyn ObjectStore>,
     object: ObjectMeta,
 ) -> Result<Arc<dyn TableProvider>, BsonError> {
-    bson_streaming_table_inner(store, vec![object], None, None).await
+    bson_streaming_table_inner(store, vec![object], None, None, false, None).await
 }
 
 async fn bson_streaming_table_inner(
@@ -52,6 +73,8 @@ async fn bson_streaming_table_inner(
     list: Vec<ObjectMeta>,
     schema: Option<Schema>,
     schema_inference_sample_size: Option<i64>,
+    union_by_name: bool,
+    compression: Option<FileCompressionType>,
 ) -> Result<Arc<dyn TableProvider>, BsonError> {
     // TODO: set a maximum (1024?) or have an adaptive mode
     // (at least n but stop after n the same) or skip documents
@@ -64,6 +87,12 @@ async fn bson_streaming_table_inner(
     // build a vector of streams, one for each file, that handle BSON's framing.
     let mut readers = VecDeque::with_capacity(list.len());
     for obj in list {
+        let compression = match compression {
+            Some(compression) => compression,
+            None => compression_from_extension(&obj),
+        };
+        let reader = object_reader(store.clone(), &obj, compression)?;
+
         readers.push_back(
             // BSON is just length-prefixed byte sequences
             LengthDelimitedCodec::builder()
@@ -79,13 +108,7 @@ async fn bson_streaming_table_inner(
                 .length_field_offset(0) // length field is first
                 .length_adjustment(0) // length prefix includes
                 .num_skip(0) // send the prefix and payload to the bson library
-                // the prefix use the object_store buffered reader
-                // to stream data from the object store:
-                .new_read(object_store::buffered::BufReader::with_capacity(
-                    store.to_owned(),
-                    &obj,
-                    32 * 1024 * 1024, // 32 MB buffer, probably still too small.
-                ))
+                .new_read(reader)
                 // convert the chunk of bytes to bson.
                 .map(
                     // TODO: this probably wants to be a raw document
@@ -113,29 +136,56 @@ async fn bson_streaming_table_inner(
     let schema = if let Some(schema) = schema {
         Arc::new(schema)
     } else {
-        // iterate through the readers and build up a sample of the first <n>
-        // documents to be used to infer the schema.
         let mut sample = Vec::with_capacity(sample_size as usize);
-        let mut first_active: usize = 0;
-        'readers: for reader in readers.iter_mut() {
-            while let Some(res) = reader.next().await {
-                match res {
-                    Ok(doc) => sample.push(doc),
-                    Err(e) => return Err(e),
-                };
-
-                if sample.len() >= sample_size as usize {
-                    break 'readers;
+        if union_by_name {
+            // take a sample of the first <n> documents from every file so
+            // that fields only present in later files are included in the
+            // schema.
+            let mut active = VecDeque::with_capacity(readers.len());
+            while let Some(mut reader) = readers.pop_front() {
+                let mut num_read = 0;
+                let mut exhausted = true;
+                while let Some(res) = reader.next().await {
+                    sample.push(res?);
+                    num_read += 1;
+
+                    if num_read >= sample_size {
+                        exhausted = false;
+                        break;
+                    }
+                }
+
+                // readers we've read all documents from don't need to be
+                // kept around, their documents are in the sample.
+                if !exhausted {
+                    active.push_back(reader);
                 }
             }
-            first_active += 1;
-        }
+            readers = active;
+        } else {
+            // iterate through the readers and build up a sample of the first
+            // <n> documents to be used to infer the schema.
+            let mut first_active: usize = 0;
+            'readers: for reader in readers.iter_mut() {
+                while let Some(res) = reader.next().await {
+                    match res {
+                        Ok(doc) => sample.push(doc),
+                        Err(e) => return Err(e),
+                    };
 
-        // if we had to read through one or more than of the input files in the
-        // glob, we already have their documents and should truncate the vector
-        // of readers.
-        for _ in 0..first_active {
-            readers.pop_front();
+                    if sample.len() >= sample_size as usize {
+                        break 'readers;
+                    }
+                }
+                first_active += 1;
+            }
+
+            // if we had to read through one or more than of the input files
+            // in the glob, we already have their documents and should
+            // truncate the vector of readers.
+            for _ in 0..first_active {
+                readers.pop_front();
+            }
         }
 
         // infer the sechema; in the future we can allow users to specify the
@@ -181,3 +231,44 @@ async fn bson_streaming_table_inner(
         streams,        // <= vector of partition streams
     )?))
 }
+
+/// Infer the compression of an object from its extension, e.g. `.bson.gz`.
+fn compression_from_extension(obj: &ObjectMeta) -> FileCompressionType {
+    obj.location
+        .extension()
+        .and_then(|ext| ext.parse().ok())
+        .unwrap_or(FileCompressionType::UNCOMPRESSED)
+}
+
+/// Get a reader for the contents of the object, decompressing the contents if
+/// needed.
+fn object_reader(
+    store: Arc<dyn ObjectStore>,
+    obj: &ObjectMeta,
+    compression: FileCompressionType,
+) -> Result<Box<dyn AsyncRead + Send + Unpin>, BsonError> {
+    if !compression.is_compressed() {
+        // use the object_store buffered reader to stream data from the object
+        // store.
+        return Ok(Box::new(object_store::buffered::BufReader::with_capacity(
+            store,
+            obj,
+            32 * 1024 * 1024, // 32 MB buffer, probably still too small.
+        )));
+    }
+
+    // decompression operates on a stream of bytes, defer the request until
+    // the stream is first polled.
+    let location = obj.location.clone();
+    let stream = futures::stream::once(async move { store.get(&location).await })
+        .map_ok(|result| result.into_stream())
+        .try_flatten()
+        .map_err(DataFusionError::from)
+        .boxed();
+
+    let stream = compression
+        .convert_stream(stream)?
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e));
+
+    Ok(Box::new(StreamReader::new(stream)))
+}
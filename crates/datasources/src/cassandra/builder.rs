@@ -5,6 +5,8 @@ use datafusion::arrow::array::{
     DurationNanosecondBuilder,
     Int64Builder,
     ListBuilder,
+    MapBuilder,
+    StructBuilder,
 };
 
 use super::{
@@ -35,6 +37,8 @@ pub(super) enum CqlValueArrayBuilder {
     Timestamp(TimestampMillisecondBuilder),
     Utf8(StringBuilder),
     List(Box<ListBuilder<Self>>),
+    Map(Box<MapBuilder<Self, Self>>),
+    Struct(Box<StructBuilder>),
 }
 
 impl ArrayBuilder for CqlValueArrayBuilder {
@@ -51,6 +55,8 @@ impl ArrayBuilder for CqlValueArrayBuilder {
             CqlValueArrayBuilder::Timestamp(b) => b.len(),
             CqlValueArrayBuilder::Utf8(b) => b.len(),
             CqlValueArrayBuilder::List(b) => b.len(),
+            CqlValueArrayBuilder::Map(b) => b.len(),
+            CqlValueArrayBuilder::Struct(b) => b.len(),
         }
     }
 
@@ -67,6 +73,8 @@ impl ArrayBuilder for CqlValueArrayBuilder {
             CqlValueArrayBuilder::Timestamp(b) => Arc::new(b.finish()),
             CqlValueArrayBuilder::Utf8(b) => Arc::new(b.finish()),
             CqlValueArrayBuilder::List(b) => Arc::new(b.finish()),
+            CqlValueArrayBuilder::Map(b) => Arc::new(b.finish()),
+            CqlValueArrayBuilder::Struct(b) => Arc::new(b.finish()),
         }
     }
 
@@ -83,6 +91,8 @@ impl ArrayBuilder for CqlValueArrayBuilder {
             CqlValueArrayBuilder::Timestamp(b) => Arc::new(b.finish_cloned()),
             CqlValueArrayBuilder::Utf8(b) => Arc::new(b.finish_cloned()),
             CqlValueArrayBuilder::List(b) => Arc::new(b.finish_cloned()),
+            CqlValueArrayBuilder::Map(b) => Arc::new(b.finish_cloned()),
+            CqlValueArrayBuilder::Struct(b) => Arc::new(b.finish_cloned()),
         }
     }
 
@@ -122,6 +132,26 @@ impl CqlValueArrayBuilder {
                 let builder = Box::new(builder);
                 CqlValueArrayBuilder::List(builder)
             }
+            DataType::Map(fld, _) => {
+                let (key, value) = match fld.data_type() {
+                    DataType::Struct(fields) if fields.len() == 2 => (&fields[0], &fields[1]),
+                    _ => unreachable!("map entries are always a struct of keys and values"),
+                };
+                let builder = MapBuilder::new(
+                    None,
+                    Self::new(key.data_type()),
+                    Self::new(value.data_type()),
+                );
+                CqlValueArrayBuilder::Map(Box::new(builder))
+            }
+            DataType::Struct(fields) => {
+                let builders = fields
+                    .iter()
+                    .map(|f| Box::new(Self::new(f.data_type())) as Box<dyn ArrayBuilder>)
+                    .collect();
+                let builder = StructBuilder::new(fields.clone(), builders);
+                CqlValueArrayBuilder::Struct(Box::new(builder))
+            }
             _ => unreachable!(
                 "the casting in `try_convert_dtype` makes the above an exhaustive match"
             ),
@@ -147,6 +177,15 @@ impl CqlValueArrayBuilder {
             CqlValueArrayBuilder::Int8(b) => b.append_null(),
             CqlValueArrayBuilder::Int64(b) => b.append_null(),
             CqlValueArrayBuilder::List(b) => b.append_null(),
+            CqlValueArrayBuilder::Map(b) => b
+                .append(false)
+                .expect("map keys and values have the same length"),
+            CqlValueArrayBuilder::Struct(b) => {
+                for idx in 0..b.num_fields() {
+                    struct_field(b, idx).append_null();
+                }
+                b.append_null()
+            }
         }
     }
 
@@ -188,15 +227,53 @@ impl CqlValueArrayBuilder {
 
                 builder.append_value(timestamp)
             }
-            (List(builder), CqlValue::List(values)) => {
+            (List(builder), CqlValue::List(values) | CqlValue::Set(values)) => {
                 for value in values {
                     builder.values().append_value(value)
                 }
                 builder.append(true)
             }
+            (Map(builder), CqlValue::Map(entries)) => {
+                for (key, value) in entries {
+                    builder.keys().append_value(key);
+                    builder.values().append_value(value);
+                }
+                builder
+                    .append(true)
+                    .expect("map keys and values have the same length")
+            }
+            (Struct(builder), CqlValue::Tuple(values)) => {
+                let num_values = values.len();
+                for (idx, value) in values.into_iter().enumerate() {
+                    struct_field(builder, idx).append_option(value);
+                }
+                for idx in num_values..builder.num_fields() {
+                    struct_field(builder, idx).append_null();
+                }
+                builder.append(true)
+            }
+            (Struct(builder), CqlValue::UserDefinedType { fields, .. }) => {
+                // Fields are returned in the order of the type definition.
+                // Fields added to the type after the value was written may be
+                // missing.
+                let num_values = fields.len();
+                for (idx, (_, value)) in fields.into_iter().enumerate() {
+                    struct_field(builder, idx).append_option(value);
+                }
+                for idx in num_values..builder.num_fields() {
+                    struct_field(builder, idx).append_null();
+                }
+                builder.append(true)
+            }
             (_, _) => {
                 unreachable!("Schema is already checked. This indicates a bug")
             }
         }
     }
 }
+
+fn struct_field(builder: &mut StructBuilder, idx: usize) -> &mut CqlValueArrayBuilder {
+    builder
+        .field_builder::<CqlValueArrayBuilder>(idx)
+        .expect("struct fields are built with value builders")
+}
@@ -36,20 +36,21 @@ use super::{
 pub(super) struct CassandraExec {
     schema: ArrowSchemaRef,
     session: Arc<Session>,
-    query: String,
+    /// Queries to execute, one per output partition.
+    queries: Vec<String>,
     metrics: ExecutionPlanMetricsSet,
 }
 
 impl CassandraExec {
     pub(super) fn new(
         schema: ArrowSchemaRef,
-        query: String,
+        queries: Vec<String>,
         session: Arc<Session>,
     ) -> CassandraExec {
         CassandraExec {
             schema,
             session,
-            query,
+            queries,
             metrics: ExecutionPlanMetricsSet::new(),
         }
     }
@@ -63,8 +64,7 @@ impl ExecutionPlan for CassandraExec {
         self.schema.clone()
     }
     fn output_partitioning(&self) -> Partitioning {
-        // TODO: does scylla driver support partitioning?
-        Partitioning::UnknownPartitioning(1)
+        Partitioning::UnknownPartitioning(self.queries.len())
     }
     fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
         None
@@ -90,18 +90,13 @@ impl ExecutionPlan for CassandraExec {
         partition: usize,
         _context: Arc<TaskContext>,
     ) -> DatafusionResult<SendableRecordBatchStream> {
-        if partition != 0 {
-            return Err(DataFusionError::Execution(
-                "only single partition supported".to_string(),
-            ));
-        }
+        let query = self
+            .queries
+            .get(partition)
+            .ok_or_else(|| DataFusionError::Execution(format!("invalid partition: {partition}")))?;
 
         Ok(Box::pin(DataSourceMetricsStreamAdapter::new(
-            CassandraRowStream::new(
-                self.session.clone(),
-                self.schema.clone(),
-                self.query.clone(),
-            ),
+            CassandraRowStream::new(self.session.clone(), self.schema.clone(), query.clone()),
             partition,
             &self.metrics,
         )))
@@ -126,7 +121,7 @@ impl fmt::Debug for CassandraExec {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("ScyllaExec")
             .field("schema", &self.schema)
-            .field("query", &self.query)
+            .field("queries", &self.queries)
             .finish_non_exhaustive()
     }
 }
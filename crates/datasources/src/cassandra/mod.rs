@@ -1,7 +1,9 @@
 mod builder;
 mod errors;
 mod exec;
+mod query;
 use std::any::Any;
+use std::collections::HashMap;
 use std::fmt;
 use std::pin::Pin;
 use std::sync::Arc;
@@ -55,6 +57,7 @@ use scylla::transport::session::Session;
 use scylla::SessionBuilder;
 
 use self::exec::CassandraExec;
+use self::query::{quote_ident, token_ranges, KeyColumns};
 
 pub struct CassandraAccess {
     host: String,
@@ -102,10 +105,84 @@ fn try_convert_dtype(ty: &ColumnType) -> Result<DataType> {
             let inner = try_convert_dtype(inner)?;
             DataType::new_list(inner, true)
         }
+        ColumnType::Map(key, value) => {
+            map_dtype(try_convert_dtype(key)?, try_convert_dtype(value)?)
+        }
+        ColumnType::Tuple(types) => {
+            let fields = types
+                .iter()
+                .enumerate()
+                .map(|(idx, ty)| Ok(Field::new(format!("c{idx}"), try_convert_dtype(ty)?, true)))
+                .collect::<Result<Fields>>()?;
+            DataType::Struct(fields)
+        }
+        ColumnType::UserDefinedType { field_types, .. } => {
+            let fields = field_types
+                .iter()
+                .map(|(name, ty)| Ok(Field::new(name, try_convert_dtype(ty)?, true)))
+                .collect::<Result<Fields>>()?;
+            DataType::Struct(fields)
+        }
         _ => return Err(CassandraError::UnsupportedDataType(format!("{:?}", ty))),
     })
 }
-fn try_convert_dtype_string(ty: &str) -> Result<DataType> {
+
+/// Arrow type for a Cassandra map. Field names and nullability match what's
+/// produced by arrow's `MapBuilder`.
+fn map_dtype(key: DataType, value: DataType) -> DataType {
+    let entries = Fields::from(vec![
+        Field::new("keys", key, false),
+        Field::new("values", value, true),
+    ]);
+    DataType::Map(
+        Arc::new(Field::new("entries", DataType::Struct(entries), false)),
+        false,
+    )
+}
+
+/// Convert a CQL type string as found in `system_schema`.
+///
+/// `udts` maps user defined type names in the keyspace to their field names
+/// and types.
+fn try_convert_dtype_string(
+    ty: &str,
+    udts: &HashMap<String, Vec<(String, String)>>,
+) -> Result<DataType> {
+    let ty = ty.trim();
+
+    // Parameterized types, e.g. "map<text, frozen<list<int>>>".
+    if let Some((name, params)) = ty.split_once('<') {
+        let params = match params.strip_suffix('>') {
+            Some(params) => split_type_params(params),
+            None => return Err(CassandraError::UnsupportedDataType(ty.to_string())),
+        };
+        return Ok(match (name.trim(), params.as_slice()) {
+            ("frozen", [inner]) => try_convert_dtype_string(inner, udts)?,
+            ("list" | "set", [inner]) => {
+                DataType::new_list(try_convert_dtype_string(inner, udts)?, true)
+            }
+            ("map", [key, value]) => map_dtype(
+                try_convert_dtype_string(key, udts)?,
+                try_convert_dtype_string(value, udts)?,
+            ),
+            ("tuple", types) => {
+                let fields = types
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, ty)| {
+                        Ok(Field::new(
+                            format!("c{idx}"),
+                            try_convert_dtype_string(ty, udts)?,
+                            true,
+                        ))
+                    })
+                    .collect::<Result<Fields>>()?;
+                DataType::Struct(fields)
+            }
+            _ => return Err(CassandraError::UnsupportedDataType(ty.to_string())),
+        });
+    }
+
     Ok(match ty {
         "custom" => return Err(CassandraError::UnsupportedDataType(ty.to_string())),
         "ascii" => DataType::Utf8,
@@ -120,20 +197,41 @@ fn try_convert_dtype_string(ty: &str) -> Result<DataType> {
         "tinyint" => DataType::Int8,
         "uuid" => DataType::Utf8,
         "bigint" => DataType::Int64,
-        // list<T>
-        lst if lst.contains('<') => {
-            // get the inner type from "list<{inner}>"
-            let inner = lst.split('<').nth(1).and_then(|s| s.split('>').next());
-
-            match inner {
-                Some(inner) => DataType::new_list(try_convert_dtype_string(inner)?, true),
-                None => return Err(CassandraError::UnsupportedDataType(ty.to_string())),
+        udt => match udts.get(udt.trim_matches('"')) {
+            Some(udt_fields) => {
+                let fields = udt_fields
+                    .iter()
+                    .map(|(name, ty)| {
+                        Ok(Field::new(name, try_convert_dtype_string(ty, udts)?, true))
+                    })
+                    .collect::<Result<Fields>>()?;
+                DataType::Struct(fields)
             }
-        }
-        _ => return Err(CassandraError::UnsupportedDataType(ty.to_string())),
+            None => return Err(CassandraError::UnsupportedDataType(ty.to_string())),
+        },
     })
 }
 
+/// Split type parameters on top level commas.
+fn split_type_params(params: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (idx, c) in params.char_indices() {
+        match c {
+            '<' => depth += 1,
+            '>' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(params[start..idx].trim());
+                start = idx + 1;
+            }
+            _ => (),
+        }
+    }
+    parts.push(params[start..].trim());
+    parts
+}
+
 impl CassandraAccessState {
     pub async fn try_new(
         host: impl AsRef<str>,
@@ -149,21 +247,100 @@ impl CassandraAccessState {
         let session = session.build().await?;
         Ok(Self { session })
     }
-    async fn get_schema(&self, ks: &str, table: &str) -> Result<ArrowSchema> {
+    async fn get_schema(&self, ks: &str, table: &str) -> Result<(ArrowSchema, KeyColumns)> {
         let query = format!("SELECT * FROM {ks}.{table} LIMIT 1");
         let res = self.session.query(query, &[]).await?;
         let fields: Fields = res
             .col_specs
-            .into_iter()
+            .iter()
             .map(|c| {
-                let name = c.name;
-                let ty = c.typ;
-                let dtype = try_convert_dtype(&ty)?;
-                Ok(Field::new(name, dtype, true))
+                let dtype = try_convert_dtype(&c.typ)?;
+                Ok(Field::new(&c.name, dtype, true))
             })
             .collect::<Result<_>>()?;
-        Ok(ArrowSchema::new(fields))
+        let types = res
+            .col_specs
+            .into_iter()
+            .map(|c| (c.name, c.typ))
+            .collect::<HashMap<_, _>>();
+        let keys = self.get_key_columns(ks, table, types).await?;
+        Ok((ArrowSchema::new(fields), keys))
+    }
+
+    /// Get the partition and clustering key columns of a table.
+    async fn get_key_columns(
+        &self,
+        ks: &str,
+        table: &str,
+        mut types: HashMap<String, ColumnType>,
+    ) -> Result<KeyColumns> {
+        let query = "SELECT column_name, kind, position FROM system_schema.columns WHERE keyspace_name = ? AND table_name = ?";
+        let res = self.session.query(query, (ks, table)).await?;
+
+        let mut partition = Vec::new();
+        let mut clustering = Vec::new();
+        for row in res.rows_or_empty() {
+            let (name, kind, position) = match row.columns.as_slice() {
+                [Some(CqlValue::Text(name)), Some(CqlValue::Text(kind)), Some(CqlValue::Int(position))] => {
+                    (name, kind, *position)
+                }
+                _ => return Err(CassandraError::String("invalid column".to_string())),
+            };
+            let keys = match kind.as_str() {
+                "partition_key" => &mut partition,
+                "clustering" => &mut clustering,
+                _ => continue,
+            };
+            let ty = types.remove(name).ok_or_else(|| {
+                CassandraError::String(format!("missing type for key column {name}"))
+            })?;
+            keys.push((position, name.clone(), ty));
+        }
+
+        let ordered = |mut keys: Vec<(i32, String, ColumnType)>| {
+            keys.sort_by_key(|(position, _, _)| *position);
+            keys.into_iter()
+                .map(|(_, name, ty)| (name, ty))
+                .collect::<Vec<_>>()
+        };
+        Ok(KeyColumns {
+            partition: ordered(partition),
+            clustering: ordered(clustering),
+        })
     }
+
+    /// Get the user defined types in a keyspace, mapping type names to their
+    /// fields.
+    async fn get_udts(&self, ks: &str) -> Result<HashMap<String, Vec<(String, String)>>> {
+        let query =
+            "SELECT type_name, field_names, field_types FROM system_schema.types WHERE keyspace_name = ?";
+        let res = self.session.query(query, (ks,)).await?;
+
+        let text_list = |value: &Option<CqlValue>| match value {
+            Some(CqlValue::List(values)) => values
+                .iter()
+                .map(|value| match value {
+                    CqlValue::Text(s) => Ok(s.clone()),
+                    _ => Err(CassandraError::String("invalid type".to_string())),
+                })
+                .collect::<Result<Vec<_>>>(),
+            _ => Err(CassandraError::String("invalid type".to_string())),
+        };
+
+        res.rows_or_empty()
+            .into_iter()
+            .map(|row| {
+                let name = match &row.columns[0] {
+                    Some(CqlValue::Text(s)) => s.clone(),
+                    _ => return Err(CassandraError::String("invalid type".to_string())),
+                };
+                let names = text_list(&row.columns[1])?;
+                let types = text_list(&row.columns[2])?;
+                Ok((name, names.into_iter().zip(types).collect()))
+            })
+            .collect()
+    }
+
     pub async fn validate_table_access(&self, ks: &str, table: &str) -> Result<()> {
         let query = format!("SELECT * FROM {ks}.{table} LIMIT 1");
         let res = self.session.query(query, &[]).await?;
@@ -180,6 +357,7 @@ impl CassandraAccessState {
 #[derive(Debug, Clone)]
 pub struct CassandraTableProvider {
     schema: Arc<ArrowSchema>,
+    keys: KeyColumns,
     ks: String,
     table: String,
     session: Arc<Session>,
@@ -194,9 +372,10 @@ impl CassandraTableProvider {
         pass: Option<String>,
     ) -> Result<Self> {
         let access = CassandraAccessState::try_new(host, user, pass).await?;
-        let schema = access.get_schema(&ks, &table).await?;
+        let (schema, keys) = access.get_schema(&ks, &table).await?;
         Ok(Self {
             schema: Arc::new(schema),
+            keys,
             session: Arc::new(access.session),
             ks,
             table,
@@ -220,16 +399,22 @@ impl TableProvider for CassandraTableProvider {
 
     fn supports_filter_pushdown(
         &self,
-        _filter: &Expr,
+        filter: &Expr,
     ) -> DatafusionResult<TableProviderFilterPushDown> {
-        Ok(TableProviderFilterPushDown::Inexact)
+        // Whether or not a key restriction ends up in the query depends on the
+        // rest of the filters, so always filter again.
+        if self.keys.is_pushdown_candidate(filter) {
+            Ok(TableProviderFilterPushDown::Inexact)
+        } else {
+            Ok(TableProviderFilterPushDown::Unsupported)
+        }
     }
 
     async fn scan(
         &self,
-        _ctx: &SessionState,
+        ctx: &SessionState,
         projection: Option<&Vec<usize>>,
-        _filters: &[Expr],
+        filters: &[Expr],
         limit: Option<usize>,
     ) -> DatafusionResult<Arc<dyn ExecutionPlan>> {
         let projected_schema = match projection {
@@ -245,20 +430,39 @@ impl TableProvider for CassandraTableProvider {
             projected_schema
                 .fields
                 .iter()
-                .map(|f| f.name().clone())
+                .map(|f| quote_ident(f.name()))
                 .collect::<Vec<_>>()
                 .join(",")
         };
-        let mut query = format!(
+        let query = format!(
             "SELECT {} FROM {}.{}",
             projection_string, self.ks, self.table
         );
 
+        let mut queries = match self.keys.where_clause(filters) {
+            Some(conditions) => vec![format!("{query} WHERE {conditions}")],
+            // Split full scans into token ranges so that they can be read in
+            // parallel. Limited scans are expected to be small, and are kept as
+            // a single query.
+            None if limit.is_none() && !self.keys.partition.is_empty() => {
+                let token = self.keys.token_expr();
+                token_ranges(ctx.config().target_partitions())
+                    .into_iter()
+                    .map(|(start, end)| {
+                        format!("{query} WHERE {token} >= {start} AND {token} <= {end}")
+                    })
+                    .collect()
+            }
+            None => vec![query],
+        };
+
         if let Some(limit) = limit {
-            query.push_str(&format!(" LIMIT {}", limit));
+            for query in &mut queries {
+                query.push_str(&format!(" LIMIT {}", limit));
+            }
         }
 
-        let exec = CassandraExec::new(projected_schema, query, self.session.clone());
+        let exec = CassandraExec::new(projected_schema, queries, self.session.clone());
         Ok(Arc::new(exec))
     }
 
@@ -319,6 +523,10 @@ impl VirtualLister for CassandraAccessState {
 
     /// List columns for a specific table in the datasource.
     async fn list_columns(&self, schema: &str, table: &str) -> Result<Fields, ExtensionError> {
+        let udts = self
+            .get_udts(schema)
+            .await
+            .map_err(ExtensionError::access)?;
         let query = format!(
             "SELECT column_name, type FROM system_schema.columns WHERE keyspace_name = '{}' AND table_name = '{}'",
             schema, table
@@ -339,7 +547,7 @@ impl VirtualLister for CassandraAccessState {
                             Some(CqlValue::Text(ref s)) => s.clone(),
                             _ => return Err(CassandraError::String("invalid column".to_string())),
                         };
-                        let dtype = try_convert_dtype_string(&ty)?;
+                        let dtype = try_convert_dtype_string(&ty, &udts)?;
 
                         Ok(Field::new(name, dtype, true))
                     })
@@ -348,3 +556,92 @@ impl VirtualLister for CassandraAccessState {
             .map_err(ExtensionError::access)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use datafusion::arrow::array::ArrayBuilder;
+
+    use super::builder::CqlValueArrayBuilder;
+    use super::*;
+
+    #[test]
+    fn convert_dtype_strings() {
+        let udts = HashMap::from([(
+            "address".to_string(),
+            vec![
+                ("street".to_string(), "text".to_string()),
+                ("zip".to_string(), "int".to_string()),
+            ],
+        )]);
+        let address = DataType::Struct(Fields::from(vec![
+            Field::new("street", DataType::Utf8, true),
+            Field::new("zip", DataType::Int32, true),
+        ]));
+
+        let cases = [
+            ("int", DataType::Int32),
+            ("set<text>", DataType::new_list(DataType::Utf8, true)),
+            (
+                "map<text, frozen<list<int>>>",
+                map_dtype(DataType::Utf8, DataType::new_list(DataType::Int32, true)),
+            ),
+            (
+                "frozen<tuple<int, map<text, bigint>>>",
+                DataType::Struct(Fields::from(vec![
+                    Field::new("c0", DataType::Int32, true),
+                    Field::new("c1", map_dtype(DataType::Utf8, DataType::Int64), true),
+                ])),
+            ),
+            ("frozen<address>", address.clone()),
+            ("list<frozen<address>>", DataType::new_list(address, true)),
+        ];
+        for (ty, expected) in cases {
+            assert_eq!(
+                expected,
+                try_convert_dtype_string(ty, &udts).unwrap(),
+                "{ty}"
+            );
+        }
+
+        for ty in ["custom", "unknown_udt", "map<text>", "list<int"] {
+            try_convert_dtype_string(ty, &udts).unwrap_err();
+        }
+    }
+
+    #[test]
+    fn build_nested_values() {
+        let ty = ColumnType::List(Box::new(ColumnType::Map(
+            Box::new(ColumnType::Text),
+            Box::new(ColumnType::UserDefinedType {
+                type_name: "address".to_string(),
+                keyspace: "test".to_string(),
+                field_types: vec![
+                    ("street".to_string(), ColumnType::Text),
+                    ("zip".to_string(), ColumnType::Int),
+                ],
+            }),
+        )));
+        let dtype = try_convert_dtype(&ty).unwrap();
+
+        let address = CqlValue::UserDefinedType {
+            keyspace: "test".to_string(),
+            type_name: "address".to_string(),
+            // Missing trailing field.
+            fields: vec![(
+                "street".to_string(),
+                Some(CqlValue::Text("main".to_string())),
+            )],
+        };
+        let mut builder = CqlValueArrayBuilder::new(&dtype);
+        builder.append_option(Some(CqlValue::List(vec![CqlValue::Map(vec![(
+            CqlValue::Text("home".to_string()),
+            address,
+        )])])));
+        builder.append_option(None);
+
+        let arr = builder.finish();
+        assert_eq!(&dtype, arr.data_type());
+        assert_eq!(2, arr.len());
+        assert_eq!(1, arr.null_count());
+    }
+}
@@ -0,0 +1,394 @@
+//! Translation of DataFusion filters into CQL `WHERE` clauses.
+//!
+//! Cassandra only allows filtering on primary key columns without `ALLOW
+//! FILTERING`, and only in a restricted shape: every partition key column must
+//! be restricted by equality (or `IN`), after which clustering columns may be
+//! restricted in order, with at most one range restriction on the last
+//! restricted clustering column. Anything else is left for DataFusion to
+//! filter.
+use std::collections::HashMap;
+
+use chrono::DateTime;
+use datafusion::logical_expr::expr::InList;
+use datafusion::logical_expr::{Between, BinaryExpr, Expr, Operator};
+use datafusion::scalar::ScalarValue;
+use scylla::frame::response::result::ColumnType;
+
+const MILLIS_PER_DAY: i64 = 86_400_000;
+
+/// Primary key columns of a table, in key order.
+#[derive(Debug, Clone, Default)]
+pub(super) struct KeyColumns {
+    pub(super) partition: Vec<(String, ColumnType)>,
+    pub(super) clustering: Vec<(String, ColumnType)>,
+}
+
+impl KeyColumns {
+    /// Returns if the filter restricts any of the key columns in a way that
+    /// could be pushed down to Cassandra.
+    pub(super) fn is_pushdown_candidate(&self, filter: &Expr) -> bool {
+        let mut restrictions = HashMap::new();
+        self.collect_restrictions(filter, &mut restrictions);
+        !restrictions.is_empty()
+    }
+
+    /// Build the conditions of a `WHERE` clause for the given filters.
+    ///
+    /// Returns `None` if the filters don't restrict the partition key, in
+    /// which case the table needs to be scanned in full.
+    pub(super) fn where_clause(&self, filters: &[Expr]) -> Option<String> {
+        if self.partition.is_empty() {
+            return None;
+        }
+
+        let mut restrictions = HashMap::new();
+        for filter in filters {
+            self.collect_restrictions(filter, &mut restrictions);
+        }
+
+        let mut conditions = Vec::new();
+        for (name, _) in &self.partition {
+            let restriction = restrictions.get(name.as_str())?;
+            if let Some(value) = &restriction.eq {
+                conditions.push(format!("{} = {value}", quote_ident(name)));
+            } else if let Some(values) = &restriction.in_list {
+                conditions.push(format!("{} IN ({})", quote_ident(name), values.join(", ")));
+            } else {
+                return None;
+            }
+        }
+
+        for (name, _) in &self.clustering {
+            let restriction = match restrictions.get(name.as_str()) {
+                Some(restriction) => restriction,
+                None => break,
+            };
+
+            if let Some(value) = &restriction.eq {
+                conditions.push(format!("{} = {value}", quote_ident(name)));
+                continue;
+            }
+
+            if let Some(values) = &restriction.in_list {
+                conditions.push(format!("{} IN ({})", quote_ident(name), values.join(", ")));
+            } else {
+                for (op, value) in [&restriction.lower, &restriction.upper]
+                    .into_iter()
+                    .flatten()
+                {
+                    conditions.push(format!("{} {op} {value}", quote_ident(name)));
+                }
+            }
+            // Nothing may be restricted after an `IN` or range restriction.
+            break;
+        }
+
+        Some(conditions.join(" AND "))
+    }
+
+    /// Build the `token(...)` function call over the partition key.
+    pub(super) fn token_expr(&self) -> String {
+        let cols = self
+            .partition
+            .iter()
+            .map(|(name, _)| quote_ident(name))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!("token({cols})")
+    }
+
+    fn collect_restrictions<'a>(
+        &'a self,
+        expr: &Expr,
+        restrictions: &mut HashMap<&'a str, Restriction>,
+    ) {
+        match expr {
+            Expr::BinaryExpr(BinaryExpr {
+                left,
+                op: Operator::And,
+                right,
+            }) => {
+                self.collect_restrictions(left, restrictions);
+                self.collect_restrictions(right, restrictions);
+            }
+            Expr::BinaryExpr(BinaryExpr { left, op, right })
+                if matches!(
+                    op,
+                    Operator::Eq | Operator::Lt | Operator::LtEq | Operator::Gt | Operator::GtEq
+                ) =>
+            {
+                let (col, op, value) = match (left.as_ref(), right.as_ref()) {
+                    (Expr::Column(col), Expr::Literal(value)) => (col, *op, value),
+                    (Expr::Literal(value), Expr::Column(col)) => match op.swap() {
+                        Some(op) => (col, op, value),
+                        None => return,
+                    },
+                    _ => return,
+                };
+                if let Some((name, value)) = self.key_literal(&col.name, value) {
+                    restrictions.entry(name).or_default().add(op, value);
+                }
+            }
+            Expr::Between(Between {
+                expr,
+                negated: false,
+                low,
+                high,
+            }) => {
+                if let (Expr::Column(col), Expr::Literal(low), Expr::Literal(high)) =
+                    (expr.as_ref(), low.as_ref(), high.as_ref())
+                {
+                    if let (Some((name, low)), Some((_, high))) = (
+                        self.key_literal(&col.name, low),
+                        self.key_literal(&col.name, high),
+                    ) {
+                        let restriction = restrictions.entry(name).or_default();
+                        restriction.add(Operator::GtEq, low);
+                        restriction.add(Operator::LtEq, high);
+                    }
+                }
+            }
+            Expr::InList(InList {
+                expr,
+                list,
+                negated: false,
+            }) => {
+                let col = match expr.as_ref() {
+                    Expr::Column(col) => col,
+                    _ => return,
+                };
+                let mut name = None;
+                let mut values = Vec::with_capacity(list.len());
+                for item in list {
+                    match item {
+                        Expr::Literal(value) => match self.key_literal(&col.name, value) {
+                            Some((n, value)) => {
+                                name = Some(n);
+                                values.push(value);
+                            }
+                            None => return,
+                        },
+                        _ => return,
+                    }
+                }
+                if let Some(name) = name {
+                    let restriction = restrictions.entry(name).or_default();
+                    if restriction.in_list.is_none() {
+                        restriction.in_list = Some(values);
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+
+    /// Get the key column with the given name along with the literal encoded
+    /// for that column.
+    fn key_literal<'a>(&'a self, name: &str, value: &ScalarValue) -> Option<(&'a str, String)> {
+        let (col, ty) = self
+            .partition
+            .iter()
+            .chain(self.clustering.iter())
+            .find(|(col, _)| col == name)?;
+        encode_literal(ty, value).map(|value| (col.as_str(), value))
+    }
+}
+
+/// Restrictions on a single key column.
+///
+/// Cassandra rejects multiple restrictions for the same bound, so only the
+/// first restriction of each kind is kept. The rest are applied by DataFusion.
+#[derive(Debug, Default)]
+struct Restriction {
+    eq: Option<String>,
+    in_list: Option<Vec<String>>,
+    lower: Option<(&'static str, String)>,
+    upper: Option<(&'static str, String)>,
+}
+
+impl Restriction {
+    fn add(&mut self, op: Operator, value: String) {
+        match op {
+            Operator::Eq if self.eq.is_none() => self.eq = Some(value),
+            Operator::Gt if self.lower.is_none() => self.lower = Some((">", value)),
+            Operator::GtEq if self.lower.is_none() => self.lower = Some((">=", value)),
+            Operator::Lt if self.upper.is_none() => self.upper = Some(("<", value)),
+            Operator::LtEq if self.upper.is_none() => self.upper = Some(("<=", value)),
+            _ => (),
+        }
+    }
+}
+
+/// Encode a literal for comparing against a column of the given type.
+///
+/// Returns `None` if the literal can't be represented exactly.
+fn encode_literal(ty: &ColumnType, value: &ScalarValue) -> Option<String> {
+    Some(match (ty, value) {
+        (ColumnType::Ascii | ColumnType::Text, ScalarValue::Utf8(Some(s))) => {
+            let mut buf = String::with_capacity(s.len() + 2);
+            buf.push('\'');
+            for c in s.chars() {
+                if c == '\'' {
+                    buf.push('\'');
+                }
+                buf.push(c);
+            }
+            buf.push('\'');
+            buf
+        }
+        (ColumnType::Uuid, ScalarValue::Utf8(Some(s))) => {
+            uuid::Uuid::parse_str(s).ok()?.to_string()
+        }
+        (ColumnType::TinyInt, ScalarValue::Int8(Some(v))) => v.to_string(),
+        (ColumnType::SmallInt, ScalarValue::Int16(Some(v))) => v.to_string(),
+        (ColumnType::Int, ScalarValue::Int32(Some(v))) => v.to_string(),
+        (ColumnType::BigInt, ScalarValue::Int64(Some(v))) => v.to_string(),
+        (ColumnType::Float, ScalarValue::Float32(Some(v))) if v.is_finite() => v.to_string(),
+        (ColumnType::Double, ScalarValue::Float64(Some(v))) if v.is_finite() => v.to_string(),
+        (ColumnType::Timestamp, ScalarValue::TimestampMillisecond(Some(v), None)) => v.to_string(),
+        // Dates are read as midnight timestamps, anything else can't be
+        // compared against a date without changing the result.
+        (ColumnType::Date, ScalarValue::Date64(Some(v))) if v % MILLIS_PER_DAY == 0 => {
+            let date = DateTime::from_timestamp_millis(*v)?.date_naive();
+            format!("'{}'", date.format("%Y-%m-%d"))
+        }
+        _ => return None,
+    })
+}
+
+/// Quote an identifier so that it's used as is (case sensitive).
+pub(super) fn quote_ident(ident: &str) -> String {
+    format!("\"{}\"", ident.replace('"', "\"\""))
+}
+
+/// Split the full Murmur3 token ring into `n` contiguous, inclusive ranges.
+pub(super) fn token_ranges(n: usize) -> Vec<(i64, i64)> {
+    let n = n.max(1) as i128;
+    let min = i64::MIN as i128;
+    let span = i64::MAX as i128 - min + 1;
+    (0..n)
+        .map(|i| {
+            let start = min + span * i / n;
+            let end = min + span * (i + 1) / n - 1;
+            (start as i64, end as i64)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use datafusion::logical_expr::{col, lit};
+
+    use super::*;
+
+    fn keys() -> KeyColumns {
+        KeyColumns {
+            partition: vec![
+                ("sensor".to_string(), ColumnType::Text),
+                ("day".to_string(), ColumnType::Date),
+            ],
+            clustering: vec![
+                ("hour".to_string(), ColumnType::Int),
+                ("minute".to_string(), ColumnType::Int),
+            ],
+        }
+    }
+
+    fn day() -> Expr {
+        lit(ScalarValue::Date64(Some(19_358 * MILLIS_PER_DAY)))
+    }
+
+    #[test]
+    fn where_clause() {
+        let keys = keys();
+
+        // Partition key not fully restricted.
+        assert_eq!(None, keys.where_clause(&[col("sensor").eq(lit("a"))]));
+        assert_eq!(None, keys.where_clause(&[col("hour").eq(lit(1))]));
+
+        assert_eq!(
+            Some(r#""sensor" = 'it''s' AND "day" = '2023-01-01'"#.to_string()),
+            keys.where_clause(&[col("sensor").eq(lit("it's")), day().eq(col("day"))])
+        );
+
+        assert_eq!(
+            Some(
+                r#""sensor" IN ('a', 'b') AND "day" = '2023-01-01' AND "hour" = 3 AND "minute" >= 10 AND "minute" < 20"#
+                    .to_string()
+            ),
+            keys.where_clause(&[
+                col("sensor").in_list(vec![lit("a"), lit("b")], false),
+                col("day").eq(day()),
+                col("minute").gt_eq(lit(10)).and(col("minute").lt(lit(20))),
+                col("hour").eq(lit(3)),
+                // Not a key column.
+                col("value").gt(lit(1.0)),
+            ])
+        );
+
+        // Clustering columns after a range can't be restricted.
+        assert_eq!(
+            Some(
+                r#""sensor" = 'a' AND "day" = '2023-01-01' AND "hour" >= 1 AND "hour" <= 4"#
+                    .to_string()
+            ),
+            keys.where_clause(&[
+                col("sensor").eq(lit("a")),
+                col("day").eq(day()),
+                col("hour").between(lit(1), lit(4)),
+                col("minute").eq(lit(10)),
+            ])
+        );
+
+        // Clustering columns can't be skipped.
+        assert_eq!(
+            Some(r#""sensor" = 'a' AND "day" = '2023-01-01'"#.to_string()),
+            keys.where_clause(&[
+                col("sensor").eq(lit("a")),
+                col("day").eq(day()),
+                col("minute").eq(lit(10)),
+            ])
+        );
+
+        // Only one bound of each kind per column.
+        assert_eq!(
+            Some(r#""sensor" = 'a' AND "day" = '2023-01-01' AND "hour" > 1"#.to_string()),
+            keys.where_clause(&[
+                col("sensor").eq(lit("a")),
+                col("day").eq(day()),
+                lit(1).lt(col("hour")),
+                col("hour").gt(lit(2)),
+            ])
+        );
+    }
+
+    #[test]
+    fn literals_not_pushed_down() {
+        let keys = keys();
+
+        // Not a midnight timestamp.
+        let noon = lit(ScalarValue::Date64(Some(19_358 * MILLIS_PER_DAY + 1)));
+        assert!(!keys.is_pushdown_candidate(&col("day").eq(noon)));
+        // Mismatched type.
+        assert!(!keys.is_pushdown_candidate(&col("hour").eq(lit(1_i64))));
+        assert!(!keys.is_pushdown_candidate(&col("sensor").not_eq(lit("a"))));
+        assert!(!keys.is_pushdown_candidate(&col("value").eq(lit("a"))));
+
+        assert!(keys.is_pushdown_candidate(&col("hour").lt(lit(1))));
+    }
+
+    #[test]
+    fn token_ranges_cover_ring() {
+        assert_eq!(vec![(i64::MIN, i64::MAX)], token_ranges(0));
+        assert_eq!(vec![(i64::MIN, i64::MAX)], token_ranges(1));
+        assert_eq!(vec![(i64::MIN, -1), (0, i64::MAX)], token_ranges(2));
+
+        let ranges = token_ranges(7);
+        assert_eq!(7, ranges.len());
+        assert_eq!(i64::MIN, ranges[0].0);
+        assert_eq!(i64::MAX, ranges[6].1);
+        for pair in ranges.windows(2) {
+            assert_eq!(pair[0].1 + 1, pair[1].0);
+        }
+    }
+}
@@ -14,7 +14,11 @@ use datafusion::arrow::array::{
     Int32Array,
     Int64Array,
     Int8Array,
+    ListArray,
+    MapArray,
     StringBuilder,
+    StringDictionaryBuilder,
+    StructArray,
     TimestampMicrosecondBuilder,
     TimestampMillisecondBuilder,
     TimestampNanosecondBuilder,
@@ -24,7 +28,16 @@ use datafusion::arrow::array::{
     UInt64Array,
     UInt8Array,
 };
-use datafusion::arrow::datatypes::{DataType, Schema, SchemaRef, TimeUnit};
+use datafusion::arrow::buffer::{NullBuffer, OffsetBuffer};
+use datafusion::arrow::datatypes::{
+    DataType,
+    Field,
+    Fields,
+    Int32Type,
+    Schema,
+    SchemaRef,
+    TimeUnit,
+};
 use datafusion::arrow::record_batch::{RecordBatch, RecordBatchOptions};
 use datafusion::error::DataFusionError;
 use datafusion::physical_plan::RecordBatchStream;
@@ -327,6 +340,151 @@ fn column_to_array(
             }
             Arc::new(vals.finish().with_timezone_opt(tz))
         }
+        DataType::Dictionary(key, value)
+            if matches!(
+                (key.as_ref(), value.as_ref()),
+                (DataType::Int32, DataType::Utf8)
+            ) =>
+        {
+            let mut vals = StringDictionaryBuilder::<Int32Type>::new();
+            for val in column {
+                match val {
+                    Value::String(v) => {
+                        vals.append_value(String::from_utf8(v)?);
+                    }
+                    Value::Null if nullable => vals.append_null(),
+                    other => {
+                        return Err(ClickhouseError::String(format!(
+                            "unexpected value type: {other}"
+                        )))
+                    }
+                }
+            }
+            Arc::new(vals.finish())
+        }
+        DataType::List(field) => {
+            let mut lengths = Vec::with_capacity(column.len());
+            let mut validity = Vec::with_capacity(column.len());
+            let mut values = Vec::new();
+            for val in column {
+                match val {
+                    Value::Array(vals) => {
+                        lengths.push(vals.len());
+                        validity.push(true);
+                        values.extend(vals);
+                    }
+                    Value::Null if nullable => {
+                        lengths.push(0);
+                        validity.push(false);
+                    }
+                    other => {
+                        return Err(ClickhouseError::String(format!(
+                            "unexpected value type: {other}"
+                        )))
+                    }
+                }
+            }
+            let values = column_to_array(field.data_type().clone(), values, field.is_nullable())?;
+            Arc::new(ListArray::try_new(
+                field,
+                OffsetBuffer::from_lengths(lengths),
+                values,
+                validity_to_nulls(validity),
+            )?)
+        }
+        DataType::Map(field, sorted) => {
+            let entry_fields = match field.data_type() {
+                DataType::Struct(fields) if fields.len() == 2 => fields.clone(),
+                other => {
+                    return Err(ClickhouseError::String(format!(
+                        "unexpected map entries type: {other}"
+                    )))
+                }
+            };
+            let mut lengths = Vec::with_capacity(column.len());
+            let mut validity = Vec::with_capacity(column.len());
+            let mut keys = Vec::new();
+            let mut values = Vec::new();
+            for val in column {
+                match val {
+                    Value::Map(k, v) if k.len() == v.len() => {
+                        lengths.push(k.len());
+                        validity.push(true);
+                        keys.extend(k);
+                        values.extend(v);
+                    }
+                    Value::Null if nullable => {
+                        lengths.push(0);
+                        validity.push(false);
+                    }
+                    other => {
+                        return Err(ClickhouseError::String(format!(
+                            "unexpected value type: {other}"
+                        )))
+                    }
+                }
+            }
+            let keys = column_to_array(
+                entry_fields[0].data_type().clone(),
+                keys,
+                entry_fields[0].is_nullable(),
+            )?;
+            let values = column_to_array(
+                entry_fields[1].data_type().clone(),
+                values,
+                entry_fields[1].is_nullable(),
+            )?;
+            let entries = StructArray::try_new(entry_fields, vec![keys, values], None)?;
+            Arc::new(MapArray::try_new(
+                field,
+                OffsetBuffer::from_lengths(lengths),
+                entries,
+                validity_to_nulls(validity),
+                sorted,
+            )?)
+        }
+        DataType::Struct(fields) => {
+            let mut children: Vec<Vec<Value>> = fields
+                .iter()
+                .map(|_| Vec::with_capacity(column.len()))
+                .collect();
+            let mut validity = Vec::with_capacity(column.len());
+            for val in column {
+                match val {
+                    Value::Tuple(vals) if vals.len() == fields.len() => {
+                        for (child, val) in children.iter_mut().zip(vals) {
+                            child.push(val);
+                        }
+                        validity.push(true);
+                    }
+                    Value::Null if nullable => {
+                        for child in children.iter_mut() {
+                            child.push(Value::Null);
+                        }
+                        validity.push(false);
+                    }
+                    other => {
+                        return Err(ClickhouseError::String(format!(
+                            "unexpected value type: {other}"
+                        )))
+                    }
+                }
+            }
+            let nulls = validity_to_nulls(validity);
+            // Children of null tuples are null as well.
+            let arrs = fields
+                .iter()
+                .zip(children)
+                .map(|(field, vals)| {
+                    column_to_array(
+                        field.data_type().clone(),
+                        vals,
+                        field.is_nullable() || nulls.is_some(),
+                    )
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Arc::new(StructArray::try_new(fields, arrs, nulls)?)
+        }
         other => {
             return Err(ClickhouseError::String(format!(
                 "unhandled data type trying to convert to arrow array: {other}"
@@ -337,6 +495,16 @@ fn column_to_array(
     Ok(arr)
 }
 
+/// Create a null buffer from validity, returning `None` if every value is
+/// valid.
+fn validity_to_nulls(validity: Vec<bool>) -> Option<NullBuffer> {
+    if validity.iter().all(|v| *v) {
+        None
+    } else {
+        Some(NullBuffer::from(validity))
+    }
+}
+
 pub struct ArrowDataType {
     pub nullable: bool,
     pub inner: DataType,
@@ -357,6 +525,12 @@ impl From<DataType> for ArrowDataType {
 pub fn clickhouse_type_to_arrow_type(
     clickhouse_type: &str,
 ) -> Result<ArrowDataType, KlickhouseError> {
+    parse_type(clickhouse_type, false)
+}
+
+/// Parse a clickhouse type, `nested` indicating if the type is an element of
+/// an Array, Map, Tuple or Nested type.
+fn parse_type(clickhouse_type: &str, nested: bool) -> Result<ArrowDataType, KlickhouseError> {
     fn eat_identifier(input: &str) -> (&str, &str) {
         for (i, c) in input.char_indices() {
             if c.is_alphabetic() || c == '_' || c == '$' || (i > 0 && c.is_numeric()) {
@@ -378,9 +552,23 @@ pub fn clickhouse_type_to_arrow_type(
         let mut out = vec![];
         let mut in_parens = 0usize;
         let mut last_start = 0;
-        // TODO: handle parens in enum strings?
+        let mut in_string = false;
+        let mut escaped = false;
         for (i, c) in input.char_indices() {
+            // Enum names may contain commas and parens.
+            if in_string {
+                match c {
+                    _ if escaped => escaped = false,
+                    '\\' => escaped = true,
+                    '\'' => in_string = false,
+                    _ => (),
+                }
+                continue;
+            }
             match c {
+                '\'' => {
+                    in_string = true;
+                }
                 ',' => {
                     if in_parens == 0 {
                         out.push(input[last_start..i].trim());
@@ -407,6 +595,25 @@ pub fn clickhouse_type_to_arrow_type(
         Ok(out)
     }
 
+    /// Parse the elements of a Tuple or Nested type into struct fields.
+    ///
+    /// Unnamed tuple elements are named by their position starting at 1,
+    /// matching how clickhouse accesses them.
+    fn parse_tuple_fields(args: &[&str]) -> Result<Fields, KlickhouseError> {
+        let mut fields = Vec::with_capacity(args.len());
+        for (idx, arg) in args.iter().enumerate() {
+            let (name, following) = eat_identifier(arg);
+            let (name, typ) = if following.starts_with(char::is_whitespace) {
+                (name.to_string(), following.trim())
+            } else {
+                ((idx + 1).to_string(), *arg)
+            };
+            let dt = parse_type(typ, true)?;
+            fields.push(Field::new(name, dt.inner, dt.nullable));
+        }
+        Ok(fields.into())
+    }
+
     fn parse_scale(from: &str) -> Result<usize, KlickhouseError> {
         from.parse()
             .map_err(|_| KlickhouseError::TypeParseError("couldn't parse scale".to_string()))
@@ -552,15 +759,17 @@ pub fn clickhouse_type_to_arrow_type(
                     )));
                 }
             }
-            "Enum8" => {
-                return Err(KlickhouseError::TypeParseError(
-                    "unsupported Enum8 type".to_string(),
-                ));
-            }
-            "Enum16" => {
-                return Err(KlickhouseError::TypeParseError(
-                    "unsupported Enum16 type".to_string(),
-                ));
+            "Enum8" | "Enum16" => {
+                if nested {
+                    return Err(KlickhouseError::TypeParseError(format!(
+                        "unsupported {ident} type in nested type"
+                    )));
+                }
+                // Enum values are selected as strings (see `select_column`).
+                ArrowDataType::from(DataType::Dictionary(
+                    Box::new(DataType::Int32),
+                    Box::new(DataType::Utf8),
+                ))
             }
             "LowCardinality" => {
                 if args.len() != 1 {
@@ -569,9 +778,19 @@ pub fn clickhouse_type_to_arrow_type(
                         args.len()
                     )));
                 }
-                return Err(KlickhouseError::TypeParseError(
-                    "unsupported LowCardinality type".to_string(),
-                ));
+                // Values are sent as the inner type, only top level strings
+                // are dictionary encoded.
+                let dt = parse_type(args[0], nested)?;
+                match dt.inner {
+                    DataType::Utf8 if !nested => ArrowDataType {
+                        nullable: dt.nullable,
+                        inner: DataType::Dictionary(
+                            Box::new(DataType::Int32),
+                            Box::new(DataType::Utf8),
+                        ),
+                    },
+                    _ => dt,
+                }
             }
             "Array" => {
                 if args.len() != 1 {
@@ -580,23 +799,17 @@ pub fn clickhouse_type_to_arrow_type(
                         args.len()
                     )));
                 }
-                return Err(KlickhouseError::TypeParseError(
-                    "unsupported Array type".to_string(),
-                ));
+                let dt = parse_type(args[0], true)?;
+                DataType::new_list(dt.inner, true).into()
             }
             "Nested" => {
-                return Err(KlickhouseError::TypeParseError(
-                    "unsupported Nested type".to_string(),
-                ));
+                // Nested columns are arrays of tuples.
+                let fields = parse_tuple_fields(&args)?;
+                DataType::new_list(DataType::Struct(fields), true).into()
             }
             "Tuple" => {
-                // let mut inner = vec![];
-                // for arg in args {
-                //     inner.push(arg.trim().parse()?);
-                // }
-                return Err(KlickhouseError::TypeParseError(
-                    "unsupported Tuple type".to_string(),
-                ));
+                let fields = parse_tuple_fields(&args)?;
+                DataType::Struct(fields).into()
             }
             "Nullable" => {
                 if args.len() != 1 {
@@ -605,7 +818,7 @@ pub fn clickhouse_type_to_arrow_type(
                         args.len()
                     )));
                 }
-                let dt = clickhouse_type_to_arrow_type(args[0])?;
+                let dt = parse_type(args[0], nested)?;
                 ArrowDataType {
                     nullable: true,
                     inner: dt.inner,
@@ -618,9 +831,17 @@ pub fn clickhouse_type_to_arrow_type(
                         args.len()
                     )));
                 }
-                return Err(KlickhouseError::TypeParseError(
-                    "unsupported Map type".to_string(),
-                ));
+                let key = parse_type(args[0], true)?;
+                let value = parse_type(args[1], true)?;
+                let entries = Field::new(
+                    "entries",
+                    DataType::Struct(Fields::from(vec![
+                        Field::new("keys", key.inner, false),
+                        Field::new("values", value.inner, true),
+                    ])),
+                    false,
+                );
+                DataType::Map(Arc::new(entries), false).into()
             }
             _ => {
                 return Err(KlickhouseError::TypeParseError(format!(
@@ -695,3 +916,125 @@ pub fn clickhouse_type_to_arrow_type(
         }
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(typ: &str) -> ArrowDataType {
+        clickhouse_type_to_arrow_type(typ).unwrap()
+    }
+
+    #[test]
+    fn parse_array() {
+        let dt = parse("Array(Nullable(String))");
+        assert_eq!(DataType::new_list(DataType::Utf8, true), dt.inner);
+        assert!(!dt.nullable);
+
+        let dt = parse("Array(Array(Int32))");
+        assert_eq!(
+            DataType::new_list(DataType::new_list(DataType::Int32, true), true),
+            dt.inner
+        );
+    }
+
+    #[test]
+    fn parse_map() {
+        let dt = parse("Map(LowCardinality(String), UInt64)");
+        let entries = Field::new(
+            "entries",
+            DataType::Struct(Fields::from(vec![
+                Field::new("keys", DataType::Utf8, false),
+                Field::new("values", DataType::UInt64, true),
+            ])),
+            false,
+        );
+        assert_eq!(DataType::Map(Arc::new(entries), false), dt.inner);
+    }
+
+    #[test]
+    fn parse_tuple() {
+        let dt = parse("Tuple(Int32, Nullable(String))");
+        assert_eq!(
+            DataType::Struct(Fields::from(vec![
+                Field::new("1", DataType::Int32, false),
+                Field::new("2", DataType::Utf8, true),
+            ])),
+            dt.inner
+        );
+
+        let dt = parse("Tuple(a Int32, b DateTime64(3, 'UTC'))");
+        assert_eq!(
+            DataType::Struct(Fields::from(vec![
+                Field::new("a", DataType::Int32, false),
+                Field::new(
+                    "b",
+                    DataType::Timestamp(TimeUnit::Millisecond, Some("UTC".into())),
+                    false
+                ),
+            ])),
+            dt.inner
+        );
+    }
+
+    #[test]
+    fn parse_nested() {
+        let dt = parse("Nested(x Int32, y String)");
+        assert_eq!(
+            DataType::new_list(
+                DataType::Struct(Fields::from(vec![
+                    Field::new("x", DataType::Int32, false),
+                    Field::new("y", DataType::Utf8, false),
+                ])),
+                true
+            ),
+            dt.inner
+        );
+    }
+
+    #[test]
+    fn parse_dictionary() {
+        let dict = DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8));
+
+        let dt = parse("LowCardinality(Nullable(String))");
+        assert_eq!(dict, dt.inner);
+        assert!(dt.nullable);
+
+        let dt = parse("Enum8('a, (b)' = 1, 'it\\'s' = 2)");
+        assert_eq!(dict, dt.inner);
+        assert!(!dt.nullable);
+
+        // Only strings are dictionary encoded.
+        let dt = parse("LowCardinality(UInt32)");
+        assert_eq!(DataType::UInt32, dt.inner);
+
+        // Enums can't be read inside other types.
+        clickhouse_type_to_arrow_type("Array(Enum16('a' = 1))").unwrap_err();
+    }
+
+    #[test]
+    fn convert_nested_values() {
+        let dt = parse("Array(Tuple(Int32, Nullable(String)))");
+        let column = vec![
+            Value::Array(vec![
+                Value::Tuple(vec![Value::Int32(1), Value::String(b"a".to_vec())]),
+                Value::Tuple(vec![Value::Int32(2), Value::Null]),
+            ]),
+            Value::Array(vec![]),
+        ];
+        let arr = column_to_array(dt.inner, column, false).unwrap();
+        let arr = arr.as_any().downcast_ref::<ListArray>().unwrap();
+        assert_eq!(2, arr.len());
+        assert_eq!(2, arr.value_length(0));
+        assert_eq!(0, arr.value_length(1));
+
+        let dt = parse("Map(String, Int64)");
+        let column = vec![Value::Map(
+            vec![Value::String(b"k".to_vec())],
+            vec![Value::Int64(4)],
+        )];
+        let arr = column_to_array(dt.inner, column, false).unwrap();
+        let arr = arr.as_any().downcast_ref::<MapArray>().unwrap();
+        assert_eq!(1, arr.value_length(0));
+    }
+}
@@ -0,0 +1,413 @@
+//! Run UPDATE and DELETE statements against tables in external databases.
+//!
+//! DataFusion's `TableProvider` only knows how to insert, so table providers
+//! that are able to modify rows in place implement [`ExternalDml`] instead.
+//! Statements are only run if every expression can be executed by the
+//! external database, we never fall back to filtering rows ourselves.
+
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use datafusion::arrow::datatypes::{DataType, Schema};
+use datafusion::common::{DFSchema, DFSchemaRef};
+use datafusion::datasource::TableProvider;
+use datafusion::error::{DataFusionError, Result as DatafusionResult};
+use datafusion::logical_expr::expr_rewriter::unnormalize_col;
+use datafusion::logical_expr::{Expr, ExprSchemable};
+use datafusion::optimizer::simplify_expressions::{ExprSimplifier, SimplifyContext};
+use datafusion::physical_expr::execution_props::ExecutionProps;
+
+use super::query::{SqlExprWriter, SqlQueryPushdown};
+use crate::mysql::MysqlTableProvider;
+use crate::postgres::PostgresTableProvider;
+use crate::sqlite::SqliteTableProvider;
+use crate::sqlserver::SqlServerTableProvider;
+
+/// A table provider for an external table that's able to update and delete
+/// rows.
+#[async_trait]
+pub trait ExternalDml: Sync + Send {
+    /// Set columns to the given values for all rows matching `where_expr`,
+    /// or all rows if there's no expression.
+    ///
+    /// Returns the number of updated rows.
+    async fn update(
+        &self,
+        updates: &[(String, Expr)],
+        where_expr: Option<&Expr>,
+    ) -> DatafusionResult<u64>;
+
+    /// Delete all rows matching `where_expr`, or all rows if there's no
+    /// expression.
+    ///
+    /// Returns the number of deleted rows.
+    async fn delete(&self, where_expr: Option<&Expr>) -> DatafusionResult<u64>;
+}
+
+/// External databases with tables implementing [`ExternalDml`].
+///
+/// Decided when planning from the table's options, and used to get the DML
+/// implementation of the table provider created for those options.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DmlDatasource {
+    Sqlite,
+    Postgres,
+    Mysql,
+    SqlServer,
+}
+
+impl DmlDatasource {
+    /// Get the DML implementation of a provider for a table in this
+    /// datasource.
+    pub fn external_dml<'a>(
+        &self,
+        provider: &'a dyn TableProvider,
+    ) -> DatafusionResult<&'a dyn ExternalDml> {
+        let provider = provider.as_any();
+        let dml: Option<&dyn ExternalDml> = match self {
+            Self::Sqlite => provider
+                .downcast_ref::<SqliteTableProvider>()
+                .map(|p| p as _),
+            Self::Postgres => provider
+                .downcast_ref::<PostgresTableProvider>()
+                .map(|p| p as _),
+            Self::Mysql => provider
+                .downcast_ref::<MysqlTableProvider>()
+                .map(|p| p as _),
+            Self::SqlServer => provider
+                .downcast_ref::<SqlServerTableProvider>()
+                .map(|p| p as _),
+        };
+        dml.ok_or_else(|| {
+            DataFusionError::Internal(format!("table provider is not a {self} table provider"))
+        })
+    }
+}
+
+impl fmt::Display for DmlDatasource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Sqlite => "sqlite",
+            Self::Postgres => "postgres",
+            Self::Mysql => "mysql",
+            Self::SqlServer => "sql_server",
+        })
+    }
+}
+
+impl FromStr for DmlDatasource {
+    type Err = DataFusionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "sqlite" => Self::Sqlite,
+            "postgres" => Self::Postgres,
+            "mysql" => Self::Mysql,
+            "sql_server" => Self::SqlServer,
+            other => {
+                return Err(DataFusionError::Internal(format!(
+                    "unknown dml datasource: {other}"
+                )))
+            }
+        })
+    }
+}
+
+/// Generate an UPDATE statement for a table in a database supporting query
+/// pushdown.
+pub fn sql_update_statement(
+    pushdown: &dyn SqlQueryPushdown,
+    schema: &Schema,
+    updates: &[(String, Expr)],
+    where_expr: Option<&Expr>,
+) -> DatafusionResult<String> {
+    if updates.is_empty() {
+        return Err(DataFusionError::Plan(
+            "UPDATE requires at least one column to set".to_string(),
+        ));
+    }
+
+    let writer = DmlExprWriter::try_new(pushdown, schema)?;
+
+    let mut seen = HashSet::with_capacity(updates.len());
+    let mut assignments = Vec::with_capacity(updates.len());
+    for (column, expr) in updates {
+        // Make sure we're not setting some arbitrary column.
+        schema.field_with_name(column)?;
+        if !seen.insert(column.as_str()) {
+            return Err(DataFusionError::Plan(format!(
+                "column {column} set more than once"
+            )));
+        }
+        assignments.push(format!(
+            "{} = {}",
+            pushdown.column_reference(column),
+            writer.write(expr)?
+        ));
+    }
+
+    let mut stmt = format!(
+        "UPDATE {} SET {}",
+        pushdown.table_reference(),
+        assignments.join(", ")
+    );
+    if let Some(expr) = where_expr {
+        stmt.push_str(" WHERE ");
+        stmt.push_str(&writer.write_predicate(expr)?);
+    }
+
+    Ok(stmt)
+}
+
+/// Generate a DELETE statement for a table in a database supporting query
+/// pushdown.
+pub fn sql_delete_statement(
+    pushdown: &dyn SqlQueryPushdown,
+    schema: &Schema,
+    where_expr: Option<&Expr>,
+) -> DatafusionResult<String> {
+    let writer = DmlExprWriter::try_new(pushdown, schema)?;
+
+    let mut stmt = format!("DELETE FROM {}", pushdown.table_reference());
+    if let Some(expr) = where_expr {
+        stmt.push_str(" WHERE ");
+        stmt.push_str(&writer.write_predicate(expr)?);
+    }
+
+    Ok(stmt)
+}
+
+/// Writes expressions in UPDATE and DELETE statements, erroring if any part
+/// of an expression can't be executed by the database.
+struct DmlExprWriter<'a> {
+    pushdown: &'a dyn SqlQueryPushdown,
+    schema: DFSchemaRef,
+    /// Quoted references to the columns in the schema.
+    columns: Vec<String>,
+    /// Props for folding constants, `now()` is evaluated once for the whole
+    /// statement.
+    props: ExecutionProps,
+}
+
+impl<'a> DmlExprWriter<'a> {
+    fn try_new(pushdown: &'a dyn SqlQueryPushdown, schema: &Schema) -> DatafusionResult<Self> {
+        let columns = schema
+            .fields()
+            .iter()
+            .map(|f| pushdown.column_reference(f.name()))
+            .collect();
+
+        // Props start out at the unix epoch, which would be used for `now()`.
+        let mut props = ExecutionProps::new();
+        props.start_execution();
+
+        Ok(DmlExprWriter {
+            pushdown,
+            schema: Arc::new(DFSchema::try_from(schema.clone())?),
+            columns,
+            props,
+        })
+    }
+
+    fn write(&self, expr: &Expr) -> DatafusionResult<String> {
+        // Columns are qualified with the name of the table in our catalog,
+        // the statement only references the one table.
+        let expr = unnormalize_col(expr.clone());
+
+        // Fold expressions that the database might not be able to execute
+        // (e.g. `now() - interval '1 day'`) into literals.
+        let simplifier =
+            ExprSimplifier::new(SimplifyContext::new(&self.props).with_schema(self.schema.clone()));
+        let expr = simplifier.simplify(expr.clone()).unwrap_or(expr);
+
+        // Strings have to be compared the same way we would, a case insensitive
+        // collation would modify rows that don't match the predicate.
+        SqlExprWriter::new(self.pushdown, &self.schema, &self.columns)
+            .with_strings_compared_by_bytes()
+            .write(&expr)
+            .ok_or_else(|| {
+                DataFusionError::Plan(format!(
+                    "cannot execute expression in external database: {expr}"
+                ))
+            })
+    }
+
+    fn write_predicate(&self, expr: &Expr) -> DatafusionResult<String> {
+        let typ = unnormalize_col(expr.clone()).get_type(self.schema.as_ref())?;
+        if typ != DataType::Boolean {
+            return Err(DataFusionError::Plan(format!(
+                "WHERE expression must be a boolean, got {typ}"
+            )));
+        }
+        self.write(expr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use datafusion::arrow::datatypes::{Field, SchemaRef, TimeUnit};
+    use datafusion::common::Column;
+    use datafusion::logical_expr::expr_fn::now;
+    use datafusion::physical_plan::empty::EmptyExec;
+    use datafusion::physical_plan::ExecutionPlan;
+    use datafusion::prelude::{col, lit};
+
+    use super::*;
+    use crate::common::query::quote_table_reference;
+    use crate::common::util::Datasource;
+
+    struct TestPushdown(Datasource);
+
+    #[async_trait]
+    impl SqlQueryPushdown for TestPushdown {
+        fn dialect(&self) -> Datasource {
+            self.0
+        }
+
+        fn table_reference(&self) -> String {
+            quote_table_reference(self.0, Some("public"), "t")
+        }
+
+        fn cast_type(&self, _datatype: &DataType) -> Option<String> {
+            None
+        }
+
+        async fn query_exec(
+            &self,
+            _query: String,
+            schema: SchemaRef,
+        ) -> DatafusionResult<Arc<dyn ExecutionPlan>> {
+            Ok(Arc::new(EmptyExec::new(schema)))
+        }
+    }
+
+    fn test_schema() -> Schema {
+        Schema::new(vec![
+            Field::new("a", DataType::Int64, true),
+            Field::new("b", DataType::Utf8, true),
+            Field::new(
+                "Created At",
+                DataType::Timestamp(TimeUnit::Nanosecond, Some("+00:00".into())),
+                true,
+            ),
+        ])
+    }
+
+    #[test]
+    fn update_statement() {
+        let schema = test_schema();
+        let stmt = sql_update_statement(
+            &TestPushdown(Datasource::Postgres),
+            &schema,
+            &[
+                ("a".to_string(), col("t.a") + lit(1_i64)),
+                ("b".to_string(), lit("x")),
+            ],
+            Some(&col("t.a").gt(lit(1_i64) + lit(2_i64))),
+        )
+        .unwrap();
+        assert_eq!(
+            "UPDATE \"public\".\"t\" SET \"a\" = (\"a\" + 1), \"b\" = 'x' WHERE (\"a\" > 3)",
+            stmt
+        );
+
+        sql_update_statement(
+            &TestPushdown(Datasource::Postgres),
+            &schema,
+            &[("c".to_string(), lit(1))],
+            None,
+        )
+        .unwrap_err();
+        sql_update_statement(
+            &TestPushdown(Datasource::Postgres),
+            &schema,
+            &[("a".to_string(), lit(1)), ("a".to_string(), lit(2))],
+            None,
+        )
+        .unwrap_err();
+    }
+
+    #[test]
+    fn delete_statement() {
+        let schema = test_schema();
+        let stmt =
+            sql_delete_statement(&TestPushdown(Datasource::Postgres), &schema, None).unwrap();
+        assert_eq!("DELETE FROM \"public\".\"t\"", stmt);
+
+        let stmt = sql_delete_statement(
+            &TestPushdown(Datasource::Postgres),
+            &schema,
+            Some(&col("b").is_null()),
+        )
+        .unwrap();
+        assert_eq!("DELETE FROM \"public\".\"t\" WHERE (\"b\" IS NULL)", stmt);
+
+        // Not a predicate.
+        sql_delete_statement(
+            &TestPushdown(Datasource::Postgres),
+            &schema,
+            Some(&col("a")),
+        )
+        .unwrap_err();
+        // Modulo isn't pushed down.
+        sql_delete_statement(
+            &TestPushdown(Datasource::Postgres),
+            &schema,
+            Some(&(col("a") % lit(2_i64)).eq(lit(0_i64))),
+        )
+        .unwrap_err();
+    }
+
+    #[test]
+    fn now_is_current_time() {
+        let schema = test_schema();
+        let stmt = sql_delete_statement(
+            &TestPushdown(Datasource::Postgres),
+            &schema,
+            Some(&Expr::Column(Column::from_name("Created At")).lt(now())),
+        )
+        .unwrap();
+
+        // `now()` is folded into a literal of the current time rather than
+        // the unix epoch.
+        assert!(!stmt.contains("1970-01-01"), "{stmt}");
+        assert!(
+            stmt.starts_with("DELETE FROM \"public\".\"t\" WHERE (\"Created At\" < "),
+            "{stmt}"
+        );
+    }
+
+    #[test]
+    fn strings_compared_by_bytes() {
+        let schema = test_schema();
+
+        let stmt = sql_delete_statement(
+            &TestPushdown(Datasource::Postgres),
+            &schema,
+            Some(&col("b").eq(lit("x")).or(col("b").lt(lit("y")))),
+        )
+        .unwrap();
+        assert_eq!(
+            "DELETE FROM \"public\".\"t\" WHERE ((\"b\" = 'x') OR ((\"b\" COLLATE \"C\") < ('y' COLLATE \"C\")))",
+            stmt
+        );
+
+        // Collations may consider different strings equal, or order them
+        // differently.
+        for dialect in [Datasource::MySql, Datasource::SqlServer] {
+            let pushdown = TestPushdown(dialect);
+            sql_delete_statement(&pushdown, &schema, Some(&col("b").eq(lit("x")))).unwrap_err();
+            sql_delete_statement(&pushdown, &schema, Some(&col("b").gt(lit("x")))).unwrap_err();
+            sql_update_statement(
+                &pushdown,
+                &schema,
+                &[("b".to_string(), lit("x"))],
+                Some(&col("a").eq(lit(1_i64))),
+            )
+            .unwrap();
+        }
+    }
+}
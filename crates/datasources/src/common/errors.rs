@@ -9,9 +9,44 @@ pub enum DatasourceCommonError {
     #[error("Scalar of type '{0}' not supported")]
     UnsupportedDatafusionScalar(datafusion::arrow::datatypes::DataType),
 
+    #[error("Incompatible types for column '{column}': {left} and {right}")]
+    IncompatibleColumnTypes {
+        column: String,
+        left: datafusion::arrow::datatypes::DataType,
+        right: datafusion::arrow::datatypes::DataType,
+    },
+
     #[error("Invalid url: {0}")]
     InvalidUrl(String),
 
+    #[error("Invalid ssl mode '{0}', expected one of 'disable', 'prefer' or 'require'")]
+    InvalidSslMode(String),
+
+    #[error("Invalid TLS configuration: {0}")]
+    InvalidTlsConfig(String),
+
+    #[error("Timed out after {timeout:?} waiting for a {datasource} connection to {description}")]
+    ConnectionPoolTimeout {
+        datasource: &'static str,
+        description: String,
+        timeout: std::time::Duration,
+    },
+
+    #[error("Invalid secret reference '{0}', expected 'secret://<env|file|vault>/<path>'")]
+    InvalidSecretReference(String),
+
+    #[error("Failed to resolve secret '{reference}': {reason}")]
+    SecretResolution { reference: String, reason: String },
+
+    #[error("Secret '{0}' is not allowed on this server")]
+    SecretNotAllowed(String),
+
+    #[error("Invalid secret rule '{0}', expected '<env|file|vault>:<pattern>'")]
+    InvalidSecretRule(String),
+
+    #[error("Secret access already set")]
+    SecretAccessAlreadySet,
+
     #[error(transparent)]
     ReprError(#[from] repr::error::ReprError),
 
@@ -30,8 +65,14 @@ pub enum DatasourceCommonError {
     #[error(transparent)]
     IoError(#[from] std::io::Error),
 
+    #[error(transparent)]
+    SerdeJsonError(#[from] serde_json::Error),
+
     #[error(transparent)]
     UrlParseError(#[from] url::ParseError),
+
+    #[error(transparent)]
+    XlsxError(#[from] rust_xlsxwriter::XlsxError),
 }
 
 pub type Result<T, E = DatasourceCommonError> = std::result::Result<T, E>;
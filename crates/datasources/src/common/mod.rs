@@ -11,9 +11,15 @@ use datafusion::physical_expr::create_physical_expr;
 use datafusion::physical_plan::PhysicalExpr;
 use datafusion::prelude::Expr;
 
+pub mod dml;
 pub mod errors;
+pub mod pool;
+pub mod query;
+pub mod schema;
+pub mod secrets;
 pub mod sink;
 pub mod ssh;
+pub mod tls;
 pub mod url;
 pub mod util;
 
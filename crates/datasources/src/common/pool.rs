@@ -0,0 +1,529 @@
+//! Engine-wide connection pooling for external databases.
+//!
+//! Opening a connection (and possibly an ssh tunnel) is expensive, and doing
+//! it for every query can exhaust the connection limit of the upstream
+//! database. Data sources instead check connections out of a
+//! [`ConnectionPool`] keyed by everything that's needed to connect, and
+//! connections are returned to the pool once dropped.
+//!
+//! Connections should only be checked out for as long as they're needed (e.g.
+//! for a single stream or statement). Holding on to a connection while
+//! checking out another one for the same key can exhaust the pool.
+//!
+//! A connection dropped in the middle of an operation (see
+//! [`PooledConnection::begin_operation`]) is closed instead of being returned
+//! to the pool, since it may still be streaming results for the operation.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Weak};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tracing::debug;
+
+use super::errors::DatasourceCommonError;
+
+/// Max number of connections that can be open at once for a single key.
+pub const DEFAULT_MAX_CONNECTIONS: usize = 16;
+
+/// How long a connection can sit idle in the pool before it's closed.
+pub const DEFAULT_IDLE_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+/// How long to wait for a connection to become available when the pool is at
+/// capacity.
+pub const DEFAULT_ACQUIRE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How often idle connections are checked for expiration.
+const REAP_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Stats for all pools, used for the `connection_pools` system table.
+static POOL_STATS: Lazy<Mutex<Vec<Arc<PoolStats>>>> = Lazy::new(|| Mutex::new(Vec::new()));
+
+/// A connection that can be stored in a pool.
+#[async_trait]
+pub trait PoolableConnection: Send + Sync + 'static {
+    /// Check if the connection is still usable.
+    ///
+    /// Called before handing out a connection that's been sitting idle.
+    async fn is_healthy(&mut self) -> bool;
+}
+
+/// A pool of connections for a single data source.
+pub struct ConnectionPool<C> {
+    datasource: &'static str,
+    max_connections: usize,
+    idle_timeout: Duration,
+    acquire_timeout: Duration,
+    pools: Mutex<HashMap<String, Arc<KeyedPool<C>>>>,
+    reaper_started: AtomicBool,
+}
+
+impl<C: PoolableConnection> ConnectionPool<C> {
+    /// Create a new pool using the default limits.
+    pub fn new(datasource: &'static str) -> Self {
+        ConnectionPool {
+            datasource,
+            max_connections: DEFAULT_MAX_CONNECTIONS,
+            idle_timeout: DEFAULT_IDLE_TIMEOUT,
+            acquire_timeout: DEFAULT_ACQUIRE_TIMEOUT,
+            pools: Mutex::new(HashMap::new()),
+            reaper_started: AtomicBool::new(false),
+        }
+    }
+
+    /// Get a connection for `key`, reusing an idle connection if there's a
+    /// healthy one.
+    ///
+    /// `key` needs to uniquely identify everything used to connect (including
+    /// credentials and tunnels). `description` is only used for display, and
+    /// shouldn't contain any secrets. `connect` is called if a new connection
+    /// needs to be opened.
+    pub async fn get<F, Fut, E>(
+        self: &Arc<Self>,
+        key: &str,
+        description: impl FnOnce() -> String,
+        connect: F,
+    ) -> Result<PooledConnection<C>, E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<C, E>>,
+        E: From<DatasourceCommonError>,
+    {
+        self.start_reaper();
+
+        let pool = {
+            let mut pools = self.pools.lock();
+            pools
+                .entry(key.to_string())
+                .or_insert_with(|| {
+                    let stats = Arc::new(PoolStats {
+                        datasource: self.datasource,
+                        description: description(),
+                        max_connections: self.max_connections,
+                        in_use: AtomicUsize::new(0),
+                        idle: AtomicUsize::new(0),
+                        opened: AtomicU64::new(0),
+                        reused: AtomicU64::new(0),
+                        closed: AtomicU64::new(0),
+                    });
+                    POOL_STATS.lock().push(stats.clone());
+                    Arc::new(KeyedPool {
+                        semaphore: Arc::new(Semaphore::new(self.max_connections)),
+                        idle: Mutex::new(Vec::new()),
+                        stats,
+                    })
+                })
+                .clone()
+        };
+
+        let permit = match tokio::time::timeout(
+            self.acquire_timeout,
+            pool.semaphore.clone().acquire_owned(),
+        )
+        .await
+        {
+            Ok(permit) => permit.expect("semaphore should never be closed"),
+            Err(_) => {
+                return Err(DatasourceCommonError::ConnectionPoolTimeout {
+                    datasource: self.datasource,
+                    description: pool.stats.description.clone(),
+                    timeout: self.acquire_timeout,
+                }
+                .into())
+            }
+        };
+
+        pool.evict_expired(self.idle_timeout);
+
+        // Prefer the most recently used connection, it's the most likely to
+        // still be alive.
+        while let Some(mut idle) = pool.pop_idle() {
+            if idle.conn.is_healthy().await {
+                pool.stats.reused.fetch_add(1, Ordering::Relaxed);
+                return Ok(PooledConnection::new(idle.conn, pool, permit));
+            }
+            debug!(datasource = %self.datasource, "closing unhealthy pooled connection");
+            pool.stats.closed.fetch_add(1, Ordering::Relaxed);
+        }
+
+        let conn = connect().await?;
+        pool.stats.opened.fetch_add(1, Ordering::Relaxed);
+
+        Ok(PooledConnection::new(conn, pool, permit))
+    }
+
+    /// Close all idle connections that have been idle for too long.
+    ///
+    /// Pools for keys without any open connections are removed.
+    fn evict_expired(&self) {
+        let mut pools = self.pools.lock();
+        pools.retain(|_, pool| {
+            pool.evict_expired(self.idle_timeout);
+            // Checked out connections and pending `get`s hold a reference to
+            // the keyed pool.
+            Arc::strong_count(pool) > 1 || !pool.idle.lock().is_empty()
+        });
+    }
+
+    /// Start a background task for closing expired idle connections if one
+    /// isn't already running.
+    fn start_reaper(self: &Arc<Self>) {
+        if self.reaper_started.swap(true, Ordering::Relaxed) {
+            return;
+        }
+
+        let pool: Weak<Self> = Arc::downgrade(self);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(REAP_INTERVAL);
+            loop {
+                interval.tick().await;
+                match pool.upgrade() {
+                    Some(pool) => pool.evict_expired(),
+                    None => return,
+                }
+            }
+        });
+    }
+}
+
+/// Connections for a single key.
+struct KeyedPool<C> {
+    /// Limits the number of open connections.
+    semaphore: Arc<Semaphore>,
+    idle: Mutex<Vec<IdleConnection<C>>>,
+    stats: Arc<PoolStats>,
+}
+
+impl<C> KeyedPool<C> {
+    fn pop_idle(&self) -> Option<IdleConnection<C>> {
+        let idle = self.idle.lock().pop();
+        if idle.is_some() {
+            self.stats.idle.fetch_sub(1, Ordering::Relaxed);
+        }
+        idle
+    }
+
+    fn evict_expired(&self, idle_timeout: Duration) {
+        let mut idle = self.idle.lock();
+        let before = idle.len();
+        idle.retain(|conn| conn.idle_since.elapsed() < idle_timeout);
+        let evicted = before - idle.len();
+        if evicted > 0 {
+            self.stats.idle.fetch_sub(evicted, Ordering::Relaxed);
+            self.stats
+                .closed
+                .fetch_add(evicted as u64, Ordering::Relaxed);
+        }
+    }
+}
+
+impl<C> Drop for KeyedPool<C> {
+    fn drop(&mut self) {
+        POOL_STATS
+            .lock()
+            .retain(|stats| !Arc::ptr_eq(stats, &self.stats));
+    }
+}
+
+struct IdleConnection<C> {
+    conn: C,
+    idle_since: Instant,
+}
+
+/// A connection checked out from a pool.
+///
+/// The connection is returned to the pool when dropped.
+pub struct PooledConnection<C> {
+    /// Only `None` after being returned to the pool.
+    conn: Option<C>,
+    /// Number of operations currently in progress on the connection.
+    operations: AtomicUsize,
+    pool: Arc<KeyedPool<C>>,
+    _permit: OwnedSemaphorePermit,
+}
+
+impl<C> PooledConnection<C> {
+    fn new(conn: C, pool: Arc<KeyedPool<C>>, permit: OwnedSemaphorePermit) -> Self {
+        pool.stats.in_use.fetch_add(1, Ordering::Relaxed);
+        PooledConnection {
+            conn: Some(conn),
+            operations: AtomicUsize::new(0),
+            pool,
+            _permit: permit,
+        }
+    }
+
+    /// Close the connection instead of returning it to the pool.
+    ///
+    /// Should be used if the connection is known to be in a bad state.
+    pub fn discard(mut self) {
+        self.conn = None;
+        self.pool.stats.closed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Mark the start of an operation that leaves the connection unusable
+    /// for other queries until completed, e.g. a COPY, a result stream or a
+    /// transaction.
+    ///
+    /// If the connection is dropped before the matching
+    /// [`end_operation`](Self::end_operation), it's closed instead of being
+    /// returned to the pool.
+    pub fn begin_operation(&self) {
+        self.operations.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Mark an operation started with
+    /// [`begin_operation`](Self::begin_operation) as completed.
+    pub fn end_operation(&self) {
+        let _ = self
+            .operations
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1));
+    }
+}
+
+impl<C> Deref for PooledConnection<C> {
+    type Target = C;
+
+    fn deref(&self) -> &Self::Target {
+        self.conn.as_ref().expect("connection to be present")
+    }
+}
+
+impl<C> DerefMut for PooledConnection<C> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.conn.as_mut().expect("connection to be present")
+    }
+}
+
+impl<C> Drop for PooledConnection<C> {
+    fn drop(&mut self) {
+        self.pool.stats.in_use.fetch_sub(1, Ordering::Relaxed);
+        if self.operations.load(Ordering::Relaxed) > 0 {
+            if self.conn.take().is_some() {
+                debug!("closing pooled connection dropped during an operation");
+                self.pool.stats.closed.fetch_add(1, Ordering::Relaxed);
+            }
+            return;
+        }
+        if let Some(conn) = self.conn.take() {
+            self.pool.idle.lock().push(IdleConnection {
+                conn,
+                idle_since: Instant::now(),
+            });
+            self.pool.stats.idle.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+impl<C: std::fmt::Debug> std::fmt::Debug for PooledConnection<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PooledConnection")
+            .field("conn", &self.conn)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Stats for connections of a single key in a pool.
+#[derive(Debug)]
+struct PoolStats {
+    datasource: &'static str,
+    description: String,
+    max_connections: usize,
+    in_use: AtomicUsize,
+    idle: AtomicUsize,
+    opened: AtomicU64,
+    reused: AtomicU64,
+    closed: AtomicU64,
+}
+
+/// Point in time stats for a connection pool.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConnectionPoolStats {
+    pub datasource: &'static str,
+    /// Description of the connection. Doesn't include secrets.
+    pub description: String,
+    pub max_connections: usize,
+    pub in_use: usize,
+    pub idle: usize,
+    /// Total number of connections opened.
+    pub opened: u64,
+    /// Total number of times an idle connection was reused.
+    pub reused: u64,
+    /// Total number of connections closed for being idle too long,
+    /// unhealthy, or dropped during an operation.
+    pub closed: u64,
+}
+
+/// Get the current stats for all connection pools.
+pub fn connection_pool_stats() -> Vec<ConnectionPoolStats> {
+    POOL_STATS
+        .lock()
+        .iter()
+        .map(|stats| ConnectionPoolStats {
+            datasource: stats.datasource,
+            description: stats.description.clone(),
+            max_connections: stats.max_connections,
+            in_use: stats.in_use.load(Ordering::Relaxed),
+            idle: stats.idle.load(Ordering::Relaxed),
+            opened: stats.opened.load(Ordering::Relaxed),
+            reused: stats.reused.load(Ordering::Relaxed),
+            closed: stats.closed.load(Ordering::Relaxed),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    struct TestConn {
+        id: usize,
+        healthy: bool,
+    }
+
+    #[async_trait]
+    impl PoolableConnection for TestConn {
+        async fn is_healthy(&mut self) -> bool {
+            self.healthy
+        }
+    }
+
+    async fn get(
+        pool: &Arc<ConnectionPool<TestConn>>,
+        id: usize,
+        healthy: bool,
+    ) -> PooledConnection<TestConn> {
+        pool.get::<_, _, DatasourceCommonError>(
+            "key",
+            || "test".to_string(),
+            || async move { Ok(TestConn { id, healthy }) },
+        )
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn reuse_connections() {
+        let pool = Arc::new(ConnectionPool::new("test"));
+
+        let conn = get(&pool, 1, true).await;
+        assert_eq!(1, conn.id);
+        drop(conn);
+
+        // Reuses the idle connection.
+        let conn = get(&pool, 2, true).await;
+        assert_eq!(1, conn.id);
+
+        // Opens a new connection while the first is in use.
+        let conn2 = get(&pool, 3, true).await;
+        assert_eq!(3, conn2.id);
+
+        drop(conn);
+        drop(conn2);
+
+        let stats = pool.pools.lock().get("key").unwrap().stats.clone();
+        assert_eq!(0, stats.in_use.load(Ordering::Relaxed));
+        assert_eq!(2, stats.idle.load(Ordering::Relaxed));
+        assert_eq!(2, stats.opened.load(Ordering::Relaxed));
+        assert_eq!(1, stats.reused.load(Ordering::Relaxed));
+    }
+
+    #[tokio::test]
+    async fn skip_unhealthy_connections() {
+        let pool = Arc::new(ConnectionPool::new("test"));
+
+        drop(get(&pool, 1, false).await);
+
+        let conn = get(&pool, 2, true).await;
+        assert_eq!(2, conn.id);
+    }
+
+    #[tokio::test]
+    async fn discard_connection() {
+        let pool = Arc::new(ConnectionPool::new("test"));
+
+        get(&pool, 1, true).await.discard();
+
+        let conn = get(&pool, 2, true).await;
+        assert_eq!(2, conn.id);
+    }
+
+    #[tokio::test]
+    async fn discard_connection_dropped_during_operation() {
+        let pool = Arc::new(ConnectionPool::new("test"));
+
+        let conn = get(&pool, 1, true).await;
+        conn.begin_operation();
+        drop(conn);
+
+        let conn = get(&pool, 2, true).await;
+        assert_eq!(2, conn.id);
+        conn.begin_operation();
+        conn.end_operation();
+        drop(conn);
+
+        // Completed operations don't prevent reuse.
+        let conn = get(&pool, 3, true).await;
+        assert_eq!(2, conn.id);
+    }
+
+    #[tokio::test]
+    async fn remove_stats_for_unused_pools() {
+        let pool = Arc::new(ConnectionPool {
+            idle_timeout: Duration::ZERO,
+            ..ConnectionPool::new("test_unused")
+        });
+        let has_stats = || {
+            POOL_STATS
+                .lock()
+                .iter()
+                .any(|stats| stats.datasource == "test_unused")
+        };
+
+        let conn = get(&pool, 1, true).await;
+        assert!(has_stats());
+
+        // Keep pools with checked out connections.
+        pool.evict_expired();
+        assert!(has_stats());
+
+        drop(conn);
+        pool.evict_expired();
+        assert!(pool.pools.lock().is_empty());
+        assert!(!has_stats());
+    }
+
+    #[tokio::test]
+    async fn timeout_when_exhausted() {
+        let pool = Arc::new(ConnectionPool {
+            max_connections: 1,
+            acquire_timeout: Duration::from_millis(10),
+            ..ConnectionPool::new("test")
+        });
+
+        let _conn = get(&pool, 1, true).await;
+        let res = pool
+            .get::<_, _, DatasourceCommonError>(
+                "key",
+                || "test".to_string(),
+                || async {
+                    Ok(TestConn {
+                        id: 2,
+                        healthy: true,
+                    })
+                },
+            )
+            .await;
+        assert!(matches!(
+            res,
+            Err(DatasourceCommonError::ConnectionPoolTimeout { .. })
+        ));
+    }
+}
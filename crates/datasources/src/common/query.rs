@@ -0,0 +1,634 @@
+//! Push down whole queries to external SQL databases.
+//!
+//! Table providers for SQL databases implement [`SqlQueryPushdown`] so that
+//! aggregates, sorts, limits and joins over tables in the same database can be
+//! executed by the database instead of pulling every row into GlareDB.
+
+use std::any::Any;
+use std::fmt::Write;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use datafusion::arrow::datatypes::{DataType, SchemaRef as ArrowSchemaRef};
+use datafusion::common::DFSchema;
+use datafusion::datasource::TableProvider;
+use datafusion::error::{DataFusionError, Result as DatafusionResult};
+use datafusion::execution::context::SessionState;
+use datafusion::logical_expr::expr::{AggregateFunction, AggregateFunctionDefinition, Alias, Cast};
+use datafusion::logical_expr::{
+    AggregateFunction as AggregateFunctionType,
+    BinaryExpr,
+    Expr,
+    ExprSchemable,
+    Operator,
+    TableType,
+};
+use datafusion::physical_expr::expressions::Column as PhysicalColumn;
+use datafusion::physical_plan::projection::ProjectionExec;
+use datafusion::physical_plan::{ExecutionPlan, PhysicalExpr};
+
+use super::util::{encode_literal_to_text, quote_ident, Datasource};
+use crate::clickhouse::ClickhouseTableProvider;
+use crate::mysql::MysqlTableProvider;
+use crate::postgres::PostgresTableProvider;
+use crate::snowflake::SnowflakeTableProvider;
+use crate::sqlserver::SqlServerTableProvider;
+
+/// A table provider for a SQL database that's able to execute arbitrary
+/// queries against its table.
+#[async_trait]
+pub trait SqlQueryPushdown: Sync + Send {
+    /// Dialect to generate queries in.
+    fn dialect(&self) -> Datasource;
+
+    /// Reference to the table for use in the query's FROM clause, quoted if
+    /// needed.
+    fn table_reference(&self) -> String;
+
+    /// Reference to a column of the table, quoted if needed.
+    fn column_reference(&self, column: &str) -> String {
+        quote_ident(self.dialect(), column)
+    }
+
+    /// Name of the type to cast expressions to so that the database returns
+    /// values of the given arrow type.
+    ///
+    /// Expressions producing types without a cast type won't be pushed down.
+    fn cast_type(&self, datatype: &DataType) -> Option<String>;
+
+    /// Check if `other` is a table that can be queried over the same
+    /// connection as this one, allowing joins between the two to be pushed
+    /// down.
+    fn shares_connection(&self, _other: &dyn TableProvider) -> bool {
+        false
+    }
+
+    /// Create an execution plan for running the query. The query is generated
+    /// such that its output matches the provided schema.
+    async fn query_exec(
+        &self,
+        query: String,
+        schema: ArrowSchemaRef,
+    ) -> DatafusionResult<Arc<dyn ExecutionPlan>>;
+}
+
+/// Get the query pushdown implementation for a table provider, if it has one.
+pub fn as_sql_query_pushdown(provider: &dyn TableProvider) -> Option<&dyn SqlQueryPushdown> {
+    let provider = provider.as_any();
+    if let Some(p) = provider.downcast_ref::<PostgresTableProvider>() {
+        return Some(p);
+    }
+    if let Some(p) = provider.downcast_ref::<MysqlTableProvider>() {
+        return Some(p);
+    }
+    if let Some(p) = provider.downcast_ref::<SqlServerTableProvider>() {
+        return Some(p);
+    }
+    if let Some(p) = provider.downcast_ref::<ClickhouseTableProvider>() {
+        return Some(p);
+    }
+    if let Some(p) = provider.downcast_ref::<SnowflakeTableProvider>() {
+        return Some(p);
+    }
+    None
+}
+
+/// A single ORDER BY expression.
+#[derive(Debug, Clone)]
+pub struct SqlOrderBy {
+    pub expr: String,
+    pub asc: bool,
+    pub nulls_first: bool,
+}
+
+/// Parts of a query against a table or a join of tables.
+#[derive(Debug, Clone, Default)]
+pub struct SqlQuery {
+    /// Expressions in the SELECT list, including any aliases.
+    pub select: Vec<String>,
+    pub filters: Vec<String>,
+    pub group_by: Vec<String>,
+    pub having: Vec<String>,
+    pub order_by: Vec<SqlOrderBy>,
+    pub limit: Option<usize>,
+    /// Rows to skip. Only used if `limit` is set.
+    pub offset: usize,
+}
+
+impl SqlQuery {
+    /// Generate the SQL for this query in the given dialect, selecting from
+    /// the given FROM clause.
+    pub fn to_sql(&self, dialect: Datasource, from: &str) -> String {
+        let mut sql = String::from("SELECT ");
+
+        // SQL Server doesn't support LIMIT, use TOP if we can. Otherwise
+        // OFFSET ... FETCH is added after the ORDER BY.
+        let use_top = dialect == Datasource::SqlServer && self.offset == 0;
+        if let (true, Some(limit)) = (use_top, self.limit) {
+            write!(sql, "TOP {limit} ").unwrap();
+        }
+
+        write!(sql, "{} FROM {from}", self.select.join(", ")).unwrap();
+
+        if !self.filters.is_empty() {
+            write!(sql, " WHERE {}", self.filters.join(" AND ")).unwrap();
+        }
+        if !self.group_by.is_empty() {
+            write!(sql, " GROUP BY {}", self.group_by.join(", ")).unwrap();
+        }
+        if !self.having.is_empty() {
+            write!(sql, " HAVING {}", self.having.join(" AND ")).unwrap();
+        }
+
+        let order_by: Vec<_> = self
+            .order_by
+            .iter()
+            .map(|o| order_by_to_sql(dialect, o))
+            .collect();
+
+        match (dialect, self.limit) {
+            (Datasource::SqlServer, Some(limit)) if !use_top => {
+                // OFFSET requires an ORDER BY.
+                if order_by.is_empty() {
+                    sql.push_str(" ORDER BY (SELECT NULL)");
+                } else {
+                    write!(sql, " ORDER BY {}", order_by.join(", ")).unwrap();
+                }
+                write!(
+                    sql,
+                    " OFFSET {} ROWS FETCH NEXT {limit} ROWS ONLY",
+                    self.offset
+                )
+                .unwrap();
+            }
+            (_, limit) => {
+                if !order_by.is_empty() {
+                    write!(sql, " ORDER BY {}", order_by.join(", ")).unwrap();
+                }
+                if let (false, Some(limit)) = (use_top, limit) {
+                    write!(sql, " LIMIT {limit}").unwrap();
+                    if self.offset != 0 {
+                        write!(sql, " OFFSET {}", self.offset).unwrap();
+                    }
+                }
+            }
+        }
+
+        sql
+    }
+}
+
+fn order_by_to_sql(dialect: Datasource, order_by: &SqlOrderBy) -> String {
+    let dir = if order_by.asc { "ASC" } else { "DESC" };
+    match dialect {
+        // No support for NULLS FIRST/LAST, order by nullness first.
+        Datasource::MySql | Datasource::SqlServer => {
+            let (null_val, not_null_val) = if order_by.nulls_first { (0, 1) } else { (1, 0) };
+            format!(
+                "CASE WHEN {expr} IS NULL THEN {null_val} ELSE {not_null_val} END, {expr} {dir}",
+                expr = order_by.expr,
+            )
+        }
+        _ => format!(
+            "{} {dir} NULLS {}",
+            order_by.expr,
+            if order_by.nulls_first {
+                "FIRST"
+            } else {
+                "LAST"
+            }
+        ),
+    }
+}
+
+/// Write a string expression so that it's ordered by its UTF-8 bytes, matching
+/// how strings are ordered locally.
+///
+/// Returns `None` if the dialect has no way of ordering strings by their
+/// bytes. Ordering by the database's collation (e.g. case insensitive or
+/// ignoring punctuation) would select different rows for a pushed down limit.
+pub fn byte_ordered_string(dialect: Datasource, expr: String) -> Option<String> {
+    match dialect {
+        Datasource::Postgres => Some(format!("({expr} COLLATE \"C\")")),
+        Datasource::Snowflake => Some(format!("COLLATE({expr}, 'utf8')")),
+        Datasource::Sqlite => Some(format!("({expr} COLLATE BINARY)")),
+        // Strings are always compared byte-wise.
+        Datasource::Clickhouse => Some(expr),
+        _ => None,
+    }
+}
+
+/// Whether strings are only considered equal in the dialect if their bytes are
+/// equal, which is the case for all dialects able to order strings by bytes.
+///
+/// Other dialects compare strings with the column's collation, which is
+/// commonly case insensitive. Grouping, distinct and equality on strings would
+/// treat different strings as the same value.
+fn compares_strings_by_bytes(dialect: Datasource) -> bool {
+    byte_ordered_string(dialect, String::new()).is_some()
+}
+
+/// Quote a table reference made up of an optional schema and a table name.
+pub fn quote_table_reference(dialect: Datasource, schema: Option<&str>, table: &str) -> String {
+    let table = quote_ident(dialect, table);
+    match schema {
+        Some(schema) => format!("{}.{table}", quote_ident(dialect, schema)),
+        None => table,
+    }
+}
+
+/// Writes logical expressions as SQL for a pushed down query.
+pub struct SqlExprWriter<'a> {
+    pushdown: &'a dyn SqlQueryPushdown,
+    /// Schema the expressions are evaluated against.
+    schema: &'a DFSchema,
+    /// SQL for each of the columns in the schema.
+    columns: &'a [String],
+    /// If comparisons between strings should compare their bytes.
+    compare_strings_by_bytes: bool,
+}
+
+impl<'a> SqlExprWriter<'a> {
+    pub fn new(
+        pushdown: &'a dyn SqlQueryPushdown,
+        schema: &'a DFSchema,
+        columns: &'a [String],
+    ) -> Self {
+        SqlExprWriter {
+            pushdown,
+            schema,
+            columns,
+            compare_strings_by_bytes: false,
+        }
+    }
+
+    /// Write comparisons between strings so that the strings are compared by
+    /// their bytes, matching how they're compared locally.
+    ///
+    /// Comparisons between strings can't be written if the dialect has no way
+    /// of doing that (see [`byte_ordered_string`]).
+    pub fn with_strings_compared_by_bytes(mut self) -> Self {
+        self.compare_strings_by_bytes = true;
+        self
+    }
+
+    /// Write the expression as SQL.
+    ///
+    /// Returns `None` if the expression can't be pushed down.
+    pub fn write(&self, expr: &Expr) -> Option<String> {
+        Some(match expr {
+            Expr::Alias(Alias { expr, .. }) => self.write(expr)?,
+            Expr::Column(col) => {
+                let idx = self.schema.index_of_column(col).ok()?;
+                self.columns.get(idx)?.clone()
+            }
+            Expr::Literal(val) => {
+                let mut buf = String::new();
+                encode_literal_to_text(self.pushdown.dialect(), &mut buf, val).ok()?;
+                buf
+            }
+            Expr::BinaryExpr(BinaryExpr { left, op, right }) => {
+                // Division and modulo are left out since integer semantics
+                // differ between databases.
+                match op {
+                    Operator::Eq
+                    | Operator::NotEq
+                    | Operator::Lt
+                    | Operator::LtEq
+                    | Operator::Gt
+                    | Operator::GtEq
+                    | Operator::Plus
+                    | Operator::Minus
+                    | Operator::Multiply
+                    | Operator::And
+                    | Operator::Or => (),
+                    _ => return None,
+                }
+                let (left, right) = match op {
+                    Operator::Eq | Operator::NotEq if self.compare_strings_by_bytes => (
+                        self.write_byte_compared(left)?,
+                        self.write_byte_compared(right)?,
+                    ),
+                    Operator::Lt | Operator::LtEq | Operator::Gt | Operator::GtEq
+                        if self.compare_strings_by_bytes =>
+                    {
+                        (
+                            self.write_byte_ordered(left)?,
+                            self.write_byte_ordered(right)?,
+                        )
+                    }
+                    _ => (self.write(left)?, self.write(right)?),
+                };
+                format!("({left} {op} {right})")
+            }
+            Expr::Not(expr) => format!("(NOT {})", self.write(expr)?),
+            Expr::IsNull(expr) => format!("({} IS NULL)", self.write(expr)?),
+            Expr::IsNotNull(expr) => format!("({} IS NOT NULL)", self.write(expr)?),
+            Expr::Cast(Cast { expr, data_type }) => self.write_cast(expr, data_type)?,
+            Expr::AggregateFunction(AggregateFunction {
+                func_def: AggregateFunctionDefinition::BuiltIn(func),
+                args,
+                distinct,
+                filter: None,
+                order_by: None,
+            }) => {
+                if args.len() != 1 {
+                    return None;
+                }
+                let arg = &args[0];
+                match (func, distinct) {
+                    (AggregateFunctionType::Count, distinct) => {
+                        let count = if self.pushdown.dialect() == Datasource::SqlServer {
+                            "COUNT_BIG"
+                        } else {
+                            "COUNT"
+                        };
+                        if *distinct {
+                            format!("{count}(DISTINCT {})", self.write_byte_compared(arg)?)
+                        } else {
+                            format!("{count}({})", self.write(arg)?)
+                        }
+                    }
+                    // Cast the input to the output type so that the database
+                    // computes the same result type we would (e.g. avoid
+                    // integer averages).
+                    (AggregateFunctionType::Sum, false) => {
+                        let typ = expr.get_type(self.schema).ok()?;
+                        format!("SUM({})", self.write_cast(arg, &typ)?)
+                    }
+                    (AggregateFunctionType::Avg, false) => {
+                        let typ = expr.get_type(self.schema).ok()?;
+                        format!("AVG({})", self.write_cast(arg, &typ)?)
+                    }
+                    (AggregateFunctionType::Min, false) => {
+                        format!("MIN({})", self.write_byte_ordered(arg)?)
+                    }
+                    (AggregateFunctionType::Max, false) => {
+                        format!("MAX({})", self.write_byte_ordered(arg)?)
+                    }
+                    _ => return None,
+                }
+            }
+            _ => return None,
+        })
+    }
+
+    /// Write an expression that's used for ordering values, e.g. a sort key or
+    /// the argument to MIN and MAX.
+    ///
+    /// Strings are written so that they're ordered by their bytes, returns
+    /// `None` if the dialect can't do that (see [`byte_ordered_string`]).
+    pub fn write_byte_ordered(&self, expr: &Expr) -> Option<String> {
+        let sql = self.write(expr)?;
+        if self.is_string(expr)? {
+            byte_ordered_string(self.pushdown.dialect(), sql)
+        } else {
+            Some(sql)
+        }
+    }
+
+    /// Write an expression that's compared for equality, e.g. a grouping key,
+    /// a distinct value or a join key.
+    ///
+    /// Returns `None` for strings if the dialect doesn't compare strings by
+    /// their bytes.
+    pub fn write_byte_compared(&self, expr: &Expr) -> Option<String> {
+        if self.is_string(expr)? && !compares_strings_by_bytes(self.pushdown.dialect()) {
+            return None;
+        }
+        self.write(expr)
+    }
+
+    fn is_string(&self, expr: &Expr) -> Option<bool> {
+        Some(matches!(
+            expr.get_type(self.schema).ok()?,
+            DataType::Utf8 | DataType::LargeUtf8
+        ))
+    }
+
+    /// Write the expression cast to the given type.
+    pub fn write_cast(&self, expr: &Expr, datatype: &DataType) -> Option<String> {
+        let typ = self.pushdown.cast_type(datatype)?;
+        Some(format!("CAST({} AS {typ})", self.write(expr)?))
+    }
+}
+
+/// Table provider for a query that's been pushed down to an external
+/// database.
+pub struct SqlQueryTableProvider {
+    /// Provider for the table the query runs against.
+    provider: Arc<dyn TableProvider>,
+    query: String,
+    schema: ArrowSchemaRef,
+}
+
+impl SqlQueryTableProvider {
+    /// Create a new provider for the query.
+    ///
+    /// Errors if the provider doesn't support query pushdown.
+    pub fn try_new(
+        provider: Arc<dyn TableProvider>,
+        query: String,
+        schema: ArrowSchemaRef,
+    ) -> DatafusionResult<Self> {
+        if as_sql_query_pushdown(provider.as_ref()).is_none() {
+            return Err(DataFusionError::Internal(
+                "table provider does not support query pushdown".to_string(),
+            ));
+        }
+        Ok(SqlQueryTableProvider {
+            provider,
+            query,
+            schema,
+        })
+    }
+}
+
+#[async_trait]
+impl TableProvider for SqlQueryTableProvider {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> ArrowSchemaRef {
+        self.schema.clone()
+    }
+
+    fn table_type(&self) -> TableType {
+        TableType::View
+    }
+
+    async fn scan(
+        &self,
+        _ctx: &SessionState,
+        projection: Option<&Vec<usize>>,
+        _filters: &[Expr],
+        _limit: Option<usize>,
+    ) -> DatafusionResult<Arc<dyn ExecutionPlan>> {
+        // Checked on create.
+        let pushdown = as_sql_query_pushdown(self.provider.as_ref()).unwrap();
+        let exec = pushdown
+            .query_exec(self.query.clone(), self.schema.clone())
+            .await?;
+
+        match projection {
+            Some(projection) => {
+                let exprs = projection
+                    .iter()
+                    .map(|idx| {
+                        let name = self.schema.field(*idx).name().clone();
+                        let col: Arc<dyn PhysicalExpr> = Arc::new(PhysicalColumn::new(&name, *idx));
+                        (col, name)
+                    })
+                    .collect();
+                Ok(Arc::new(ProjectionExec::try_new(exprs, exec)?))
+            }
+            None => Ok(exec),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use datafusion::arrow::datatypes::{Field, Schema};
+    use datafusion::logical_expr::expr_fn::{count_distinct, max, min};
+    use datafusion::physical_plan::empty::EmptyExec;
+    use datafusion::prelude::col;
+
+    use super::*;
+
+    struct TestPushdown(Datasource);
+
+    #[async_trait]
+    impl SqlQueryPushdown for TestPushdown {
+        fn dialect(&self) -> Datasource {
+            self.0
+        }
+
+        fn table_reference(&self) -> String {
+            quote_table_reference(self.0, None, "t")
+        }
+
+        fn cast_type(&self, _datatype: &DataType) -> Option<String> {
+            None
+        }
+
+        async fn query_exec(
+            &self,
+            _query: String,
+            schema: ArrowSchemaRef,
+        ) -> DatafusionResult<Arc<dyn ExecutionPlan>> {
+            Ok(Arc::new(EmptyExec::new(schema)))
+        }
+    }
+
+    #[test]
+    fn query_to_sql() {
+        let query = SqlQuery {
+            select: vec!["region AS c0".to_string(), "COUNT(1) AS c1".to_string()],
+            filters: vec!["(a > 1)".to_string()],
+            group_by: vec!["region".to_string()],
+            having: Vec::new(),
+            order_by: vec![SqlOrderBy {
+                expr: "COUNT(1)".to_string(),
+                asc: false,
+                nulls_first: true,
+            }],
+            limit: Some(10),
+            offset: 0,
+        };
+
+        assert_eq!(
+            "SELECT region AS c0, COUNT(1) AS c1 FROM public.t WHERE (a > 1) GROUP BY region ORDER BY COUNT(1) DESC NULLS FIRST LIMIT 10",
+            query.to_sql(Datasource::Postgres, "public.t"),
+        );
+        assert_eq!(
+            "SELECT TOP 10 region AS c0, COUNT(1) AS c1 FROM dbo.t WHERE (a > 1) GROUP BY region ORDER BY CASE WHEN COUNT(1) IS NULL THEN 0 ELSE 1 END, COUNT(1) DESC",
+            query.to_sql(Datasource::SqlServer, "dbo.t"),
+        );
+
+        let query = SqlQuery {
+            offset: 5,
+            order_by: Vec::new(),
+            ..query
+        };
+        assert_eq!(
+            "SELECT region AS c0, COUNT(1) AS c1 FROM dbo.t WHERE (a > 1) GROUP BY region ORDER BY (SELECT NULL) OFFSET 5 ROWS FETCH NEXT 10 ROWS ONLY",
+            query.to_sql(Datasource::SqlServer, "dbo.t"),
+        );
+        assert_eq!(
+            "SELECT region AS c0, COUNT(1) AS c1 FROM db.t WHERE (a > 1) GROUP BY region LIMIT 10 OFFSET 5",
+            query.to_sql(Datasource::MySql, "db.t"),
+        );
+    }
+
+    #[test]
+    fn quote_references() {
+        assert_eq!(
+            "\"public\".\"My Table\"",
+            quote_table_reference(Datasource::Postgres, Some("public"), "My Table"),
+        );
+        assert_eq!(
+            "[dbo].[t]",
+            quote_table_reference(Datasource::SqlServer, Some("dbo"), "t"),
+        );
+        assert_eq!("`t`", quote_table_reference(Datasource::MySql, None, "t"));
+    }
+
+    #[test]
+    fn byte_ordered_strings() {
+        assert_eq!(
+            Some("(a COLLATE \"C\")".to_string()),
+            byte_ordered_string(Datasource::Postgres, "a".to_string()),
+        );
+        assert_eq!(
+            Some("(a COLLATE BINARY)".to_string()),
+            byte_ordered_string(Datasource::Sqlite, "a".to_string()),
+        );
+        assert_eq!(
+            None,
+            byte_ordered_string(Datasource::MySql, "a".to_string())
+        );
+        assert_eq!(
+            None,
+            byte_ordered_string(Datasource::SqlServer, "a".to_string())
+        );
+    }
+
+    #[test]
+    fn strings_compared_by_bytes() {
+        let schema = DFSchema::try_from(Schema::new(vec![
+            Field::new("a", DataType::Int64, true),
+            Field::new("b", DataType::Utf8, true),
+        ]))
+        .unwrap();
+        let columns = vec!["a".to_string(), "b".to_string()];
+
+        for dialect in [Datasource::MySql, Datasource::SqlServer] {
+            let pushdown = TestPushdown(dialect);
+            let writer = SqlExprWriter::new(&pushdown, &schema, &columns);
+
+            // Collations may consider different strings equal, or order them
+            // differently.
+            assert_eq!(None, writer.write_byte_compared(&col("b")));
+            assert_eq!(None, writer.write(&min(col("b"))));
+            assert_eq!(None, writer.write(&max(col("b"))));
+            assert_eq!(None, writer.write(&count_distinct(col("b"))));
+
+            assert_eq!(Some("a".to_string()), writer.write_byte_compared(&col("a")));
+            assert_eq!(Some("MIN(a)".to_string()), writer.write(&min(col("a"))));
+        }
+
+        let pushdown = TestPushdown(Datasource::Postgres);
+        let writer = SqlExprWriter::new(&pushdown, &schema, &columns);
+        assert_eq!(Some("b".to_string()), writer.write_byte_compared(&col("b")));
+        assert_eq!(
+            Some("MAX((b COLLATE \"C\"))".to_string()),
+            writer.write(&max(col("b")))
+        );
+        assert_eq!(
+            Some("COUNT(DISTINCT b)".to_string()),
+            writer.write(&count_distinct(col("b")))
+        );
+    }
+}
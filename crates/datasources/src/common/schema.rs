@@ -0,0 +1,193 @@
+//! Utilities for combining schemas from multiple sources.
+
+use datafusion::arrow::compute::can_cast_types;
+use datafusion::arrow::datatypes::{DataType, Field, Schema};
+use indexmap::IndexMap;
+
+use super::errors::{DatasourceCommonError, Result};
+
+/// Merge schemas by column name.
+///
+/// Columns are ordered by their first appearance across all schemas. Columns
+/// that appear in more than one schema have their types widened (see
+/// [`widen_types`]). Every column in the resulting schema is nullable since
+/// any individual source may be missing it.
+pub fn union_schemas_by_name<'a>(schemas: impl IntoIterator<Item = &'a Schema>) -> Result<Schema> {
+    let mut fields: IndexMap<&str, DataType> = IndexMap::new();
+
+    for schema in schemas {
+        for field in schema.fields() {
+            match fields.get_mut(field.name().as_str()) {
+                Some(existing) => {
+                    *existing = widen_types(existing, field.data_type()).ok_or_else(|| {
+                        DatasourceCommonError::IncompatibleColumnTypes {
+                            column: field.name().clone(),
+                            left: existing.clone(),
+                            right: field.data_type().clone(),
+                        }
+                    })?;
+                }
+                None => {
+                    fields.insert(field.name(), field.data_type().clone());
+                }
+            }
+        }
+    }
+
+    Ok(Schema::new(
+        fields
+            .into_iter()
+            .map(|(name, typ)| Field::new(name, typ, true))
+            .collect::<Vec<_>>(),
+    ))
+}
+
+/// Find a type that both `left` and `right` can be losslessly (or close to
+/// losslessly) cast to.
+///
+/// - Nulls widen to the other type.
+/// - Integers widen to the larger integer type, and to Float64 when mixed
+///   with floats or when mixing signed and unsigned 64 bit integers.
+/// - Primitives mixed with strings widen to strings.
+///
+/// Returns `None` if there's no sensible common type.
+pub fn widen_types(left: &DataType, right: &DataType) -> Option<DataType> {
+    if left == right {
+        return Some(left.clone());
+    }
+
+    let widened = match (left, right) {
+        (DataType::Null, other) | (other, DataType::Null) => other.clone(),
+
+        (l, r) if l.is_integer() && r.is_integer() => widen_integers(l, r),
+        (l, r) if l.is_numeric() && r.is_numeric() => DataType::Float64,
+
+        (DataType::Utf8 | DataType::LargeUtf8, other)
+        | (other, DataType::Utf8 | DataType::LargeUtf8)
+            if other.is_primitive() || other == &DataType::Boolean =>
+        {
+            if matches!(left, DataType::LargeUtf8) || matches!(right, DataType::LargeUtf8) {
+                DataType::LargeUtf8
+            } else {
+                DataType::Utf8
+            }
+        }
+        (DataType::Utf8, DataType::LargeUtf8) | (DataType::LargeUtf8, DataType::Utf8) => {
+            DataType::LargeUtf8
+        }
+
+        (DataType::Date32, DataType::Date64) | (DataType::Date64, DataType::Date32) => {
+            DataType::Date64
+        }
+
+        _ => return None,
+    };
+
+    // Sanity check that values from both sides can actually be read as the
+    // widened type.
+    if can_cast_types(left, &widened) && can_cast_types(right, &widened) {
+        Some(widened)
+    } else {
+        None
+    }
+}
+
+fn widen_integers(left: &DataType, right: &DataType) -> DataType {
+    let signed = |typ: &DataType| {
+        matches!(
+            typ,
+            DataType::Int8 | DataType::Int16 | DataType::Int32 | DataType::Int64
+        )
+    };
+    let width = |typ: &DataType| typ.primitive_width().unwrap_or(8);
+
+    let max_width = width(left).max(width(right));
+    match (signed(left), signed(right)) {
+        (true, true) | (false, false) => {
+            if width(left) >= width(right) {
+                left.clone()
+            } else {
+                right.clone()
+            }
+        }
+        // Mixed signedness, need a signed type wide enough to hold the
+        // unsigned side.
+        (true, false) | (false, true) => {
+            let unsigned_width = if signed(left) {
+                width(right)
+            } else {
+                width(left)
+            };
+            match max_width.max(unsigned_width * 2) {
+                1 => DataType::Int8,
+                2 => DataType::Int16,
+                4 => DataType::Int32,
+                8 => DataType::Int64,
+                _ => DataType::Float64,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn widen() {
+        let cases = [
+            (DataType::Int32, DataType::Int32, Some(DataType::Int32)),
+            (DataType::Null, DataType::Utf8, Some(DataType::Utf8)),
+            (DataType::Int32, DataType::Int64, Some(DataType::Int64)),
+            (DataType::UInt8, DataType::Int8, Some(DataType::Int16)),
+            (DataType::UInt64, DataType::Int64, Some(DataType::Float64)),
+            (DataType::Int64, DataType::Float32, Some(DataType::Float64)),
+            (DataType::Int64, DataType::Utf8, Some(DataType::Utf8)),
+            (
+                DataType::Boolean,
+                DataType::LargeUtf8,
+                Some(DataType::LargeUtf8),
+            ),
+            (DataType::Date32, DataType::Date64, Some(DataType::Date64)),
+            (DataType::Boolean, DataType::Int64, None),
+            (
+                DataType::Utf8,
+                DataType::new_list(DataType::Utf8, true),
+                None,
+            ),
+        ];
+
+        for (left, right, expected) in cases {
+            assert_eq!(expected, widen_types(&left, &right), "{left} and {right}");
+            assert_eq!(expected, widen_types(&right, &left), "{right} and {left}");
+        }
+    }
+
+    #[test]
+    fn union_by_name() {
+        let a = Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("name", DataType::Utf8, true),
+        ]);
+        let b = Schema::new(vec![
+            Field::new("score", DataType::Float64, true),
+            Field::new("id", DataType::Int64, false),
+        ]);
+
+        let got = union_schemas_by_name([&a, &b]).unwrap();
+        let expected = Schema::new(vec![
+            Field::new("id", DataType::Int64, true),
+            Field::new("name", DataType::Utf8, true),
+            Field::new("score", DataType::Float64, true),
+        ]);
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn union_by_name_incompatible() {
+        let a = Schema::new(vec![Field::new("a", DataType::Boolean, true)]);
+        let b = Schema::new(vec![Field::new("a", DataType::Int64, true)]);
+
+        union_schemas_by_name([&a, &b]).unwrap_err();
+    }
+}
@@ -0,0 +1,716 @@
+//! Secrets stored outside of the catalog.
+//!
+//! Any string option may reference a secret in an external store instead of
+//! containing the secret itself:
+//!
+//! - `secret://env/<name>` reads the environment variable `<name>`.
+//! - `secret://file/<path>` reads the file at the absolute path `/<path>`.
+//! - `secret://vault/<path>#<field>` reads `<field>` of the secret at `<path>`
+//!   from a HashiCorp Vault compatible server. The server is configured
+//!   through the `VAULT_ADDR`, `VAULT_TOKEN` and (optionally) `VAULT_NAMESPACE`
+//!   environment variables. The field defaults to "value" if omitted.
+//!
+//! Only references are stored in the catalog. Secrets are resolved every time
+//! they're needed and are never persisted. Since references are provided
+//! through SQL, which secrets can be read is restricted by the process wide
+//! [`SecretAccess`].
+
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Component, Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+use glob::{MatchOptions, Pattern};
+use once_cell::sync::OnceCell;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+
+use super::errors::{DatasourceCommonError, Result};
+
+pub const SECRET_REFERENCE_PREFIX: &str = "secret://";
+
+const VAULT_ADDR_ENV: &str = "VAULT_ADDR";
+const VAULT_TOKEN_ENV: &str = "VAULT_TOKEN";
+const VAULT_NAMESPACE_ENV: &str = "VAULT_NAMESPACE";
+const DEFAULT_VAULT_FIELD: &str = "value";
+
+/// Timeout for the entire request when reading a secret from Vault.
+const VAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Looks up environment variables when resolving secrets.
+type EnvLookup = dyn Fn(&str) -> Option<String> + Send + Sync;
+
+fn process_env(name: &str) -> Option<String> {
+    std::env::var(name).ok()
+}
+
+/// Returns if the value references a secret.
+pub fn is_secret_reference(s: &str) -> bool {
+    s.starts_with(SECRET_REFERENCE_PREFIX)
+}
+
+/// Store a secret can be read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SecretStore {
+    Env,
+    File,
+    Vault,
+}
+
+impl SecretStore {
+    pub const ENV: &'static str = "env";
+    pub const FILE: &'static str = "file";
+    pub const VAULT: &'static str = "vault";
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Env => Self::ENV,
+            Self::File => Self::FILE,
+            Self::Vault => Self::VAULT,
+        }
+    }
+}
+
+impl std::fmt::Display for SecretStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Secrets in a store that can be read, e.g. `env:GLAREDB_*` or
+/// `file:/run/secrets/*`.
+///
+/// The pattern is a glob matched against the variable name for the
+/// environment, the absolute path for files, and the secret's path for Vault.
+/// `*` doesn't match across '/', use `**` to match nested paths.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecretRule {
+    pub store: SecretStore,
+    pub pattern: Pattern,
+}
+
+impl SecretRule {
+    fn matches(&self, store: SecretStore, path: &str) -> bool {
+        let opts = MatchOptions {
+            case_sensitive: true,
+            require_literal_separator: true,
+            require_literal_leading_dot: false,
+        };
+        self.store == store && self.pattern.matches_with(path, opts)
+    }
+}
+
+impl FromStr for SecretRule {
+    type Err = DatasourceCommonError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || DatasourceCommonError::InvalidSecretRule(s.to_string());
+
+        let (store, pattern) = s.split_once(':').ok_or_else(invalid)?;
+        let store = match store {
+            SecretStore::ENV => SecretStore::Env,
+            SecretStore::FILE if pattern.starts_with('/') => SecretStore::File,
+            SecretStore::VAULT => SecretStore::Vault,
+            _ => return Err(invalid()),
+        };
+        if pattern.is_empty() {
+            return Err(invalid());
+        }
+        let pattern = Pattern::new(pattern).map_err(|_| invalid())?;
+
+        Ok(Self { store, pattern })
+    }
+}
+
+/// Which secrets can be read through secret references.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SecretAccess {
+    /// Any secret readable by the process.
+    ///
+    /// Used when running locally, where the user already has access to the
+    /// environment and filesystem.
+    Unrestricted,
+    /// Only secrets matching at least one of the rules. Secret references
+    /// can't be used if there are no rules.
+    Allowlist(Vec<SecretRule>),
+}
+
+impl SecretAccess {
+    fn is_allowed(&self, store: SecretStore, path: &str) -> bool {
+        match self {
+            Self::Unrestricted => true,
+            Self::Allowlist(rules) => rules.iter().any(|rule| rule.matches(store, path)),
+        }
+    }
+}
+
+static SECRET_ACCESS: OnceCell<SecretAccess> = OnceCell::new();
+
+/// Set which secrets can be read for the rest of the process' lifetime.
+///
+/// Servers should call this on startup, otherwise all secrets are
+/// accessible. Errors if already set.
+pub fn set_secret_access(access: SecretAccess) -> Result<()> {
+    SECRET_ACCESS
+        .set(access)
+        .map_err(|_| DatasourceCommonError::SecretAccessAlreadySet)
+}
+
+fn secret_access() -> &'static SecretAccess {
+    SECRET_ACCESS.get().unwrap_or(&SecretAccess::Unrestricted)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SecretReference {
+    Env(String),
+    File(PathBuf),
+    Vault { path: String, field: String },
+}
+
+impl SecretReference {
+    /// Parse a secret reference, returning `None` if the value isn't a
+    /// reference.
+    pub fn parse(s: &str) -> Result<Option<Self>> {
+        let rest = match s.strip_prefix(SECRET_REFERENCE_PREFIX) {
+            Some(rest) => rest,
+            None => return Ok(None),
+        };
+
+        let invalid = || DatasourceCommonError::InvalidSecretReference(s.to_string());
+
+        let (store, path) = rest.split_once('/').ok_or_else(invalid)?;
+        if path.is_empty() {
+            return Err(invalid());
+        }
+
+        let reference = match store {
+            SecretStore::ENV => Self::Env(path.to_string()),
+            SecretStore::FILE => {
+                let path = PathBuf::from(format!("/{path}"));
+                // Rules match on the path, so it can't escape a directory.
+                if path.components().any(|c| c == Component::ParentDir) {
+                    return Err(invalid());
+                }
+                Self::File(path)
+            }
+            SecretStore::VAULT => {
+                let (path, field) = path.split_once('#').unwrap_or((path, DEFAULT_VAULT_FIELD));
+                if path.is_empty() || field.is_empty() {
+                    return Err(invalid());
+                }
+                // The path becomes part of the request URL, where dot
+                // segments are normalized away and '?' starts the query.
+                if path.contains(['?', '%', '\\'])
+                    || path.split('/').any(|seg| seg == "." || seg == "..")
+                {
+                    return Err(invalid());
+                }
+                Self::Vault {
+                    path: path.to_string(),
+                    field: field.to_string(),
+                }
+            }
+            _ => return Err(invalid()),
+        };
+        Ok(Some(reference))
+    }
+
+    pub fn store(&self) -> SecretStore {
+        match self {
+            Self::Env(_) => SecretStore::Env,
+            Self::File(_) => SecretStore::File,
+            Self::Vault { .. } => SecretStore::Vault,
+        }
+    }
+
+    /// Check that the secret is allowed to be read on this server.
+    pub fn check_allowed(&self) -> Result<()> {
+        self.check_allowed_with(secret_access())
+    }
+
+    fn check_allowed_with(&self, access: &SecretAccess) -> Result<()> {
+        let allowed = match self {
+            Self::Env(name) => access.is_allowed(SecretStore::Env, name),
+            Self::File(path) => is_file_allowed(access, path),
+            Self::Vault { path, .. } => access.is_allowed(SecretStore::Vault, path),
+        };
+        if allowed {
+            Ok(())
+        } else {
+            Err(DatasourceCommonError::SecretNotAllowed(self.to_string()))
+        }
+    }
+
+    /// Read the secret from the store.
+    pub async fn resolve(&self) -> Result<String> {
+        self.resolve_with(secret_access(), &process_env).await
+    }
+
+    async fn resolve_with(&self, access: &SecretAccess, env: &EnvLookup) -> Result<String> {
+        self.check_allowed_with(access)?;
+
+        match self {
+            Self::Env(name) => env(name)
+                .ok_or_else(|| self.resolution_error("environment variable not set".to_string())),
+            Self::File(path) => {
+                let path = match access {
+                    SecretAccess::Unrestricted => path.clone(),
+                    SecretAccess::Allowlist(_) => {
+                        // Resolve symlinks before checking the path again.
+                        let resolved = tokio::fs::canonicalize(path)
+                            .await
+                            .map_err(|e| self.resolution_error(e.to_string()))?;
+                        if !is_file_allowed(access, &resolved) {
+                            return Err(DatasourceCommonError::SecretNotAllowed(self.to_string()));
+                        }
+                        resolved
+                    }
+                };
+                let secret = tokio::fs::read_to_string(path)
+                    .await
+                    .map_err(|e| self.resolution_error(e.to_string()))?;
+                // Files are usually written with a trailing newline which is
+                // never part of the secret.
+                Ok(secret.trim_end_matches(['\n', '\r']).to_string())
+            }
+            Self::Vault { path, field } => {
+                let var = |name: &'static str| {
+                    env(name).ok_or_else(|| self.resolution_error(format!("{name} not set")))
+                };
+                let addr = var(VAULT_ADDR_ENV)?;
+                let token = var(VAULT_TOKEN_ENV)?;
+                let namespace = env(VAULT_NAMESPACE_ENV);
+
+                read_vault_secret(&addr, &token, namespace.as_deref(), path, field)
+                    .await
+                    .map_err(|reason| self.resolution_error(reason))
+            }
+        }
+    }
+
+    fn resolution_error(&self, reason: String) -> DatasourceCommonError {
+        DatasourceCommonError::SecretResolution {
+            reference: self.to_string(),
+            reason,
+        }
+    }
+}
+
+fn is_file_allowed(access: &SecretAccess, path: &Path) -> bool {
+    match path.to_str() {
+        Some(path) => access.is_allowed(SecretStore::File, path),
+        None => false,
+    }
+}
+
+impl std::fmt::Display for SecretReference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Env(name) => write!(f, "{SECRET_REFERENCE_PREFIX}env/{name}"),
+            Self::File(path) => write!(
+                f,
+                "{SECRET_REFERENCE_PREFIX}file/{}",
+                path.display().to_string().trim_start_matches('/')
+            ),
+            Self::Vault { path, field } => {
+                write!(f, "{SECRET_REFERENCE_PREFIX}vault/{path}#{field}")
+            }
+        }
+    }
+}
+
+/// Read a field of a secret from Vault.
+///
+/// Supports both version 1 and 2 of the key/value secrets engine.
+async fn read_vault_secret(
+    addr: &str,
+    token: &str,
+    namespace: Option<&str>,
+    path: &str,
+    field: &str,
+) -> Result<String, String> {
+    let url = format!(
+        "{}/v1/{}",
+        addr.trim_end_matches('/'),
+        path.trim_start_matches('/')
+    );
+
+    let client = reqwest::Client::builder()
+        .timeout(VAULT_REQUEST_TIMEOUT)
+        .build()
+        .map_err(|e| e.to_string())?;
+    let mut req = client.get(url).header("X-Vault-Token", token);
+    if let Some(namespace) = namespace {
+        req = req.header("X-Vault-Namespace", namespace);
+    }
+
+    let res = req.send().await.map_err(|e| e.to_string())?;
+    let status = res.status();
+    if !status.is_success() {
+        return Err(format!("vault responded with status {status}"));
+    }
+    let body: Value = res.json().await.map_err(|e| e.to_string())?;
+
+    // Version 2 nests the secret in another "data" object next to the
+    // secret's metadata.
+    let data = &body["data"];
+    let data = match (data.get("data"), data.get("metadata")) {
+        (Some(inner), Some(_)) if inner.is_object() => inner,
+        _ => data,
+    };
+
+    match data.get(field) {
+        Some(Value::String(s)) => Ok(s.clone()),
+        Some(Value::Null) | None => Err(format!("missing field '{field}'")),
+        Some(other) => Ok(other.to_string()),
+    }
+}
+
+/// Resolve all secret references in the string values of `value`.
+///
+/// The resolved value should only be used for connecting, never persisted.
+pub async fn resolve_secrets<T>(value: &T) -> Result<T>
+where
+    T: Serialize + DeserializeOwned + Clone,
+{
+    resolve_secrets_with(value, secret_access(), &process_env).await
+}
+
+async fn resolve_secrets_with<T>(value: &T, access: &SecretAccess, env: &EnvLookup) -> Result<T>
+where
+    T: Serialize + DeserializeOwned + Clone,
+{
+    let mut json = serde_json::to_value(value)?;
+
+    let mut references = BTreeSet::new();
+    visit_strings(&mut json, &mut |s| {
+        if is_secret_reference(s) {
+            references.insert(s.clone());
+        }
+    });
+    if references.is_empty() {
+        return Ok(value.clone());
+    }
+
+    let mut secrets = HashMap::with_capacity(references.len());
+    for reference in references {
+        // Always returns a reference since we checked the prefix.
+        if let Some(parsed) = SecretReference::parse(&reference)? {
+            let secret = parsed.resolve_with(access, env).await?;
+            secrets.insert(reference, secret);
+        }
+    }
+
+    visit_strings(&mut json, &mut |s| {
+        if let Some(secret) = secrets.get(s.as_str()) {
+            s.clone_from(secret);
+        }
+    });
+
+    Ok(serde_json::from_value(json)?)
+}
+
+/// Check that all secret references in the string values of `value` are
+/// valid and allowed on this server, without resolving them.
+pub fn validate_secret_references<T: Serialize>(value: &T) -> Result<()> {
+    validate_secret_references_with(value, secret_access())
+}
+
+fn validate_secret_references_with<T: Serialize>(value: &T, access: &SecretAccess) -> Result<()> {
+    let mut json = serde_json::to_value(value)?;
+    let mut result = Ok(());
+    visit_strings(&mut json, &mut |s| {
+        if result.is_ok() {
+            result = match SecretReference::parse(s) {
+                Ok(Some(reference)) => reference.check_allowed_with(access),
+                Ok(None) => Ok(()),
+                Err(e) => Err(e),
+            };
+        }
+    });
+    result
+}
+
+fn visit_strings(value: &mut Value, f: &mut impl FnMut(&mut String)) {
+    match value {
+        Value::String(s) => f(s),
+        Value::Array(values) => values.iter_mut().for_each(|v| visit_strings(v, f)),
+        Value::Object(map) => map.values_mut().for_each(|v| visit_strings(v, f)),
+        Value::Null | Value::Bool(_) | Value::Number(_) => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use protogen::metastore::types::options::{CredentialsOptions, CredentialsOptionsAws};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    fn allowlist(rules: &[&str]) -> SecretAccess {
+        SecretAccess::Allowlist(rules.iter().map(|r| r.parse().unwrap()).collect())
+    }
+
+    fn no_env(_: &str) -> Option<String> {
+        None
+    }
+
+    #[test]
+    fn parse_references() {
+        assert_eq!(None, SecretReference::parse("not a secret").unwrap());
+        assert_eq!(
+            Some(SecretReference::Env("AWS_KEY".to_string())),
+            SecretReference::parse("secret://env/AWS_KEY").unwrap()
+        );
+        assert_eq!(
+            Some(SecretReference::File(PathBuf::from("/run/secrets/key"))),
+            SecretReference::parse("secret://file/run/secrets/key").unwrap()
+        );
+        assert_eq!(
+            Some(SecretReference::Vault {
+                path: "secret/data/aws".to_string(),
+                field: "access_key_id".to_string(),
+            }),
+            SecretReference::parse("secret://vault/secret/data/aws#access_key_id").unwrap()
+        );
+        assert_eq!(
+            Some(SecretReference::Vault {
+                path: "secret/aws".to_string(),
+                field: "value".to_string(),
+            }),
+            SecretReference::parse("secret://vault/secret/aws").unwrap()
+        );
+
+        for invalid in [
+            "secret://",
+            "secret://env",
+            "secret://env/",
+            "secret://vault/path#",
+            "secret://s3/bucket/key",
+            "secret://file/run/secrets/../../etc/passwd",
+            "secret://vault/secret/glaredb/../other",
+            "secret://vault/secret/glaredb/%2e%2e/other",
+            "secret://vault/secret/glaredb?list=true",
+        ] {
+            SecretReference::parse(invalid).unwrap_err();
+        }
+    }
+
+    #[test]
+    fn display_roundtrip() {
+        for s in [
+            "secret://env/AWS_KEY",
+            "secret://file/run/secrets/key",
+            "secret://vault/secret/data/aws#access_key_id",
+        ] {
+            let reference = SecretReference::parse(s).unwrap().unwrap();
+            assert_eq!(s, reference.to_string());
+        }
+    }
+
+    #[test]
+    fn parse_rules() {
+        let rule: SecretRule = "vault:secret/data/glaredb/*".parse().unwrap();
+        assert_eq!(SecretStore::Vault, rule.store);
+        assert_eq!("secret/data/glaredb/*", rule.pattern.as_str());
+
+        for invalid in [
+            "env",
+            "env:",
+            "s3:bucket/*",
+            "file:run/secrets/*",
+            "env:[A-",
+        ] {
+            invalid.parse::<SecretRule>().unwrap_err();
+        }
+    }
+
+    #[test]
+    fn allowed_secrets() {
+        let access = allowlist(&[
+            "env:GLAREDB_*",
+            "file:/run/secrets/*",
+            "vault:secret/data/glaredb/**",
+        ]);
+
+        for allowed in [
+            "secret://env/GLAREDB_AWS_KEY",
+            "secret://file/run/secrets/aws",
+            "secret://vault/secret/data/glaredb/aws#key",
+            "secret://vault/secret/data/glaredb/team/aws#key",
+        ] {
+            let reference = SecretReference::parse(allowed).unwrap().unwrap();
+            reference.check_allowed_with(&access).unwrap();
+        }
+
+        for rejected in [
+            "secret://env/AWS_SECRET_ACCESS_KEY",
+            "secret://file/run/secrets/nested/aws",
+            "secret://file/etc/passwd",
+            "secret://vault/secret/data/other#key",
+        ] {
+            let reference = SecretReference::parse(rejected).unwrap().unwrap();
+            let err = reference.check_allowed_with(&access).unwrap_err();
+            assert!(
+                matches!(err, DatasourceCommonError::SecretNotAllowed(_)),
+                "{err}"
+            );
+        }
+
+        // Nothing is allowed without rules, everything without restrictions.
+        let reference = SecretReference::parse("secret://env/GLAREDB_AWS_KEY")
+            .unwrap()
+            .unwrap();
+        reference
+            .check_allowed_with(&SecretAccess::Allowlist(Vec::new()))
+            .unwrap_err();
+        reference
+            .check_allowed_with(&SecretAccess::Unrestricted)
+            .unwrap();
+    }
+
+    #[test]
+    fn validate_references() {
+        let creds = CredentialsOptions::Aws(CredentialsOptionsAws {
+            access_key_id: "secret://env/GLAREDB_AWS_KEY".to_string(),
+            secret_access_key: "secret://env/AWS_SECRET_ACCESS_KEY".to_string(),
+        });
+        validate_secret_references_with(&creds, &SecretAccess::Unrestricted).unwrap();
+        let err =
+            validate_secret_references_with(&creds, &allowlist(&["env:GLAREDB_*"])).unwrap_err();
+        assert!(
+            matches!(err, DatasourceCommonError::SecretNotAllowed(ref s) if s == "secret://env/AWS_SECRET_ACCESS_KEY"),
+            "{err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn resolve_references() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir_path = dir.path().canonicalize().unwrap();
+        let path = dir_path.join("secret_key");
+        std::fs::write(&path, "secret-key\n").unwrap();
+
+        let access = allowlist(&["env:GLAREDB_*", &format!("file:{}/*", dir_path.display())]);
+        let env = |name: &str| match name {
+            "GLAREDB_TEST_ACCESS_KEY" => Some("access-key".to_string()),
+            _ => None,
+        };
+
+        let creds = CredentialsOptions::Aws(CredentialsOptionsAws {
+            access_key_id: "secret://env/GLAREDB_TEST_ACCESS_KEY".to_string(),
+            secret_access_key: format!("secret://file{}", path.display()),
+        });
+
+        let resolved = resolve_secrets_with(&creds, &access, &env).await.unwrap();
+        let expected = CredentialsOptions::Aws(CredentialsOptionsAws {
+            access_key_id: "access-key".to_string(),
+            secret_access_key: "secret-key".to_string(),
+        });
+        assert_eq!(expected, resolved);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn file_symlink_outside_allowed_path() {
+        let allowed = tempfile::tempdir().unwrap();
+        let allowed_path = allowed.path().canonicalize().unwrap();
+        let other = tempfile::tempdir().unwrap();
+        let target = other.path().join("secret");
+        std::fs::write(&target, "secret").unwrap();
+        let link = allowed_path.join("link");
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+
+        let access = allowlist(&[&format!("file:{}/*", allowed_path.display())]);
+        let reference = SecretReference::File(link);
+        let err = reference.resolve_with(&access, &no_env).await.unwrap_err();
+        assert!(
+            matches!(err, DatasourceCommonError::SecretNotAllowed(_)),
+            "{err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn missing_env_secret() {
+        let err = resolve_secrets_with(
+            &"secret://env/GLAREDB_TEST_SECRET_MISSING".to_string(),
+            &SecretAccess::Unrestricted,
+            &no_env,
+        )
+        .await
+        .unwrap_err();
+        assert!(
+            matches!(err, DatasourceCommonError::SecretResolution { .. }),
+            "{err}"
+        );
+    }
+
+    /// Serve a single request with the given JSON response, returning the
+    /// address and a handle resolving to the request head.
+    async fn mock_vault(response: Value) -> (String, tokio::task::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = format!("http://{}", listener.local_addr().unwrap());
+        let handle = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buf = Vec::new();
+            while !buf.windows(4).any(|w| w == b"\r\n\r\n") {
+                let mut chunk = [0; 1024];
+                let n = stream.read(&mut chunk).await.unwrap();
+                assert_ne!(0, n);
+                buf.extend_from_slice(&chunk[..n]);
+            }
+            let response = response.to_string();
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{response}",
+                response.len()
+            );
+            stream.write_all(response.as_bytes()).await.unwrap();
+            String::from_utf8(buf).unwrap()
+        });
+        (addr, handle)
+    }
+
+    #[tokio::test]
+    async fn vault_kv_v2() {
+        let (addr, handle) = mock_vault(serde_json::json!({
+            "data": {
+                "data": { "access_key_id": "from-vault" },
+                "metadata": { "version": 3 },
+            },
+        }))
+        .await;
+
+        let env = move |name: &str| match name {
+            VAULT_ADDR_ENV => Some(addr.clone()),
+            VAULT_TOKEN_ENV => Some("token".to_string()),
+            _ => None,
+        };
+        let reference = SecretReference::parse("secret://vault/secret/data/aws#access_key_id")
+            .unwrap()
+            .unwrap();
+        let secret = reference
+            .resolve_with(&allowlist(&["vault:secret/data/*"]), &env)
+            .await
+            .unwrap();
+        assert_eq!("from-vault", secret);
+
+        let head = handle.await.unwrap().to_lowercase();
+        assert!(head.starts_with("get /v1/secret/data/aws "), "{head}");
+        assert!(head.contains("x-vault-token: token"), "{head}");
+    }
+
+    #[tokio::test]
+    async fn vault_kv_v1_missing_field() {
+        let (addr, _handle) = mock_vault(serde_json::json!({
+            "data": { "value": "from-vault" },
+        }))
+        .await;
+
+        let err = read_vault_secret(&addr, "token", Some("ns"), "secret/aws", "other")
+            .await
+            .unwrap_err();
+        assert_eq!("missing field 'other'", err);
+    }
+}
@@ -8,6 +8,7 @@ use datafusion::arrow::array::StructArray;
 use datafusion::arrow::error::ArrowError;
 use datafusion::arrow::record_batch::{RecordBatch, RecordBatchWriter};
 use datafusion::common::Result as DfResult;
+use datafusion::datasource::file_format::file_compression_type::FileCompressionType;
 use datafusion::error::DataFusionError;
 use datafusion::execution::TaskContext;
 use datafusion::physical_plan::insert::DataSink;
@@ -24,10 +25,25 @@ use crate::common::errors::Result;
 
 const BUFFER_SIZE: usize = 2 * 1024 * 1024;
 
+#[derive(Debug, Clone)]
+pub struct BsonSinkOpts {
+    /// Compression to apply to the output.
+    pub compression: FileCompressionType,
+}
+
+impl Default for BsonSinkOpts {
+    fn default() -> Self {
+        BsonSinkOpts {
+            compression: FileCompressionType::UNCOMPRESSED,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct BsonSink {
     store: Arc<dyn ObjectStore>,
     loc: ObjectPath,
+    opts: BsonSinkOpts,
 }
 
 impl Display for BsonSink {
@@ -46,10 +62,15 @@ impl DisplayAs for BsonSink {
 }
 
 impl BsonSink {
-    pub fn from_obj_store(store: Arc<dyn ObjectStore>, loc: impl Into<ObjectPath>) -> BsonSink {
+    pub fn from_obj_store(
+        store: Arc<dyn ObjectStore>,
+        loc: impl Into<ObjectPath>,
+        opts: BsonSinkOpts,
+    ) -> BsonSink {
         BsonSink {
             store,
             loc: loc.into(),
+            opts,
         }
     }
 
@@ -59,6 +80,7 @@ impl BsonSink {
 
     async fn formatted_stream(&self, mut stream: SendableRecordBatchStream) -> Result<usize> {
         let (_id, obj_handle) = self.store.put_multipart(&self.loc).await?;
+        let obj_handle = self.opts.compression.convert_async_writer(obj_handle)?;
         let mut writer = AsyncBsonWriter::new(obj_handle, BUFFER_SIZE);
         while let Some(batch) = stream.next().await {
             let batch = batch?;
@@ -6,6 +6,7 @@ use async_trait::async_trait;
 use datafusion::arrow::csv::{Writer as CsvWriter, WriterBuilder as CsvWriterBuilder};
 use datafusion::arrow::record_batch::RecordBatch;
 use datafusion::common::Result as DfResult;
+use datafusion::datasource::file_format::file_compression_type::FileCompressionType;
 use datafusion::error::DataFusionError;
 use datafusion::execution::TaskContext;
 use datafusion::physical_plan::insert::DataSink;
@@ -27,6 +28,8 @@ pub struct CsvSinkOpts {
     pub delim: u8,
     /// Include header.
     pub header: bool,
+    /// Compression to apply to the output.
+    pub compression: FileCompressionType,
 }
 
 impl Default for CsvSinkOpts {
@@ -34,6 +37,7 @@ impl Default for CsvSinkOpts {
         CsvSinkOpts {
             delim: b',',
             header: true,
+            compression: FileCompressionType::UNCOMPRESSED,
         }
     }
 }
@@ -75,6 +79,7 @@ impl CsvSink {
 
     async fn stream_into_inner(&self, mut stream: SendableRecordBatchStream) -> Result<usize> {
         let (_id, obj_handle) = self.store.put_multipart(&self.loc).await?;
+        let obj_handle = self.opts.compression.convert_async_writer(obj_handle)?;
         let mut writer = AsyncCsvWriter::new(obj_handle, BUFFER_SIZE, &self.opts);
 
         while let Some(batch) = stream.next().await {
@@ -0,0 +1,233 @@
+use std::any::Any;
+use std::fmt;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use datafusion::arrow::array::{Array, AsArray};
+use datafusion::arrow::compute::cast;
+use datafusion::arrow::datatypes::{DataType, Date64Type, Float64Type, Schema};
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::arrow::util::display::{ArrayFormatter, FormatOptions};
+use datafusion::common::Result as DfResult;
+use datafusion::error::DataFusionError;
+use datafusion::execution::TaskContext;
+use datafusion::physical_plan::insert::DataSink;
+use datafusion::physical_plan::metrics::MetricsSet;
+use datafusion::physical_plan::{DisplayAs, DisplayFormatType, SendableRecordBatchStream};
+use futures::StreamExt;
+use object_store::path::Path as ObjectPath;
+use object_store::ObjectStore;
+use rust_xlsxwriter::{Format, Workbook, Worksheet};
+
+use crate::common::errors::{DatasourceCommonError, Result};
+
+/// Maximum number of rows in a single sheet (including the header).
+const MAX_ROWS: usize = 1_048_576;
+
+/// Maximum number of columns in a single sheet.
+const MAX_COLUMNS: usize = 16_384;
+
+const DATETIME_FORMAT: &str = "yyyy-mm-dd hh:mm:ss";
+
+#[derive(Debug, Clone)]
+pub struct ExcelSinkOpts {
+    /// Name of the sheet to write to. Defaults to "Sheet1".
+    pub sheet_name: Option<String>,
+    /// Include header.
+    pub header: bool,
+}
+
+impl Default for ExcelSinkOpts {
+    fn default() -> Self {
+        ExcelSinkOpts {
+            sheet_name: None,
+            header: true,
+        }
+    }
+}
+
+/// Writes record batches to a single sheet in an xlsx workbook.
+///
+/// Workbooks are zip archives that can't be streamed, so the entire workbook
+/// is built in memory before being uploaded.
+#[derive(Debug)]
+pub struct ExcelSink {
+    store: Arc<dyn ObjectStore>,
+    loc: ObjectPath,
+    opts: ExcelSinkOpts,
+}
+
+impl fmt::Display for ExcelSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ExcelSink({}:{})", self.store, self.loc)
+    }
+}
+
+impl DisplayAs for ExcelSink {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        match t {
+            DisplayFormatType::Default => write!(f, "{self}"),
+            DisplayFormatType::Verbose => write!(f, "{self}"),
+        }
+    }
+}
+
+impl ExcelSink {
+    pub fn from_obj_store(
+        store: Arc<dyn ObjectStore>,
+        loc: impl Into<ObjectPath>,
+        opts: ExcelSinkOpts,
+    ) -> ExcelSink {
+        ExcelSink {
+            store,
+            loc: loc.into(),
+            opts,
+        }
+    }
+
+    async fn stream_into_inner(&self, mut stream: SendableRecordBatchStream) -> Result<usize> {
+        let schema = stream.schema();
+        if schema.fields().len() > MAX_COLUMNS {
+            return Err(DatasourceCommonError::Unsupported(
+                "xlsx sheets are limited to 16,384 columns",
+            ));
+        }
+
+        let max_rows = if self.opts.header {
+            MAX_ROWS - 1
+        } else {
+            MAX_ROWS
+        };
+
+        let mut batches = Vec::new();
+        let mut row_count = 0;
+        while let Some(batch) = stream.next().await {
+            let batch = batch?;
+            row_count += batch.num_rows();
+            if row_count > max_rows {
+                return Err(DatasourceCommonError::Unsupported(
+                    "xlsx sheets are limited to 1,048,576 rows",
+                ));
+            }
+            batches.push(batch);
+        }
+
+        let buf = self.build_workbook(&schema, &batches)?;
+        self.store.put(&self.loc, buf.into()).await?;
+
+        Ok(row_count)
+    }
+
+    /// Build the workbook from the batches, returning the encoded file.
+    fn build_workbook(&self, schema: &Schema, batches: &[RecordBatch]) -> Result<Vec<u8>> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.add_worksheet();
+        if let Some(name) = &self.opts.sheet_name {
+            worksheet.set_name(name)?;
+        }
+
+        let mut row: u32 = 0;
+        if self.opts.header {
+            for (col, field) in schema.fields().iter().enumerate() {
+                worksheet.write_string(0, col as u16, field.name())?;
+            }
+            row += 1;
+        }
+
+        let datetime_format = Format::new().set_num_format(DATETIME_FORMAT);
+        for batch in batches {
+            write_batch(worksheet, batch, row, &datetime_format)?;
+            row += batch.num_rows() as u32;
+        }
+
+        Ok(workbook.save_to_buffer()?)
+    }
+}
+
+/// Write a batch to the sheet, starting at the given row.
+///
+/// Numeric columns are written as numbers, and temporal columns as formatted
+/// date times so they round trip through `read_excel`. All other types are
+/// written using their string representation. Nulls are left as empty cells.
+fn write_batch(
+    worksheet: &mut Worksheet,
+    batch: &RecordBatch,
+    start_row: u32,
+    datetime_format: &Format,
+) -> Result<()> {
+    for (col, arr) in batch.columns().iter().enumerate() {
+        let col = col as u16;
+        match arr.data_type() {
+            DataType::Boolean => {
+                let arr = arr.as_boolean();
+                for (idx, val) in arr.iter().enumerate() {
+                    if let Some(val) = val {
+                        worksheet.write_boolean(start_row + idx as u32, col, val)?;
+                    }
+                }
+            }
+            dt if dt.is_numeric() => {
+                let arr = cast(arr, &DataType::Float64)?;
+                let arr = arr.as_primitive::<Float64Type>();
+                for (idx, val) in arr.iter().enumerate() {
+                    if let Some(val) = val {
+                        worksheet.write_number(start_row + idx as u32, col, val)?;
+                    }
+                }
+            }
+            DataType::Date32 | DataType::Date64 | DataType::Timestamp(_, None) => {
+                let arr = cast(arr, &DataType::Date64)?;
+                let arr = arr.as_primitive::<Date64Type>();
+                for idx in 0..arr.len() {
+                    if arr.is_null(idx) {
+                        continue;
+                    }
+                    if let Some(val) = arr.value_as_datetime(idx) {
+                        worksheet.write_datetime_with_format(
+                            start_row + idx as u32,
+                            col,
+                            &val,
+                            datetime_format,
+                        )?;
+                    }
+                }
+            }
+            _ => {
+                let opts = FormatOptions::default();
+                let formatter = ArrayFormatter::try_new(arr.as_ref(), &opts)?;
+                for idx in 0..arr.len() {
+                    if arr.is_valid(idx) {
+                        worksheet.write_string(
+                            start_row + idx as u32,
+                            col,
+                            formatter.value(idx).to_string(),
+                        )?;
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[async_trait]
+impl DataSink for ExcelSink {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        None
+    }
+
+    async fn write_all(
+        &self,
+        data: SendableRecordBatchStream,
+        _context: &Arc<TaskContext>,
+    ) -> DfResult<u64> {
+        self.stream_into_inner(data)
+            .await
+            .map(|x| x as u64)
+            .map_err(|e| DataFusionError::External(Box::new(e)))
+    }
+}
@@ -6,6 +6,7 @@ use async_trait::async_trait;
 use datafusion::arrow::json::writer::{JsonArray, JsonFormat, LineDelimited, Writer as JsonWriter};
 use datafusion::arrow::record_batch::RecordBatch;
 use datafusion::common::Result as DfResult;
+use datafusion::datasource::file_format::file_compression_type::FileCompressionType;
 use datafusion::error::DataFusionError;
 use datafusion::execution::TaskContext;
 use datafusion::physical_plan::insert::DataSink;
@@ -25,12 +26,16 @@ const BUFFER_SIZE: usize = 2 * 1024 * 1024;
 pub struct JsonSinkOpts {
     /// If the batches should be written out as a json array.
     pub array: bool,
+    /// Compression to apply to the output.
+    pub compression: FileCompressionType,
 }
 
-#[allow(clippy::derivable_impls)]
 impl Default for JsonSinkOpts {
     fn default() -> Self {
-        JsonSinkOpts { array: false }
+        JsonSinkOpts {
+            array: false,
+            compression: FileCompressionType::UNCOMPRESSED,
+        }
     }
 }
 
@@ -82,6 +87,7 @@ impl JsonSink {
         mut stream: SendableRecordBatchStream,
     ) -> Result<usize> {
         let (_id, obj_handle) = self.store.put_multipart(&self.loc).await?;
+        let obj_handle = self.opts.compression.convert_async_writer(obj_handle)?;
         let mut writer = AsyncJsonWriter::<_, F>::new(obj_handle, BUFFER_SIZE);
         while let Some(batch) = stream.next().await {
             let batch = batch?;
@@ -1,9 +1,45 @@
-use ssh_key::{LineEnding, PrivateKey};
+use once_cell::sync::OnceCell;
+use ssh_key::known_hosts::KnownHosts;
+use ssh_key::{LineEnding, PrivateKey, PublicKey};
+
+use super::SshConnectionParameters;
 
 #[derive(Debug, thiserror::Error)]
 pub enum SshKeyError {
     #[error(transparent)]
     SshKeyGen(#[from] ssh_key::Error),
+
+    #[error("Private key is encrypted, a passphrase is required")]
+    MissingPassphrase,
+
+    #[error("Failed to decrypt private key, the passphrase may be incorrect: {0}")]
+    Decrypt(ssh_key::Error),
+
+    #[error("Invalid known hosts: {0}")]
+    InvalidKnownHosts(String),
+
+    #[error("Private keys can't be stored without a server configured catalog key passphrase")]
+    MissingCatalogPassphrase,
+
+    #[error("Catalog key passphrase already set")]
+    CatalogPassphraseAlreadySet,
+}
+
+static CATALOG_KEY_PASSPHRASE: OnceCell<String> = OnceCell::new();
+
+/// Set the passphrase used to encrypt private keys stored in the catalog for
+/// the rest of the process' lifetime.
+///
+/// Without a passphrase, generated keys are stored unencrypted and user
+/// provided keys can't be stored at all. Errors if already set.
+pub fn set_catalog_key_passphrase(passphrase: String) -> Result<(), SshKeyError> {
+    CATALOG_KEY_PASSPHRASE
+        .set(passphrase)
+        .map_err(|_| SshKeyError::CatalogPassphraseAlreadySet)
+}
+
+fn catalog_key_passphrase() -> Option<&'static str> {
+    CATALOG_KEY_PASSPHRASE.get().map(String::as_str)
 }
 
 #[derive(Debug, Clone)]
@@ -19,15 +55,63 @@ impl SshKey {
         Ok(Self { keypair })
     }
 
-    /// Recreate ssh key from bytes store in catalog.
+    /// Create an ssh key from a user provided OpenSSH-formatted private key,
+    /// decrypting it with the passphrase if it's encrypted.
+    pub fn from_openssh(private_key: &str, passphrase: Option<&str>) -> Result<Self, SshKeyError> {
+        let keypair = PrivateKey::from_openssh(private_key)?;
+        let keypair = match (keypair.is_encrypted(), passphrase) {
+            (false, _) => keypair,
+            (true, Some(passphrase)) => {
+                keypair.decrypt(passphrase).map_err(SshKeyError::Decrypt)?
+            }
+            (true, None) => return Err(SshKeyError::MissingPassphrase),
+        };
+        Ok(Self { keypair })
+    }
+
+    /// Recreate ssh key from bytes store in catalog, decrypting it with the
+    /// catalog key passphrase if it's encrypted.
     pub fn from_bytes(keypair: &[u8]) -> Result<Self, SshKeyError> {
+        Self::from_bytes_with(keypair, catalog_key_passphrase())
+    }
+
+    fn from_bytes_with(keypair: &[u8], passphrase: Option<&str>) -> Result<Self, SshKeyError> {
         let keypair = PrivateKey::from_bytes(keypair)?;
+        let keypair = if keypair.is_encrypted() {
+            let passphrase = passphrase.ok_or(SshKeyError::MissingCatalogPassphrase)?;
+            keypair.decrypt(passphrase).map_err(SshKeyError::Decrypt)?
+        } else {
+            keypair
+        };
         Ok(Self { keypair })
     }
 
-    /// Serialize sshk key as raw bytes.
+    /// Serialize ssh key as raw bytes for storing in the catalog.
+    ///
+    /// The key is encrypted if a catalog key passphrase is configured.
     pub fn to_bytes(&self) -> Result<Vec<u8>, SshKeyError> {
-        Ok(self.keypair.to_bytes()?.to_vec())
+        self.to_bytes_with(catalog_key_passphrase(), false)
+    }
+
+    /// Serialize ssh key as raw bytes for storing in the catalog, erroring if
+    /// no catalog key passphrase is configured to encrypt it with.
+    ///
+    /// Used for keys provided by users, which may be used outside of GlareDB.
+    pub fn to_encrypted_bytes(&self) -> Result<Vec<u8>, SshKeyError> {
+        self.to_bytes_with(catalog_key_passphrase(), true)
+    }
+
+    fn to_bytes_with(
+        &self,
+        passphrase: Option<&str>,
+        require_encryption: bool,
+    ) -> Result<Vec<u8>, SshKeyError> {
+        let keypair = match passphrase {
+            Some(passphrase) => self.keypair.encrypt(&mut rand::thread_rng(), passphrase)?,
+            None if require_encryption => return Err(SshKeyError::MissingCatalogPassphrase),
+            None => self.keypair.clone(),
+        };
+        Ok(keypair.to_bytes()?.to_vec())
     }
 
     /// Create an OpenSSH-formatted public key as a String.
@@ -40,3 +124,138 @@ impl SshKey {
         Ok(self.keypair.to_openssh(LineEnding::default())?.to_string())
     }
 }
+
+/// Validate entries in the OpenSSH `known_hosts` format, returning the
+/// normalized entries.
+pub fn validate_known_hosts(known_hosts: &str) -> Result<String, SshKeyError> {
+    let mut entries = Vec::new();
+    for line in known_hosts.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        KnownHosts::new(line)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| SshKeyError::InvalidKnownHosts(format!("{line}: {e}")))?;
+        entries.push(line);
+    }
+
+    if entries.is_empty() {
+        return Err(SshKeyError::InvalidKnownHosts(
+            "no entries provided".to_string(),
+        ));
+    }
+
+    Ok(entries.join("\n"))
+}
+
+/// Create a `known_hosts` entry pinning the public key of a host.
+pub fn known_hosts_entry(
+    host: &SshConnectionParameters,
+    host_key: &str,
+) -> Result<String, SshKeyError> {
+    let key = PublicKey::from_openssh(host_key.trim())?;
+    // Keys for hosts on non-default ports are stored as `[host]:port`.
+    let pattern = match host.port {
+        Some(port) if port != 22 => format!("[{}]:{port}", host.host),
+        _ => host.host.clone(),
+    };
+    Ok(format!("{pattern} {}", key.to_openssh()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOST_KEY: &str =
+        "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIOMqqnkVzrm0SdG6UOoqKLsabgH5C9okWi0dh2l9GKJl";
+
+    #[test]
+    fn openssh_private_key() {
+        let key = SshKey::generate_random().unwrap();
+        let openssh = key.to_openssh().unwrap();
+
+        let parsed = SshKey::from_openssh(&openssh, None).unwrap();
+        assert_eq!(key.public_key().unwrap(), parsed.public_key().unwrap());
+
+        // Passphrase is ignored for unencrypted keys.
+        SshKey::from_openssh(&openssh, Some("passphrase")).unwrap();
+
+        SshKey::from_openssh("not a key", None).unwrap_err();
+    }
+
+    #[test]
+    fn encrypted_private_key() {
+        let key = SshKey::generate_random().unwrap();
+        let encrypted = key
+            .keypair
+            .encrypt(&mut rand::thread_rng(), "passphrase")
+            .unwrap()
+            .to_openssh(LineEnding::default())
+            .unwrap();
+
+        let err = SshKey::from_openssh(&encrypted, None).unwrap_err();
+        assert!(matches!(err, SshKeyError::MissingPassphrase), "{err}");
+        let err = SshKey::from_openssh(&encrypted, Some("wrong")).unwrap_err();
+        assert!(matches!(err, SshKeyError::Decrypt(_)), "{err}");
+
+        let decrypted = SshKey::from_openssh(&encrypted, Some("passphrase")).unwrap();
+        assert_eq!(key.public_key().unwrap(), decrypted.public_key().unwrap());
+    }
+
+    #[test]
+    fn catalog_bytes() {
+        let key = SshKey::generate_random().unwrap();
+
+        let bytes = key.to_bytes_with(None, false).unwrap();
+        let parsed = SshKey::from_bytes_with(&bytes, None).unwrap();
+        assert_eq!(key.public_key().unwrap(), parsed.public_key().unwrap());
+
+        let err = key.to_bytes_with(None, true).unwrap_err();
+        assert!(
+            matches!(err, SshKeyError::MissingCatalogPassphrase),
+            "{err}"
+        );
+
+        let bytes = key.to_bytes_with(Some("catalog"), true).unwrap();
+        assert!(PrivateKey::from_bytes(&bytes).unwrap().is_encrypted());
+        let parsed = SshKey::from_bytes_with(&bytes, Some("catalog")).unwrap();
+        assert_eq!(key.public_key().unwrap(), parsed.public_key().unwrap());
+
+        let err = SshKey::from_bytes_with(&bytes, None).unwrap_err();
+        assert!(
+            matches!(err, SshKeyError::MissingCatalogPassphrase),
+            "{err}"
+        );
+        let err = SshKey::from_bytes_with(&bytes, Some("wrong")).unwrap_err();
+        assert!(matches!(err, SshKeyError::Decrypt(_)), "{err}");
+    }
+
+    #[test]
+    fn known_hosts() {
+        let host = SshConnectionParameters {
+            host: "bastion.example.com".to_string(),
+            port: Some(2222),
+            user: "glaredb".to_string(),
+        };
+        let entry = known_hosts_entry(&host, HOST_KEY).unwrap();
+        assert_eq!(format!("[bastion.example.com]:2222 {HOST_KEY}"), entry);
+
+        let host = SshConnectionParameters {
+            port: Some(22),
+            ..host
+        };
+        let entry = known_hosts_entry(&host, HOST_KEY).unwrap();
+        assert_eq!(format!("bastion.example.com {HOST_KEY}"), entry);
+
+        let known_hosts = format!("# bastion\n{entry}\n\n10.0.0.1 {HOST_KEY}\n");
+        assert_eq!(
+            format!("{entry}\n10.0.0.1 {HOST_KEY}"),
+            validate_known_hosts(&known_hosts).unwrap()
+        );
+
+        validate_known_hosts("").unwrap_err();
+        validate_known_hosts("bastion.example.com not-a-key").unwrap_err();
+        known_hosts_entry(&host, "not-a-key").unwrap_err();
+    }
+}
@@ -68,6 +68,14 @@ impl SshTunnelSession {
 pub struct SshTunnelAccess {
     pub connection_string: String,
     pub keypair: SshKey,
+    /// Hosts to hop through before connecting to the host in
+    /// `connection_string`, in order.
+    pub jump_hosts: Vec<String>,
+    /// Entries in the `known_hosts` format used to verify the keys of all
+    /// hosts (including jump hosts).
+    ///
+    /// Host keys aren't verified if this isn't set.
+    pub known_hosts: Option<String>,
 }
 
 impl SshTunnelAccess {
@@ -88,12 +96,9 @@ impl SshTunnelAccess {
         T: ToSocketAddrs,
     {
         #[cfg(any(target_os = "linux", target_os = "macos"))]
-        let (inner, addr) =
-            unix_impl::create_tunnel(remote_addr, &self.connection_string, &self.keypair).await?;
+        let (inner, addr) = unix_impl::create_tunnel(remote_addr, self).await?;
         #[cfg(not(any(target_os = "linux", target_os = "macos")))]
-        let (inner, addr) =
-            not_unix_impl::create_tunnel(remote_addr, &self.connection_string, &self.keypair)
-                .await?;
+        let (inner, addr) = not_unix_impl::create_tunnel(remote_addr, self).await?;
         Ok((SshTunnelSession { inner }, addr))
     }
 }
@@ -104,6 +109,7 @@ impl SshTunnelAccess {
 mod unix_impl {
     use std::net::{IpAddr, Ipv4Addr};
     use std::os::unix::prelude::PermissionsExt;
+    use std::path::Path;
 
     use openssh::{ForwardType, KnownHosts, Session, SessionBuilder};
 
@@ -117,7 +123,7 @@ mod unix_impl {
         NamedTempFile,
         Permissions,
         SocketAddr,
-        SshKey,
+        SshTunnelAccess,
         SshTunnelError,
         TcpListener,
         ToSocketAddrs,
@@ -135,21 +141,26 @@ mod unix_impl {
 
     pub(super) async fn create_tunnel<T>(
         remote_addr: &T,
-        connection_str: &str,
-        keypair: &SshKey,
+        access: &SshTunnelAccess,
     ) -> Result<(SshTunnelSessionImpl, SocketAddr), SshTunnelError>
     where
         T: ToSocketAddrs,
     {
-        let temp_keyfile = generate_temp_keyfile(keypair.to_openssh()?.as_ref()).await?;
+        let temp_keyfile = generate_temp_keyfile(access.keypair.to_openssh()?.as_ref()).await?;
+        let temp_known_hosts = match &access.known_hosts {
+            Some(known_hosts) => {
+                Some(generate_temp_file("ssh_tunnel_known_hosts-", known_hosts).await?)
+            }
+            None => None,
+        };
 
         let remote_addr = remote_addr
             .to_socket_addrs()?
             .next()
             .ok_or(SshTunnelError::NoRemoteAddressesProvided)?;
 
-        let tunnel = SessionBuilder::default()
-            .known_hosts_check(KnownHosts::Accept)
+        let mut builder = SessionBuilder::default();
+        builder
             .keyfile(temp_keyfile.path())
             // Set control directory explicitly. Otherwise we run the the
             // chance of an error like the following:
@@ -157,9 +168,37 @@ mod unix_impl {
             // 'path ... too long for Unix domain socket'
             .control_directory(std::env::temp_dir())
             // Wait 15 seconds before timing out ssh connection attempt
-            .connect_timeout(Duration::from_secs(15))
-            .connect(connection_str)
-            .await?;
+            .connect_timeout(Duration::from_secs(15));
+
+        match &temp_known_hosts {
+            Some(known_hosts) => {
+                builder
+                    .known_hosts_check(KnownHosts::Strict)
+                    .user_known_hosts_file(known_hosts.path());
+            }
+            None => {
+                builder.known_hosts_check(KnownHosts::Accept);
+            }
+        }
+
+        // Options set on the builder only apply to the connection to the final
+        // host. Connections to jump hosts are configured through a config file
+        // instead.
+        let _temp_config = if access.jump_hosts.is_empty() {
+            None
+        } else {
+            let config = jump_host_config(
+                temp_keyfile.path(),
+                temp_known_hosts.as_ref().map(|f| f.path()),
+            );
+            let config = generate_temp_file("ssh_tunnel_config-", &config).await?;
+            builder
+                .config_file(config.path())
+                .jump_hosts(&access.jump_hosts);
+            Some(config)
+        };
+
+        let tunnel = builder.connect(&access.connection_string).await?;
 
         // Check the status of the connection before proceeding.
         tunnel.check().await?;
@@ -197,25 +236,41 @@ mod unix_impl {
 
     /// Generate temproary keyfile using the given private_key
     async fn generate_temp_keyfile(private_key: &str) -> Result<NamedTempFile, SshTunnelError> {
-        let temp_keyfile = tempfile::Builder::new()
-            .prefix("ssh_tunnel_key-")
-            .tempfile()?;
-        trace!(temp_keyfile = ?temp_keyfile.path(), "Temporary keyfile location");
+        generate_temp_file("ssh_tunnel_key-", private_key).await
+    }
+
+    /// Generate a temporary file only readable by the owner containing
+    /// `contents`.
+    async fn generate_temp_file(
+        prefix: &str,
+        contents: &str,
+    ) -> Result<NamedTempFile, SshTunnelError> {
+        let temp_file = tempfile::Builder::new().prefix(prefix).tempfile()?;
+        trace!(temp_file = ?temp_file.path(), "Temporary file location");
 
-        let keyfile = File::open(&temp_keyfile.path()).await?;
-        // Set keyfile to only owner read and write permissions
-        keyfile
-            .set_permissions(Permissions::from_mode(0o600))
-            .await?;
+        let file = File::open(&temp_file.path()).await?;
+        // Set file to only owner read and write permissions
+        file.set_permissions(Permissions::from_mode(0o600)).await?;
 
-        fs::write(temp_keyfile.path(), private_key.as_bytes()).await?;
+        fs::write(temp_file.path(), contents.as_bytes()).await?;
 
         // Remove write permission from file to prevent clobbering
-        keyfile
-            .set_permissions(Permissions::from_mode(0o400))
-            .await?;
+        file.set_permissions(Permissions::from_mode(0o400)).await?;
 
-        Ok(temp_keyfile)
+        Ok(temp_file)
+    }
+
+    /// Generate an ssh config applying the identity and host key checking of
+    /// the tunnel to every host, including jump hosts.
+    fn jump_host_config(keyfile: &Path, known_hosts: Option<&Path>) -> String {
+        let (known_hosts, strict) = match known_hosts {
+            Some(path) => (path.display().to_string(), "yes"),
+            None => ("/dev/null".to_string(), "no"),
+        };
+        format!(
+            "Host *\n  IdentityFile \"{}\"\n  IdentitiesOnly yes\n  UserKnownHostsFile \"{known_hosts}\"\n  GlobalKnownHostsFile /dev/null\n  StrictHostKeyChecking {strict}\n  ConnectTimeout 15\n",
+            keyfile.display(),
+        )
     }
 
     /// Get a random port to use for the localhost side of the ssh tunnel.
@@ -236,6 +291,7 @@ mod unix_impl {
     #[cfg(test)]
     mod tests {
         use super::*;
+        use crate::common::ssh::key::SshKey;
 
         #[tokio::test]
         async fn validate_temp_keyfile() {
@@ -245,6 +301,18 @@ mod unix_impl {
             let keyfile_data = std::fs::read(temp_keyfile.path()).unwrap();
             assert_eq!(keyfile_data, private_key.as_bytes());
         }
+
+        #[test]
+        fn jump_host_config_checks_host_keys() {
+            let config =
+                jump_host_config(Path::new("/tmp/key"), Some(Path::new("/tmp/known_hosts")));
+            assert!(config.contains("IdentityFile \"/tmp/key\""));
+            assert!(config.contains("UserKnownHostsFile \"/tmp/known_hosts\""));
+            assert!(config.contains("StrictHostKeyChecking yes"));
+
+            let config = jump_host_config(Path::new("/tmp/key"), None);
+            assert!(config.contains("StrictHostKeyChecking no"));
+        }
     }
 }
 
@@ -265,8 +333,7 @@ mod not_unix_impl {
 
     pub(super) async fn create_tunnel<T>(
         remote_addr: &T,
-        connection_str: &str,
-        keypair: &SshKey,
+        access: &SshTunnelAccess,
     ) -> Result<(SshTunnelSessionImpl, SocketAddr), SshTunnelError>
     where
         T: ToSocketAddrs,
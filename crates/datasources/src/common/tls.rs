@@ -0,0 +1,348 @@
+//! TLS configuration shared by data sources.
+//!
+//! Certificates and keys are read from PEM files referenced by
+//! [`TlsOptions`]. Since the options are provided through SQL, which files can
+//! be read is restricted by the process wide [`CertFileAccess`].
+
+use std::fmt;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use once_cell::sync::OnceCell;
+use protogen::metastore::types::options::TlsOptions;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use rustls::{ClientConfig, RootCertStore};
+
+use super::errors::{DatasourceCommonError, Result};
+
+/// Which certificate and key files referenced by TLS options can be read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CertFileAccess {
+    /// Any file readable by the process.
+    ///
+    /// Used when running locally, where the user already has access to the
+    /// filesystem.
+    Unrestricted,
+    /// Only files inside of a directory. Relative paths are resolved against
+    /// the directory.
+    Directory(PathBuf),
+    /// No files, certificate options can't be used.
+    Disabled,
+}
+
+static CERT_FILE_ACCESS: OnceCell<CertFileAccess> = OnceCell::new();
+
+/// Set which certificate files can be read for the rest of the process'
+/// lifetime.
+///
+/// Servers should call this on startup, otherwise all files are accessible.
+/// Errors if already set.
+pub fn set_cert_file_access(access: CertFileAccess) -> Result<()> {
+    let access = match access {
+        CertFileAccess::Directory(dir) => {
+            let dir = dir.canonicalize().map_err(|e| {
+                DatasourceCommonError::InvalidTlsConfig(format!(
+                    "invalid certificate directory '{}': {e}",
+                    dir.display()
+                ))
+            })?;
+            CertFileAccess::Directory(dir)
+        }
+        other => other,
+    };
+
+    CERT_FILE_ACCESS.set(access).map_err(|_| {
+        DatasourceCommonError::InvalidTlsConfig("certificate file access already set".to_string())
+    })
+}
+
+/// Resolve the path to a certificate or key file, erroring if the file isn't
+/// allowed to be read.
+pub fn resolve_cert_path(path: &str) -> Result<PathBuf> {
+    let access = CERT_FILE_ACCESS
+        .get()
+        .unwrap_or(&CertFileAccess::Unrestricted);
+    resolve_cert_path_with(access, path)
+}
+
+fn resolve_cert_path_with(access: &CertFileAccess, path: &str) -> Result<PathBuf> {
+    match access {
+        CertFileAccess::Unrestricted => Ok(PathBuf::from(path)),
+        CertFileAccess::Disabled => Err(DatasourceCommonError::InvalidTlsConfig(
+            "certificate files are not allowed on this server".to_string(),
+        )),
+        CertFileAccess::Directory(dir) => {
+            // Resolve symlinks and '..' before checking the directory.
+            let resolved = dir.join(path).canonicalize().map_err(|e| {
+                DatasourceCommonError::InvalidTlsConfig(format!("failed to open '{path}': {e}"))
+            })?;
+            if !resolved.starts_with(dir) {
+                return Err(DatasourceCommonError::InvalidTlsConfig(format!(
+                    "'{path}' is outside of the certificate directory"
+                )));
+            }
+            Ok(resolved)
+        }
+    }
+}
+
+/// Whether or not to use TLS when connecting to a database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SslMode {
+    /// Never use TLS.
+    Disable,
+    /// Try TLS, falling back to an unencrypted connection if that fails.
+    Prefer,
+    /// Always use TLS. The server's certificate is always verified.
+    Require,
+}
+
+impl SslMode {
+    pub const DISABLE: &'static str = "disable";
+    pub const PREFER: &'static str = "prefer";
+    pub const REQUIRE: &'static str = "require";
+
+    /// Get the ssl mode to use for a connection.
+    ///
+    /// If no ssl mode is set, but certificates are provided, TLS is required.
+    /// Returns `None` if nothing is configured, leaving it up to the
+    /// connection string.
+    pub fn from_options(opts: &TlsOptions) -> Result<Option<Self>> {
+        match &opts.ssl_mode {
+            Some(mode) => Ok(Some(mode.parse()?)),
+            None if opts.ca_cert.is_some() || opts.client_cert.is_some() => {
+                Ok(Some(SslMode::Require))
+            }
+            None => Ok(None),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Disable => Self::DISABLE,
+            Self::Prefer => Self::PREFER,
+            Self::Require => Self::REQUIRE,
+        }
+    }
+}
+
+impl FromStr for SslMode {
+    type Err = DatasourceCommonError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(match s.to_lowercase().as_str() {
+            Self::DISABLE => Self::Disable,
+            Self::PREFER => Self::Prefer,
+            Self::REQUIRE => Self::Require,
+            _ => return Err(DatasourceCommonError::InvalidSslMode(s.to_string())),
+        })
+    }
+}
+
+impl fmt::Display for SslMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Check that the options are valid and that all certificates and keys can
+/// be loaded.
+pub fn validate_tls_options(opts: &TlsOptions) -> Result<()> {
+    let mode = SslMode::from_options(opts)?;
+    if mode == Some(SslMode::Disable) && (opts.ca_cert.is_some() || opts.client_cert.is_some()) {
+        return Err(DatasourceCommonError::InvalidTlsConfig(
+            "certificates cannot be provided when 'ssl_mode' is 'disable'".to_string(),
+        ));
+    }
+    if let Some(path) = &opts.ca_cert {
+        read_certs(path)?;
+    }
+    client_identity(opts)?;
+    Ok(())
+}
+
+/// Get the resolved path to the CA certificate if provided.
+pub fn ca_cert_path(opts: &TlsOptions) -> Result<Option<PathBuf>> {
+    opts.ca_cert.as_deref().map(resolve_cert_path).transpose()
+}
+
+/// Get the paths to the client certificate and key as provided, erroring if
+/// only one of them is provided.
+///
+/// Paths need to be resolved with [`resolve_cert_path`] before reading the
+/// files.
+pub fn client_cert_paths(opts: &TlsOptions) -> Result<Option<(&str, &str)>> {
+    match (&opts.client_cert, &opts.client_key) {
+        (Some(cert), Some(key)) => Ok(Some((cert, key))),
+        (None, None) => Ok(None),
+        _ => Err(DatasourceCommonError::InvalidTlsConfig(
+            "'client_cert' and 'client_key' must be provided together".to_string(),
+        )),
+    }
+}
+
+/// Build a rustls client config.
+///
+/// Server certificates are verified against the webpki roots and any
+/// certificates in `ca_cert`.
+pub fn rustls_client_config(opts: &TlsOptions) -> Result<ClientConfig> {
+    let builder = ClientConfig::builder().with_root_certificates(root_store(opts)?);
+
+    let config = match client_identity(opts)? {
+        Some((certs, key)) => builder.with_client_auth_cert(certs, key).map_err(|e| {
+            DatasourceCommonError::InvalidTlsConfig(format!("invalid client certificate: {e}"))
+        })?,
+        None => builder.with_no_client_auth(),
+    };
+
+    Ok(config)
+}
+
+/// Create a root store containing the webpki roots along with any
+/// certificates in `ca_cert`.
+pub fn root_store(opts: &TlsOptions) -> Result<RootCertStore> {
+    let mut root_store = RootCertStore::empty();
+    root_store
+        .roots
+        .extend(webpki_roots::TLS_SERVER_ROOTS.iter().map(|r| r.to_owned()));
+
+    if let Some(path) = &opts.ca_cert {
+        for cert in read_certs(path)? {
+            root_store.add(cert).map_err(|e| {
+                DatasourceCommonError::InvalidTlsConfig(format!(
+                    "invalid CA certificate in '{path}': {e}"
+                ))
+            })?;
+        }
+    }
+
+    Ok(root_store)
+}
+
+/// Read the client certificate chain and private key if provided.
+pub fn client_identity(
+    opts: &TlsOptions,
+) -> Result<Option<(Vec<CertificateDer<'static>>, PrivateKeyDer<'static>)>> {
+    let (cert, key) = match client_cert_paths(opts)? {
+        Some(paths) => paths,
+        None => return Ok(None),
+    };
+
+    let certs = read_certs(cert)?;
+    let key = rustls_pemfile::private_key(&mut open_pem(key)?)?.ok_or_else(|| {
+        DatasourceCommonError::InvalidTlsConfig(format!("no private key found in '{key}'"))
+    })?;
+
+    Ok(Some((certs, key)))
+}
+
+fn read_certs(path: &str) -> Result<Vec<CertificateDer<'static>>> {
+    let certs = rustls_pemfile::certs(&mut open_pem(path)?).collect::<Result<Vec<_>, _>>()?;
+    if certs.is_empty() {
+        return Err(DatasourceCommonError::InvalidTlsConfig(format!(
+            "no certificates found in '{path}'"
+        )));
+    }
+    Ok(certs)
+}
+
+fn open_pem(path: &str) -> Result<BufReader<File>> {
+    let resolved = resolve_cert_path(path)?;
+    let file = File::open(&resolved).map_err(|e| {
+        DatasourceCommonError::InvalidTlsConfig(format!("failed to open '{path}': {e}"))
+    })?;
+    Ok(BufReader::new(file))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+
+    #[test]
+    fn ssl_mode_from_options() {
+        let opts = TlsOptions::default();
+        assert_eq!(None, SslMode::from_options(&opts).unwrap());
+
+        let opts = TlsOptions {
+            ssl_mode: Some("PREFER".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(Some(SslMode::Prefer), SslMode::from_options(&opts).unwrap());
+
+        // Certificates imply TLS is required.
+        let opts = TlsOptions {
+            ca_cert: Some("ca.pem".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            Some(SslMode::Require),
+            SslMode::from_options(&opts).unwrap()
+        );
+
+        let opts = TlsOptions {
+            ssl_mode: Some("verify-full".to_string()),
+            ..Default::default()
+        };
+        SslMode::from_options(&opts).unwrap_err();
+    }
+
+    #[test]
+    fn client_cert_requires_key() {
+        let opts = TlsOptions {
+            client_cert: Some("client.pem".to_string()),
+            ..Default::default()
+        };
+        client_cert_paths(&opts).unwrap_err();
+
+        let opts = TlsOptions {
+            client_cert: Some("client.pem".to_string()),
+            client_key: Some("client.key".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            Some(("client.pem", "client.key")),
+            client_cert_paths(&opts).unwrap()
+        );
+    }
+
+    #[test]
+    fn restricted_cert_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir_path = dir.path().canonicalize().unwrap();
+        std::fs::write(dir_path.join("ca.pem"), "").unwrap();
+        let access = CertFileAccess::Directory(dir_path.clone());
+
+        assert_eq!(
+            dir_path.join("ca.pem"),
+            resolve_cert_path_with(&access, "ca.pem").unwrap()
+        );
+        let absolute = dir_path.join("ca.pem");
+        assert_eq!(
+            absolute,
+            resolve_cert_path_with(&access, absolute.to_str().unwrap()).unwrap()
+        );
+
+        resolve_cert_path_with(&access, "/etc/passwd").unwrap_err();
+        resolve_cert_path_with(&access, "../../../../etc/passwd").unwrap_err();
+        resolve_cert_path_with(&access, "missing.pem").unwrap_err();
+
+        resolve_cert_path_with(&CertFileAccess::Disabled, "ca.pem").unwrap_err();
+        assert_eq!(
+            Path::new("/etc/passwd"),
+            resolve_cert_path_with(&CertFileAccess::Unrestricted, "/etc/passwd").unwrap()
+        );
+    }
+
+    #[test]
+    fn missing_ca_cert_file() {
+        let opts = TlsOptions {
+            ca_cert: Some("/this/does/not/exist.pem".to_string()),
+            ..Default::default()
+        };
+        validate_tls_options(&opts).unwrap_err();
+    }
+}
@@ -1,7 +1,7 @@
 use std::fmt::Write;
 use std::sync::Arc;
 
-use chrono::{Duration, TimeZone, Utc};
+use chrono::{Duration, NaiveDateTime, TimeZone, Utc};
 use datafusion::arrow::array::{Array, ArrayRef, UInt64Array};
 use datafusion::arrow::compute::{cast_with_options, CastOptions};
 use datafusion::arrow::datatypes::{DataType, Field, Schema, TimeUnit};
@@ -35,6 +35,8 @@ pub enum Datasource {
     Clickhouse,
     SqlServer,
     Sqlite,
+    Oracle,
+    DuckDb,
 }
 
 /// Returns true if the literal expression encoding should be wrapped inside
@@ -53,6 +55,57 @@ fn is_literal_quotable(datasource: Datasource, lit: &ScalarValue) -> bool {
     }
 }
 
+/// Returns the text to wrap a (possibly quoted) literal in.
+///
+/// Oracle needs typed literals for dates and timestamps instead of relying on
+/// the session's date format.
+fn literal_wrapping(
+    datasource: Datasource,
+    lit: &ScalarValue,
+) -> Result<(&'static str, &'static str)> {
+    if datasource != Datasource::Oracle {
+        return Ok(("", ""));
+    }
+    Ok(match lit {
+        ScalarValue::Date32(_) => ("DATE ", ""),
+        ScalarValue::TimestampNanosecond(..)
+        | ScalarValue::TimestampMicrosecond(..)
+        | ScalarValue::TimestampMillisecond(..)
+        | ScalarValue::TimestampSecond(..) => ("TIMESTAMP ", ""),
+        ScalarValue::Binary(_) => ("HEXTORAW(", ")"),
+        ScalarValue::Boolean(_) => {
+            return Err(DatasourceCommonError::Unsupported(
+                "boolean literals for Oracle",
+            ))
+        }
+        ScalarValue::Time64Nanosecond(_) | ScalarValue::Time64Microsecond(_) => {
+            return Err(DatasourceCommonError::Unsupported(
+                "time literals for Oracle",
+            ))
+        }
+        _ => ("", ""),
+    })
+}
+
+/// Encode a UTC timestamp, with a time zone if `tz` is set.
+fn put_utc_timestamp(
+    datasource: Datasource,
+    buf: &mut String,
+    naive: &NaiveDateTime,
+    tz: bool,
+) -> Result<()> {
+    if datasource == Datasource::Oracle {
+        // Oracle expects a full offset.
+        encode_utc_timestamp(buf, naive, /* tz = */ false)?;
+        if tz {
+            buf.write_str(" +00:00")?;
+        }
+    } else {
+        encode_utc_timestamp(buf, naive, tz)?;
+    }
+    Ok(())
+}
+
 /// Encodes the literal expression as a string in the buffer. This is used to
 /// translate the query's where clause.
 pub fn encode_literal_to_text(
@@ -65,6 +118,8 @@ pub fn encode_literal_to_text(
         return Ok(());
     }
 
+    let (prefix, suffix) = literal_wrapping(datasource, lit)?;
+    buf.write_str(prefix)?;
     if is_literal_quotable(datasource, lit) {
         buf.write_str("'")?;
     }
@@ -86,25 +141,27 @@ pub fn encode_literal_to_text(
         ScalarValue::Binary(Some(v)) if datasource == Datasource::MySql => {
             encode_binary_mysql(buf, v)?
         }
-        ScalarValue::Binary(Some(v)) if datasource == Datasource::Snowflake => {
+        ScalarValue::Binary(Some(v))
+            if datasource == Datasource::Snowflake || datasource == Datasource::Oracle =>
+        {
             encode_binary_snowflake(buf, v)?
         }
         ScalarValue::Binary(Some(v)) => encode_binary(buf, v)?,
         ScalarValue::TimestampNanosecond(Some(v), tz) => {
             let naive = Utc.timestamp_nanos(*v).naive_utc();
-            encode_utc_timestamp(buf, &naive, tz.is_some())?;
+            put_utc_timestamp(datasource, buf, &naive, tz.is_some())?;
         }
         ScalarValue::TimestampMicrosecond(Some(v), tz) => {
             let naive = Utc.timestamp_micros(*v).unwrap().naive_utc();
-            encode_utc_timestamp(buf, &naive, tz.is_some())?;
+            put_utc_timestamp(datasource, buf, &naive, tz.is_some())?;
         }
         ScalarValue::TimestampMillisecond(Some(v), tz) => {
             let naive = Utc.timestamp_millis_opt(*v).unwrap().naive_utc();
-            encode_utc_timestamp(buf, &naive, tz.is_some())?;
+            put_utc_timestamp(datasource, buf, &naive, tz.is_some())?;
         }
         ScalarValue::TimestampSecond(Some(v), tz) => {
             let naive = Utc.timestamp_opt(*v, 0).unwrap().naive_utc();
-            encode_utc_timestamp(buf, &naive, tz.is_some())?;
+            put_utc_timestamp(datasource, buf, &naive, tz.is_some())?;
         }
         ScalarValue::Time64Nanosecond(Some(v)) => {
             let naive = Utc.timestamp_nanos(*v).naive_utc().time();
@@ -136,9 +193,26 @@ pub fn encode_literal_to_text(
     if is_literal_quotable(datasource, lit) {
         buf.write_str("'")?;
     }
+    buf.write_str(suffix)?;
     Ok(())
 }
 
+/// Quote an identifier (column, table or schema name) using the datasource's
+/// identifier quoting.
+pub fn quote_ident(datasource: Datasource, ident: &str) -> String {
+    match datasource {
+        Datasource::MySql | Datasource::BigQuery | Datasource::Clickhouse => {
+            format!("`{}`", ident.replace('`', "``"))
+        }
+        Datasource::SqlServer => format!("[{}]", ident.replace(']', "]]")),
+        Datasource::Postgres
+        | Datasource::Snowflake
+        | Datasource::Sqlite
+        | Datasource::Oracle
+        | Datasource::DuckDb => format!("\"{}\"", ident.replace('"', "\"\"")),
+    }
+}
+
 static DEFAULT_CAST_OPTIONS: Lazy<CastOptions> = Lazy::new(|| CastOptions {
     // If a cast fails we should rather report the error and fix it instead
     // of returning NULLs. This is a programming error.
@@ -219,6 +293,19 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_quote_ident() {
+        assert_eq!(
+            r#""my ""col""""#,
+            quote_ident(Datasource::Postgres, r#"my "col""#)
+        );
+        assert_eq!("`my ``col```", quote_ident(Datasource::MySql, "my `col`"));
+        assert_eq!(
+            "[my [col]]]",
+            quote_ident(Datasource::SqlServer, "my [col]")
+        );
+    }
+
     #[test]
     fn test_literal_encode() {
         struct TestCase {
@@ -326,6 +413,24 @@ mod tests {
                 literal: ScalarValue::Decimal128(Some(123456), 38, 3),
                 expected: Some("123.456"),
             },
+            TestCase {
+                datasource: Oracle,
+                literal: ScalarValue::Binary(Some(b"abc".to_vec())),
+                expected: Some("HEXTORAW('616263')"),
+            },
+            TestCase {
+                datasource: Oracle,
+                literal: ScalarValue::Date32(Some(10_864)),
+                expected: Some("DATE '1999-09-30'"),
+            },
+            TestCase {
+                datasource: Oracle,
+                literal: ScalarValue::TimestampMicrosecond(
+                    Some(938709124 * 1_000_000),
+                    Some("UTC".into()),
+                ),
+                expected: Some("TIMESTAMP '1999-09-30 16:32:04 +00:00'"),
+            },
         ];
 
         cases.into_iter().for_each(|case| {
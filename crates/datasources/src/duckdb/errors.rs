@@ -0,0 +1,56 @@
+#[derive(thiserror::Error, Debug)]
+pub enum DuckDbError {
+    #[error("Internal Error: {0}")]
+    Internal(String),
+
+    #[error(transparent)]
+    DuckDb(#[from] duckdb::Error),
+
+    #[error(transparent)]
+    Fmt(#[from] std::fmt::Error),
+
+    #[error(transparent)]
+    DatasourceCommon(#[from] crate::common::errors::DatasourceCommonError),
+
+    #[error("found {num} objects matching specification '{url}'")]
+    NoMatchingObjectFound {
+        url: crate::common::url::DatasourceUrl,
+        num: usize,
+    },
+
+    #[error("Table does not exist: {0}")]
+    MissingTable(String),
+
+    #[error(transparent)]
+    ArrowError(#[from] datafusion::arrow::error::ArrowError),
+
+    /// Errors from the arrow version DuckDB is built against.
+    #[error(transparent)]
+    DuckDbArrowError(#[from] duckdb::arrow::error::ArrowError),
+
+    #[error(transparent)]
+    IoError(#[from] std::io::Error),
+
+    #[error(transparent)]
+    JoinError(#[from] tokio::task::JoinError),
+
+    #[error(transparent)]
+    ObjectStoreSource(#[from] crate::object_store::errors::ObjectStoreSourceError),
+
+    #[error(transparent)]
+    ObjectStoreError(#[from] object_store::Error),
+
+    #[error(transparent)]
+    ObjectStorePath(#[from] object_store::path::Error),
+
+    #[error(transparent)]
+    LakeStorageOptions(#[from] crate::lake::LakeStorageOptionsError),
+}
+
+pub type Result<T, E = DuckDbError> = std::result::Result<T, E>;
+
+impl From<DuckDbError> for datafusion_ext::errors::ExtensionError {
+    fn from(value: DuckDbError) -> Self {
+        datafusion_ext::errors::ExtensionError::access(value)
+    }
+}
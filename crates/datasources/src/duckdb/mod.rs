@@ -0,0 +1,723 @@
+//! DuckDB data source.
+//!
+//! Database files are always opened read-only. Scans go through DuckDB's arrow
+//! interface so record batches are passed along as-is instead of being built up
+//! value by value.
+//!
+//! DuckDB is built against an older arrow version than datafusion. Schemas and
+//! arrays are moved between the two over the arrow C data interface, which
+//! doesn't copy any buffers.
+
+pub mod errors;
+
+use std::any::Any;
+use std::fmt::{self, Write as _};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use async_stream::stream;
+use async_trait::async_trait;
+use datafusion::arrow::array::{ArrayRef, StructArray};
+use datafusion::arrow::datatypes::{DataType, Fields, Schema, SchemaRef};
+use datafusion::arrow::ffi::{from_ffi, FFI_ArrowArray, FFI_ArrowSchema};
+use datafusion::arrow::record_batch::{RecordBatch, RecordBatchOptions};
+use datafusion::datasource::TableProvider;
+use datafusion::error::DataFusionError;
+use datafusion::execution::context::SessionState;
+use datafusion::execution::TaskContext;
+use datafusion::logical_expr::{Operator, TableProviderFilterPushDown, TableType};
+use datafusion::physical_expr::PhysicalSortExpr;
+use datafusion::physical_plan::metrics::{ExecutionPlanMetricsSet, MetricsSet};
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
+use datafusion::physical_plan::{
+    DisplayAs,
+    DisplayFormatType,
+    ExecutionPlan,
+    Partitioning,
+    SendableRecordBatchStream,
+    Statistics,
+};
+use datafusion::prelude::Expr;
+use datafusion_ext::errors::ExtensionError;
+use datafusion_ext::functions::VirtualLister;
+use datafusion_ext::metrics::DataSourceMetricsStreamAdapter;
+use duckdb::arrow::array::Array as _;
+use duckdb::{AccessMode, Config, Connection};
+use object_store::ObjectStore;
+use parking_lot::Mutex;
+use protogen::metastore::types::options::StorageOptions;
+use tokio::sync::mpsc;
+use tracing::{debug, trace};
+use uuid::Uuid;
+
+use self::errors::{DuckDbError, Result};
+use crate::common::url::DatasourceUrl;
+use crate::common::util;
+use crate::lake::storage_options_into_store_access;
+use crate::object_store::ObjStoreAccessor;
+
+type DataFusionResult<T> = Result<T, DataFusionError>;
+
+/// Schema tables are created in when no schema is given.
+pub const DEFAULT_SCHEMA: &str = "main";
+
+#[derive(Debug, Clone)]
+pub struct DuckDbAccess {
+    pub db: PathBuf,
+    pub cache: Option<Arc<tempfile::TempDir>>,
+}
+
+impl DuckDbAccess {
+    pub async fn new(url: DatasourceUrl, opts: Option<StorageOptions>) -> Result<Self> {
+        match url {
+            DatasourceUrl::File(ref location) => {
+                if !location.try_exists()? {
+                    Err(DuckDbError::NoMatchingObjectFound {
+                        url: url.clone(),
+                        num: 0,
+                    })
+                } else {
+                    Ok(Self {
+                        db: location.clone(),
+                        cache: None,
+                    })
+                }
+            }
+            DatasourceUrl::Url(_) => {
+                let storage_options = match opts {
+                    Some(v) => v,
+                    None => {
+                        return Err(DuckDbError::Internal(
+                            "storage options are required".to_string(),
+                        ))
+                    }
+                };
+                let store_access = storage_options_into_store_access(&url, &storage_options)?;
+
+                let accessor = ObjStoreAccessor::new(store_access)?;
+                let mut list = accessor.list_globbed(url.path()).await?;
+                if list.len() != 1 {
+                    return Err(DuckDbError::NoMatchingObjectFound {
+                        url,
+                        num: list.len(),
+                    });
+                }
+
+                let store = accessor.into_object_store();
+
+                let obj = list.pop().unwrap().location;
+                let payload = store.get(&obj).await?.bytes().await?;
+
+                let tmpdir = Arc::new(
+                    tempfile::Builder::new()
+                        .prefix(
+                            storage_options
+                                .inner
+                                .get("__tmp_prefix")
+                                .map(|i| i.to_owned())
+                                .unwrap_or_else(|| Uuid::new_v4().to_string())
+                                .as_str(),
+                        )
+                        .rand_bytes(8)
+                        .tempdir()?,
+                );
+
+                let tmpdir_path = tmpdir.path();
+                let local_store =
+                    object_store::local::LocalFileSystem::new_with_prefix(tmpdir_path)?;
+
+                let local_path =
+                    object_store::path::Path::parse(obj.filename().unwrap_or("duckdb"))?;
+
+                local_store.put(&local_path, payload).await?;
+
+                let db = tmpdir_path.join(local_path.filename().unwrap());
+
+                Ok(Self {
+                    db,
+                    cache: Some(tmpdir.clone()),
+                })
+            }
+        }
+    }
+
+    pub async fn connect(&self) -> Result<DuckDbAccessState> {
+        let db = self.db.clone();
+        let conn = tokio::task::spawn_blocking(move || {
+            let config = Config::default().access_mode(AccessMode::ReadOnly)?;
+            Connection::open_with_flags(db, config)
+        })
+        .await??;
+
+        Ok(DuckDbAccessState {
+            db: self.db.clone(),
+            conn: Arc::new(Mutex::new(conn)),
+            _cache: self.cache.clone(),
+        })
+    }
+
+    pub async fn validate_access(&self) -> Result<()> {
+        let state = self.connect().await?;
+        state
+            .run_blocking(|conn| {
+                conn.query_row("SELECT 1", [], |_| Ok(()))?;
+                Ok(())
+            })
+            .await
+    }
+
+    pub async fn validate_table_access(&self, access: &DuckDbTableAccess) -> Result<()> {
+        let state = self.connect().await?;
+        state.get_table_schema(access).await?;
+        Ok(())
+    }
+}
+
+/// A (schema qualified) table in a DuckDB database.
+#[derive(Debug, Clone)]
+pub struct DuckDbTableAccess {
+    pub schema: String,
+    pub name: String,
+}
+
+impl DuckDbTableAccess {
+    /// Create the table access from a table name that's optionally qualified
+    /// with a schema, e.g. `sales` or `analytics.sales`.
+    pub fn from_qualified_name(name: &str) -> Self {
+        match name.split_once('.') {
+            Some((schema, name)) => Self {
+                schema: schema.to_string(),
+                name: name.to_string(),
+            },
+            None => Self {
+                schema: DEFAULT_SCHEMA.to_string(),
+                name: name.to_string(),
+            },
+        }
+    }
+
+    fn table_reference(&self) -> String {
+        format!("{}.{}", quote_ident(&self.schema), quote_ident(&self.name))
+    }
+}
+
+#[derive(Clone)]
+pub struct DuckDbAccessState {
+    db: PathBuf,
+    /// Connection to the database. Every query runs on its own clone of this
+    /// connection so that scans can happen concurrently.
+    conn: Arc<Mutex<Connection>>,
+    // Ties the lifetime of a downloaded database file to this connection.
+    _cache: Option<Arc<tempfile::TempDir>>,
+}
+
+impl fmt::Debug for DuckDbAccessState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "DuckDbAccessState({})", self.db.to_string_lossy())
+    }
+}
+
+impl DuckDbAccessState {
+    /// Run `f` on the blocking thread pool with a new connection to the
+    /// database.
+    async fn run_blocking<T, F>(&self, f: F) -> Result<T>
+    where
+        F: FnOnce(&Connection) -> Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let conn = self.conn.clone();
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.lock().try_clone()?;
+            f(&conn)
+        })
+        .await?
+    }
+
+    async fn get_table_schema(&self, access: &DuckDbTableAccess) -> Result<Schema> {
+        let query = format!("SELECT * FROM {} LIMIT 0", access.table_reference());
+        let schema = self
+            .run_blocking(move |conn| {
+                let mut stmt = conn.prepare(&query)?;
+                let arrow = stmt.query_arrow([])?;
+                schema_from_duckdb(arrow.get_schema().as_ref())
+            })
+            .await?;
+
+        Ok(schema)
+    }
+}
+
+#[async_trait]
+impl VirtualLister for DuckDbAccessState {
+    async fn list_schemas(&self) -> Result<Vec<String>, ExtensionError> {
+        let schemas = self
+            .run_blocking(|conn| {
+                let mut stmt = conn.prepare(
+                    "SELECT schema_name FROM information_schema.schemata
+                    WHERE catalog_name = current_database()
+                    ORDER BY schema_name",
+                )?;
+                let names = stmt
+                    .query_map([], |row| row.get::<_, String>(0))?
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(names)
+            })
+            .await
+            .map_err(ExtensionError::access)?;
+
+        Ok(schemas)
+    }
+
+    async fn list_tables(&self, schema: &str) -> Result<Vec<String>, ExtensionError> {
+        let schema = schema.to_string();
+        let tables = self
+            .run_blocking(move |conn| {
+                let mut stmt = conn.prepare(
+                    "SELECT table_name FROM information_schema.tables
+                    WHERE table_catalog = current_database() AND table_schema = ?
+                    ORDER BY table_name",
+                )?;
+                let names = stmt
+                    .query_map([schema], |row| row.get::<_, String>(0))?
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(names)
+            })
+            .await
+            .map_err(ExtensionError::access)?;
+
+        Ok(tables)
+    }
+
+    async fn list_columns(&self, schema: &str, table: &str) -> Result<Fields, ExtensionError> {
+        let access = DuckDbTableAccess {
+            schema: schema.to_string(),
+            name: table.to_string(),
+        };
+        let table_schema = self
+            .get_table_schema(&access)
+            .await
+            .map_err(ExtensionError::access)?;
+
+        Ok(table_schema.fields)
+    }
+}
+
+pub struct DuckDbTableProvider {
+    state: DuckDbAccessState,
+    access: DuckDbTableAccess,
+    schema: SchemaRef,
+}
+
+impl DuckDbTableProvider {
+    pub async fn try_new(state: DuckDbAccessState, access: DuckDbTableAccess) -> Result<Self> {
+        let schema = state.get_table_schema(&access).await?;
+        Ok(Self {
+            state,
+            access,
+            schema: Arc::new(schema),
+        })
+    }
+}
+
+#[async_trait]
+impl TableProvider for DuckDbTableProvider {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn table_type(&self) -> TableType {
+        TableType::Base
+    }
+
+    fn supports_filter_pushdown(
+        &self,
+        _filter: &Expr,
+    ) -> DataFusionResult<TableProviderFilterPushDown> {
+        Ok(TableProviderFilterPushDown::Inexact)
+    }
+
+    async fn scan(
+        &self,
+        _ctx: &SessionState,
+        projection: Option<&Vec<usize>>,
+        filters: &[Expr],
+        limit: Option<usize>,
+    ) -> DataFusionResult<Arc<dyn ExecutionPlan>> {
+        // Project the schema.
+        let projected_schema = match projection {
+            Some(projection) => Arc::new(self.schema.project(projection)?),
+            None => self.schema.clone(),
+        };
+
+        // Get the projected columns, joined by a ','. This will be put in the
+        // 'SELECT ...' portion of the query. Nothing needs to be read if no
+        // columns are projected, only the number of rows is used.
+        let projection_string = if projected_schema.fields().is_empty() {
+            "1".to_string()
+        } else {
+            projected_schema
+                .fields
+                .iter()
+                .map(|f| quote_ident(f.name()))
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+
+        let limit_string = match limit {
+            Some(limit) => format!("LIMIT {}", limit),
+            None => String::new(),
+        };
+
+        let predicate_string = exprs_to_predicate_string(filters, &self.schema)
+            .map_err(|e| DataFusionError::External(Box::new(e)))?;
+
+        let query = format!(
+            "SELECT {} FROM {} {} {} {}",
+            projection_string,             // SELECT <str>
+            self.access.table_reference(), // FROM <schema>.<table>
+            // [WHERE]
+            if predicate_string.is_empty() {
+                ""
+            } else {
+                "WHERE "
+            },
+            predicate_string.as_str(), // <where-predicate>
+            limit_string,              // [LIMIT ..]
+        );
+        trace!(?query);
+
+        Ok(Arc::new(DuckDbQueryExec {
+            predicate: predicate_string,
+            access: self.access.clone(),
+            query,
+            state: self.state.clone(),
+            schema: projected_schema,
+            metrics: ExecutionPlanMetricsSet::new(),
+        }))
+    }
+}
+
+#[derive(Debug)]
+pub struct DuckDbQueryExec {
+    predicate: String,
+    access: DuckDbTableAccess,
+    query: String,
+    state: DuckDbAccessState,
+    schema: SchemaRef,
+    metrics: ExecutionPlanMetricsSet,
+}
+
+impl ExecutionPlan for DuckDbQueryExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        Vec::new()
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> DataFusionResult<Arc<dyn ExecutionPlan>> {
+        if children.is_empty() {
+            Ok(self)
+        } else {
+            Err(DataFusionError::Execution(
+                "cannot replace children for DuckDbQueryExec".to_string(),
+            ))
+        }
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        _context: Arc<TaskContext>,
+    ) -> DataFusionResult<SendableRecordBatchStream> {
+        if partition != 0 {
+            return Err(DataFusionError::Execution(format!(
+                "invalid partition: {partition}"
+            )));
+        }
+
+        let stream = query_stream(
+            self.state.conn.clone(),
+            self.query.clone(),
+            self.schema.clone(),
+        );
+
+        Ok(Box::pin(DataSourceMetricsStreamAdapter::new(
+            stream,
+            partition,
+            &self.metrics,
+        )))
+    }
+
+    fn statistics(&self) -> DataFusionResult<Statistics> {
+        Ok(Statistics::new_unknown(self.schema().as_ref()))
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metrics.clone_inner())
+    }
+}
+
+impl DisplayAs for DuckDbQueryExec {
+    fn fmt_as(&self, _t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "DuckDbQueryExec: schema={}, name={}, predicate={}",
+            self.access.schema,
+            self.access.name,
+            if self.predicate.is_empty() {
+                "None"
+            } else {
+                self.predicate.as_str()
+            }
+        )
+    }
+}
+
+/// Run the query on the blocking thread pool, streaming the arrow record
+/// batches produced by DuckDB.
+///
+/// Reading stops once the returned stream is dropped.
+fn query_stream(
+    conn: Arc<Mutex<Connection>>,
+    query: String,
+    schema: SchemaRef,
+) -> SendableRecordBatchStream {
+    let (tx, mut rx) = mpsc::channel::<DataFusionResult<RecordBatch>>(2);
+
+    let batch_schema = schema.clone();
+    tokio::task::spawn_blocking(move || {
+        let read = || -> Result<()> {
+            let conn = conn.lock().try_clone()?;
+            let mut stmt = conn.prepare(&query)?;
+            for batch in stmt.query_arrow([])? {
+                // Batches from DuckDB carry their own schema, use the one
+                // that was planned with. With an empty projection only the
+                // number of rows is kept.
+                let num_rows = batch.num_rows();
+                let columns = if batch_schema.fields().is_empty() {
+                    Vec::new()
+                } else {
+                    columns_from_duckdb(batch)?
+                };
+                let batch = RecordBatch::try_new_with_options(
+                    batch_schema.clone(),
+                    columns,
+                    &RecordBatchOptions::new().with_row_count(Some(num_rows)),
+                )?;
+                if tx.blocking_send(Ok(batch)).is_err() {
+                    // Stream dropped, no need to keep reading.
+                    return Ok(());
+                }
+            }
+            Ok(())
+        };
+
+        if let Err(e) = read() {
+            let _ = tx.blocking_send(Err(DataFusionError::External(Box::new(e))));
+        }
+    });
+
+    let stream = stream! {
+        while let Some(batch) = rx.recv().await {
+            yield batch;
+        }
+    };
+
+    Box::pin(RecordBatchStreamAdapter::new(schema, stream))
+}
+
+/// Convert a schema from DuckDB's arrow version.
+fn schema_from_duckdb(schema: &duckdb::arrow::datatypes::Schema) -> Result<Schema> {
+    let ffi = duckdb::arrow::ffi::FFI_ArrowSchema::try_from(schema)?;
+    // SAFETY: Both types are `#[repr(C)]` definitions of the C data interface
+    // `ArrowSchema` struct. The release callback moves along with it.
+    let ffi: FFI_ArrowSchema = unsafe { std::mem::transmute(ffi) };
+    Ok(Schema::try_from(&ffi)?)
+}
+
+/// Convert the columns of a record batch from DuckDB's arrow version.
+fn columns_from_duckdb(batch: duckdb::arrow::record_batch::RecordBatch) -> Result<Vec<ArrayRef>> {
+    let array = duckdb::arrow::array::StructArray::from(batch);
+    let (array, schema) = duckdb::arrow::ffi::to_ffi(&array.to_data())?;
+    // SAFETY: See `schema_from_duckdb`, the same holds for `ArrowArray`.
+    let (array, schema): (FFI_ArrowArray, FFI_ArrowSchema) =
+        unsafe { (std::mem::transmute(array), std::mem::transmute(schema)) };
+    // SAFETY: The array and schema were just exported by arrow.
+    let data = unsafe { from_ffi(array, &schema)? };
+    Ok(StructArray::from(data).columns().to_vec())
+}
+
+fn quote_ident(ident: &str) -> String {
+    format!("\"{}\"", ident.replace('"', "\"\""))
+}
+
+/// Convert filtering expressions to a predicate string usable with the
+/// generated DuckDB query.
+fn exprs_to_predicate_string(exprs: &[Expr], schema: &Schema) -> Result<String> {
+    let mut ss = Vec::new();
+    let mut buf = String::new();
+    for expr in exprs {
+        if write_expr(expr, schema, &mut buf)? {
+            ss.push(buf);
+            buf = String::new();
+        } else {
+            buf.clear();
+        }
+    }
+
+    Ok(ss.join(" AND "))
+}
+
+/// Try to write the expression to the string, returning true if it was written.
+fn write_expr(expr: &Expr, schema: &Schema, buf: &mut String) -> Result<bool> {
+    match expr {
+        Expr::Column(col) => {
+            if schema.index_of(&col.name).is_err() {
+                return Ok(false);
+            }
+            write!(buf, "{}", quote_ident(&col.name))?;
+        }
+        Expr::Literal(val) => {
+            // Binary literals are encoded differently in DuckDB, leave those
+            // to datafusion.
+            if matches!(val.data_type(), DataType::Binary | DataType::LargeBinary) {
+                return Ok(false);
+            }
+            if util::encode_literal_to_text(util::Datasource::DuckDb, buf, val).is_err() {
+                return Ok(false);
+            }
+        }
+        Expr::IsNull(expr) => {
+            if write_expr(expr, schema, buf)? {
+                write!(buf, " IS NULL")?;
+            } else {
+                return Ok(false);
+            }
+        }
+        Expr::IsNotNull(expr) => {
+            if write_expr(expr, schema, buf)? {
+                write!(buf, " IS NOT NULL")?;
+            } else {
+                return Ok(false);
+            }
+        }
+        Expr::IsTrue(expr) => {
+            if write_expr(expr, schema, buf)? {
+                write!(buf, " IS TRUE")?;
+            } else {
+                return Ok(false);
+            }
+        }
+        Expr::IsFalse(expr) => {
+            if write_expr(expr, schema, buf)? {
+                write!(buf, " IS FALSE")?;
+            } else {
+                return Ok(false);
+            }
+        }
+        Expr::BinaryExpr(binary) => {
+            match binary.op {
+                Operator::Eq
+                | Operator::NotEq
+                | Operator::Lt
+                | Operator::LtEq
+                | Operator::Gt
+                | Operator::GtEq
+                | Operator::Plus
+                | Operator::Minus
+                | Operator::Multiply
+                | Operator::And
+                | Operator::Or => (),
+                _ => return Ok(false),
+            }
+            write!(buf, "(")?;
+            if !write_expr(binary.left.as_ref(), schema, buf)? {
+                return Ok(false);
+            }
+            write!(buf, " {} ", binary.op)?;
+            if !write_expr(binary.right.as_ref(), schema, buf)? {
+                return Ok(false);
+            }
+            write!(buf, ")")?;
+        }
+        expr => {
+            // Unsupported.
+            debug!(?expr, "Unsupported filter used");
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use datafusion::arrow::datatypes::Field;
+    use datafusion::common::Column;
+    use datafusion::logical_expr::BinaryExpr;
+    use datafusion::scalar::ScalarValue;
+
+    use super::*;
+
+    #[test]
+    fn qualified_table_name() {
+        let access = DuckDbTableAccess::from_qualified_name("sales");
+        assert_eq!("\"main\".\"sales\"", access.table_reference());
+
+        let access = DuckDbTableAccess::from_qualified_name("analytics.sales");
+        assert_eq!("\"analytics\".\"sales\"", access.table_reference());
+    }
+
+    #[test]
+    fn valid_expr_string() {
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int64, true),
+            Field::new("b", DataType::Utf8, true),
+            Field::new("c", DataType::Binary, true),
+        ]);
+
+        let exprs = vec![
+            Expr::BinaryExpr(BinaryExpr {
+                left: Box::new(Expr::Column(Column::from_name("a"))),
+                op: Operator::Gt,
+                right: Box::new(Expr::Literal(ScalarValue::Int64(Some(4)))),
+            }),
+            Expr::BinaryExpr(BinaryExpr {
+                left: Box::new(Expr::Column(Column::from_name("b"))),
+                op: Operator::Eq,
+                right: Box::new(Expr::Literal(ScalarValue::Utf8(Some("abc".to_string())))),
+            }),
+            // Binary literals aren't pushed down.
+            Expr::BinaryExpr(BinaryExpr {
+                left: Box::new(Expr::Column(Column::from_name("c"))),
+                op: Operator::Eq,
+                right: Box::new(Expr::Literal(ScalarValue::Binary(Some(vec![1, 2])))),
+            }),
+            Expr::IsNotNull(Box::new(Expr::Column(Column::from_name("b")))),
+        ];
+
+        let out = exprs_to_predicate_string(&exprs, &schema).unwrap();
+        assert_eq!(r#"("a" > 4) AND ("b" = 'abc') AND "b" IS NOT NULL"#, out);
+    }
+}
@@ -11,6 +11,16 @@ use crate::object_store::errors::ObjectStoreSourceError;
 pub enum ExcelError {
     #[error("Failed to load XLSX: {0}")]
     Load(String),
+    #[error("Invalid option: {0}")]
+    InvalidOption(String),
+    #[error("Invalid cell range '{0}', expected a range like 'A1:H100'")]
+    InvalidCellRange(String),
+    #[error("Invalid column type '{0}', expected '<column> <type>'")]
+    InvalidColumnType(String),
+    #[error("Sheet not found: {0}")]
+    MissingSheet(String),
+    #[error("Column not found: {0}")]
+    MissingColumn(String),
     #[error("Failed to create record batch: {0}")]
     CreateRecordBatch(#[from] ArrowError),
     #[error(transparent)]
@@ -1,14 +1,19 @@
-use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
-use std::io::Cursor;
-use std::path::PathBuf;
+use std::io::{Cursor, Read, Seek};
+use std::str::FromStr;
 use std::sync::Arc;
 
-use calamine::{open_workbook, DataType as CalamineDataType, Range, Reader, Sheets, Xlsx};
-use datafusion::arrow::array::{ArrayRef, BooleanArray, Date64Array, PrimitiveArray, StringArray};
-use datafusion::arrow::datatypes::{DataType, Field, Float64Type, Int64Type, Schema};
+use calamine::{DataType as CalamineDataType, Range, Reader, Sheets};
+use datafusion::arrow::array::{
+    new_null_array,
+    ArrayRef,
+    BooleanArray,
+    Date64Array,
+    PrimitiveArray,
+    StringArray,
+};
+use datafusion::arrow::datatypes::{DataType, Field, Float64Type, Int64Type, Schema, SchemaRef};
 use datafusion::arrow::record_batch::RecordBatch;
-use datafusion::datasource::MemTable;
 use object_store::{ObjectMeta, ObjectStore};
 
 use crate::common::url::DatasourceUrl;
@@ -20,33 +25,183 @@ pub mod table;
 
 use errors::ExcelError;
 
+/// Name of the column added when reading all sheets from a workbook.
+pub const SHEET_COLUMN_NAME: &str = "sheet";
+
+/// Which sheet(s) to read from a workbook.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum SheetSelection {
+    /// Read the first sheet in the workbook.
+    #[default]
+    First,
+    /// Read the sheet with the given name.
+    Name(String),
+    /// Read the sheet at the given (zero-based) index.
+    Index(usize),
+    /// Read all sheets in the workbook, adding a column containing the sheet
+    /// name for each row.
+    All,
+}
+
+impl SheetSelection {
+    /// Create a sheet selection from user provided options.
+    pub fn try_new(
+        sheet_name: Option<String>,
+        sheet_index: Option<usize>,
+        all_sheets: bool,
+    ) -> Result<SheetSelection, ExcelError> {
+        match (sheet_name, sheet_index, all_sheets) {
+            (None, None, false) => Ok(SheetSelection::First),
+            (Some(name), None, false) => Ok(SheetSelection::Name(name)),
+            (None, Some(idx), false) => Ok(SheetSelection::Index(idx)),
+            (None, None, true) => Ok(SheetSelection::All),
+            _ => Err(ExcelError::InvalidOption(
+                "only one of 'sheet_name', 'sheet_index' or 'all_sheets' may be provided"
+                    .to_string(),
+            )),
+        }
+    }
+}
+
+/// A rectangular range of cells, e.g. `A3:H500`.
+///
+/// Positions are zero-based (row, column) pairs, and both ends are inclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CellRange {
+    pub start: (u32, u32),
+    pub end: (u32, u32),
+}
+
+impl FromStr for CellRange {
+    type Err = ExcelError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, end) = s
+            .split_once(':')
+            .ok_or_else(|| ExcelError::InvalidCellRange(s.to_string()))?;
+
+        let start = parse_cell_reference(start.trim())
+            .ok_or_else(|| ExcelError::InvalidCellRange(s.to_string()))?;
+        let end = parse_cell_reference(end.trim())
+            .ok_or_else(|| ExcelError::InvalidCellRange(s.to_string()))?;
+
+        if start.0 > end.0 || start.1 > end.1 {
+            return Err(ExcelError::InvalidCellRange(s.to_string()));
+        }
+
+        Ok(CellRange { start, end })
+    }
+}
+
+/// Parse a cell reference like `B12` into a zero-based (row, column) pair.
+fn parse_cell_reference(s: &str) -> Option<(u32, u32)> {
+    let split = s.find(|c: char| c.is_ascii_digit())?;
+    let (col, row) = s.split_at(split);
+    if col.is_empty() {
+        return None;
+    }
+
+    let mut col_idx: u32 = 0;
+    for c in col.chars() {
+        if !c.is_ascii_alphabetic() {
+            return None;
+        }
+        let digit = (c.to_ascii_uppercase() as u32) - ('A' as u32) + 1;
+        col_idx = col_idx.checked_mul(26)?.checked_add(digit)?;
+    }
+
+    let row: u32 = row.parse().ok()?;
+    if row == 0 {
+        return None;
+    }
+
+    Some((row - 1, col_idx - 1))
+}
+
+/// Parse user provided column types, e.g. `'amount float, posted date'`.
+///
+/// Column names are normalized the same way as inferred column names (spaces
+/// replaced with underscores).
+pub fn parse_column_types(s: &str) -> Result<Vec<(String, DataType)>, ExcelError> {
+    s.split(',')
+        .map(str::trim)
+        .filter(|col| !col.is_empty())
+        .map(|col| {
+            let (name, typ) = col
+                .rsplit_once(char::is_whitespace)
+                .ok_or_else(|| ExcelError::InvalidColumnType(col.to_string()))?;
+            let typ = match typ.to_lowercase().as_str() {
+                "bool" | "boolean" => DataType::Boolean,
+                "int" | "integer" | "bigint" | "int8" | "int64" => DataType::Int64,
+                "float" | "double" | "real" | "float8" | "float64" | "numeric" | "decimal" => {
+                    DataType::Float64
+                }
+                "date" | "datetime" | "timestamp" => DataType::Date64,
+                "text" | "string" | "varchar" | "utf8" => DataType::Utf8,
+                _ => return Err(ExcelError::InvalidColumnType(col.to_string())),
+            };
+            Ok((normalize_column_name(name.trim()), typ))
+        })
+        .collect()
+}
+
+/// Options for reading data out of a workbook.
+#[derive(Debug, Clone)]
+pub struct ExcelReadOptions {
+    /// Which sheet(s) to read.
+    pub sheets: SheetSelection,
+    /// Only read cells within this range. Reads all cells in the sheet if not
+    /// provided.
+    pub cell_range: Option<CellRange>,
+    /// Offset of the header row from the start of the range. Rows before the
+    /// header (e.g. titles) are skipped.
+    pub header_row: usize,
+    /// If the first row (after applying the offset) contains column names.
+    pub has_header: bool,
+    /// Number of rows to sample when inferring column types.
+    pub infer_rows: usize,
+    /// Explicit types for columns, overriding inferred types.
+    pub column_types: Vec<(String, DataType)>,
+}
+
+impl Default for ExcelReadOptions {
+    fn default() -> Self {
+        ExcelReadOptions {
+            sheets: SheetSelection::First,
+            cell_range: None,
+            header_row: 0,
+            has_header: true,
+            infer_rows: 100,
+            column_types: Vec::new(),
+        }
+    }
+}
+
+/// Cells read from a single sheet, already limited to the requested range.
+#[derive(Debug, Clone)]
+pub struct ExcelSheet {
+    pub name: String,
+    pub cell_range: Range<calamine::Data>,
+}
+
 pub struct ExcelTable {
-    cell_range: Range<calamine::Data>,
-    has_header: bool,
+    sheets: Vec<ExcelSheet>,
+    options: ExcelReadOptions,
 }
 
 impl ExcelTable {
     pub async fn open(
         store_access: Arc<dyn ObjStoreAccess>,
         source_url: DatasourceUrl,
-        sheet_name: Option<&str>,
-        has_header: bool,
+        options: ExcelReadOptions,
     ) -> Result<ExcelTable, ExcelError> {
         match source_url {
             DatasourceUrl::File(path) => {
                 let path = ioutil::resolve_path(&path)?;
-                let mut sheet = calamine::open_workbook_auto(path)?;
-
-                let first_sheet = sheet_name.map(Cow::Borrowed).unwrap_or_else(|| {
-                    let sheets = sheet.sheet_names();
-                    let first = sheets.first().expect("file has a sheet");
-                    Cow::Owned(first.clone())
-                });
-
-                Ok(ExcelTable {
-                    cell_range: sheet.worksheet_range(&first_sheet)?,
-                    has_header,
-                })
+                let mut workbook = calamine::open_workbook_auto(path)?;
+                let sheets = read_sheets(&mut workbook, &options)?;
+
+                Ok(ExcelTable { sheets, options })
             }
 
             DatasourceUrl::Url(_) => {
@@ -66,7 +221,7 @@ impl ExcelTable {
                 let meta = list.pop().expect("remote file has a sheet");
                 let store = accessor.into_object_store();
 
-                excel_table_from_object(store.as_ref(), meta, sheet_name, has_header).await
+                excel_table_from_object(store.as_ref(), meta, options).await
             }
         }
     }
@@ -75,62 +230,276 @@ impl ExcelTable {
 pub async fn excel_table_from_object(
     store: &dyn ObjectStore,
     meta: ObjectMeta,
-    sheet_name: Option<&str>,
-    has_header: bool,
+    options: ExcelReadOptions,
 ) -> Result<ExcelTable, ExcelError> {
     let bs = store.get(&meta.location).await?.bytes().await?;
 
     let buffer = Cursor::new(bs);
-    let mut sheets: Sheets<_> = calamine::open_workbook_auto_from_rs(buffer).unwrap();
+    let mut workbook: Sheets<_> = calamine::open_workbook_auto_from_rs(buffer)?;
+    let sheets = read_sheets(&mut workbook, &options)?;
 
-    let first_sheet = sheet_name.map(Cow::Borrowed).unwrap_or_else(|| {
-        let sheets = sheets.sheet_names();
-        let first = sheets.first().unwrap();
-        Cow::Owned(first.clone())
-    });
+    Ok(ExcelTable { sheets, options })
+}
 
-    Ok(ExcelTable {
-        cell_range: sheets.worksheet_range(&first_sheet).unwrap(),
-        has_header,
-    })
+/// Read the selected sheets out of the workbook, limiting each to the
+/// requested cell range and header offset.
+fn read_sheets<RS: Read + Seek>(
+    workbook: &mut Sheets<RS>,
+    options: &ExcelReadOptions,
+) -> Result<Vec<ExcelSheet>, ExcelError> {
+    let names = workbook.sheet_names();
+    let selected: Vec<String> = match &options.sheets {
+        SheetSelection::First => vec![names
+            .first()
+            .cloned()
+            .ok_or_else(|| ExcelError::Load("workbook has no sheets".to_string()))?],
+        SheetSelection::Name(name) => {
+            if !names.contains(name) {
+                return Err(ExcelError::MissingSheet(name.clone()));
+            }
+            vec![name.clone()]
+        }
+        SheetSelection::Index(idx) => vec![names
+            .get(*idx)
+            .cloned()
+            .ok_or_else(|| ExcelError::MissingSheet(format!("index {idx}")))?],
+        SheetSelection::All => names,
+    };
+
+    selected
+        .into_iter()
+        .map(|name| {
+            let range = workbook.worksheet_range(&name)?;
+            let cell_range = select_cells(range, options.cell_range, options.header_row);
+            Ok(ExcelSheet { name, cell_range })
+        })
+        .collect()
+}
+
+/// Limit the cells to the given range, skipping the first `skip_rows` rows.
+///
+/// The selected cells are clamped to the cells actually used by the sheet.
+fn select_cells(
+    range: Range<calamine::Data>,
+    cell_range: Option<CellRange>,
+    skip_rows: usize,
+) -> Range<calamine::Data> {
+    let (sheet_start, sheet_end) = match (range.start(), range.end()) {
+        (Some(start), Some(end)) => (start, end),
+        // Sheet is empty.
+        _ => return Range::empty(),
+    };
+
+    let (start, end) = match cell_range {
+        Some(cells) => (cells.start, cells.end),
+        None => (sheet_start, sheet_end),
+    };
+
+    let start = (
+        start.0.saturating_add(skip_rows as u32).max(sheet_start.0),
+        start.1.max(sheet_start.1),
+    );
+    let end = (end.0.min(sheet_end.0), end.1.min(sheet_end.1));
+    if start.0 > end.0 || start.1 > end.1 {
+        return Range::empty();
+    }
+
+    if (start, end) == (sheet_start, sheet_end) {
+        return range;
+    }
+
+    range.range(start, end)
 }
 
-pub async fn read_excel_impl(
-    path: &PathBuf,
-    sheet_name: Option<&str>,
+/// Normalize a column name read from a sheet.
+fn normalize_column_name(name: &str) -> String {
+    name.replace(' ', "_")
+}
+
+/// Get the column names for a sheet.
+///
+/// Header cells that are empty are assumed to be part of a merged cell, and
+/// take the name of the closest non-empty header to the left. Duplicate names
+/// get a numeric suffix.
+fn column_names(r: &Range<calamine::Data>, has_header: bool) -> Result<Vec<String>, ExcelError> {
+    let header = r
+        .rows()
+        .next()
+        .ok_or_else(|| ExcelError::Load("sheet contains no rows".to_string()))?;
+
+    if !has_header {
+        return Ok((0..header.len()).map(|i| format!("col{}", i)).collect());
+    }
+
+    let mut names: Vec<String> = Vec::with_capacity(header.len());
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    let mut prev: Option<String> = None;
+
+    for (i, cell) in header.iter().enumerate() {
+        let base = match cell {
+            calamine::Data::Empty => prev.clone().unwrap_or_else(|| format!("col{}", i)),
+            calamine::Data::String(s) if s.trim().is_empty() => {
+                prev.clone().unwrap_or_else(|| format!("col{}", i))
+            }
+            calamine::Data::String(s) => normalize_column_name(s.trim()),
+            other => normalize_column_name(&other.to_string()),
+        };
+        prev = Some(base.clone());
+
+        let count = seen.entry(base.clone()).or_insert(0);
+        *count += 1;
+        if *count == 1 {
+            names.push(base);
+        } else {
+            names.push(format!("{base}_{count}"));
+        }
+    }
+
+    Ok(names)
+}
+
+/// Pick a single type for a column that contained values of multiple types.
+fn merge_types(types: &HashSet<DataType>) -> DataType {
+    match types.len() {
+        0 => DataType::Utf8,
+        1 => types.iter().next().cloned().unwrap(),
+        2 if types.contains(&DataType::Int64) && types.contains(&DataType::Float64) => {
+            DataType::Float64
+        }
+        _ => DataType::Utf8,
+    }
+}
+
+pub fn infer_schema(
+    r: &Range<calamine::Data>,
     has_header: bool,
     infer_schema_length: usize,
-) -> Result<MemTable, ExcelError> {
-    let mut workbook: Xlsx<_> = open_workbook(path).unwrap();
-    let sheet = sheet_name.map(Cow::Borrowed).unwrap_or_else(|| {
-        let sheets = workbook.sheet_names();
-        let first = sheets.first().expect("sheet is not empty");
-        Cow::Owned(first.clone())
-    });
-
-    if let Ok(r) = workbook.worksheet_range(&sheet) {
-        let batch = xlsx_sheet_value_to_record_batch(r, has_header, infer_schema_length)?;
-        let schema_ref = batch.schema();
-        let partitions = vec![vec![batch]];
-        Ok(MemTable::try_new(schema_ref, partitions).unwrap())
+) -> Result<Schema, ExcelError> {
+    let col_names = column_names(r, has_header)?;
+    let mut col_types: Vec<HashSet<DataType>> = vec![HashSet::new(); col_names.len()];
+
+    let rows = if has_header {
+        r.rows().skip(1)
     } else {
-        Err(ExcelError::Load("Failed to open .xlsx file.".to_owned()))
+        #[allow(clippy::iter_skip_zero)]
+        r.rows().skip(0)
+    };
+
+    for row in rows.take(infer_schema_length) {
+        for (i, col_val) in row.iter().enumerate() {
+            if let Ok(col_type) = infer_value_type(col_val) {
+                if col_type == DataType::Null {
+                    continue;
+                }
+                if let Some(types) = col_types.get_mut(i) {
+                    types.insert(col_type);
+                }
+            }
+        }
     }
+
+    let fields: Vec<Field> = col_names
+        .into_iter()
+        .zip(col_types.iter())
+        .map(|(col_name, types)| Field::new(col_name, merge_types(types), true))
+        .collect();
+
+    Ok(Schema::new(fields))
+}
+
+/// Infer a single schema for all sheets being read, applying any user provided
+/// column types.
+///
+/// Columns are matched across sheets by name. If reading all sheets, an
+/// additional column containing the sheet name is added.
+pub fn infer_sheets_schema(
+    sheets: &[ExcelSheet],
+    options: &ExcelReadOptions,
+) -> Result<Schema, ExcelError> {
+    let mut names: Vec<String> = Vec::new();
+    let mut types: HashMap<String, HashSet<DataType>> = HashMap::new();
+
+    for sheet in sheets {
+        if sheet.cell_range.is_empty() {
+            continue;
+        }
+        let schema = infer_schema(&sheet.cell_range, options.has_header, options.infer_rows)?;
+        for field in schema.fields() {
+            let entry = types.entry(field.name().clone()).or_insert_with(|| {
+                names.push(field.name().clone());
+                HashSet::new()
+            });
+            entry.insert(field.data_type().clone());
+        }
+    }
+
+    if names.is_empty() {
+        return Err(ExcelError::Load("sheet contains no rows".to_string()));
+    }
+
+    let mut fields: Vec<Field> = names
+        .iter()
+        .map(|name| Field::new(name, merge_types(&types[name]), true))
+        .collect();
+
+    for (name, typ) in &options.column_types {
+        let field = fields
+            .iter_mut()
+            .find(|f| f.name() == name)
+            .ok_or_else(|| ExcelError::MissingColumn(name.clone()))?;
+        *field = Field::new(name, typ.clone(), true);
+    }
+
+    if options.sheets == SheetSelection::All {
+        if names.iter().any(|name| name == SHEET_COLUMN_NAME) {
+            return Err(ExcelError::InvalidOption(format!(
+                "column '{SHEET_COLUMN_NAME}' already exists, cannot read all sheets"
+            )));
+        }
+        fields.push(Field::new(SHEET_COLUMN_NAME, DataType::Utf8, false));
+    }
+
+    Ok(Schema::new(fields))
 }
 
 // TODO: vectorize this to improve performance
 // Ideally we can iterate over the columns instead of iterating over the rows
-fn xlsx_sheet_value_to_record_batch(
-    r: Range<calamine::Data>,
+pub(crate) fn xlsx_sheet_value_to_record_batch(
+    sheet: &ExcelSheet,
     has_header: bool,
-    infer_schema_length: usize,
+    schema: SchemaRef,
 ) -> Result<RecordBatch, ExcelError> {
-    let schema = infer_schema(&r, has_header, infer_schema_length)?;
+    let r = &sheet.cell_range;
+    let num_rows = if has_header {
+        r.height().saturating_sub(1)
+    } else {
+        r.height()
+    };
+
+    let col_indices: HashMap<String, usize> = if r.is_empty() {
+        HashMap::new()
+    } else {
+        column_names(r, has_header)?
+            .into_iter()
+            .enumerate()
+            .map(|(i, name)| (name, i))
+            .collect()
+    };
+
     let arrays = schema
         .fields()
         .iter()
-        .enumerate()
-        .map(|(i, field)| {
+        .map(|field| {
+            let i = match col_indices.get(field.name()) {
+                Some(i) => *i,
+                None if field.name() == SHEET_COLUMN_NAME && !field.is_nullable() => {
+                    return Arc::new(StringArray::from(vec![sheet.name.as_str(); num_rows]))
+                        as ArrayRef;
+                }
+                // Column only exists in other sheets.
+                None => return new_null_array(field.data_type(), num_rows),
+            };
+
             let rows = if has_header {
                 r.rows().skip(1)
             } else {
@@ -146,11 +515,11 @@ fn xlsx_sheet_value_to_record_batch(
                         .collect::<BooleanArray>(),
                 ) as ArrayRef,
                 DataType::Int64 => Arc::new(
-                    rows.map(|r| r.get(i).and_then(|v| v.get_int()))
+                    rows.map(|r| r.get(i).and_then(|v| v.as_i64()))
                         .collect::<PrimitiveArray<Int64Type>>(),
                 ) as ArrayRef,
                 DataType::Float64 => Arc::new(
-                    rows.map(|r| r.get(i).and_then(|v| v.get_float()))
+                    rows.map(|r| r.get(i).and_then(|v| v.as_f64()))
                         .collect::<PrimitiveArray<Float64Type>>(),
                 ) as ArrayRef,
                 DataType::Date64 => {
@@ -166,67 +535,18 @@ fn xlsx_sheet_value_to_record_batch(
                     Arc::new(arr.finish())
                 }
                 _ => Arc::new(
-                    rows.map(|r| r.get(i).map(|v| v.get_string().unwrap_or("null")))
-                        .collect::<StringArray>(),
+                    rows.map(|r| match r.get(i) {
+                        None | Some(calamine::Data::Empty) => None,
+                        Some(calamine::Data::String(s)) => Some(s.clone()),
+                        Some(v) => Some(v.to_string()),
+                    })
+                    .collect::<StringArray>(),
                 ) as ArrayRef,
             }
         })
         .collect::<Vec<ArrayRef>>();
 
-    Ok(RecordBatch::try_new(Arc::new(schema), arrays)?)
-}
-
-pub fn infer_schema(
-    r: &Range<calamine::Data>,
-    has_header: bool,
-    infer_schema_length: usize,
-) -> Result<Schema, ExcelError> {
-    let mut col_types: HashMap<&str, HashSet<DataType>> = HashMap::new();
-    let mut rows = r.rows();
-    let col_names: Vec<String> = rows
-        .next()
-        .unwrap()
-        .iter()
-        .enumerate()
-        .map(
-            |(i, c)| match (has_header, c.get_string().map(|s| s.to_string())) {
-                (true, Some(s)) => Ok(s),
-                (true, None) => Err(ExcelError::Load("failed to parse header".to_string())),
-                (false, _) => Ok(format!("col{}", i)),
-            },
-        )
-        .collect::<Result<_, _>>()?;
-
-    for row in rows.take(infer_schema_length) {
-        for (i, col_val) in row.iter().enumerate() {
-            let col_name = col_names.get(i).unwrap();
-            if let Ok(col_type) = infer_value_type(col_val) {
-                if col_type == DataType::Null {
-                    continue;
-                }
-                let entry = col_types.entry(col_name).or_default();
-                entry.insert(col_type);
-            } else {
-                continue;
-            }
-        }
-    }
-
-    let fields: Vec<Field> = col_names
-        .iter()
-        .map(|col_name| {
-            let set = col_types.entry(col_name).or_insert_with(|| {
-                let mut set = HashSet::new();
-                set.insert(DataType::Utf8);
-                set
-            });
-
-            let dt = set.iter().next().cloned().unwrap_or(DataType::Utf8);
-            Field::new(col_name.replace(' ', "_"), dt, true)
-        })
-        .collect();
-
-    Ok(Schema::new(fields))
+    Ok(RecordBatch::try_new(schema, arrays)?)
 }
 
 fn infer_value_type(v: &calamine::Data) -> Result<DataType, ExcelError> {
@@ -243,3 +563,95 @@ fn infer_value_type(v: &calamine::Data) -> Result<DataType, ExcelError> {
         )),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_cell_range() {
+        let range: CellRange = "A3:H500".parse().unwrap();
+        assert_eq!(
+            CellRange {
+                start: (2, 0),
+                end: (499, 7),
+            },
+            range
+        );
+
+        let range: CellRange = "aa1:AB10".parse().unwrap();
+        assert_eq!(
+            CellRange {
+                start: (0, 26),
+                end: (9, 27),
+            },
+            range
+        );
+
+        "A3".parse::<CellRange>().unwrap_err();
+        "3:H5".parse::<CellRange>().unwrap_err();
+        "A0:B2".parse::<CellRange>().unwrap_err();
+        "B3:A5".parse::<CellRange>().unwrap_err();
+    }
+
+    #[test]
+    fn parse_types() {
+        let types = parse_column_types("amount float, posted at date,name text").unwrap();
+        assert_eq!(
+            vec![
+                ("amount".to_string(), DataType::Float64),
+                ("posted_at".to_string(), DataType::Date64),
+                ("name".to_string(), DataType::Utf8),
+            ],
+            types
+        );
+
+        parse_column_types("amount").unwrap_err();
+        parse_column_types("amount money").unwrap_err();
+    }
+
+    #[test]
+    fn merged_header_names() {
+        let mut range = Range::new((0, 0), (1, 3));
+        range.set_value((0, 0), calamine::Data::String("name".to_string()));
+        range.set_value((0, 1), calamine::Data::String("Q1 sales".to_string()));
+        range.set_value((0, 3), calamine::Data::Float(2024.0));
+
+        let names = column_names(&range, true).unwrap();
+        assert_eq!(vec!["name", "Q1_sales", "Q1_sales_2", "2024"], names);
+    }
+
+    #[test]
+    fn select_cells_empty_sheet() {
+        let cells: CellRange = "A3:H500".parse().unwrap();
+
+        assert!(select_cells(Range::empty(), None, 0).is_empty());
+        assert!(select_cells(Range::empty(), None, 2).is_empty());
+        assert!(select_cells(Range::empty(), Some(cells), 1).is_empty());
+    }
+
+    #[test]
+    fn select_cells_out_of_bounds() {
+        let mut range = Range::new((1, 1), (3, 2));
+        range.set_value((1, 1), calamine::Data::String("a".to_string()));
+        range.set_value((3, 2), calamine::Data::Int(1));
+
+        // Whole sheet, clamped to the used cells.
+        let cells: CellRange = "A1:XFD1048576".parse().unwrap();
+        let selected = select_cells(range.clone(), Some(cells), 0);
+        assert_eq!(Some((1, 1)), selected.start());
+        assert_eq!(Some((3, 2)), selected.end());
+
+        // Header offset applied before clamping.
+        let selected = select_cells(range.clone(), Some(cells), 2);
+        assert_eq!(Some((2, 1)), selected.start());
+        assert_eq!(Some((3, 2)), selected.end());
+
+        // Entirely outside the used cells.
+        let cells: CellRange = "E10:F20".parse().unwrap();
+        assert!(select_cells(range.clone(), Some(cells), 0).is_empty());
+
+        // Skipping past the last row.
+        assert!(select_cells(range, None, 3).is_empty());
+    }
+}
@@ -2,7 +2,6 @@ use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
 
-use calamine::{Data, Range};
 use datafusion::arrow::datatypes::{Schema, SchemaRef};
 use datafusion::arrow::record_batch::RecordBatch;
 use datafusion::error::DataFusionError;
@@ -11,6 +10,7 @@ use futures::{Stream, StreamExt};
 
 use crate::excel;
 use crate::excel::errors::ExcelError;
+use crate::excel::ExcelSheet;
 
 pub struct ExcelStream {
     schema: Arc<Schema>,
@@ -18,12 +18,33 @@ pub struct ExcelStream {
 }
 
 impl ExcelStream {
-    pub fn new(r: Range<Data>, header: bool, schema: Arc<Schema>) -> Self {
-        let schema = schema.clone();
-        let inferred_schema_length = r.width();
+    /// Create a stream producing one batch per sheet.
+    ///
+    /// Batches are created using the full table schema, then projected.
+    pub fn new(
+        sheets: Vec<ExcelSheet>,
+        header: bool,
+        table_schema: Arc<Schema>,
+        projection: Option<Vec<usize>>,
+    ) -> Self {
+        let schema = match &projection {
+            Some(projection) => Arc::new(
+                table_schema
+                    .project(projection)
+                    .expect("projection to be valid for table schema"),
+            ),
+            None => table_schema.clone(),
+        };
 
-        let batch = excel::xlsx_sheet_value_to_record_batch(r, header, inferred_schema_length);
-        let stream = Box::pin(futures::stream::iter(vec![batch]));
+        let batches = sheets.into_iter().map(move |sheet| {
+            let batch =
+                excel::xlsx_sheet_value_to_record_batch(&sheet, header, table_schema.clone())?;
+            match &projection {
+                Some(projection) => Ok(batch.project(projection)?),
+                None => Ok(batch),
+            }
+        });
+        let stream = Box::pin(futures::stream::iter(batches));
 
         Self { schema, stream }
     }
@@ -22,24 +22,22 @@ use datafusion::physical_plan::{
 use super::errors::ExcelError;
 use crate::excel;
 use crate::excel::stream::ExcelStream;
-use crate::excel::ExcelTable;
+use crate::excel::{ExcelSheet, ExcelTable};
 
 pub struct ExcelTableProvider {
-    cell_range: calamine::Range<calamine::Data>,
+    sheets: Vec<ExcelSheet>,
     header: bool,
     schema: Arc<Schema>,
 }
 
 impl ExcelTableProvider {
     pub async fn try_new(t: ExcelTable) -> Result<Self, ExcelError> {
-        let cell_range = t.cell_range;
-        let schema_len = cell_range.width();
-        let schema = excel::infer_schema(&cell_range, t.has_header, schema_len)?;
+        let schema = excel::infer_sheets_schema(&t.sheets, &t.options)?;
 
         Ok(ExcelTableProvider {
             schema: Arc::new(schema),
-            cell_range,
-            header: t.has_header,
+            sheets: t.sheets,
+            header: t.options.has_header,
         })
     }
 }
@@ -66,16 +64,19 @@ impl TableProvider for ExcelTableProvider {
         _limit: Option<usize>,
     ) -> DatafusionResult<Arc<dyn ExecutionPlan>> {
         // Basic Projection
-        let projected_schema = match projection {
-            Some(projection) if !projection.is_empty() => {
-                Arc::new(self.schema.project(projection)?)
-            }
-            _ => self.schema.clone(),
+        let (projected_schema, projection) = match projection {
+            Some(projection) if !projection.is_empty() => (
+                Arc::new(self.schema.project(projection)?),
+                Some(projection.clone()),
+            ),
+            _ => (self.schema.clone(), None),
         };
 
         Ok(Arc::new(ExcelExecutionPlan {
             arrow_schema: projected_schema,
-            cell_range: self.cell_range.clone(),
+            table_schema: self.schema.clone(),
+            projection,
+            sheets: self.sheets.clone(),
             header: self.header,
         }))
     }
@@ -84,7 +85,9 @@ impl TableProvider for ExcelTableProvider {
 #[derive(Debug)]
 struct ExcelExecutionPlan {
     arrow_schema: ArrowSchemaRef,
-    cell_range: calamine::Range<calamine::Data>,
+    table_schema: ArrowSchemaRef,
+    projection: Option<Vec<usize>>,
+    sheets: Vec<ExcelSheet>,
     header: bool,
 }
 
@@ -129,11 +132,16 @@ impl ExecutionPlan for ExcelExecutionPlan {
     ) -> DatafusionResult<SendableRecordBatchStream> {
         if partition != 0 {
             return Err(DataFusionError::Execution(
-                "only single excel sheet partition supported".to_string(),
+                "only single excel partition supported".to_string(),
             ));
         }
 
-        let stream = ExcelStream::new(self.cell_range.clone(), self.header, self.schema());
+        let stream = ExcelStream::new(
+            self.sheets.clone(),
+            self.header,
+            self.table_schema.clone(),
+            self.projection.clone(),
+        );
 
         Ok(Box::pin(RecordBatchStreamAdapter::new(
             self.schema(),
@@ -13,10 +13,15 @@ use crate::json::errors::JsonError;
 use crate::json::stream::{ObjectStorePartition, VectorPartition};
 use crate::object_store::{ObjStoreAccess, ObjStoreAccessor};
 
+/// Create a streaming table for the json files matching `source_url`.
+///
+/// If `union_by_name` is set, the schema is inferred from every file instead of
+/// from a single file. This requires reading all files up front.
 pub async fn json_streaming_table(
     store_access: Arc<dyn ObjStoreAccess>,
     source_url: DatasourceUrl,
     fields: Option<Vec<FieldRef>>,
+    union_by_name: bool,
 ) -> Result<Arc<dyn TableProvider>, JsonError> {
     let path = source_url.path().into_owned();
 
@@ -33,14 +38,14 @@ pub async fn json_streaming_table(
 
     let store = accessor.into_object_store();
 
-    json_streaming_table_inner(store, &path, list, fields).await
+    json_streaming_table_inner(store, &path, list, fields, union_by_name).await
 }
 
 pub async fn json_streaming_table_from_object(
     store: Arc<dyn ObjectStore>,
     object: ObjectMeta,
 ) -> Result<Arc<dyn TableProvider>, JsonError> {
-    json_streaming_table_inner(store, "", vec![object], None).await
+    json_streaming_table_inner(store, "", vec![object], None, false).await
 }
 
 async fn json_streaming_table_inner(
@@ -48,52 +53,43 @@ async fn json_streaming_table_inner(
     original_path: &str, // Just for error
     mut list: Vec<ObjectMeta>,
     fields: Option<Vec<FieldRef>>,
+    union_by_name: bool,
 ) -> Result<Arc<dyn TableProvider>, JsonError> {
     let mut streams = Vec::<Arc<dyn PartitionStream>>::with_capacity(list.len());
 
     let schema = match fields {
         Some(fields) => Arc::new(Schema::new(fields)),
-        None => {
-            let mut data = Vec::new();
-            {
-                let first_obj = list
-                    .pop()
-                    .ok_or_else(|| JsonError::NotFound(original_path.to_string()))?;
-
-                let blob = store
-                    .get(&first_obj.location)
-                    .await?
-                    .bytes()
-                    .await?
-                    .to_vec();
-
-                push_unwind_json_values(
-                    &mut data,
-                    serde_json::Deserializer::from_slice(&blob).into_iter(),
-                )?;
+        None if union_by_name => {
+            if list.is_empty() {
+                return Err(JsonError::NotFound(original_path.to_string()));
             }
 
+            // Every file needs to be read to infer the schema, keep the
+            // documents around so we don't need to read them again.
             let mut field_set = indexmap::IndexMap::<String, DataType>::new();
-            for obj in &data {
-                for (key, value) in obj.into_iter() {
-                    let typ = type_for_value(value);
-                    match field_set.get(key) {
-                        Some(v) => match widen_type(v, typ) {
-                            Some(wider) => field_set.insert(key.to_string(), wider),
-                            None => None,
-                        },
-                        None => field_set.insert(key.to_string(), typ),
-                    };
-                }
+            let mut objects = Vec::with_capacity(list.len());
+            for obj in list.drain(..) {
+                let data = read_json_object(&store, &obj).await?;
+                add_fields_for_values(&mut field_set, &data);
+                objects.push(data);
             }
 
-            let schema = Arc::new(Schema::new(
-                field_set
-                    .into_iter()
-                    .map(|(k, v)| Field::new(k, v, true))
-                    .collect::<Vec<_>>(),
-            ));
+            let schema = schema_from_field_set(field_set);
+            for data in objects {
+                streams.push(Arc::new(VectorPartition::new(schema.clone(), data)));
+            }
+            schema
+        }
+        None => {
+            let first_obj = list
+                .pop()
+                .ok_or_else(|| JsonError::NotFound(original_path.to_string()))?;
+            let data = read_json_object(&store, &first_obj).await?;
+
+            let mut field_set = indexmap::IndexMap::<String, DataType>::new();
+            add_fields_for_values(&mut field_set, &data);
 
+            let schema = schema_from_field_set(field_set);
             streams.push(Arc::new(VectorPartition::new(schema.clone(), data)));
             schema
         }
@@ -111,6 +107,53 @@ async fn json_streaming_table_inner(
     Ok(Arc::new(StreamingTable::try_new(schema.clone(), streams)?))
 }
 
+/// Read and parse all json documents in an object.
+async fn read_json_object(
+    store: &Arc<dyn ObjectStore>,
+    obj: &ObjectMeta,
+) -> Result<Vec<Map<String, Value>>, JsonError> {
+    let blob = store.get(&obj.location).await?.bytes().await?.to_vec();
+
+    let mut data = Vec::new();
+    push_unwind_json_values(
+        &mut data,
+        serde_json::Deserializer::from_slice(&blob).into_iter(),
+    )?;
+
+    Ok(data)
+}
+
+/// Add fields for all keys in the documents to the field set, widening
+/// existing fields where possible.
+pub(crate) fn add_fields_for_values(
+    field_set: &mut indexmap::IndexMap<String, DataType>,
+    data: &[Map<String, Value>],
+) {
+    for obj in data {
+        for (key, value) in obj.into_iter() {
+            let typ = type_for_value(value);
+            match field_set.get(key) {
+                Some(v) => match widen_type(v, typ) {
+                    Some(wider) => field_set.insert(key.to_string(), wider),
+                    None => None,
+                },
+                None => field_set.insert(key.to_string(), typ),
+            };
+        }
+    }
+}
+
+pub(crate) fn schema_from_field_set(
+    field_set: indexmap::IndexMap<String, DataType>,
+) -> Arc<Schema> {
+    Arc::new(Schema::new(
+        field_set
+            .into_iter()
+            .map(|(k, v)| Field::new(k, v, true))
+            .collect::<Vec<_>>(),
+    ))
+}
+
 
 fn push_unwind_json_values(
     data: &mut Vec<Map<String, Value>>,
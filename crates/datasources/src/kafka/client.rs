@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use rskafka::client::partition::{OffsetAt, PartitionClient, UnknownTopicHandling};
+use rskafka::client::{Client, ClientBuilder};
+
+use super::errors::{KafkaError, Result};
+
+/// Upper bound on the number of bytes returned by a single fetch.
+const MAX_FETCH_BYTES: i32 = 1024 * 1024;
+
+/// How long the broker may wait for `MAX_FETCH_BYTES` to be available before
+/// returning what it has.
+const MAX_FETCH_WAIT_MS: i32 = 500;
+
+/// A message read from a topic partition.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KafkaMessage {
+    pub partition: i32,
+    pub offset: i64,
+    pub timestamp: DateTime<Utc>,
+    pub key: Option<Vec<u8>>,
+    pub value: Option<Vec<u8>>,
+}
+
+/// Read access to the partitions of a topic.
+///
+/// This is the only part of the scan that talks to the brokers, so it can be
+/// swapped out for an in-process stand-in.
+#[async_trait]
+pub trait KafkaClient: fmt::Debug + Send + Sync {
+    /// List the partitions of a topic.
+    async fn partitions(&self, topic: &str) -> Result<Vec<i32>>;
+
+    /// Get the earliest offset and the high watermark (the offset the next
+    /// message will be written at) of a partition.
+    async fn watermarks(&self, topic: &str, partition: i32) -> Result<(i64, i64)>;
+
+    /// Fetch messages starting at `offset`.
+    ///
+    /// May return fewer messages than are available, and may include messages
+    /// before `offset`.
+    async fn fetch(&self, topic: &str, partition: i32, offset: i64) -> Result<Vec<KafkaMessage>>;
+}
+
+/// Client for Kafka compatible brokers (Kafka, Redpanda, ...).
+pub struct BrokerClient {
+    client: Client,
+    /// Clients for partitions we've already read from, keyed by topic and
+    /// partition.
+    partitions: Mutex<HashMap<(String, i32), Arc<PartitionClient>>>,
+}
+
+impl fmt::Debug for BrokerClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BrokerClient").finish_non_exhaustive()
+    }
+}
+
+impl BrokerClient {
+    /// Connect to a cluster using a comma separated list of bootstrap
+    /// brokers.
+    pub async fn connect(brokers: &str) -> Result<Self> {
+        let brokers = brokers
+            .split(',')
+            .map(|b| b.trim().to_string())
+            .filter(|b| !b.is_empty())
+            .collect::<Vec<_>>();
+        if brokers.is_empty() {
+            return Err(KafkaError::InvalidOption {
+                option: "brokers",
+                value: String::new(),
+            });
+        }
+
+        let client = ClientBuilder::new(brokers).build().await?;
+        Ok(Self {
+            client,
+            partitions: Mutex::new(HashMap::new()),
+        })
+    }
+
+    async fn partition_client(&self, topic: &str, partition: i32) -> Result<Arc<PartitionClient>> {
+        let key = (topic.to_string(), partition);
+        if let Some(client) = self.partitions.lock().get(&key) {
+            return Ok(client.clone());
+        }
+
+        let client = Arc::new(
+            self.client
+                .partition_client(topic, partition, UnknownTopicHandling::Error)
+                .await?,
+        );
+        self.partitions.lock().insert(key, client.clone());
+        Ok(client)
+    }
+}
+
+#[async_trait]
+impl KafkaClient for BrokerClient {
+    async fn partitions(&self, topic: &str) -> Result<Vec<i32>> {
+        let topics = self.client.list_topics().await?;
+        let topic = topics
+            .into_iter()
+            .find(|t| t.name == topic)
+            .ok_or_else(|| KafkaError::UnknownTopic(topic.to_string()))?;
+        Ok(topic.partitions.into_iter().collect())
+    }
+
+    async fn watermarks(&self, topic: &str, partition: i32) -> Result<(i64, i64)> {
+        let client = self.partition_client(topic, partition).await?;
+        let earliest = client.get_offset(OffsetAt::Earliest).await?;
+        let latest = client.get_offset(OffsetAt::Latest).await?;
+        Ok((earliest, latest))
+    }
+
+    async fn fetch(&self, topic: &str, partition: i32, offset: i64) -> Result<Vec<KafkaMessage>> {
+        let client = self.partition_client(topic, partition).await?;
+        let (records, _high_watermark) = client
+            .fetch_records(offset, 1..MAX_FETCH_BYTES, MAX_FETCH_WAIT_MS)
+            .await?;
+
+        Ok(records
+            .into_iter()
+            .map(|r| KafkaMessage {
+                partition,
+                offset: r.offset,
+                timestamp: r.record.timestamp,
+                key: r.record.key,
+                value: r.record.value,
+            })
+            .collect())
+    }
+}
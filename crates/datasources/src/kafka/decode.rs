@@ -0,0 +1,215 @@
+use std::sync::Arc;
+
+use apache_avro::Schema as AvroSchema;
+use datafusion::arrow::array::{
+    ArrayRef,
+    BinaryArray,
+    Int32Array,
+    Int64Array,
+    TimestampMillisecondArray,
+};
+use datafusion::arrow::datatypes::{DataType, Field, Schema, SchemaRef, TimeUnit};
+use datafusion::arrow::json::ReaderBuilder;
+use datafusion::arrow::record_batch::{RecordBatch, RecordBatchOptions};
+use serde_json::{Map, Value};
+
+use super::client::KafkaMessage;
+use super::errors::{KafkaError, Result};
+use crate::json::table::{add_fields_for_values, schema_from_field_set};
+
+/// Columns holding message metadata, these come before any columns decoded
+/// from the message value.
+pub const PARTITION_COLUMN: &str = "_partition";
+pub const OFFSET_COLUMN: &str = "_offset";
+pub const TIMESTAMP_COLUMN: &str = "_timestamp";
+pub const KEY_COLUMN: &str = "_key";
+
+const METADATA_COLUMNS: [&str; 4] = [
+    PARTITION_COLUMN,
+    OFFSET_COLUMN,
+    TIMESTAMP_COLUMN,
+    KEY_COLUMN,
+];
+
+/// Column holding the message value for the raw format.
+pub const VALUE_COLUMN: &str = "value";
+
+/// Format of message values.
+#[derive(Debug, Clone)]
+pub enum KafkaFormat {
+    /// Values are kept as is in a binary column.
+    Raw,
+    /// Values are json objects, every key becomes a column.
+    Json,
+    /// Values are avro records encoded with the given schema, without any
+    /// framing.
+    Avro(AvroSchema),
+}
+
+impl KafkaFormat {
+    /// Get the format from the name of the format and the avro schema (as
+    /// json), which is required for avro.
+    pub fn try_new(format: &str, avro_schema: Option<&str>) -> Result<Self> {
+        match (format.to_lowercase().as_str(), avro_schema) {
+            ("raw", _) => Ok(Self::Raw),
+            ("json", _) => Ok(Self::Json),
+            ("avro", Some(schema)) => Ok(Self::Avro(AvroSchema::parse_str(schema)?)),
+            ("avro", None) => Err(KafkaError::InvalidOption {
+                option: "avro_schema",
+                value: "an avro schema is required for the avro format".to_string(),
+            }),
+            (other, _) => Err(KafkaError::InvalidOption {
+                option: "format",
+                value: other.to_string(),
+            }),
+        }
+    }
+
+    /// Decode the value of a message into a json object.
+    ///
+    /// Messages without a value (tombstones) produce an empty object.
+    fn decode_document(&self, msg: &KafkaMessage) -> Result<Map<String, Value>> {
+        let bytes = match &msg.value {
+            Some(bytes) => bytes,
+            None => return Ok(Map::new()),
+        };
+
+        let decode_err = |msg_text: String| KafkaError::Decode {
+            partition: msg.partition,
+            offset: msg.offset,
+            msg: msg_text,
+        };
+
+        let value = match self {
+            Self::Raw => unreachable!("raw values are not decoded"),
+            Self::Json => {
+                serde_json::from_slice::<Value>(bytes).map_err(|e| decode_err(e.to_string()))?
+            }
+            Self::Avro(schema) => {
+                let value = apache_avro::from_avro_datum(schema, &mut bytes.as_slice(), None)
+                    .map_err(|e| decode_err(e.to_string()))?;
+                Value::try_from(value).map_err(|e| decode_err(e.to_string()))?
+            }
+        };
+
+        match value {
+            Value::Object(doc) => Ok(doc),
+            Value::Null => Ok(Map::new()),
+            _ => Err(decode_err("expected an object".to_string())),
+        }
+    }
+}
+
+/// Decodes messages into record batches.
+#[derive(Debug)]
+pub struct MessageDecoder {
+    format: KafkaFormat,
+    /// Columns decoded from the message values.
+    value_schema: SchemaRef,
+    /// Metadata columns followed by the value columns.
+    schema: SchemaRef,
+}
+
+impl MessageDecoder {
+    /// Create a decoder, inferring the value columns from a sample of
+    /// messages for formats that need it.
+    pub fn try_new(format: KafkaFormat, sample: &[KafkaMessage]) -> Result<Self> {
+        let value_schema = match &format {
+            KafkaFormat::Raw => Arc::new(Schema::new(vec![Field::new(
+                VALUE_COLUMN,
+                DataType::Binary,
+                true,
+            )])),
+            KafkaFormat::Json | KafkaFormat::Avro(_) => {
+                let docs = sample
+                    .iter()
+                    .map(|msg| format.decode_document(msg))
+                    .collect::<Result<Vec<_>>>()?;
+
+                let mut field_set = indexmap::IndexMap::new();
+                add_fields_for_values(&mut field_set, &docs);
+                // Metadata columns take precedence over keys with the same
+                // name.
+                field_set.retain(|name, _| !METADATA_COLUMNS.contains(&name.as_str()));
+                schema_from_field_set(field_set)
+            }
+        };
+
+        let mut fields = vec![
+            Field::new(PARTITION_COLUMN, DataType::Int32, false),
+            Field::new(OFFSET_COLUMN, DataType::Int64, false),
+            Field::new(
+                TIMESTAMP_COLUMN,
+                DataType::Timestamp(TimeUnit::Millisecond, Some("UTC".into())),
+                false,
+            ),
+            Field::new(KEY_COLUMN, DataType::Binary, true),
+        ];
+        fields.extend(value_schema.fields().iter().map(|f| f.as_ref().clone()));
+
+        Ok(Self {
+            format,
+            value_schema,
+            schema: Arc::new(Schema::new(fields)),
+        })
+    }
+
+    pub fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    pub fn schema_ref(&self) -> &SchemaRef {
+        &self.schema
+    }
+
+    pub fn decode(&self, messages: &[KafkaMessage]) -> Result<RecordBatch> {
+        let mut columns: Vec<ArrayRef> = vec![
+            Arc::new(Int32Array::from_iter_values(
+                messages.iter().map(|m| m.partition),
+            )),
+            Arc::new(Int64Array::from_iter_values(
+                messages.iter().map(|m| m.offset),
+            )),
+            Arc::new(
+                TimestampMillisecondArray::from_iter_values(
+                    messages.iter().map(|m| m.timestamp.timestamp_millis()),
+                )
+                .with_timezone("UTC"),
+            ),
+            Arc::new(
+                messages
+                    .iter()
+                    .map(|m| m.key.as_deref())
+                    .collect::<BinaryArray>(),
+            ),
+        ];
+
+        match &self.format {
+            KafkaFormat::Raw => columns.push(Arc::new(
+                messages
+                    .iter()
+                    .map(|m| m.value.as_deref())
+                    .collect::<BinaryArray>(),
+            )),
+            KafkaFormat::Json | KafkaFormat::Avro(_) => {
+                let docs = messages
+                    .iter()
+                    .map(|msg| self.format.decode_document(msg))
+                    .collect::<Result<Vec<_>>>()?;
+
+                let mut decoder = ReaderBuilder::new(self.value_schema.clone()).build_decoder()?;
+                decoder.serialize(&docs)?;
+                let values = decoder
+                    .flush()?
+                    .unwrap_or_else(|| RecordBatch::new_empty(self.value_schema.clone()));
+                columns.extend(values.columns().iter().cloned());
+            }
+        }
+
+        Ok(RecordBatch::try_new_with_options(
+            self.schema.clone(),
+            columns,
+            &RecordBatchOptions::new().with_row_count(Some(messages.len())),
+        )?)
+    }
+}
@@ -0,0 +1,38 @@
+#[derive(Debug, thiserror::Error)]
+pub enum KafkaError {
+    #[error("Topic does not exist: {0}")]
+    UnknownTopic(String),
+
+    #[error("Invalid value for '{option}': {value}")]
+    InvalidOption { option: &'static str, value: String },
+
+    #[error("Failed to decode message at partition {partition}, offset {offset}: {msg}")]
+    Decode {
+        partition: i32,
+        offset: i64,
+        msg: String,
+    },
+
+    #[error(transparent)]
+    Client(#[from] rskafka::client::error::Error),
+
+    #[error(transparent)]
+    Avro(#[from] apache_avro::Error),
+
+    #[error(transparent)]
+    SerdeJson(#[from] serde_json::Error),
+
+    #[error(transparent)]
+    Arrow(#[from] datafusion::arrow::error::ArrowError),
+
+    #[error(transparent)]
+    DataFusion(#[from] datafusion::error::DataFusionError),
+}
+
+impl From<KafkaError> for datafusion_ext::errors::ExtensionError {
+    fn from(value: KafkaError) -> Self {
+        datafusion_ext::errors::ExtensionError::access(value)
+    }
+}
+
+pub type Result<T, E = KafkaError> = std::result::Result<T, E>;
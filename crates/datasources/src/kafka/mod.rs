@@ -0,0 +1,454 @@
+//! Bounded scans over Kafka (and Kafka compatible) topics.
+pub mod errors;
+
+mod client;
+mod decode;
+
+use std::str::FromStr;
+use std::sync::Arc;
+
+use async_stream::try_stream;
+use datafusion::arrow::datatypes::SchemaRef;
+use datafusion::datasource::streaming::StreamingTable;
+use datafusion::datasource::TableProvider;
+use datafusion::error::DataFusionError;
+use datafusion::execution::TaskContext;
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
+use datafusion::physical_plan::streaming::PartitionStream;
+use datafusion::physical_plan::SendableRecordBatchStream;
+use futures::TryStreamExt;
+
+pub use self::client::{BrokerClient, KafkaClient, KafkaMessage};
+pub use self::decode::KafkaFormat;
+use self::decode::MessageDecoder;
+use self::errors::{KafkaError, Result};
+
+/// Max number of messages used to infer the columns for json and avro
+/// values.
+const SCHEMA_SAMPLE_SIZE: usize = 100;
+
+/// Offset to start reading each partition at.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StartOffset {
+    #[default]
+    Earliest,
+    Offset(i64),
+}
+
+impl FromStr for StartOffset {
+    type Err = KafkaError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "earliest" => Ok(Self::Earliest),
+            other => other
+                .parse()
+                .map(Self::Offset)
+                .map_err(|_| KafkaError::InvalidOption {
+                    option: "start_offset",
+                    value: s.to_string(),
+                }),
+        }
+    }
+}
+
+/// Offset (exclusive) to stop reading each partition at.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EndOffset {
+    /// The high watermark at the time the scan is planned.
+    #[default]
+    Latest,
+    Offset(i64),
+}
+
+impl FromStr for EndOffset {
+    type Err = KafkaError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "latest" => Ok(Self::Latest),
+            other => other
+                .parse()
+                .map(Self::Offset)
+                .map_err(|_| KafkaError::InvalidOption {
+                    option: "end_offset",
+                    value: s.to_string(),
+                }),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct KafkaScanOptions {
+    pub format: KafkaFormat,
+    pub start_offset: StartOffset,
+    pub end_offset: EndOffset,
+}
+
+/// Range of offsets to read from a single partition.
+#[derive(Debug, Clone, Copy)]
+struct PartitionRange {
+    partition: i32,
+    start: i64,
+    /// Exclusive.
+    end: i64,
+}
+
+/// Create a table provider reading every partition of the topic from the
+/// start offset up to the end offset.
+///
+/// The end of each partition is resolved once, so messages produced after
+/// this call are never read.
+pub async fn kafka_table(
+    client: Arc<dyn KafkaClient>,
+    topic: &str,
+    opts: KafkaScanOptions,
+) -> Result<Arc<dyn TableProvider>> {
+    let mut ranges = Vec::new();
+    for partition in client.partitions(topic).await? {
+        let (earliest, latest) = client.watermarks(topic, partition).await?;
+        let start = match opts.start_offset {
+            StartOffset::Earliest => earliest,
+            StartOffset::Offset(offset) => offset.max(earliest),
+        };
+        let end = match opts.end_offset {
+            EndOffset::Latest => latest,
+            EndOffset::Offset(offset) => offset.min(latest),
+        };
+        ranges.push(PartitionRange {
+            partition,
+            start,
+            end,
+        });
+    }
+
+    let sample = match opts.format {
+        KafkaFormat::Raw => Vec::new(),
+        KafkaFormat::Json | KafkaFormat::Avro(_) => {
+            sample_messages(client.as_ref(), topic, &ranges).await?
+        }
+    };
+    let decoder = Arc::new(MessageDecoder::try_new(opts.format, &sample)?);
+    let schema = decoder.schema();
+
+    let streams = ranges
+        .into_iter()
+        .map(|range| {
+            Arc::new(KafkaPartitionStream {
+                client: client.clone(),
+                topic: topic.to_string(),
+                range,
+                decoder: decoder.clone(),
+            }) as Arc<dyn PartitionStream>
+        })
+        .collect();
+
+    Ok(Arc::new(StreamingTable::try_new(schema, streams)?))
+}
+
+/// Collect messages from the start of every partition range for schema
+/// inference.
+async fn sample_messages(
+    client: &dyn KafkaClient,
+    topic: &str,
+    ranges: &[PartitionRange],
+) -> Result<Vec<KafkaMessage>> {
+    let mut sample = Vec::new();
+    for range in ranges {
+        if sample.len() >= SCHEMA_SAMPLE_SIZE {
+            break;
+        }
+        if range.start >= range.end {
+            continue;
+        }
+        let messages = client.fetch(topic, range.partition, range.start).await?;
+        sample.extend(
+            messages
+                .into_iter()
+                .filter(|m| m.offset >= range.start && m.offset < range.end)
+                .take(SCHEMA_SAMPLE_SIZE - sample.len()),
+        );
+    }
+    Ok(sample)
+}
+
+#[derive(Debug)]
+struct KafkaPartitionStream {
+    client: Arc<dyn KafkaClient>,
+    topic: String,
+    range: PartitionRange,
+    decoder: Arc<MessageDecoder>,
+}
+
+impl PartitionStream for KafkaPartitionStream {
+    fn schema(&self) -> &SchemaRef {
+        self.decoder.schema_ref()
+    }
+
+    fn execute(&self, _ctx: Arc<TaskContext>) -> SendableRecordBatchStream {
+        let client = self.client.clone();
+        let topic = self.topic.clone();
+        let range = self.range;
+        let decoder = self.decoder.clone();
+
+        let stream = try_stream! {
+            let mut offset = range.start;
+            while offset < range.end {
+                let messages = client.fetch(&topic, range.partition, offset).await?;
+                let next = match messages.last() {
+                    Some(last) => last.offset + 1,
+                    // Nothing left in the partition.
+                    None => break,
+                };
+
+                let messages: Vec<_> = messages
+                    .into_iter()
+                    .filter(|m| m.offset >= offset && m.offset < range.end)
+                    .collect();
+                if !messages.is_empty() {
+                    yield decoder.decode(&messages)?;
+                }
+
+                if next <= offset {
+                    break;
+                }
+                offset = next;
+            }
+        };
+
+        Box::pin(RecordBatchStreamAdapter::new(
+            self.decoder.schema(),
+            stream.map_err(|e: KafkaError| DataFusionError::External(Box::new(e))),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use async_trait::async_trait;
+    use chrono::{TimeZone, Utc};
+    use datafusion::arrow::array::{Array, AsArray};
+    use datafusion::arrow::datatypes::{DataType, Int32Type, Int64Type};
+    use datafusion::arrow::record_batch::RecordBatch;
+    use datafusion::prelude::SessionContext;
+
+    use super::*;
+
+    /// In-process stand-in for a broker, each topic holds the messages for
+    /// every partition.
+    #[derive(Debug, Default)]
+    struct MemoryClient {
+        topics: HashMap<String, Vec<Vec<KafkaMessage>>>,
+    }
+
+    /// Max number of messages returned for a single fetch, kept small so
+    /// that scans need multiple fetches.
+    const FETCH_SIZE: usize = 2;
+
+    impl MemoryClient {
+        fn with_topic(mut self, topic: &str, partitions: Vec<Vec<Option<&str>>>) -> Self {
+            let partitions = partitions
+                .into_iter()
+                .enumerate()
+                .map(|(partition, values)| {
+                    values
+                        .into_iter()
+                        .enumerate()
+                        .map(|(offset, value)| KafkaMessage {
+                            partition: partition as i32,
+                            offset: offset as i64,
+                            timestamp: Utc.timestamp_millis_opt(offset as i64 * 1000).unwrap(),
+                            key: Some(format!("key{offset}").into_bytes()),
+                            value: value.map(|v| v.as_bytes().to_vec()),
+                        })
+                        .collect()
+                })
+                .collect();
+            self.topics.insert(topic.to_string(), partitions);
+            self
+        }
+
+        fn partition(&self, topic: &str, partition: i32) -> Result<&[KafkaMessage]> {
+            self.topics
+                .get(topic)
+                .and_then(|p| p.get(partition as usize))
+                .map(|msgs| msgs.as_slice())
+                .ok_or_else(|| KafkaError::UnknownTopic(topic.to_string()))
+        }
+    }
+
+    #[async_trait]
+    impl KafkaClient for MemoryClient {
+        async fn partitions(&self, topic: &str) -> Result<Vec<i32>> {
+            let partitions = self
+                .topics
+                .get(topic)
+                .ok_or_else(|| KafkaError::UnknownTopic(topic.to_string()))?;
+            Ok((0..partitions.len() as i32).collect())
+        }
+
+        async fn watermarks(&self, topic: &str, partition: i32) -> Result<(i64, i64)> {
+            let msgs = self.partition(topic, partition)?;
+            Ok((0, msgs.len() as i64))
+        }
+
+        async fn fetch(
+            &self,
+            topic: &str,
+            partition: i32,
+            offset: i64,
+        ) -> Result<Vec<KafkaMessage>> {
+            let msgs = self.partition(topic, partition)?;
+            Ok(msgs
+                .iter()
+                .skip(offset as usize)
+                .take(FETCH_SIZE)
+                .cloned()
+                .collect())
+        }
+    }
+
+    async fn scan(client: MemoryClient, topic: &str, opts: KafkaScanOptions) -> Vec<RecordBatch> {
+        let table = kafka_table(Arc::new(client), topic, opts).await.unwrap();
+        let ctx = SessionContext::new();
+        let df = ctx
+            .read_table(table)
+            .unwrap()
+            .sort(vec![
+                datafusion::prelude::col("_partition").sort(true, false),
+                datafusion::prelude::col("_offset").sort(true, false),
+            ])
+            .unwrap();
+        df.collect().await.unwrap()
+    }
+
+    fn opts(format: KafkaFormat) -> KafkaScanOptions {
+        KafkaScanOptions {
+            format,
+            start_offset: StartOffset::Earliest,
+            end_offset: EndOffset::Latest,
+        }
+    }
+
+    #[tokio::test]
+    async fn raw_scan_reads_all_partitions() {
+        let client = MemoryClient::default().with_topic(
+            "events",
+            vec![vec![Some("a"), Some("b"), Some("c")], vec![Some("d"), None]],
+        );
+
+        let batches = scan(client, "events", opts(KafkaFormat::Raw)).await;
+        let batch =
+            datafusion::arrow::compute::concat_batches(&batches[0].schema(), &batches).unwrap();
+
+        assert_eq!(5, batch.num_rows());
+        assert_eq!(
+            vec!["_partition", "_offset", "_timestamp", "_key", "value"],
+            batch
+                .schema()
+                .fields()
+                .iter()
+                .map(|f| f.name().as_str())
+                .collect::<Vec<_>>()
+        );
+
+        let partitions = batch.column(0).as_primitive::<Int32Type>();
+        assert_eq!(&[0, 0, 0, 1, 1], partitions.values().as_ref());
+        let offsets = batch.column(1).as_primitive::<Int64Type>();
+        assert_eq!(&[0, 1, 2, 0, 1], offsets.values().as_ref());
+        let values = batch.column(4).as_binary::<i32>();
+        assert_eq!(b"d", values.value(3));
+        assert!(values.is_null(4));
+    }
+
+    #[tokio::test]
+    async fn json_scan_respects_offsets() {
+        let client = MemoryClient::default().with_topic(
+            "users",
+            vec![vec![
+                Some(r#"{"id": 1, "name": "alice"}"#),
+                Some(r#"{"id": 2, "name": "bob"}"#),
+                Some(r#"{"id": 3, "name": "carol", "admin": true}"#),
+                Some(r#"{"id": 4}"#),
+            ]],
+        );
+
+        let opts = KafkaScanOptions {
+            format: KafkaFormat::Json,
+            start_offset: StartOffset::Offset(1),
+            end_offset: EndOffset::Offset(3),
+        };
+        let batches = scan(client, "users", opts).await;
+        let batch =
+            datafusion::arrow::compute::concat_batches(&batches[0].schema(), &batches).unwrap();
+
+        assert_eq!(2, batch.num_rows());
+        let schema = batch.schema();
+        assert_eq!(
+            &DataType::Int64,
+            schema.field_with_name("id").unwrap().data_type()
+        );
+        assert_eq!(
+            &DataType::Utf8,
+            schema.field_with_name("name").unwrap().data_type()
+        );
+        assert_eq!(
+            &DataType::Boolean,
+            schema.field_with_name("admin").unwrap().data_type()
+        );
+
+        let offsets = batch.column(1).as_primitive::<Int64Type>();
+        assert_eq!(&[1, 2], offsets.values().as_ref());
+        let names = batch.column_by_name("name").unwrap().as_string::<i32>();
+        assert_eq!("bob", names.value(0));
+        assert_eq!("carol", names.value(1));
+    }
+
+    #[tokio::test]
+    async fn avro_scan() {
+        let raw_schema = r#"{
+            "type": "record",
+            "name": "reading",
+            "fields": [
+                {"name": "sensor", "type": "string"},
+                {"name": "value", "type": "double"}
+            ]
+        }"#;
+        let schema = apache_avro::Schema::parse_str(raw_schema).unwrap();
+
+        let mut record = apache_avro::types::Record::new(&schema).unwrap();
+        record.put("sensor", "s1");
+        record.put("value", 1.5);
+        let encoded = apache_avro::to_avro_datum(&schema, record).unwrap();
+
+        let mut client = MemoryClient::default().with_topic("readings", vec![vec![None]]);
+        client.topics.get_mut("readings").unwrap()[0][0].value = Some(encoded);
+
+        let format = KafkaFormat::try_new("avro", Some(raw_schema)).unwrap();
+        let batches = scan(client, "readings", opts(format)).await;
+        let batch = &batches[0];
+
+        assert_eq!(1, batch.num_rows());
+        let sensors = batch.column_by_name("sensor").unwrap().as_string::<i32>();
+        assert_eq!("s1", sensors.value(0));
+    }
+
+    #[tokio::test]
+    async fn unknown_topic() {
+        let client = Arc::new(MemoryClient::default());
+        let err = kafka_table(client, "missing", opts(KafkaFormat::Raw))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, KafkaError::UnknownTopic(_)));
+    }
+
+    #[test]
+    fn parse_offsets() {
+        assert_eq!(StartOffset::Earliest, "earliest".parse().unwrap());
+        assert_eq!(StartOffset::Offset(10), "10".parse().unwrap());
+        assert_eq!(EndOffset::Latest, "LATEST".parse().unwrap());
+        assert!("soon".parse::<EndOffset>().is_err());
+    }
+}
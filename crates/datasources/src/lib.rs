@@ -6,14 +6,17 @@ pub mod cassandra;
 pub mod clickhouse;
 pub mod common;
 pub mod debug;
+pub mod duckdb;
 pub mod excel;
 pub mod json;
+pub mod kafka;
 pub mod lake;
 pub mod lance;
 pub mod mongodb;
 pub mod mysql;
 pub mod native;
 pub mod object_store;
+pub mod oracle;
 pub mod postgres;
 pub mod snowflake;
 pub mod sqlite;
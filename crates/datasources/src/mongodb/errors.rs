@@ -21,6 +21,9 @@ pub enum MongoDbError {
     #[error(transparent)]
     Bson(#[from] crate::bson::errors::BsonError),
 
+    #[error(transparent)]
+    DatasourceCommon(#[from] crate::common::errors::DatasourceCommonError),
+
     #[error(transparent)]
     RawBson(#[from] mongodb::bson::raw::Error),
 }
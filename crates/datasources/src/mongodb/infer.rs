@@ -12,6 +12,35 @@ const SAMPLE_PCT: f32 = 0.05;
 const MAX_SAMPLE_SIZE: usize = 100;
 const MIN_SAMPLE_SIZE: usize = 10;
 
+/// Number of document schemas to collect before merging them when scanning
+/// the full collection.
+const FULL_SCAN_MERGE_BATCH: usize = 1000;
+
+/// How many documents to read when inferring the schema of a collection.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SampleSize {
+    /// Sample a percentage of the collection, bounded by a minimum and
+    /// maximum number of documents.
+    #[default]
+    Auto,
+    /// Sample a fixed number of documents.
+    Documents(usize),
+    /// Read every document in the collection.
+    FullScan,
+}
+
+impl SampleSize {
+    /// Get the sample size from the user provided options. A full scan takes
+    /// precedence over a sample size.
+    pub fn from_options(sample_size: Option<usize>, full_scan: bool) -> SampleSize {
+        match (sample_size, full_scan) {
+            (_, true) => SampleSize::FullScan,
+            (Some(n), false) => SampleSize::Documents(n),
+            (None, false) => SampleSize::Auto,
+        }
+    }
+}
+
 /// Sample a table to allow inferring the table's schema.
 pub struct TableSampler<'a> {
     collection: &'a Collection<Document>,
@@ -29,16 +58,24 @@ impl<'a> TableSampler<'a> {
     /// and a "Utf8", the type will be automatically widened to "Utf8" in the
     /// final schema.
     #[tracing::instrument(skip(self))]
-    pub async fn infer_schema_from_sample(&self, count: u64) -> Result<ArrowSchema> {
-        let sample_count = Self::sample_size(count as usize) as i64;
+    pub async fn infer_schema_from_sample(
+        &self,
+        count: u64,
+        sample_size: SampleSize,
+    ) -> Result<ArrowSchema> {
+        let sample_count = match sample_size {
+            SampleSize::Auto => Self::sample_size(count as usize),
+            SampleSize::Documents(n) => n.max(1),
+            SampleSize::FullScan => return self.infer_schema_from_full_scan().await,
+        };
 
         let sample_pipeline = [doc! {
-            "$sample": {"size": sample_count}
+            "$sample": {"size": sample_count as i64}
         }];
 
         let mut cursor = self.collection.aggregate(sample_pipeline, None).await?;
 
-        let mut schemas = Vec::with_capacity(sample_count as usize);
+        let mut schemas = Vec::with_capacity(sample_count);
         while let Some(doc) = cursor.try_next().await? {
             let schema = schema_from_document(&RawDocumentBuf::from_document(&doc)?);
             schemas.push(schema);
@@ -53,6 +90,26 @@ impl<'a> TableSampler<'a> {
         Ok(merged)
     }
 
+    /// Infer the schema from every document in the collection.
+    ///
+    /// Schemas are merged in batches to avoid holding a schema for every
+    /// document in memory. Fields that are only null within the first batch
+    /// end up as strings, the same as with sampling.
+    async fn infer_schema_from_full_scan(&self) -> Result<ArrowSchema> {
+        let mut cursor = self.collection.find(None, None).await?;
+
+        let mut merged = ArrowSchema::empty();
+        let mut schemas = Vec::with_capacity(FULL_SCAN_MERGE_BATCH);
+        while let Some(doc) = cursor.try_next().await? {
+            schemas.push(schema_from_document(&RawDocumentBuf::from_document(&doc)?));
+            if schemas.len() >= FULL_SCAN_MERGE_BATCH {
+                merged = merge_schemas(std::iter::once(Ok(merged)).chain(schemas.drain(..)))?;
+            }
+        }
+
+        Ok(merge_schemas(std::iter::once(Ok(merged)).chain(schemas))?)
+    }
+
     fn sample_size(doc_count: usize) -> usize {
         let mut sample_count = (doc_count as f32 * SAMPLE_PCT) as usize;
         if sample_count > MAX_SAMPLE_SIZE {
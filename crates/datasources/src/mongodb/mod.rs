@@ -4,6 +4,7 @@ pub mod errors;
 mod exec;
 mod infer;
 mod insert;
+pub mod pipeline;
 
 use std::any::Any;
 use std::fmt::{Display, Write};
@@ -18,27 +19,33 @@ use datafusion::arrow::datatypes::{
     Schema as ArrowSchema,
     SchemaRef as ArrowSchemaRef,
 };
+use datafusion::common::DFSchema;
 use datafusion::datasource::TableProvider;
 use datafusion::error::{DataFusionError, Result as DatafusionResult};
 use datafusion::execution::context::SessionState;
-use datafusion::logical_expr::{Expr, Operator, TableProviderFilterPushDown, TableType};
+use datafusion::logical_expr::expr_rewriter::unnormalize_cols;
+use datafusion::logical_expr::{Expr, TableProviderFilterPushDown, TableType};
 use datafusion::physical_plan::ExecutionPlan;
 use datafusion::scalar::ScalarValue;
 use datafusion_ext::errors::ExtensionError;
 use datafusion_ext::functions::VirtualLister;
 use mongodb::bson::spec::BinarySubtype;
-use mongodb::bson::{bson, Binary, Bson, Document, RawDocumentBuf};
-use mongodb::options::{ClientOptions, FindOptions};
+use mongodb::bson::{doc, Binary, Bson, Document, RawDocumentBuf};
+use mongodb::options::{ClientOptions, Tls, TlsOptions as MongoTlsOptions};
 use mongodb::{Client, Collection};
 use parser::errors::ParserError;
 use parser::options::{OptionValue, ParseOptionValue};
 use parser::{parser_err, unexpected_type_err};
+use protogen::metastore::types::options::TlsOptions;
 use tracing::debug;
 
 use crate::bson::array_to_bson;
+use crate::common::tls::{ca_cert_path, client_cert_paths, resolve_cert_path, SslMode};
 use crate::mongodb::errors::{MongoDbError, Result};
 use crate::mongodb::exec::MongoDbQueryExecPlan;
+pub use crate::mongodb::infer::SampleSize;
 use crate::mongodb::infer::TableSampler;
+use crate::mongodb::pipeline::PipelineExprWriter;
 
 /// Field name in mongo for uniquely identifying a record. Some special handling
 /// needs to be done with the field when projecting.
@@ -147,16 +154,52 @@ pub struct MongoDbAccessor {
 }
 
 impl MongoDbAccessor {
-    pub async fn connect(connection_string: &str) -> Result<MongoDbAccessor> {
+    pub async fn connect(connection_string: &str, tls: &TlsOptions) -> Result<MongoDbAccessor> {
         let mut opts = ClientOptions::parse(connection_string).await?;
         opts.app_name = Some("GlareDB (MongoDB Data source)".to_string());
+        Self::apply_tls(&mut opts, tls)?;
         let client = Client::with_options(opts)?;
 
         Ok(MongoDbAccessor { client })
     }
 
-    pub async fn validate_external_database(connection_string: &str) -> Result<()> {
-        let accessor = Self::connect(connection_string).await?;
+    /// Override the TLS settings from the connection string.
+    ///
+    /// The driver doesn't support falling back to an unencrypted connection,
+    /// so "prefer" keeps whatever the connection string specifies. The driver
+    /// also expects the client certificate and key in a single file.
+    fn apply_tls(opts: &mut ClientOptions, tls: &TlsOptions) -> Result<()> {
+        let cert_key_file_path = match client_cert_paths(tls)? {
+            Some((cert, key)) if cert != key => {
+                return Err(MongoDbError::Internal(
+                    "MongoDB requires 'client_cert' and 'client_key' to be the same file"
+                        .to_string(),
+                ))
+            }
+            Some((cert, _)) => Some(resolve_cert_path(cert)?),
+            None => None,
+        };
+
+        match SslMode::from_options(tls)? {
+            Some(SslMode::Disable) => opts.tls = Some(Tls::Disabled),
+            Some(SslMode::Require) => {
+                let tls_opts = MongoTlsOptions::builder()
+                    .ca_file_path(ca_cert_path(tls)?)
+                    .cert_key_file_path(cert_key_file_path)
+                    .build();
+                opts.tls = Some(Tls::Enabled(tls_opts));
+            }
+            Some(SslMode::Prefer) | None => (),
+        }
+
+        Ok(())
+    }
+
+    pub async fn validate_external_database(
+        connection_string: &str,
+        tls: &TlsOptions,
+    ) -> Result<()> {
+        let accessor = Self::connect(connection_string, tls).await?;
         let mut filter = Document::new();
         filter.insert("name".to_string(), Bson::String("glaredb".to_string()));
         let _ = accessor
@@ -216,7 +259,7 @@ impl VirtualLister for MongoDbAccessor {
             .map_err(|e| ListingErrBoxed(Box::new(e)))?;
 
         let schema = sampler
-            .infer_schema_from_sample(count)
+            .infer_schema_from_sample(count, SampleSize::Auto)
             .await
             .map_err(|e| ListingErrBoxed(Box::new(e)))?;
 
@@ -228,7 +271,10 @@ impl VirtualLister for MongoDbAccessor {
 pub struct MongoDbTableAccessInfo {
     pub database: String, // "Schema"
     pub collection: String,
-    pub fields: Option<Vec<FieldRef>>, // filter
+    /// User provided schema. Skips inferring the schema when set.
+    pub fields: Option<Vec<FieldRef>>,
+    /// Number of documents to read when inferring the schema.
+    pub sample_size: SampleSize,
 }
 
 #[derive(Debug, Clone)]
@@ -256,16 +302,19 @@ impl MongoDbTableAccessor {
             .database(&self.info.database)
             .collection(&self.info.collection);
 
-        let schema = if self.info.fields.is_some() {
-            ArrowSchema::new(self.info.fields.unwrap())
-        } else {
-            TableSampler::new(&collection)
-                .infer_schema_from_sample(128)
-                .await?
+        let estimated_count = collection.estimated_document_count(None).await?;
+
+        let schema = match self.info.fields {
+            Some(fields) => ArrowSchema::new(fields),
+            None => {
+                TableSampler::new(&collection)
+                    .infer_schema_from_sample(estimated_count, self.info.sample_size)
+                    .await?
+            }
         };
 
         Ok(MongoDbTableProvider {
-            estimated_count: collection.estimated_document_count(None).await?,
+            estimated_count,
             schema: Arc::new(schema),
             collection: self
                 .client
@@ -306,7 +355,7 @@ impl TableProvider for MongoDbTableProvider {
         &self,
         _ctx: &SessionState,
         projection: Option<&Vec<usize>>,
-        _filters: &[Expr],
+        filters: &[Expr],
         limit: Option<usize>,
     ) -> DatafusionResult<Arc<dyn ExecutionPlan>> {
         // Projection.
@@ -334,22 +383,39 @@ impl TableProvider for MongoDbTableProvider {
             proj_doc.insert(ID_FIELD_NAME, 0);
         }
 
-        let mut find_opts = FindOptions::default();
-        find_opts.limit = limit.map(|v| v as i64);
-        find_opts.projection = Some(proj_doc);
+        // Filters are inexact, any filters we're not able to translate are
+        // still applied to the output of the scan.
+        let table_fields: Vec<String> = self
+            .schema
+            .fields()
+            .iter()
+            .map(|f| f.name().clone())
+            .collect();
+        let df_schema = DFSchema::try_from(self.schema.as_ref().clone())?;
+        let writer = PipelineExprWriter::new(&df_schema, &table_fields);
+        let mut filters: Vec<Document> = unnormalize_cols(filters.iter().cloned())
+            .iter()
+            .filter_map(|expr| writer.write_filter(expr))
+            .collect();
+        debug!(?filters, "mongodb pushdown filters");
+
+        let mut pipeline = Vec::with_capacity(3);
+        match filters.len() {
+            0 => (),
+            1 => pipeline.push(doc! { "$match": filters.pop().unwrap() }),
+            _ => pipeline.push(doc! { "$match": { "$and": filters } }),
+        }
+        pipeline.push(doc! { "$project": proj_doc });
+        if let Some(limit) = limit {
+            pipeline.push(doc! { "$limit": limit as i64 });
+        }
 
-        let filter = match exprs_to_mdb_query(_filters) {
-            Ok(query) => query,
-            Err(err) => {
-                debug!("mdb pushdown query err: {}", err.to_string());
-                Document::new()
-            }
-        };
         let cursor = Mutex::new(Some(
             self.collection
-                .find(Some(filter), Some(find_opts))
+                .aggregate(pipeline, None)
                 .await
-                .map_err(|e| DataFusionError::External(Box::new(e)))?,
+                .map_err(|e| DataFusionError::External(Box::new(e)))?
+                .with_type::<RawDocumentBuf>(),
         ));
         Ok(Arc::new(MongoDbQueryExecPlan::new(
             cursor,
@@ -376,64 +442,6 @@ impl TableProvider for MongoDbTableProvider {
     }
 }
 
-fn exprs_to_mdb_query(exprs: &[Expr]) -> Result<Document, ExtensionError> {
-    let mut doc = Document::new();
-    for e in exprs {
-        let expr = &e;
-        match expr {
-            Expr::BinaryExpr(val) => {
-                match val.left.as_ref() {
-                    Expr::Column(key) => match val.right.as_ref() {
-                        Expr::Literal(v) => doc.insert(
-                            key.to_string(),
-                            bson!({operator_to_mdbq(val.op)?: df_to_bson(v.clone())?}),
-                        ),
-                        _ => {
-                            continue;
-                        }
-                    },
-                    Expr::Literal(v) => match val.right.as_ref() {
-                        Expr::Column(key) => doc.insert(
-                            key.to_string(),
-                            bson!({operator_to_mdbq(val.op)?: df_to_bson(v.clone())?}),
-                        ),
-                        _ => {
-                            continue;
-                        }
-                    },
-                    _ => {
-                        continue;
-                    }
-                };
-            }
-            _ => {
-                continue;
-            }
-        };
-    }
-
-    Ok(doc.to_owned())
-}
-
-fn operator_to_mdbq(op: Operator) -> Result<String, ExtensionError> {
-    match op {
-        Operator::Eq => Ok("$eq".to_string()),
-        Operator::Gt => Ok("$gt".to_string()),
-        Operator::Lt => Ok("$lt".to_string()),
-        Operator::NotEq => Ok("$ne".to_string()),
-        Operator::GtEq => Ok("$gte".to_string()),
-        Operator::LtEq => Ok("$lte".to_string()),
-        Operator::And => Ok("$and".to_string()),
-        Operator::Or => Ok("$or".to_string()),
-        Operator::Modulo => Ok("$mod".to_string()),
-        Operator::RegexMatch => Ok("$regex".to_string()),
-        _ => Err(ExtensionError::String(format!(
-            "{} operator is not translated",
-            op
-        ))),
-    }
-}
-
 fn df_to_bson(val: ScalarValue) -> Result<Bson, ExtensionError> {
     match val {
         ScalarValue::Binary(v) => Ok(Bson::Binary(Binary {
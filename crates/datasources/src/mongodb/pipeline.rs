@@ -0,0 +1,704 @@
+//! Translate parts of a query into a MongoDB aggregation pipeline.
+//!
+//! Filters are written as `$match` stages using the query language, which is
+//! able to make use of indexes. Aggregates are written as `$group`
+//! accumulators, taking care to match SQL semantics for nulls.
+
+use std::any::Any;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use datafusion::arrow::datatypes::{DataType, SchemaRef as ArrowSchemaRef, TimeUnit};
+use datafusion::common::DFSchema;
+use datafusion::datasource::TableProvider;
+use datafusion::error::{DataFusionError, Result as DatafusionResult};
+use datafusion::execution::context::SessionState;
+use datafusion::logical_expr::expr::{
+    AggregateFunction,
+    AggregateFunctionDefinition,
+    Alias,
+    Between,
+    InList,
+    Like,
+};
+use datafusion::logical_expr::{
+    AggregateFunction as AggregateFunctionType,
+    BinaryExpr,
+    Expr,
+    ExprSchemable,
+    Operator,
+    TableType,
+};
+use datafusion::physical_expr::expressions::Column as PhysicalColumn;
+use datafusion::physical_plan::projection::ProjectionExec;
+use datafusion::physical_plan::{ExecutionPlan, PhysicalExpr};
+use datafusion::scalar::ScalarValue;
+use mongodb::bson::{doc, Bson, Document, RawDocumentBuf};
+use mongodb::options::AggregateOptions;
+use mongodb::Collection;
+
+use super::exec::MongoDbQueryExecPlan;
+use super::{df_to_bson, MongoDbTableProvider};
+
+/// Check if a field name can be referenced in a pipeline. Names containing
+/// dots or starting with a dollar sign would be interpreted as paths or
+/// operators.
+pub fn is_valid_field_name(name: &str) -> bool {
+    !name.is_empty() && !name.contains('.') && !name.starts_with('$')
+}
+
+/// Check if values of the given type can be read from the documents produced
+/// by a pipeline.
+pub fn is_supported_type(datatype: &DataType) -> bool {
+    matches!(
+        datatype,
+        DataType::Boolean
+            | DataType::Int32
+            | DataType::Int64
+            | DataType::Float64
+            | DataType::Utf8
+            | DataType::LargeUtf8
+            | DataType::Binary
+            | DataType::LargeBinary
+            | DataType::Date32
+            | DataType::Date64
+            | DataType::Decimal128(_, _)
+            | DataType::Timestamp(
+                TimeUnit::Second
+                    | TimeUnit::Millisecond
+                    | TimeUnit::Microsecond
+                    | TimeUnit::Nanosecond,
+                _
+            )
+    )
+}
+
+/// A `$group` accumulator for an aggregate.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Accumulator {
+    /// The accumulator expression, e.g. `{"$max": "$a"}`.
+    pub expr: Bson,
+    /// Accumulator counting the non-null inputs, used to return null instead
+    /// of zero when summing only nulls.
+    pub non_null_count: Option<Bson>,
+}
+
+/// Writes expressions as MongoDB filters and accumulators.
+pub struct PipelineExprWriter<'a> {
+    /// Schema the expressions are evaluated against.
+    schema: &'a DFSchema,
+    /// Document field for each of the columns in the schema.
+    fields: &'a [String],
+}
+
+impl<'a> PipelineExprWriter<'a> {
+    pub fn new(schema: &'a DFSchema, fields: &'a [String]) -> Self {
+        PipelineExprWriter { schema, fields }
+    }
+
+    /// Get the document field for a column expression.
+    pub fn field(&self, expr: &Expr) -> Option<&'a str> {
+        match expr {
+            Expr::Alias(Alias { expr, .. }) => self.field(expr),
+            Expr::Column(col) => {
+                let idx = self.schema.index_of_column(col).ok()?;
+                let field = self.fields.get(idx)?;
+                is_valid_field_name(field).then_some(field.as_str())
+            }
+            _ => None,
+        }
+    }
+
+    /// Write a predicate as a query document for a `$match` stage.
+    ///
+    /// The document matches exactly the rows the predicate would evaluate to
+    /// true for. Returns `None` if the predicate can't be pushed down.
+    pub fn write_filter(&self, expr: &Expr) -> Option<Document> {
+        Some(match expr {
+            Expr::Alias(Alias { expr, .. }) => self.write_filter(expr)?,
+            Expr::BinaryExpr(BinaryExpr { left, op, right }) => match op {
+                Operator::And => doc! {
+                    "$and": [self.write_filter(left)?, self.write_filter(right)?],
+                },
+                Operator::Or => doc! {
+                    "$or": [self.write_filter(left)?, self.write_filter(right)?],
+                },
+                Operator::RegexMatch | Operator::RegexIMatch => {
+                    let field = self.field(left)?;
+                    let pattern = match right.as_ref() {
+                        Expr::Literal(ScalarValue::Utf8(Some(pattern))) => pattern,
+                        _ => return None,
+                    };
+                    let options = if *op == Operator::RegexIMatch {
+                        "i"
+                    } else {
+                        ""
+                    };
+                    doc! { field: { "$regex": pattern.as_str(), "$options": options } }
+                }
+                _ => {
+                    // Normalize to `field op literal`.
+                    let (field, op, lit) = match (left.as_ref(), right.as_ref()) {
+                        (_, Expr::Literal(lit)) => (self.field(left)?, *op, lit),
+                        (Expr::Literal(lit), _) => (self.field(right)?, op.swap()?, lit),
+                        _ => return None,
+                    };
+                    let lit = self.write_literal(lit)?;
+                    match op {
+                        Operator::Eq => doc! { field: { "$eq": lit } },
+                        // Nulls never compare as not equal in SQL.
+                        Operator::NotEq => doc! { field: { "$nin": [lit, Bson::Null] } },
+                        Operator::Lt => doc! { field: { "$lt": lit } },
+                        Operator::LtEq => doc! { field: { "$lte": lit } },
+                        Operator::Gt => doc! { field: { "$gt": lit } },
+                        Operator::GtEq => doc! { field: { "$gte": lit } },
+                        _ => return None,
+                    }
+                }
+            },
+            // Matches both null and missing fields.
+            Expr::IsNull(expr) => doc! { self.field(expr)?: Bson::Null },
+            Expr::IsNotNull(expr) => doc! { self.field(expr)?: { "$ne": Bson::Null } },
+            Expr::InList(InList {
+                expr,
+                list,
+                negated,
+            }) => {
+                let field = self.field(expr)?;
+                let mut values = list
+                    .iter()
+                    .map(|expr| match expr {
+                        Expr::Literal(lit) => self.write_literal(lit),
+                        _ => None,
+                    })
+                    .collect::<Option<Vec<_>>>()?;
+                if *negated {
+                    values.push(Bson::Null);
+                    doc! { field: { "$nin": values } }
+                } else {
+                    doc! { field: { "$in": values } }
+                }
+            }
+            Expr::Between(Between {
+                expr,
+                negated: false,
+                low,
+                high,
+            }) => {
+                let field = self.field(expr)?;
+                let (low, high) = match (low.as_ref(), high.as_ref()) {
+                    (Expr::Literal(low), Expr::Literal(high)) => {
+                        (self.write_literal(low)?, self.write_literal(high)?)
+                    }
+                    _ => return None,
+                };
+                doc! { field: { "$gte": low, "$lte": high } }
+            }
+            Expr::Like(Like {
+                negated,
+                expr,
+                pattern,
+                escape_char,
+                case_insensitive,
+            }) => {
+                let field = self.field(expr)?;
+                let pattern = match pattern.as_ref() {
+                    Expr::Literal(ScalarValue::Utf8(Some(pattern))) => {
+                        like_to_regex(pattern, *escape_char)?
+                    }
+                    _ => return None,
+                };
+                let options = if *case_insensitive { "si" } else { "s" };
+                let regex = doc! { "$regex": pattern, "$options": options };
+                if *negated {
+                    doc! {
+                        "$and": [
+                            { field: { "$ne": Bson::Null } },
+                            { field: { "$not": regex } },
+                        ],
+                    }
+                } else {
+                    doc! { field: regex }
+                }
+            }
+            _ => return None,
+        })
+    }
+
+    /// Write an aggregate as a `$group` accumulator.
+    ///
+    /// Returns `None` if the aggregate can't be pushed down.
+    pub fn write_accumulator(&self, expr: &Expr) -> Option<Accumulator> {
+        let (func, arg) = match expr {
+            Expr::Alias(Alias { expr, .. }) => return self.write_accumulator(expr),
+            Expr::AggregateFunction(AggregateFunction {
+                func_def: AggregateFunctionDefinition::BuiltIn(func),
+                args,
+                distinct: false,
+                filter: None,
+                order_by: None,
+            }) if args.len() == 1 => (func, &args[0]),
+            _ => return None,
+        };
+
+        // Summing or averaging other types (e.g. decimals) would produce
+        // values of a different type than we expect.
+        if matches!(
+            func,
+            AggregateFunctionType::Sum | AggregateFunctionType::Avg
+        ) {
+            let typ = arg.get_type(self.schema).ok()?;
+            if !matches!(typ, DataType::Int32 | DataType::Int64 | DataType::Float64) {
+                return None;
+            }
+        }
+
+        let accumulator = |expr| Accumulator {
+            expr,
+            non_null_count: None,
+        };
+
+        match (func, arg) {
+            // COUNT(*) and COUNT(<literal>).
+            (AggregateFunctionType::Count, Expr::Literal(lit)) if !lit.is_null() => {
+                Some(accumulator(Bson::Document(doc! { "$sum": 1 })))
+            }
+            (AggregateFunctionType::Count, _) => {
+                let field = format!("${}", self.field(arg)?);
+                Some(accumulator(Bson::Document(doc! {
+                    "$sum": non_null_indicator(field),
+                })))
+            }
+            (AggregateFunctionType::Sum, _) => {
+                let field = format!("${}", self.field(arg)?);
+                Some(Accumulator {
+                    expr: Bson::Document(doc! { "$sum": field.clone() }),
+                    non_null_count: Some(Bson::Document(doc! {
+                        "$sum": non_null_indicator(field),
+                    })),
+                })
+            }
+            (AggregateFunctionType::Avg, _) => {
+                let field = format!("${}", self.field(arg)?);
+                Some(accumulator(Bson::Document(doc! { "$avg": field })))
+            }
+            (AggregateFunctionType::Min, _) => {
+                let field = format!("${}", self.field(arg)?);
+                Some(accumulator(Bson::Document(doc! { "$min": field })))
+            }
+            (AggregateFunctionType::Max, _) => {
+                let field = format!("${}", self.field(arg)?);
+                Some(accumulator(Bson::Document(doc! { "$max": field })))
+            }
+            _ => None,
+        }
+    }
+
+    fn write_literal(&self, lit: &ScalarValue) -> Option<Bson> {
+        // Comparisons with null are never true in SQL.
+        if lit.is_null() {
+            return None;
+        }
+        df_to_bson(lit.clone()).ok()
+    }
+}
+
+/// A single sort key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SortField {
+    pub field: String,
+    pub asc: bool,
+    pub nulls_first: bool,
+}
+
+/// An aggregation pipeline built up from the parts of a query.
+///
+/// Stages refer to the fields of the documents produced by the previous stage.
+/// Stages that produce new documents (e.g. `$group`) generate field names that
+/// are unique within the pipeline.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MongoDbPipeline {
+    stages: Vec<Document>,
+    /// Number of fields generated so far.
+    generated: usize,
+}
+
+impl MongoDbPipeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn stages(&self) -> &[Document] {
+        &self.stages
+    }
+
+    fn next_field(&mut self) -> String {
+        let field = format!("__c{}", self.generated);
+        self.generated += 1;
+        field
+    }
+
+    pub fn push_match(&mut self, filter: Document) {
+        self.stages.push(doc! { "$match": filter });
+    }
+
+    /// Group documents by the given fields, returning the fields holding the
+    /// groups followed by the fields holding the accumulated values.
+    pub fn push_group(
+        &mut self,
+        group_fields: &[String],
+        accumulators: Vec<Accumulator>,
+    ) -> Vec<String> {
+        let mut fields = Vec::with_capacity(group_fields.len() + accumulators.len());
+        let mut group_id = Document::new();
+        let mut group = Document::new();
+        let mut project = doc! { "_id": 0 };
+
+        // Missing fields and nulls are the same group.
+        for group_field in group_fields {
+            let field = self.next_field();
+            group_id.insert(
+                &field,
+                doc! { "$ifNull": [format!("${group_field}"), Bson::Null] },
+            );
+            project.insert(&field, format!("$_id.{field}"));
+            fields.push(field);
+        }
+        group.insert("_id", group_id);
+
+        for accumulator in accumulators {
+            let field = self.next_field();
+            group.insert(&field, accumulator.expr);
+            match accumulator.non_null_count {
+                Some(count) => {
+                    let count_field = self.next_field();
+                    group.insert(&count_field, count);
+                    project.insert(
+                        &field,
+                        doc! {
+                            "$cond": [
+                                { "$eq": [format!("${count_field}"), 0] },
+                                Bson::Null,
+                                format!("${field}"),
+                            ],
+                        },
+                    );
+                }
+                None => {
+                    project.insert(&field, 1);
+                }
+            }
+            fields.push(field);
+        }
+
+        self.stages.push(doc! { "$group": group });
+        self.stages.push(doc! { "$project": project });
+        fields
+    }
+
+    /// Sort documents by the given keys.
+    ///
+    /// MongoDB sorts nulls before any other value. Keys with a different null
+    /// ordering are sorted on a temporary field indicating if the value is
+    /// null first.
+    pub fn push_sort(&mut self, sort_fields: &[SortField]) {
+        let mut helpers = Document::new();
+        let mut sort = Document::new();
+        for sort_field in sort_fields {
+            if sort_field.asc != sort_field.nulls_first {
+                let helper = self.next_field();
+                helpers.insert(
+                    &helper,
+                    non_null_indicator(format!("${}", sort_field.field)),
+                );
+                sort.insert(helper, if sort_field.nulls_first { 1 } else { -1 });
+            }
+            sort.insert(&sort_field.field, if sort_field.asc { 1 } else { -1 });
+        }
+
+        if helpers.is_empty() {
+            self.stages.push(doc! { "$sort": sort });
+            return;
+        }
+
+        let hide_helpers: Document = helpers
+            .keys()
+            .map(|k| (k.clone(), Bson::Int32(0)))
+            .collect();
+        self.stages.push(doc! { "$addFields": helpers });
+        self.stages.push(doc! { "$sort": sort });
+        self.stages.push(doc! { "$project": hide_helpers });
+    }
+
+    pub fn push_skip(&mut self, skip: usize) {
+        self.stages.push(doc! { "$skip": skip as i64 });
+    }
+
+    pub fn push_limit(&mut self, limit: usize) {
+        self.stages.push(doc! { "$limit": limit as i64 });
+    }
+
+    /// Finish the pipeline, producing documents containing exactly the given
+    /// fields, renamed to `c0`, `c1`, etc.
+    pub fn finish(mut self, fields: &[String]) -> Vec<Document> {
+        let mut project = doc! { "_id": 0 };
+        for (idx, field) in fields.iter().enumerate() {
+            project.insert(format!("c{idx}"), format!("${field}"));
+        }
+        self.stages.push(doc! { "$project": project });
+        self.stages
+    }
+}
+
+/// Aggregation expression evaluating to 0 for null or missing values, and 1
+/// otherwise.
+pub fn non_null_indicator(field: impl Into<Bson>) -> Document {
+    doc! {
+        "$cond": [{ "$eq": [{ "$ifNull": [field.into(), Bson::Null] }, Bson::Null] }, 0, 1],
+    }
+}
+
+/// Convert a SQL LIKE pattern into an anchored regular expression.
+///
+/// Returns `None` if the pattern ends with a dangling escape character.
+fn like_to_regex(pattern: &str, escape_char: Option<char>) -> Option<String> {
+    let escape_char = escape_char.unwrap_or('\\');
+
+    let mut regex = String::with_capacity(pattern.len() + 2);
+    regex.push('^');
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c == escape_char => push_literal_char(&mut regex, chars.next()?),
+            '%' => regex.push_str(".*"),
+            '_' => regex.push('.'),
+            c => push_literal_char(&mut regex, c),
+        }
+    }
+    regex.push('$');
+
+    Some(regex)
+}
+
+fn push_literal_char(regex: &mut String, c: char) {
+    if "\\^$.|?*+()[]{}".contains(c) {
+        regex.push('\\');
+    }
+    regex.push(c);
+}
+
+/// Table provider for an aggregation pipeline that's been pushed down to
+/// MongoDB.
+///
+/// Documents produced by the pipeline are expected to contain exactly the
+/// fields in the provider's schema.
+pub struct MongoDbPipelineTableProvider {
+    collection: Collection<RawDocumentBuf>,
+    pipeline: Vec<Document>,
+    schema: ArrowSchemaRef,
+    estimated_count: u64,
+}
+
+impl MongoDbPipelineTableProvider {
+    pub fn new(
+        table: &MongoDbTableProvider,
+        pipeline: Vec<Document>,
+        schema: ArrowSchemaRef,
+    ) -> Self {
+        MongoDbPipelineTableProvider {
+            collection: table.collection.clone(),
+            pipeline,
+            schema,
+            estimated_count: table.estimated_count,
+        }
+    }
+
+    pub fn pipeline(&self) -> &[Document] {
+        &self.pipeline
+    }
+}
+
+#[async_trait]
+impl TableProvider for MongoDbPipelineTableProvider {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> ArrowSchemaRef {
+        self.schema.clone()
+    }
+
+    fn table_type(&self) -> TableType {
+        TableType::View
+    }
+
+    async fn scan(
+        &self,
+        _ctx: &SessionState,
+        projection: Option<&Vec<usize>>,
+        _filters: &[Expr],
+        _limit: Option<usize>,
+    ) -> DatafusionResult<Arc<dyn ExecutionPlan>> {
+        // Sorts and groups may need more memory than the server allows for a
+        // single stage.
+        let opts = AggregateOptions::builder().allow_disk_use(true).build();
+        let cursor = self
+            .collection
+            .aggregate(self.pipeline.clone(), opts)
+            .await
+            .map_err(|e| DataFusionError::External(Box::new(e)))?
+            .with_type::<RawDocumentBuf>();
+        let exec = Arc::new(MongoDbQueryExecPlan::new(
+            Mutex::new(Some(cursor)),
+            self.schema.clone(),
+            None,
+            self.estimated_count,
+        ));
+
+        match projection {
+            Some(projection) => {
+                let exprs = projection
+                    .iter()
+                    .map(|idx| {
+                        let name = self.schema.field(*idx).name().clone();
+                        let col: Arc<dyn PhysicalExpr> = Arc::new(PhysicalColumn::new(&name, *idx));
+                        (col, name)
+                    })
+                    .collect();
+                Ok(Arc::new(ProjectionExec::try_new(exprs, exec)?))
+            }
+            None => Ok(exec),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use datafusion::arrow::datatypes::{Field, Schema};
+    use datafusion::logical_expr::{col, count, lit, max, sum, Expr};
+
+    use super::*;
+
+    fn test_schema() -> (DFSchema, Vec<String>) {
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int64, true),
+            Field::new("b", DataType::Utf8, true),
+        ]);
+        let schema = DFSchema::try_from_qualified_schema("t", &schema).unwrap();
+        (schema, vec!["a".to_string(), "b".to_string()])
+    }
+
+    #[test]
+    fn write_filters() {
+        let (schema, fields) = test_schema();
+        let writer = PipelineExprWriter::new(&schema, &fields);
+
+        let test_cases: Vec<(Expr, Option<Document>)> = vec![
+            (
+                col("a").eq(lit(1_i64)),
+                Some(doc! { "a": { "$eq": 1_i64 } }),
+            ),
+            (
+                lit(1_i64).lt(col("a")),
+                Some(doc! { "a": { "$gt": 1_i64 } }),
+            ),
+            (
+                col("a").not_eq(lit(1_i64)),
+                Some(doc! { "a": { "$nin": [1_i64, Bson::Null] } }),
+            ),
+            (
+                col("a").gt(lit(1_i64)).and(col("b").is_null()),
+                Some(doc! { "$and": [{ "a": { "$gt": 1_i64 } }, { "b": Bson::Null }] }),
+            ),
+            (
+                col("b").in_list(vec![lit("x"), lit("y")], false),
+                Some(doc! { "b": { "$in": ["x", "y"] } }),
+            ),
+            (
+                col("b").like(lit("a_c%.")),
+                Some(doc! { "b": { "$regex": "^a.c.*\\.$", "$options": "s" } }),
+            ),
+            (
+                col("a").between(lit(1_i64), lit(5_i64)),
+                Some(doc! { "a": { "$gte": 1_i64, "$lte": 5_i64 } }),
+            ),
+            // Comparisons between columns and with null aren't pushed down.
+            (col("a").eq(col("b")), None),
+            (col("a").eq(lit(ScalarValue::Int64(None))), None),
+        ];
+
+        for (expr, expected) in test_cases {
+            assert_eq!(expected, writer.write_filter(&expr), "expr: {expr}");
+        }
+    }
+
+    #[test]
+    fn write_accumulators() {
+        let (schema, fields) = test_schema();
+        let writer = PipelineExprWriter::new(&schema, &fields);
+
+        let acc = writer.write_accumulator(&count(lit(1_u8))).unwrap();
+        assert_eq!(Bson::Document(doc! { "$sum": 1 }), acc.expr);
+
+        let acc = writer.write_accumulator(&max(col("b"))).unwrap();
+        assert_eq!(Bson::Document(doc! { "$max": "$b" }), acc.expr);
+        assert_eq!(None, acc.non_null_count);
+
+        let acc = writer.write_accumulator(&sum(col("a"))).unwrap();
+        assert_eq!(Bson::Document(doc! { "$sum": "$a" }), acc.expr);
+        assert_eq!(
+            Some(Bson::Document(doc! { "$sum": non_null_indicator("$a") })),
+            acc.non_null_count
+        );
+    }
+
+    #[test]
+    fn pipeline_stages() {
+        let mut pipeline = MongoDbPipeline::new();
+        pipeline.push_match(doc! { "a": { "$gt": 1 } });
+        let fields = pipeline.push_group(
+            &["b".to_string()],
+            vec![Accumulator {
+                expr: Bson::Document(doc! { "$sum": "$a" }),
+                non_null_count: Some(Bson::Document(doc! { "$sum": non_null_indicator("$a") })),
+            }],
+        );
+        assert_eq!(vec!["__c0".to_string(), "__c1".to_string()], fields);
+
+        pipeline.push_sort(&[SortField {
+            field: "__c1".to_string(),
+            asc: true,
+            nulls_first: false,
+        }]);
+        pipeline.push_limit(10);
+        let stages = pipeline.finish(&fields);
+
+        let expected = vec![
+            doc! { "$match": { "a": { "$gt": 1 } } },
+            doc! { "$group": {
+                "_id": { "__c0": { "$ifNull": ["$b", Bson::Null] } },
+                "__c1": { "$sum": "$a" },
+                "__c2": { "$sum": non_null_indicator("$a") },
+            } },
+            doc! { "$project": {
+                "_id": 0,
+                "__c0": "$_id.__c0",
+                "__c1": { "$cond": [{ "$eq": ["$__c2", 0] }, Bson::Null, "$__c1"] },
+            } },
+            doc! { "$addFields": { "__c3": non_null_indicator("$__c1") } },
+            doc! { "$sort": { "__c3": -1, "__c1": 1 } },
+            doc! { "$project": { "__c3": 0 } },
+            doc! { "$limit": 10_i64 },
+            doc! { "$project": { "_id": 0, "c0": "$__c0", "c1": "$__c1" } },
+        ];
+        assert_eq!(expected, stages);
+    }
+
+    #[test]
+    fn like_patterns() {
+        assert_eq!("^100%$", like_to_regex("100\\%", None).unwrap());
+        assert_eq!("^a\\(b\\).*$", like_to_regex("a(b)%", None).unwrap());
+        assert_eq!("^a_b$", like_to_regex("a!_b", Some('!')).unwrap());
+        assert_eq!(None, like_to_regex("abc\\", None));
+    }
+}
@@ -2,6 +2,7 @@ pub mod errors;
 
 use std::any::Any;
 use std::fmt::{self, Write};
+use std::ops::{Deref, DerefMut};
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
@@ -44,21 +45,28 @@ use futures::{Stream, StreamExt, TryStreamExt};
 use mysql_async::consts::{ColumnFlags, ColumnType};
 use mysql_async::prelude::Queryable;
 use mysql_async::{
+    ClientIdentity,
     Column as MysqlColumn,
     Conn,
     IsolationLevel,
     Opts,
     OptsBuilder,
+    PathOrBuf,
     Row as MysqlRow,
+    SslOpts,
     TxOpts,
 };
-use protogen::metastore::types::options::TunnelOptions;
+use once_cell::sync::Lazy;
+use protogen::metastore::types::options::{TlsOptions, TunnelOptions};
 use serde::{Deserialize, Serialize};
-use tokio::sync::RwLock;
 use tracing::{debug, trace};
 
+use crate::common::dml::{sql_delete_statement, sql_update_statement, ExternalDml};
+use crate::common::pool::{ConnectionPool, PoolableConnection, PooledConnection};
+use crate::common::query::{quote_table_reference, SqlQueryPushdown};
 use crate::common::ssh::key::SshKey;
 use crate::common::ssh::session::{SshTunnelAccess, SshTunnelSession};
+use crate::common::tls::{ca_cert_path, client_cert_paths, resolve_cert_path, SslMode};
 use crate::common::util::{self, create_count_record_batch, COUNT_SCHEMA};
 
 #[derive(Debug)]
@@ -112,47 +120,140 @@ pub struct MysqlTableAccess {
     pub name: String,
 }
 
+/// Pool of connections shared by all mysql data sources.
+static MYSQL_POOL: Lazy<Arc<ConnectionPool<MysqlConnection>>> =
+    Lazy::new(|| Arc::new(ConnectionPool::new("mysql")));
+
+/// An open connection to a mysql instance.
 #[derive(Debug)]
-pub struct MysqlAccessor {
-    conn: RwLock<Conn>,
+struct MysqlConnection {
+    conn: Conn,
     /// `Session` for the underlying ssh tunnel
     ///
     /// Kept on struct to avoid dropping ssh tunnel
     _ssh_tunnel: Option<SshTunnelSession>,
 }
 
+impl Deref for MysqlConnection {
+    type Target = Conn;
+
+    fn deref(&self) -> &Self::Target {
+        &self.conn
+    }
+}
+
+impl DerefMut for MysqlConnection {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.conn
+    }
+}
+
+#[async_trait]
+impl PoolableConnection for MysqlConnection {
+    async fn is_healthy(&mut self) -> bool {
+        self.conn.ping().await.is_ok()
+    }
+}
+
+/// Describe the connection for display, leaving out credentials.
+fn describe_connection(connection_string: &str, tunnel: Option<&TunnelOptions>) -> String {
+    let desc = match Opts::from_url(connection_string) {
+        Ok(opts) => format!(
+            "{}:{}/{}",
+            opts.ip_or_hostname(),
+            opts.tcp_port(),
+            opts.db_name().unwrap_or_default()
+        ),
+        Err(_) => "<unknown>".to_string(),
+    };
+
+    match tunnel {
+        Some(tunnel) => format!("{desc} (via {tunnel} tunnel)"),
+        None => desc,
+    }
+}
+
+#[derive(Debug)]
+pub struct MysqlAccessor {
+    connection_string: String,
+    tunnel: Option<TunnelOptions>,
+    tls: TlsOptions,
+}
+
 impl MysqlAccessor {
     /// Connect to a mysql instance.
-    pub async fn connect(connection_string: &str, tunnel: Option<TunnelOptions>) -> Result<Self> {
-        let (conn, _ssh_tunnel) = Self::connect_internal(connection_string, tunnel).await?;
-        let conn = RwLock::new(conn);
+    ///
+    /// Connections are checked out of the connection pool whenever needed,
+    /// and returned once the operation using it completes.
+    pub async fn connect(
+        connection_string: &str,
+        tunnel: Option<TunnelOptions>,
+        tls: TlsOptions,
+    ) -> Result<Self> {
+        let accessor = Self {
+            connection_string: connection_string.to_string(),
+            tunnel,
+            tls,
+        };
+        // Make sure we're able to connect.
+        accessor.checkout().await?;
+        Ok(accessor)
+    }
 
-        Ok(Self { conn, _ssh_tunnel })
+    /// Check out a connection, reusing a pooled connection if possible.
+    async fn checkout(&self) -> Result<PooledConnection<MysqlConnection>> {
+        let key = format!(
+            "{}|{:?}|{:?}",
+            self.connection_string, self.tunnel, self.tls
+        );
+        let description = describe_connection(&self.connection_string, self.tunnel.as_ref());
+
+        MYSQL_POOL
+            .get(
+                &key,
+                || description,
+                || async move {
+                    let (conn, _ssh_tunnel) = Self::connect_internal(
+                        &self.connection_string,
+                        self.tunnel.clone(),
+                        &self.tls,
+                    )
+                    .await?;
+                    Ok::<_, MysqlError>(MysqlConnection { conn, _ssh_tunnel })
+                },
+            )
+            .await
     }
 
     async fn connect_internal(
         connection_string: &str,
         tunnel: Option<TunnelOptions>,
+        tls: &TlsOptions,
     ) -> Result<(Conn, Option<SshTunnelSession>)> {
         match tunnel {
-            None => Self::connect_direct(connection_string).await,
+            None => Self::connect_direct(connection_string, tls).await,
             Some(TunnelOptions::Ssh(ssh_options)) => {
                 let keypair = SshKey::from_bytes(&ssh_options.ssh_key)?;
                 let access = SshTunnelAccess {
                     connection_string: ssh_options.connection_string,
                     keypair,
+                    jump_hosts: ssh_options.jump_hosts,
+                    known_hosts: ssh_options.known_hosts,
                 };
-                Self::connect_with_ssh_tunnel(connection_string, access).await
+                Self::connect_with_ssh_tunnel(connection_string, access, tls).await
             }
             Some(opt) => Err(MysqlError::UnsupportedTunnel(opt.to_string())),
         }
     }
 
-    async fn connect_direct(connection_string: &str) -> Result<(Conn, Option<SshTunnelSession>)> {
+    async fn connect_direct(
+        connection_string: &str,
+        tls: &TlsOptions,
+    ) -> Result<(Conn, Option<SshTunnelSession>)> {
         let database_url = connection_string;
 
         let opts = Opts::from_url(database_url)?;
-        let conn = Conn::new(opts).await?;
+        let conn = Self::connect_with_tls(opts, tls, None).await?;
 
         Ok((conn, None))
     }
@@ -160,15 +261,18 @@ impl MysqlAccessor {
     async fn connect_with_ssh_tunnel(
         connection_string: &str,
         ssh_tunnel: SshTunnelAccess,
+        tls: &TlsOptions,
     ) -> Result<(Conn, Option<SshTunnelSession>)> {
         let database_url = connection_string;
         let opts = Opts::from_url(database_url)?;
 
-        let mysql_host = opts.ip_or_hostname();
+        let mysql_host = opts.ip_or_hostname().to_string();
         let mysql_port = opts.tcp_port();
 
         // Open ssh tunnel
-        let (session, tunnel_addr) = ssh_tunnel.create_tunnel(&(mysql_host, mysql_port)).await?;
+        let (session, tunnel_addr) = ssh_tunnel
+            .create_tunnel(&(mysql_host.as_str(), mysql_port))
+            .await?;
 
         let opts: Opts = OptsBuilder::from_opts(opts)
             .ip_or_hostname(tunnel_addr.ip().to_string())
@@ -176,17 +280,67 @@ impl MysqlAccessor {
             .prefer_socket(false)
             .into();
 
-        let conn = Conn::new(opts).await?;
+        // Certificates are issued for the mysql host, not the local end of
+        // the tunnel.
+        let conn = Self::connect_with_tls(opts, tls, Some(mysql_host)).await?;
 
         Ok((conn, Some(session)))
     }
 
+    /// Open a connection, configuring TLS according to the provided options.
+    ///
+    /// TLS settings in the connection string are used if no options are set.
+    async fn connect_with_tls(
+        opts: Opts,
+        tls: &TlsOptions,
+        tls_hostname: Option<String>,
+    ) -> Result<Conn> {
+        let mode = match SslMode::from_options(tls)? {
+            Some(mode) => mode,
+            None => return Ok(Conn::new(opts).await?),
+        };
+
+        let without_tls = || OptsBuilder::from_opts(opts.clone()).ssl_opts(None::<SslOpts>);
+        let with_tls = || -> Result<OptsBuilder> {
+            let mut ssl_opts = SslOpts::default();
+            if let Some(path) = ca_cert_path(tls)? {
+                ssl_opts = ssl_opts.with_root_certs(vec![PathOrBuf::from(path)]);
+            }
+            if let Some((cert, key)) = client_cert_paths(tls)? {
+                ssl_opts = ssl_opts.with_client_identity(Some(ClientIdentity::new(
+                    PathOrBuf::from(resolve_cert_path(cert)?),
+                    PathOrBuf::from(resolve_cert_path(key)?),
+                )));
+            }
+            if let Some(host) = &tls_hostname {
+                ssl_opts = ssl_opts.with_tls_hostname_override(Some(host.clone()));
+            }
+            Ok(OptsBuilder::from_opts(opts.clone()).ssl_opts(ssl_opts))
+        };
+
+        let conn = match mode {
+            SslMode::Disable => Conn::new(without_tls()).await?,
+            SslMode::Prefer => match Conn::new(with_tls()?).await {
+                Ok(conn) => conn,
+                Err(e) => {
+                    debug!(%e, "mysql conn falling back to no tls");
+                    Conn::new(without_tls()).await?
+                }
+            },
+            SslMode::Require => Conn::new(with_tls()?).await?,
+        };
+
+        Ok(conn)
+    }
+
     /// Validate mysql external database
     pub async fn validate_external_database(
         connection_string: &str,
         tunnel: Option<TunnelOptions>,
+        tls: &TlsOptions,
     ) -> Result<()> {
-        let (mut conn, _ssh_tunnel) = Self::connect_internal(connection_string, tunnel).await?;
+        let (mut conn, _ssh_tunnel) =
+            Self::connect_internal(connection_string, tunnel, tls).await?;
 
         conn.query_drop("SELECT 1").await?;
         Ok(())
@@ -197,8 +351,10 @@ impl MysqlAccessor {
         connection_string: &str,
         access: &MysqlTableAccess,
         tunnel: Option<TunnelOptions>,
+        tls: &TlsOptions,
     ) -> Result<()> {
-        let (mut conn, _ssh_tunnel) = Self::connect_internal(connection_string, tunnel).await?;
+        let (mut conn, _ssh_tunnel) =
+            Self::connect_internal(connection_string, tunnel, tls).await?;
 
         let query = format!(
             "SELECT * FROM {}.{} where false",
@@ -210,7 +366,7 @@ impl MysqlAccessor {
 
     /// Get the arrow schema for the MySQL table.
     async fn get_table_schema(&self, schema: &str, table: &str) -> Result<ArrowSchema> {
-        let mut conn = self.conn.write().await;
+        let mut conn = self.checkout().await?;
 
         let cols = conn
             .exec_iter(
@@ -248,7 +404,10 @@ impl VirtualLister for MysqlAccessor {
     async fn list_schemas(&self) -> Result<Vec<String>, ExtensionError> {
         use ExtensionError::ListingErrBoxed;
 
-        let mut conn = self.conn.write().await;
+        let mut conn = self
+            .checkout()
+            .await
+            .map_err(|e| ListingErrBoxed(Box::new(e)))?;
 
         let cols = conn
             .exec_iter("SELECT schema_name FROM information_schema.schemata", ())
@@ -266,7 +425,10 @@ impl VirtualLister for MysqlAccessor {
     async fn list_tables(&self, schema: &str) -> Result<Vec<String>, ExtensionError> {
         use ExtensionError::ListingErrBoxed;
 
-        let mut conn = self.conn.write().await;
+        let mut conn = self
+            .checkout()
+            .await
+            .map_err(|e| ListingErrBoxed(Box::new(e)))?;
 
         let cols = conn
             .exec_iter(
@@ -461,6 +623,87 @@ impl TableProvider for MysqlTableProvider {
     }
 }
 
+#[async_trait]
+impl ExternalDml for MysqlTableProvider {
+    async fn update(
+        &self,
+        updates: &[(String, Expr)],
+        where_expr: Option<&Expr>,
+    ) -> DatafusionResult<u64> {
+        let stmt = sql_update_statement(self, &self.arrow_schema, updates, where_expr)?;
+        self.execute_dml(stmt).await
+    }
+
+    async fn delete(&self, where_expr: Option<&Expr>) -> DatafusionResult<u64> {
+        let stmt = sql_delete_statement(self, &self.arrow_schema, where_expr)?;
+        self.execute_dml(stmt).await
+    }
+}
+
+impl MysqlTableProvider {
+    async fn execute_dml(&self, stmt: String) -> DatafusionResult<u64> {
+        debug!(%stmt, "modifying rows in mysql datasource");
+        let mut conn = self
+            .accessor
+            .checkout()
+            .await
+            .map_err(|e| DataFusionError::External(Box::new(e)))?;
+        conn.query_drop(stmt)
+            .await
+            .map_err(|e| DataFusionError::External(Box::new(e)))?;
+        Ok(conn.affected_rows())
+    }
+}
+
+#[async_trait]
+impl SqlQueryPushdown for MysqlTableProvider {
+    fn dialect(&self) -> util::Datasource {
+        util::Datasource::MySql
+    }
+
+    fn table_reference(&self) -> String {
+        quote_table_reference(
+            self.dialect(),
+            Some(&self.table_access.schema),
+            &self.table_access.name,
+        )
+    }
+
+    fn cast_type(&self, datatype: &DataType) -> Option<String> {
+        Some(match datatype {
+            DataType::Int8 | DataType::Int16 | DataType::Int32 | DataType::Int64 => {
+                "SIGNED".to_string()
+            }
+            DataType::UInt8 | DataType::UInt16 | DataType::UInt32 | DataType::UInt64 => {
+                "UNSIGNED".to_string()
+            }
+            DataType::Float32 => "FLOAT".to_string(),
+            DataType::Float64 => "DOUBLE".to_string(),
+            DataType::Decimal128(precision, scale) => format!("DECIMAL({precision},{scale})"),
+            DataType::Date32 => "DATE".to_string(),
+            DataType::Utf8 => "CHAR".to_string(),
+            _ => return None,
+        })
+    }
+
+    async fn query_exec(
+        &self,
+        query: String,
+        schema: ArrowSchemaRef,
+    ) -> DatafusionResult<Arc<dyn ExecutionPlan>> {
+        trace!(?query);
+        Ok(Arc::new(MysqlExec {
+            predicate: String::new(),
+            table_access: self.table_access.clone(),
+            accessor: self.accessor.clone(),
+            query,
+            arrow_schema: schema,
+            metrics: ExecutionPlanMetricsSet::new(),
+            query_type: QueryType::Dql,
+        }))
+    }
+}
+
 #[derive(Debug)]
 struct MysqlExec {
     predicate: String,
@@ -570,7 +813,15 @@ impl MysqlQueryStream {
         let schema = arrow_schema.clone();
 
         let stream = stream! {
-            let mut conn = accessor.conn.write().await;
+            // Only check out the connection once the stream is polled, and
+            // return it as soon as the query completes.
+            let mut conn = accessor
+                .checkout()
+                .await
+                .map_err(|e| DataFusionError::External(Box::new(e)))?;
+            // Results (or the transaction) of a query that didn't complete
+            // would remain on the connection if the stream is dropped early.
+            conn.begin_operation();
             match query_type {
                 QueryType::Dql => {
                     // Open Mysql Binary stream
@@ -607,6 +858,7 @@ impl MysqlQueryStream {
                     tx.commit()
                         .await
                         .map_err(|e| DataFusionError::External(Box::new(e)))?;
+                    conn.end_operation();
                 },
                 QueryType::Dml => {
                     conn.exec::<MysqlRow, _, _>(query, ())
@@ -614,6 +866,7 @@ impl MysqlQueryStream {
                         .map_err(|e| DataFusionError::External(Box::new(e)))?;
 
                     let count = conn.affected_rows();
+                    conn.end_operation();
                     let record_batch = create_count_record_batch(count);
 
                     yield Ok(record_batch);
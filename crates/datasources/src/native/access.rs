@@ -21,6 +21,7 @@ use deltalake::kernel::{ArrayType, DataType as DeltaDataType};
 use deltalake::logstore::{default_logstore, logstores, LogStore, LogStoreFactory};
 use deltalake::operations::create::CreateBuilder;
 use deltalake::operations::delete::DeleteBuilder;
+use deltalake::operations::optimize::{OptimizeBuilder, OptimizeType};
 use deltalake::operations::update::UpdateBuilder;
 pub use deltalake::protocol::SaveMode;
 use deltalake::storage::{factories, ObjectStoreFactory, ObjectStoreRef, StorageOptions};
@@ -225,8 +226,11 @@ impl NativeTableStorage {
                 );
             }
 
+            if !opts.partition_by.is_empty() {
+                builder = builder.with_partition_columns(opts.partition_by.clone());
+            }
+
             let delta_table = builder.await?;
-            // TODO: Partitioning
             NativeTable::new(delta_table)
         };
 
@@ -326,6 +330,33 @@ impl NativeTableStorage {
         let updated_rows = builder.await?.1.num_updated_rows;
         Ok(updated_rows)
     }
+
+    /// Rewrite the table's data files.
+    ///
+    /// Small files are compacted into larger ones. If `zorder_by` columns are
+    /// provided, rows are also clustered by those columns so that file level
+    /// statistics can be used to skip files when filtering on them.
+    ///
+    /// Returns the number of files that were rewritten.
+    pub async fn optimize_table(
+        &self,
+        table: &TableEntry,
+        zorder_by: Vec<String>,
+    ) -> Result<usize> {
+        let table = self.load_table(table).await?;
+        let optimize_type = if zorder_by.is_empty() {
+            OptimizeType::Compact
+        } else {
+            OptimizeType::ZOrder(zorder_by)
+        };
+
+        let (_, metrics) =
+            OptimizeBuilder::new(table.delta.log_store(), table.delta.state.unwrap())
+                .with_type(optimize_type)
+                .await?;
+
+        Ok(metrics.num_files_removed as usize)
+    }
 }
 
 #[derive(Debug)]
@@ -381,7 +412,9 @@ impl TableProvider for NativeTable {
     /// but for now we just do a projection
     fn schema(&self) -> Arc<ArrowSchema> {
         let mut fields = vec![];
-        let arrow_schema = self.delta.snapshot().unwrap().arrow_schema().unwrap();
+        // Use the schema the table was defined with. The scan schema moves
+        // partition columns to the end.
+        let arrow_schema = ArrowSchema::try_from(self.delta.snapshot().unwrap().schema()).unwrap();
 
         for col in arrow_schema.fields() {
             let mut field = col.clone();
@@ -433,9 +466,24 @@ impl TableProvider for NativeTable {
             let schema = self.schema();
             Ok(Arc::new(EmptyExec::new(schema)))
         } else {
-            let plan = self.delta.scan(session, projection, filters, limit).await?;
-            let output_schema = plan.schema();
             let mut schema = self.schema();
+
+            // Map the projection onto the scan schema so that partition
+            // columns are returned in table order.
+            let scan_schema = self.delta.snapshot()?.arrow_schema()?;
+            let scan_projection = match projection {
+                Some(projection) => projection.clone(),
+                None => (0..schema.fields().len()).collect(),
+            }
+            .into_iter()
+            .map(|idx| scan_schema.index_of(schema.field(idx).name()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+            let plan = self
+                .delta
+                .scan(session, Some(&scan_projection), filters, limit)
+                .await?;
+            let output_schema = plan.schema();
             if let Some(projection) = projection {
                 schema = Arc::new(schema.project(projection)?);
             }
@@ -496,6 +544,7 @@ impl TableProvider for NativeTable {
 #[cfg(test)]
 mod tests {
     use datafusion::arrow::datatypes::DataType;
+    use datafusion::datasource::TableProvider;
     use deltalake::protocol::SaveMode;
     use object_store_util::conf::StorageConfig;
     use protogen::metastore::types::catalog::{EntryMeta, EntryType, SourceAccessMode, TableEntry};
@@ -536,6 +585,8 @@ mod tests {
                     nullable: true,
                     arrow_type: DataType::Int32,
                 }],
+                partition_by: Vec::new(),
+                view_sql: None,
             }
             .into(),
             tunnel_id: None,
@@ -558,4 +609,65 @@ mod tests {
             .unwrap_err();
         assert_eq!(err, "Error loading table");
     }
+
+    #[tokio::test]
+    async fn test_create_partitioned_table() {
+        let db_id = Uuid::new_v4();
+        let dir = tempdir().unwrap();
+        let conf = StorageConfig::Local {
+            path: dir.path().to_path_buf(),
+        };
+
+        let storage = NativeTableStorage::new(
+            db_id,
+            Url::from_file_path(dir.path()).unwrap(),
+            conf.new_object_store().unwrap(),
+        );
+
+        let entry = TableEntry {
+            meta: EntryMeta {
+                entry_type: EntryType::Table,
+                id: 12345,
+                parent: 54321,
+                name: "table_1".to_string(),
+                builtin: false,
+                external: false,
+                is_temp: false,
+            },
+            options: TableOptionsInternal {
+                columns: vec![
+                    InternalColumnDefinition {
+                        name: "region".to_string(),
+                        nullable: true,
+                        arrow_type: DataType::Utf8,
+                    },
+                    InternalColumnDefinition {
+                        name: "id".to_string(),
+                        nullable: true,
+                        arrow_type: DataType::Int32,
+                    },
+                ],
+                partition_by: vec!["region".to_string()],
+                view_sql: None,
+            }
+            .into(),
+            tunnel_id: None,
+            access_mode: SourceAccessMode::ReadOnly,
+            columns: None,
+        };
+
+        storage
+            .create_table(&entry, SaveMode::ErrorIfExists)
+            .await
+            .unwrap();
+
+        let table = storage.load_table(&entry).await.unwrap();
+        let partition_columns = &table.delta.metadata().unwrap().partition_columns;
+        assert_eq!(&vec!["region".to_string()], partition_columns);
+
+        // Partition columns should stay in the order they were defined in.
+        let schema = TableProvider::schema(&table);
+        let names: Vec<_> = schema.fields().iter().map(|f| f.name().as_str()).collect();
+        assert_eq!(vec!["region", "id"], names);
+    }
 }
@@ -8,7 +8,7 @@ use datafusion::arrow::datatypes::SchemaRef;
 use datafusion::common::FileType;
 use datafusion::datasource::file_format::FileFormat;
 use datafusion::datasource::listing::PartitionedFile;
-use datafusion::datasource::physical_plan::FileScanConfig;
+use datafusion::datasource::physical_plan::{FileScanConfig, ParquetExec};
 use datafusion::datasource::{get_statistics_with_limit, TableProvider};
 use datafusion::error::{DataFusionError, Result as DatafusionResult};
 use datafusion::execution::context::SessionState;
@@ -21,7 +21,7 @@ use datafusion::physical_plan::{project_schema, ExecutionPlan};
 use datafusion::prelude::Expr;
 use datafusion_ext::metrics::ReadOnlyDataSourceMetricsExecAdapter;
 use errors::{ObjectStoreSourceError, Result};
-use futures::StreamExt;
+use futures::{StreamExt, TryStreamExt};
 use glob::{MatchOptions, Pattern};
 use object_store::path::Path as ObjectStorePath;
 use object_store::{ObjectMeta, ObjectStore};
@@ -29,7 +29,9 @@ use protogen::metastore::types::options::{TableOptionsObjectStore, TableOptionsV
 
 use self::azure::AzureStoreAccess;
 use self::glob_util::{get_resolved_patterns, ResolvedPattern};
+use self::pruning::PagePruningReaderFactory;
 use crate::common::exprs_to_phys_exprs;
+use crate::common::schema::union_schemas_by_name;
 use crate::common::url::DatasourceUrl;
 use crate::lake::storage_options_into_store_access;
 use crate::object_store::gcs::GcsStoreAccess;
@@ -42,6 +44,7 @@ pub mod gcs;
 pub mod glob_util;
 pub mod http;
 pub mod local;
+pub mod pruning;
 pub mod s3;
 
 pub struct MultiSourceTableProvider {
@@ -258,6 +261,56 @@ impl ObjStoreAccessor {
         }))
     }
 
+    /// Infers a schema for the objects by inferring the schema of each object
+    /// individually, and merging the results by column name.
+    ///
+    /// Unlike `into_table_provider`, this allows objects to have columns in a
+    /// different order, be missing columns, or have differing (but
+    /// compatible) column types.
+    pub async fn infer_schema_by_name(
+        &self,
+        state: &SessionState,
+        file_format: &dyn FileFormat,
+        objects: &[ObjectMeta],
+    ) -> Result<SchemaRef> {
+        let schemas = futures::stream::iter(objects)
+            .map(|object| {
+                self.access.infer_schema(
+                    &self.store,
+                    state,
+                    file_format,
+                    std::slice::from_ref(object),
+                )
+            })
+            .buffered(state.config_options().execution.meta_fetch_concurrency)
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        let schema = union_schemas_by_name(schemas.iter().map(|schema| schema.as_ref()))?;
+        Ok(Arc::new(schema))
+    }
+
+    /// Takes all the objects and creates the table provider using the
+    /// provided schema.
+    ///
+    /// The schema should be compatible with the schemas of all objects, see
+    /// `infer_schema_by_name`.
+    pub fn into_table_provider_with_schema(
+        self,
+        file_format: Arc<dyn FileFormat>,
+        arrow_schema: SchemaRef,
+        objects: Vec<ObjectMeta>,
+    ) -> Result<Arc<dyn TableProvider>> {
+        let base_url = self.access.base_url()?;
+        Ok(Arc::new(ObjStoreTableProvider {
+            store: self.store,
+            arrow_schema,
+            base_url,
+            objects,
+            file_format,
+        }))
+    }
+
     /// Take the accessor and return the underlying object store.
     pub fn into_object_store(self) -> Arc<dyn ObjectStore> {
         self.store
@@ -356,6 +409,7 @@ impl TableProvider for ObjStoreTableProvider {
             .create_physical_plan(ctx, config, filters.as_ref())
             .await
             .map_err(|e| DataFusionError::External(Box::new(e)))?;
+        let plan = with_parquet_pruning(plan, self.store.clone(), filters.is_some());
         Ok(Arc::new(ReadOnlyDataSourceMetricsExecAdapter::new(plan)))
     }
 
@@ -372,6 +426,34 @@ impl TableProvider for ObjStoreTableProvider {
     }
 }
 
+/// Enable page index and bloom filter pruning if the plan is a parquet scan.
+///
+/// Row group statistics are always used for pruning, but page indexes and
+/// bloom filters are only read if explicitly enabled. Both are cheap to check
+/// relative to reading the data they let us skip, and make selective lookups
+/// (equality and IN predicates) on large files practical.
+///
+/// Pruned row groups and pages are reported through the metrics adapter
+/// wrapping the plan. Page indexes are only loaded if the scan has filters.
+fn with_parquet_pruning(
+    plan: Arc<dyn ExecutionPlan>,
+    store: Arc<dyn ObjectStore>,
+    has_filters: bool,
+) -> Arc<dyn ExecutionPlan> {
+    match plan.as_any().downcast_ref::<ParquetExec>() {
+        Some(exec) => Arc::new(
+            exec.clone()
+                .with_enable_page_index(true)
+                .with_enable_bloom_filter(true)
+                .with_parquet_file_reader_factory(Arc::new(PagePruningReaderFactory::new(
+                    store,
+                    has_filters,
+                ))),
+        ),
+        None => plan,
+    }
+}
+
 pub fn file_type_from_path(path: &ObjectStorePath) -> Result<FileType> {
     path.extension()
         .ok_or(ObjectStoreSourceError::NoFileExtension)?
@@ -446,7 +528,9 @@ pub fn init_session_registry<'a>(
             | TableOptionsV0::Clickhouse(_)
             | TableOptionsV0::Cassandra(_)
             | TableOptionsV0::Excel(_)
-            | TableOptionsV0::Sqlite(_) => continue,
+            | TableOptionsV0::Sqlite(_)
+            | TableOptionsV0::Oracle(_)
+            | TableOptionsV0::DuckDb(_) => continue,
         };
 
         let base_url = access.base_url()?;
@@ -0,0 +1,140 @@
+//! Parquet reader counting pages skipped using page indexes.
+//!
+//! Datafusion only reports the number of rows skipped using page indexes, so
+//! we wrap its reader and count pages that were never fetched instead.
+
+use std::ops::Range;
+use std::sync::Arc;
+
+use bytes::Bytes;
+use datafusion::datasource::physical_plan::parquet::{
+    DefaultParquetFileReaderFactory,
+    ParquetFileReaderFactory,
+};
+use datafusion::datasource::physical_plan::FileMeta;
+use datafusion::error::Result as DatafusionResult;
+use datafusion::parquet::arrow::async_reader::{AsyncFileReader, MetadataLoader};
+use datafusion::parquet::errors::Result as ParquetResult;
+use datafusion::parquet::file::metadata::ParquetMetaData;
+use datafusion::physical_plan::metrics::{Count, ExecutionPlanMetricsSet, MetricBuilder};
+use datafusion_ext::metrics::PARQUET_PAGES_PRUNED_METRIC;
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use object_store::ObjectStore;
+
+/// Creates readers recording the number of pages skipped for each file.
+#[derive(Debug)]
+pub struct PagePruningReaderFactory {
+    inner: DefaultParquetFileReaderFactory,
+    /// Load page indexes along with the file metadata.
+    ///
+    /// Only needed if the scan has filters that could be used for pruning.
+    load_page_index: bool,
+}
+
+impl PagePruningReaderFactory {
+    pub fn new(store: Arc<dyn ObjectStore>, load_page_index: bool) -> Self {
+        PagePruningReaderFactory {
+            inner: DefaultParquetFileReaderFactory::new(store),
+            load_page_index,
+        }
+    }
+}
+
+impl ParquetFileReaderFactory for PagePruningReaderFactory {
+    fn create_reader(
+        &self,
+        partition_index: usize,
+        file_meta: FileMeta,
+        metadata_size_hint: Option<usize>,
+        metrics: &ExecutionPlanMetricsSet,
+    ) -> DatafusionResult<Box<dyn AsyncFileReader + Send>> {
+        let pages_pruned = MetricBuilder::new(metrics)
+            .with_new_label("filename", file_meta.location().to_string())
+            .counter(PARQUET_PAGES_PRUNED_METRIC, partition_index);
+        let inner: Box<dyn AsyncFileReader> =
+            self.inner
+                .create_reader(partition_index, file_meta, metadata_size_hint, metrics)?;
+
+        Ok(Box::new(PagePruningReader {
+            inner,
+            load_page_index: self.load_page_index,
+            metadata: None,
+            pages_pruned,
+        }))
+    }
+}
+
+struct PagePruningReader {
+    inner: Box<dyn AsyncFileReader>,
+    load_page_index: bool,
+    /// Metadata including the offset index, set once loaded.
+    metadata: Option<Arc<ParquetMetaData>>,
+    pages_pruned: Count,
+}
+
+impl PagePruningReader {
+    /// Count the pages skipped for the column chunks touched by a fetch.
+    ///
+    /// When datafusion is able to create a row selection from the page index,
+    /// only the selected pages of a column chunk are fetched. Column chunks
+    /// are fetched at most once per row group, so any page of a fetched chunk
+    /// that isn't part of the fetch was pruned.
+    fn record_fetch(&self, ranges: &[Range<usize>]) {
+        let offset_index = match self.metadata.as_ref().and_then(|m| m.offset_index()) {
+            Some(index) => index,
+            None => return,
+        };
+
+        let mut pruned = 0;
+        for pages in offset_index.iter().flatten() {
+            let fetched = pages
+                .iter()
+                .filter(|page| {
+                    let start = page.offset as usize;
+                    let end = start + page.compressed_page_size as usize;
+                    ranges.iter().any(|r| r.start <= start && end <= r.end)
+                })
+                .count();
+            if fetched > 0 {
+                pruned += pages.len() - fetched;
+            }
+        }
+
+        self.pages_pruned.add(pruned);
+    }
+}
+
+impl AsyncFileReader for PagePruningReader {
+    fn get_bytes(&mut self, range: Range<usize>) -> BoxFuture<'_, ParquetResult<Bytes>> {
+        self.record_fetch(std::slice::from_ref(&range));
+        self.inner.get_bytes(range)
+    }
+
+    fn get_byte_ranges(
+        &mut self,
+        ranges: Vec<Range<usize>>,
+    ) -> BoxFuture<'_, ParquetResult<Vec<Bytes>>> {
+        self.record_fetch(&ranges);
+        self.inner.get_byte_ranges(ranges)
+    }
+
+    fn get_metadata(&mut self) -> BoxFuture<'_, ParquetResult<Arc<ParquetMetaData>>> {
+        async move {
+            let mut metadata = self.inner.get_metadata().await?;
+
+            // The arrow reader doesn't load page indexes if the metadata
+            // already contains them, so this doesn't result in extra reads.
+            if self.load_page_index && metadata.offset_index().is_none() {
+                let m = Arc::try_unwrap(metadata).unwrap_or_else(|m| m.as_ref().clone());
+                let mut loader = MetadataLoader::new(&mut self.inner, m);
+                loader.load_page_index(true, true).await?;
+                metadata = Arc::new(loader.finish());
+            }
+
+            self.metadata = Some(metadata.clone());
+            Ok(metadata)
+        }
+        .boxed()
+    }
+}
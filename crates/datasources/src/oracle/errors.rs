@@ -0,0 +1,31 @@
+#[derive(Debug, thiserror::Error)]
+pub enum OracleError {
+    #[error("Unsupported Oracle type: {0}, column: {1}")]
+    UnsupportedOracleType(String, String),
+
+    #[error("Unable to convert oracle value for column {0}: {1}, datatype: {2}")]
+    UnsupportedArrowType(usize, String, datafusion::arrow::datatypes::DataType),
+
+    #[error("Invalid Oracle connection string: {0}")]
+    InvalidConnectionString(String),
+
+    #[error(transparent)]
+    Oracle(#[from] oracle::Error),
+
+    #[error(transparent)]
+    Arrow(#[from] datafusion::arrow::error::ArrowError),
+
+    #[error(transparent)]
+    Fmt(#[from] std::fmt::Error),
+
+    #[error(transparent)]
+    UrlParse(#[from] url::ParseError),
+
+    #[error(transparent)]
+    Join(#[from] tokio::task::JoinError),
+
+    #[error(transparent)]
+    Common(#[from] crate::common::errors::DatasourceCommonError),
+}
+
+pub type Result<T, E = OracleError> = std::result::Result<T, E>;
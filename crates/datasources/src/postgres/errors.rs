@@ -27,6 +27,12 @@ pub enum PostgresError {
     #[error("Unsupported tunnel '{0}' for Postgres")]
     UnsupportedTunnel(String),
 
+    #[error("Number of partitions must be greater than zero")]
+    InvalidNumPartitions,
+
+    #[error("Invalid partition column '{column}': {reason}")]
+    InvalidPartitionColumn { column: String, reason: String },
+
     #[error("Overflow converting '{0}' to {1}")]
     DataOverflow(String, datafusion::arrow::datatypes::DataType),
 
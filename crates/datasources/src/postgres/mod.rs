@@ -1,12 +1,14 @@
 pub mod errors;
 
+mod partition;
 mod query_exec;
 
 use std::any::Any;
 use std::borrow::{Borrow, Cow};
 use std::fmt::{self, Write};
+use std::ops::Deref;
 use std::pin::Pin;
-use std::sync::Arc;
+use std::sync::{Arc, Weak};
 use std::task::{Context, Poll};
 
 use async_trait::async_trait;
@@ -61,12 +63,15 @@ use errors::{PostgresError, Result};
 use futures::future::BoxFuture;
 use futures::stream::BoxStream;
 use futures::{ready, FutureExt, Stream, StreamExt};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
 use protogen::metastore::types::options::TunnelOptions;
 use protogen::{FromOptionalField, ProtoConvError};
 use rustls::ClientConfig;
 use serde::{Deserialize, Serialize};
 use tokio::io::{AsyncRead, AsyncWrite};
 use tokio::net::TcpStream;
+use tokio::sync::OnceCell;
 use tokio::task::JoinHandle;
 use tokio_postgres::binary_copy::{BinaryCopyOutRow, BinaryCopyOutStream};
 use tokio_postgres::config::{Host, SslMode};
@@ -75,10 +80,14 @@ use tokio_postgres::types::{FromSql, Type as PostgresType};
 use tokio_postgres::{Client, Config, Connection, CopyOutStream, NoTls, Socket};
 use tracing::{debug, warn};
 
+use self::partition::{range_predicates, split_range, PartitionColumnType};
 use self::query_exec::PostgresInsertExec;
+use crate::common::dml::{sql_delete_statement, sql_update_statement, ExternalDml};
+use crate::common::pool::{ConnectionPool, PoolableConnection, PooledConnection};
+use crate::common::query::{quote_table_reference, SqlQueryPushdown};
 use crate::common::ssh::key::SshKey;
 use crate::common::ssh::session::{SshTunnelAccess, SshTunnelSession};
-use crate::common::util::{self, create_count_record_batch};
+use crate::common::util::{self, create_count_record_batch, quote_ident};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum PostgresDbConnection {
@@ -160,7 +169,11 @@ impl PostgresAccess {
     /// Validates access to a single table.
     pub async fn validate_table_access(&self, schema: &str, table: &str) -> Result<()> {
         let state = self.connect().await?;
-        let query = format!("SELECT * FROM {}.{} where false", schema, table);
+        let query = format!(
+            "SELECT * FROM {}.{} where false",
+            quote_ident(util::Datasource::Postgres, schema),
+            quote_ident(util::Datasource::Postgres, table),
+        );
         state.client.execute(query.as_str(), &[]).await?;
         Ok(())
     }
@@ -187,10 +200,14 @@ impl From<PostgresAccess> for protogen::sqlexec::common::PostgresAccess {
     }
 }
 
+/// Pool of connections shared by all postgres data sources.
+static POSTGRES_POOL: Lazy<Arc<ConnectionPool<PostgresConnection>>> =
+    Lazy::new(|| Arc::new(ConnectionPool::new("postgres")));
+
+/// An open connection to a postgres instance.
 #[derive(Debug)]
-pub struct PostgresAccessState {
-    /// The Postgres client.
-    client: tokio_postgres::Client,
+struct PostgresConnection {
+    client: Client,
     /// Handle for the underlying Postgres connection.
     /// Also contains the `Session` for the underlying ssh tunnel
     ///
@@ -199,15 +216,75 @@ pub struct PostgresAccessState {
     conn_handle: JoinHandle<()>,
 }
 
+impl Deref for PostgresConnection {
+    type Target = Client;
+
+    fn deref(&self) -> &Self::Target {
+        &self.client
+    }
+}
+
+#[async_trait]
+impl PoolableConnection for PostgresConnection {
+    async fn is_healthy(&mut self) -> bool {
+        !self.client.is_closed() && self.client.simple_query("SELECT 1").await.is_ok()
+    }
+}
+
+/// Describe the connection for display, leaving out credentials.
+fn describe_connection(connection_string: &str, tunnel: Option<&TunnelOptions>) -> String {
+    let desc = match connection_string.parse::<Config>() {
+        Ok(config) => {
+            let host = config
+                .get_hosts()
+                .iter()
+                .find_map(|host| match host {
+                    Host::Tcp(host) => Some(host.clone()),
+                    _ => None,
+                })
+                .unwrap_or_default();
+            let port = config.get_ports().first().copied().unwrap_or(5432);
+            let database = config.get_dbname().unwrap_or_default();
+            format!("{host}:{port}/{database}")
+        }
+        Err(_) => "<unknown>".to_string(),
+    };
+
+    match tunnel {
+        Some(tunnel) => format!("{desc} (via {tunnel} tunnel)"),
+        None => desc,
+    }
+}
+
+#[derive(Debug)]
+pub struct PostgresAccessState {
+    /// The Postgres client, checked out from the connection pool.
+    client: PooledConnection<PostgresConnection>,
+}
+
 impl PostgresAccessState {
-    /// Connect to a postgres instance.
+    /// Connect to a postgres instance, reusing a pooled connection if
+    /// possible.
     async fn connect(connection_string: &str, tunnel: Option<TunnelOptions>) -> Result<Self> {
-        let (client, conn_handle) = Self::connect_internal(connection_string, tunnel).await?;
+        let key = format!("{connection_string}|{tunnel:?}");
+        let description = describe_connection(connection_string, tunnel.as_ref());
+
+        let client = POSTGRES_POOL
+            .get(
+                &key,
+                || description,
+                || async move {
+                    let (client, conn_handle) =
+                        Self::connect_internal(connection_string, tunnel).await?;
+                    Ok::<_, PostgresError>(PostgresConnection {
+                        client,
+                        conn_handle,
+                    })
+                },
+            )
+            .await?;
 
-        Ok(PostgresAccessState {
-            client,
-            conn_handle,
-        })
+        Ok(PostgresAccessState { client })
     }
 
     async fn connect_internal(
@@ -221,6 +298,8 @@ impl PostgresAccessState {
                 let tunnel_access = SshTunnelAccess {
                     connection_string: ssh_options.connection_string,
                     keypair,
+                    jump_hosts: ssh_options.jump_hosts,
+                    known_hosts: ssh_options.known_hosts,
                 };
                 Self::connect_with_ssh_tunnel(connection_string, &tunnel_access).await
             }
@@ -468,10 +547,6 @@ WHERE nspname=$1 AND relname=$2;
         };
         let oid: u32 = row.try_get(0)?;
 
-        // TODO: Get approx pages to allow us to calculate number of pages to
-        // scan per thread once we do parallel scanning.
-        // let approx_pages: i64 = row.try_get(1)?;
-
         // Get table schema.
         let rows = self
             .client
@@ -519,6 +594,49 @@ ORDER BY attnum;
         let arrow_schema = try_create_arrow_schema(names, &pg_types)?;
         Ok((arrow_schema, pg_types))
     }
+
+    /// Get the approximate number of pages for a table.
+    ///
+    /// This relies on table statistics and so may be out of date.
+    async fn get_approx_table_pages(&self, schema: &str, name: &str) -> Result<i64> {
+        let row = self
+            .client
+            .query_one(
+                "
+SELECT
+    GREATEST(relpages, 1)::int8
+FROM pg_class INNER JOIN pg_namespace ON relnamespace = pg_namespace.oid
+WHERE nspname=$1 AND relname=$2;
+",
+                &[&schema, &name],
+            )
+            .await?;
+        Ok(row.try_get(0)?)
+    }
+
+    /// Get the min and max values of a column as i64s.
+    ///
+    /// Returns `None` if the table contains no non-null values for the column.
+    async fn get_column_bounds(
+        &self,
+        schema: &str,
+        name: &str,
+        column: &str,
+        typ: PartitionColumnType,
+    ) -> Result<Option<(i64, i64)>> {
+        let column = quote_ident(util::Datasource::Postgres, column);
+        let query = format!(
+            "SELECT {}, {} FROM {}.{}",
+            typ.bound_select_expr("MIN", &column),
+            typ.bound_select_expr("MAX", &column),
+            quote_ident(util::Datasource::Postgres, schema),
+            quote_ident(util::Datasource::Postgres, name),
+        );
+        let row = self.client.query_one(&query, &[]).await?;
+        let min: Option<i64> = row.try_get(0)?;
+        let max: Option<i64> = row.try_get(1)?;
+        Ok(min.zip(max))
+    }
 }
 
 #[async_trait]
@@ -587,6 +705,12 @@ pub struct PostgresTableProviderConfig {
     pub access: PostgresAccess,
     pub schema: String,
     pub table: String,
+    /// Column to partition scans on. Scans are partitioned by ctid ranges if
+    /// not provided.
+    pub partition_column: Option<String>,
+    /// Number of partitions to split scans into. Defaults to a single
+    /// partition.
+    pub num_partitions: Option<usize>,
 }
 
 impl TryFrom<protogen::sqlexec::table_provider::PostgresTableProviderConfig>
@@ -600,6 +724,8 @@ impl TryFrom<protogen::sqlexec::table_provider::PostgresTableProviderConfig>
             access: value.access.required("postgres access")?,
             schema: value.schema,
             table: value.table,
+            partition_column: value.partition_column,
+            num_partitions: value.num_partitions.map(|n| n as usize),
         })
     }
 }
@@ -612,6 +738,8 @@ impl From<PostgresTableProviderConfig>
             access: Some(value.access.into()),
             schema: value.schema,
             table: value.table,
+            partition_column: value.partition_column,
+            num_partitions: value.num_partitions.map(|n| n as u64),
         }
     }
 }
@@ -622,34 +750,103 @@ pub struct PostgresTableProvider {
     schema: String,
     /// Table we're accessing.
     table: String,
-    state: Arc<PostgresAccessState>,
+    /// Access used for checking out connections when needed, and for
+    /// checking if other tables are in the same database.
+    ///
+    /// Connections aren't held by the provider, scans and statements check
+    /// out their own connection for as long as they run.
+    access: Arc<PostgresAccess>,
     arrow_schema: ArrowSchemaRef,
     pg_types: Arc<Vec<PostgresType>>,
+    /// Column (and its type) to partition scans on.
+    partition_column: Option<(String, PartitionColumnType)>,
+    num_partitions: usize,
 }
 
 impl PostgresTableProvider {
     /// Try to create a new postgres table provider.
-    ///
-    /// If postgres access state (client) isn't provided, a new one will be
-    /// created.
     pub async fn try_new(conf: PostgresTableProviderConfig) -> Result<Self> {
         let PostgresTableProviderConfig {
             access,
             schema,
             table,
+            partition_column,
+            num_partitions,
         } = conf;
 
-        let state = Arc::new(access.connect().await?);
+        let num_partitions = num_partitions.unwrap_or(1);
+        if num_partitions == 0 {
+            return Err(PostgresError::InvalidNumPartitions);
+        }
+
+        let state = access.connect().await?;
         let (arrow_schema, pg_types) = state.get_table_schema(&schema, &table).await?;
+        drop(state);
+
+        let partition_column = match partition_column {
+            Some(column) => {
+                let idx = arrow_schema.index_of(&column).map_err(|_| {
+                    PostgresError::InvalidPartitionColumn {
+                        column: column.clone(),
+                        reason: format!("column does not exist in '{schema}.{table}'"),
+                    }
+                })?;
+                let typ = PartitionColumnType::try_from_pg_type(&column, &pg_types[idx])?;
+                Some((column, typ))
+            }
+            None => None,
+        };
 
         Ok(PostgresTableProvider {
             schema,
             table,
-            state,
+            access: Arc::new(access),
             arrow_schema: Arc::new(arrow_schema),
             pg_types: Arc::new(pg_types),
+            partition_column,
+            num_partitions,
         })
     }
+
+    /// Get the predicates for splitting a scan into partitions.
+    ///
+    /// Returns an empty vec if the scan shouldn't be split.
+    async fn partition_predicates(&self) -> Result<Vec<String>> {
+        if self.num_partitions <= 1 {
+            return Ok(Vec::new());
+        }
+
+        let state = self.access.connect().await?;
+        match &self.partition_column {
+            Some((column, typ)) => {
+                let bounds = state
+                    .get_column_bounds(&self.schema, &self.table, column, *typ)
+                    .await?;
+                let bounds = match bounds {
+                    Some((min, max)) => split_range(min, max, self.num_partitions),
+                    None => return Ok(Vec::new()),
+                };
+                Ok(range_predicates(
+                    &quote_ident(util::Datasource::Postgres, column),
+                    &bounds,
+                    |bound| typ.literal(bound),
+                    true,
+                ))
+            }
+            None => {
+                let pages = state
+                    .get_approx_table_pages(&self.schema, &self.table)
+                    .await?;
+                let bounds = split_range(0, pages - 1, self.num_partitions);
+                Ok(range_predicates(
+                    "ctid",
+                    &bounds,
+                    |page| format!("'({page},0)'::tid"),
+                    false,
+                ))
+            }
+        }
+    }
 }
 
 #[async_trait]
@@ -705,7 +902,7 @@ impl TableProvider for PostgresTableProvider {
             projected_schema
                 .fields
                 .iter()
-                .map(|f| f.name().clone())
+                .map(|f| quote_ident(util::Datasource::Postgres, f.name()))
                 .collect::<Vec<_>>()
                 .join(",")
         };
@@ -724,25 +921,48 @@ impl TableProvider for PostgresTableProvider {
                 .map_err(|e| DataFusionError::External(Box::new(e)))?
         };
 
-        // Build copy query.
-        let query = format!(
-            "COPY (SELECT {} FROM {}.{} {} {} {}) TO STDOUT (FORMAT binary)",
-            projection_string, // SELECT <str>
-            self.schema,       // FROM <schema>
-            self.table,        // .<table>
-            // [WHERE]
-            if predicate_string.is_empty() {
-                ""
-            } else {
-                "WHERE "
-            },
-            predicate_string.as_str(), // <where-predicate>
-            limit_string,              // [LIMIT ..]
-        );
+        // Split the scan into partitions. Limits are applied per query, so
+        // only split if there isn't one.
+        let partition_predicates = if limit.is_none() {
+            self.partition_predicates()
+                .await
+                .map_err(|e| DataFusionError::External(Box::new(e)))?
+        } else {
+            Vec::new()
+        };
+
+        let copy_query = |predicate: &str| {
+            format!(
+                "COPY (SELECT {} FROM {}.{} {} {} {}) TO STDOUT (FORMAT binary)",
+                projection_string, // SELECT <str>
+                quote_ident(util::Datasource::Postgres, &self.schema), // FROM <schema>
+                quote_ident(util::Datasource::Postgres, &self.table), // .<table>
+                // [WHERE]
+                if predicate.is_empty() { "" } else { "WHERE " },
+                predicate,    // <where-predicate>
+                limit_string, // [LIMIT ..]
+            )
+        };
+
+        // Build copy queries, one per partition.
+        let copy_queries = if partition_predicates.is_empty() {
+            vec![copy_query(&predicate_string)]
+        } else {
+            partition_predicates
+                .iter()
+                .map(|partition_predicate| {
+                    if predicate_string.is_empty() {
+                        copy_query(partition_predicate)
+                    } else {
+                        copy_query(&format!("({predicate_string}) AND ({partition_predicate})"))
+                    }
+                })
+                .collect()
+        };
 
         let exec = PostgresBinaryCopyExec::try_new(BinaryCopyConfig::State {
-            copy_query: query,
-            state: self.state.clone(),
+            copy_queries,
+            access: self.access.clone(),
             pg_types: projected_types,
             arrow_schema: projected_schema,
         })
@@ -791,12 +1011,103 @@ impl TableProvider for PostgresTableProvider {
 
         let query = format!(
             "INSERT INTO {}.{} VALUES {}",
-            self.schema, self.table, values
+            quote_ident(util::Datasource::Postgres, &self.schema),
+            quote_ident(util::Datasource::Postgres, &self.table),
+            values
         );
 
         debug!(%query, "inserting into postgres datasource");
 
-        let exec = PostgresInsertExec::new(query, self.state.clone());
+        let exec = PostgresInsertExec::new(query, self.access.clone());
+        Ok(Arc::new(exec))
+    }
+}
+
+#[async_trait]
+impl ExternalDml for PostgresTableProvider {
+    async fn update(
+        &self,
+        updates: &[(String, Expr)],
+        where_expr: Option<&Expr>,
+    ) -> DatafusionResult<u64> {
+        let stmt = sql_update_statement(self, &self.arrow_schema, updates, where_expr)?;
+        self.execute_dml(stmt).await
+    }
+
+    async fn delete(&self, where_expr: Option<&Expr>) -> DatafusionResult<u64> {
+        let stmt = sql_delete_statement(self, &self.arrow_schema, where_expr)?;
+        self.execute_dml(stmt).await
+    }
+}
+
+impl PostgresTableProvider {
+    async fn execute_dml(&self, stmt: String) -> DatafusionResult<u64> {
+        debug!(%stmt, "modifying rows in postgres datasource");
+        let state = self
+            .access
+            .connect()
+            .await
+            .map_err(|e| DataFusionError::External(Box::new(e)))?;
+        state
+            .client
+            .execute(stmt.as_str(), &[])
+            .await
+            .map_err(|e| DataFusionError::External(Box::new(e)))
+    }
+}
+
+#[async_trait]
+impl SqlQueryPushdown for PostgresTableProvider {
+    fn dialect(&self) -> util::Datasource {
+        util::Datasource::Postgres
+    }
+
+    fn table_reference(&self) -> String {
+        quote_table_reference(self.dialect(), Some(&self.schema), &self.table)
+    }
+
+    fn cast_type(&self, datatype: &DataType) -> Option<String> {
+        // Binary copy requires knowing the exact output types, so everything
+        // needs to map back to a postgres type.
+        try_arrow_to_postgres_type(datatype).map(|typ| typ.name().to_string())
+    }
+
+    fn shares_connection(&self, other: &dyn TableProvider) -> bool {
+        match other.as_any().downcast_ref::<PostgresTableProvider>() {
+            Some(other) => {
+                self.access.conn_str.connection_string()
+                    == other.access.conn_str.connection_string()
+                    && self.access.tunnel == other.access.tunnel
+            }
+            None => false,
+        }
+    }
+
+    async fn query_exec(
+        &self,
+        query: String,
+        schema: ArrowSchemaRef,
+    ) -> DatafusionResult<Arc<dyn ExecutionPlan>> {
+        let pg_types = schema
+            .fields()
+            .iter()
+            .map(|f| try_arrow_to_postgres_type(f.data_type()))
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| {
+                DataFusionError::Internal(format!(
+                    "unable to map schema to postgres types: {schema}"
+                ))
+            })?;
+
+        let exec = PostgresBinaryCopyExec::try_new(BinaryCopyConfig::State {
+            copy_queries: vec![format!("COPY ({query}) TO STDOUT (FORMAT binary)")],
+            access: self.access.clone(),
+            pg_types: Arc::new(pg_types),
+            arrow_schema: schema,
+        })
+        .await
+        .map_err(|e| DataFusionError::External(Box::new(e)))?;
+
         Ok(Arc::new(exec))
     }
 }
@@ -808,12 +1119,12 @@ pub enum BinaryCopyConfig {
         access: Box<PostgresAccess>,
         schema: String,
         table: String,
-        copy_query: String,
+        copy_queries: Vec<String>,
     },
     /// Not serializable config.
     State {
-        copy_query: String,
-        state: Arc<PostgresAccessState>,
+        copy_queries: Vec<String>,
+        access: Arc<PostgresAccess>,
         pg_types: Arc<Vec<PostgresType>>,
         arrow_schema: ArrowSchemaRef,
     },
@@ -828,7 +1139,7 @@ impl TryFrom<protogen::sqlexec::physical_plan::PostgresBinaryCopyConfig> for Bin
             access: Box::new(value.access.required("postgres access")?),
             schema: value.schema,
             table: value.table,
-            copy_query: value.copy_query,
+            copy_queries: value.copy_queries,
         })
     }
 }
@@ -844,22 +1155,30 @@ impl TryFrom<BinaryCopyConfig> for protogen::sqlexec::physical_plan::PostgresBin
                 access,
                 schema,
                 table,
-                copy_query,
+                copy_queries,
             } => Ok(Self {
                 access: Some((*access).into()),
                 schema,
                 table,
-                copy_query,
+                copy_queries,
             }),
         }
     }
 }
 
 /// Copy data from the source Postgres table using the binary copy protocol.
+///
+/// Each partition runs its own copy query on a connection checked out for the
+/// duration of the stream. When split into multiple partitions, all partitions
+/// read from the same snapshot (see [`ExportedSnapshot`]).
 pub struct PostgresBinaryCopyExec {
     pg_types: Arc<Vec<PostgresType>>,
     arrow_schema: ArrowSchemaRef,
-    opener: StreamOpener,
+    /// Copy queries, one per partition.
+    copy_queries: Vec<String>,
+    access: Arc<PostgresAccess>,
+    /// Snapshot shared by the partitions currently being executed.
+    snapshot: Mutex<Weak<SharedSnapshot>>,
     metrics: ExecutionPlanMetricsSet,
 }
 
@@ -871,31 +1190,45 @@ impl PostgresBinaryCopyExec {
                 access,
                 schema,
                 table,
-                copy_query,
+                copy_queries,
             } => {
-                let state = Arc::new(access.connect().await?);
+                let state = access.connect().await?;
                 let (arrow_schema, pg_types) = state.get_table_schema(&schema, &table).await?;
-                let opener = StreamOpener { copy_query, state };
                 Ok(PostgresBinaryCopyExec {
                     pg_types: Arc::new(pg_types),
                     arrow_schema: Arc::new(arrow_schema),
-                    opener,
+                    copy_queries,
+                    access: Arc::new(*access),
+                    snapshot: Mutex::new(Weak::new()),
                     metrics: ExecutionPlanMetricsSet::new(),
                 })
             }
             BinaryCopyConfig::State {
-                copy_query,
-                state,
+                copy_queries,
+                access,
                 pg_types,
                 arrow_schema,
-            } => {
-                let opener = StreamOpener { copy_query, state };
-                Ok(PostgresBinaryCopyExec {
-                    pg_types,
-                    arrow_schema,
-                    opener,
-                    metrics: ExecutionPlanMetricsSet::new(),
-                })
+            } => Ok(PostgresBinaryCopyExec {
+                pg_types,
+                arrow_schema,
+                copy_queries,
+                access,
+                snapshot: Mutex::new(Weak::new()),
+                metrics: ExecutionPlanMetricsSet::new(),
+            }),
+        }
+    }
+
+    /// Get the snapshot to share between partitions, creating a new one if no
+    /// partitions are currently being executed.
+    fn shared_snapshot(&self) -> Arc<SharedSnapshot> {
+        let mut snapshot = self.snapshot.lock();
+        match snapshot.upgrade() {
+            Some(shared) => shared,
+            None => {
+                let shared = Arc::new(SharedSnapshot::new());
+                *snapshot = Arc::downgrade(&shared);
+                shared
             }
         }
     }
@@ -911,7 +1244,7 @@ impl ExecutionPlan for PostgresBinaryCopyExec {
     }
 
     fn output_partitioning(&self) -> Partitioning {
-        Partitioning::UnknownPartitioning(1)
+        Partitioning::UnknownPartitioning(self.copy_queries.len())
     }
 
     fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
@@ -940,10 +1273,28 @@ impl ExecutionPlan for PostgresBinaryCopyExec {
         partition: usize,
         _context: Arc<TaskContext>,
     ) -> DatafusionResult<SendableRecordBatchStream> {
+        let copy_query = self.copy_queries.get(partition).ok_or_else(|| {
+            DataFusionError::Execution(format!(
+                "invalid partition {partition} for PostgresBinaryCopyExec with {} partitions",
+                self.copy_queries.len()
+            ))
+        })?;
+
+        let opener = StreamOpener {
+            copy_query: copy_query.clone(),
+            access: self.access.clone(),
+            partition,
+            snapshot: if self.copy_queries.len() > 1 {
+                Some(self.shared_snapshot())
+            } else {
+                None
+            },
+        };
+
         let stream = ChunkStream {
             state: StreamState::Idle,
             types: self.pg_types.clone(),
-            opener: self.opener.clone(),
+            opener,
             arrow_schema: self.arrow_schema.clone(),
         };
         Ok(Box::pin(DataSourceMetricsStreamAdapter::new(
@@ -973,24 +1324,154 @@ impl fmt::Debug for PostgresBinaryCopyExec {
         f.debug_struct("PostgresBinaryCopyExec")
             .field("pg_types", &self.pg_types)
             .field("arrow_schema", &self.arrow_schema)
+            .field("copy_queries", &self.copy_queries)
             .finish()
     }
 }
 
+/// Snapshot shared by all partitions of a scan, exported once the first
+/// partition is opened.
+type SharedSnapshot = OnceCell<Arc<ExportedSnapshot>>;
+
+/// A snapshot exported for scans split into multiple partitions.
+///
+/// Every partition imports the snapshot so that all partitions see a
+/// consistent view of the table, even with concurrent writes.
+struct ExportedSnapshot {
+    /// Transaction that exported the snapshot. The snapshot can only be
+    /// imported while this transaction is open.
+    txn: SnapshotTransaction,
+    snapshot_id: String,
+}
+
+impl ExportedSnapshot {
+    async fn export(access: &PostgresAccess) -> Result<Self> {
+        let txn = SnapshotTransaction::begin(access).await?;
+        let row = txn
+            .state
+            .client
+            .query_one("SELECT pg_export_snapshot()", &[])
+            .await?;
+        let snapshot_id = row.try_get(0)?;
+        Ok(ExportedSnapshot { txn, snapshot_id })
+    }
+
+    /// Begin a transaction on a new connection using this snapshot.
+    async fn import(&self, access: &PostgresAccess) -> Result<SnapshotTransaction> {
+        let txn = SnapshotTransaction::begin(access).await?;
+        txn.state
+            .client
+            .batch_execute(&format!(
+                "SET TRANSACTION SNAPSHOT '{}'",
+                self.snapshot_id.replace('\'', "''")
+            ))
+            .await?;
+        Ok(txn)
+    }
+}
+
+/// A read only transaction that's rolled back once dropped.
+struct SnapshotTransaction {
+    state: Arc<PostgresAccessState>,
+}
+
+impl SnapshotTransaction {
+    async fn begin(access: &PostgresAccess) -> Result<Self> {
+        let state = Arc::new(access.connect().await?);
+        // The connection is only returned to the pool once the transaction
+        // is rolled back.
+        state.client.begin_operation();
+        state
+            .client
+            .batch_execute("BEGIN ISOLATION LEVEL REPEATABLE READ READ ONLY")
+            .await?;
+        Ok(SnapshotTransaction { state })
+    }
+}
+
+impl Drop for SnapshotTransaction {
+    fn drop(&mut self) {
+        // Make sure the transaction is closed before the connection is
+        // returned to the pool. The connection is closed instead if we can't
+        // roll back.
+        let state = self.state.clone();
+        if let Ok(handle) = tokio::runtime::Handle::try_current() {
+            handle.spawn(async move {
+                match state.client.batch_execute("ROLLBACK").await {
+                    Ok(_) => state.client.end_operation(),
+                    Err(e) => warn!(%e, "failed to roll back postgres snapshot transaction"),
+                }
+            });
+        }
+    }
+}
+
+/// Connection a copy is being read from.
+enum ScanConnection {
+    /// A connection checked out for this partition only.
+    Connection(PostgresAccessState),
+    /// The connection that exported the snapshot shared by all partitions.
+    ExportedSnapshot(Arc<ExportedSnapshot>),
+    /// A connection that imported the shared snapshot.
+    ImportedSnapshot(SnapshotTransaction),
+}
+
+impl ScanConnection {
+    fn state(&self) -> &PostgresAccessState {
+        match self {
+            Self::Connection(state) => state,
+            Self::ExportedSnapshot(snapshot) => &snapshot.txn.state,
+            Self::ImportedSnapshot(txn) => &txn.state,
+        }
+    }
+}
+
 /// Open a copy stream.
-#[derive(Clone)]
 struct StreamOpener {
     /// Query used to initiate the binary copy.
     copy_query: String,
-    state: Arc<PostgresAccessState>,
+    /// Access for checking out connections once the stream is opened.
+    access: Arc<PostgresAccess>,
+    partition: usize,
+    /// Snapshot shared with the other partitions, if the scan is partitioned.
+    snapshot: Option<Arc<SharedSnapshot>>,
 }
 
 impl StreamOpener {
-    /// Build a future that returns the copy stream.
-    fn open(&self) -> BoxFuture<'static, Result<CopyOutStream, tokio_postgres::Error>> {
+    /// Build a future that returns the copy stream along with the connection
+    /// it's being read from.
+    fn open(&self) -> BoxFuture<'static, Result<(CopyOutStream, ScanConnection)>> {
         let query = self.copy_query.clone();
-        let accessor = self.state.clone();
-        Box::pin(async move { accessor.client.copy_out(&query).await })
+        let access = self.access.clone();
+        let partition = self.partition;
+        let snapshot = self.snapshot.clone();
+        Box::pin(async move {
+            let conn = match snapshot {
+                None => ScanConnection::Connection(access.connect().await?),
+                Some(snapshot) => {
+                    let exported = snapshot
+                        .get_or_try_init(|| async {
+                            ExportedSnapshot::export(&access).await.map(Arc::new)
+                        })
+                        .await?
+                        .clone();
+                    // The first partition reads using the exporting
+                    // transaction, others import the snapshot.
+                    if partition == 0 {
+                        ScanConnection::ExportedSnapshot(exported)
+                    } else {
+                        ScanConnection::ImportedSnapshot(exported.import(&access).await?)
+                    }
+                }
+            };
+            // Rows of an unfinished copy would still be sent on the
+            // connection, so it can't be reused if the stream is dropped
+            // early.
+            let client = &conn.state().client;
+            client.begin_operation();
+            let stream = client.copy_out(&query).await?;
+            Ok((stream, conn))
+        })
     }
 }
 
@@ -1008,11 +1489,14 @@ enum StreamState {
     Idle,
     /// Open the copy stream.
     Open {
-        fut: BoxFuture<'static, Result<CopyOutStream, tokio_postgres::Error>>,
+        fut: BoxFuture<'static, Result<(CopyOutStream, ScanConnection)>>,
     },
     /// Binary copy scan ongoing.
     Scan {
         stream: BoxStream<'static, Vec<Result<BinaryCopyOutRow, tokio_postgres::Error>>>,
+        /// Connection the copy is being read from. Kept around to ensure the
+        /// connection (and snapshot) stays open for the duration of the scan.
+        conn: ScanConnection,
     },
     /// Scan finished.
     Done,
@@ -1042,7 +1526,7 @@ impl Stream for ChunkStream {
                     self.state = StreamState::Open { fut };
                 }
                 StreamState::Open { fut } => match ready!(fut.poll_unpin(cx)) {
-                    Ok(stream) => {
+                    Ok((stream, conn)) => {
                         // Get the binary stream from postgres.
                         let stream = BinaryCopyOutStream::new(stream, &self.types);
                         // Chunk the rows. We'll be returning a single record
@@ -1050,6 +1534,7 @@ impl Stream for ChunkStream {
                         let chunked = stream.chunks(1000); // TODO: Make configurable.
                         self.state = StreamState::Scan {
                             stream: chunked.boxed(),
+                            conn,
                         };
                     }
                     Err(e) => {
@@ -1057,7 +1542,7 @@ impl Stream for ChunkStream {
                         return Poll::Ready(Some(Err(DataFusionError::External(Box::new(e)))));
                     }
                 },
-                StreamState::Scan { stream } => match ready!(stream.poll_next_unpin(cx)) {
+                StreamState::Scan { stream, conn } => match ready!(stream.poll_next_unpin(cx)) {
                     Some(rows) => {
                         match binary_rows_to_record_batch(rows, self.arrow_schema.clone()) {
                             Ok(batch) => {
@@ -1072,6 +1557,7 @@ impl Stream for ChunkStream {
                         }
                     }
                     None => {
+                        conn.state().client.end_operation();
                         self.state = StreamState::Done;
                     }
                 },
@@ -1276,6 +1762,26 @@ fn binary_rows_to_record_batch<E: Into<PostgresError>>(
     Ok(batch)
 }
 
+/// Get the postgres type that's read as the given arrow type.
+fn try_arrow_to_postgres_type(datatype: &DataType) -> Option<PostgresType> {
+    Some(match datatype {
+        DataType::Boolean => PostgresType::BOOL,
+        DataType::Int16 => PostgresType::INT2,
+        DataType::Int32 => PostgresType::INT4,
+        DataType::Int64 => PostgresType::INT8,
+        DataType::Float32 => PostgresType::FLOAT4,
+        DataType::Float64 => PostgresType::FLOAT8,
+        DataType::Utf8 => PostgresType::TEXT,
+        DataType::Binary => PostgresType::BYTEA,
+        DataType::Decimal128(_, _) => PostgresType::NUMERIC,
+        DataType::Timestamp(TimeUnit::Microsecond, None) => PostgresType::TIMESTAMP,
+        DataType::Timestamp(TimeUnit::Microsecond, Some(_)) => PostgresType::TIMESTAMPTZ,
+        DataType::Time64(TimeUnit::Microsecond) => PostgresType::TIME,
+        DataType::Date32 => PostgresType::DATE,
+        _ => return None,
+    })
+}
+
 /// Create an arrow schema from a list of names and stringified postgres types.
 fn try_create_arrow_schema(names: Vec<String>, types: &Vec<PostgresType>) -> Result<ArrowSchema> {
     let mut fields = Vec::with_capacity(names.len());
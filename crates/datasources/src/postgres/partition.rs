@@ -0,0 +1,165 @@
+//! Helpers for splitting a Postgres table scan into multiple partitions.
+//!
+//! Partitions are described by predicates that get appended to the scan's
+//! `WHERE` clause. Every row of the table matches exactly one of the
+//! predicates.
+
+use tokio_postgres::types::Type as PostgresType;
+
+use super::errors::{PostgresError, Result};
+
+/// Type of column a scan can be partitioned on.
+///
+/// Values are converted to i64 to compute partition bounds. Dates and
+/// timestamps are converted to microseconds since the epoch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PartitionColumnType {
+    Integer,
+    Date,
+    Timestamp,
+    TimestampTz,
+}
+
+impl PartitionColumnType {
+    pub fn try_from_pg_type(column: &str, typ: &PostgresType) -> Result<Self> {
+        Ok(match typ {
+            &PostgresType::INT2 | &PostgresType::INT4 | &PostgresType::INT8 => Self::Integer,
+            &PostgresType::DATE => Self::Date,
+            &PostgresType::TIMESTAMP => Self::Timestamp,
+            &PostgresType::TIMESTAMPTZ => Self::TimestampTz,
+            other => {
+                return Err(PostgresError::InvalidPartitionColumn {
+                    column: column.to_string(),
+                    reason: format!(
+                        "unsupported type '{other}', expected an integer, date or timestamp column"
+                    ),
+                })
+            }
+        })
+    }
+
+    /// Expression for getting the min or max value of the column as an i64.
+    pub fn bound_select_expr(&self, agg: &str, column: &str) -> String {
+        match self {
+            Self::Integer => format!("{agg}({column})::int8"),
+            Self::Date | Self::Timestamp => {
+                format!("(EXTRACT(EPOCH FROM {agg}({column})::timestamp) * 1000000)::int8")
+            }
+            Self::TimestampTz => {
+                format!("(EXTRACT(EPOCH FROM {agg}({column})) * 1000000)::int8")
+            }
+        }
+    }
+
+    /// Convert a bound back into a literal comparable against the column.
+    pub fn literal(&self, bound: i64) -> String {
+        match self {
+            Self::Integer => bound.to_string(),
+            Self::Date | Self::Timestamp => {
+                format!("(TIMESTAMP 'epoch' + {bound} * INTERVAL '1 microsecond')")
+            }
+            Self::TimestampTz => {
+                format!("(TIMESTAMPTZ 'epoch' + {bound} * INTERVAL '1 microsecond')")
+            }
+        }
+    }
+}
+
+/// Split the inclusive range `[min, max]` into at most `n` contiguous
+/// ranges, returning the boundaries between them.
+///
+/// Fewer boundaries are returned if the range is too small to be split `n`
+/// ways.
+pub fn split_range(min: i64, max: i64, n: usize) -> Vec<i64> {
+    if n <= 1 || max <= min {
+        return Vec::new();
+    }
+
+    let width = max as i128 - min as i128 + 1;
+    let n = n as i128;
+    let stride = (width + n - 1) / n;
+
+    (1..n)
+        .map(|i| min as i128 + i * stride)
+        .take_while(|bound| *bound <= max as i128)
+        .map(|bound| bound as i64)
+        .collect()
+}
+
+/// Create range predicates on `column` from the given boundaries.
+///
+/// The first and last ranges are left open so that values outside of the
+/// boundaries used during planning (e.g. from concurrent inserts) are still
+/// scanned. If `include_nulls` is set, nulls are included in the first range.
+///
+/// Returns an empty vec if there's nothing to split on.
+pub fn range_predicates(
+    column: &str,
+    bounds: &[i64],
+    literal: impl Fn(i64) -> String,
+    include_nulls: bool,
+) -> Vec<String> {
+    if bounds.is_empty() {
+        return Vec::new();
+    }
+
+    let mut preds = Vec::with_capacity(bounds.len() + 1);
+
+    let first = format!("{column} < {}", literal(bounds[0]));
+    preds.push(if include_nulls {
+        format!("({first} OR {column} IS NULL)")
+    } else {
+        first
+    });
+
+    for window in bounds.windows(2) {
+        preds.push(format!(
+            "{column} >= {} AND {column} < {}",
+            literal(window[0]),
+            literal(window[1])
+        ));
+    }
+
+    preds.push(format!("{column} >= {}", literal(bounds[bounds.len() - 1])));
+
+    preds
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split() {
+        assert_eq!(Vec::<i64>::new(), split_range(0, 100, 1));
+        assert_eq!(Vec::<i64>::new(), split_range(5, 5, 4));
+        assert_eq!(vec![25, 50, 75], split_range(0, 99, 4));
+        assert_eq!(vec![4, 7, 10], split_range(1, 10, 4));
+        assert_eq!(vec![1, 2], split_range(0, 2, 8));
+        assert_eq!(vec![0], split_range(i64::MIN, i64::MAX, 2));
+    }
+
+    #[test]
+    fn predicates() {
+        let preds = range_predicates("a", &[10, 20], |b| b.to_string(), true);
+        assert_eq!(
+            vec![
+                "(a < 10 OR a IS NULL)".to_string(),
+                "a >= 10 AND a < 20".to_string(),
+                "a >= 20".to_string(),
+            ],
+            preds
+        );
+
+        let preds = range_predicates("ctid", &[8], |b| format!("'({b},0)'::tid"), false);
+        assert_eq!(
+            vec![
+                "ctid < '(8,0)'::tid".to_string(),
+                "ctid >= '(8,0)'::tid".to_string(),
+            ],
+            preds
+        );
+
+        assert!(range_predicates("a", &[], |b| b.to_string(), true).is_empty());
+    }
+}
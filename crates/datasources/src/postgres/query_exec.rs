@@ -23,21 +23,22 @@ use datafusion_ext::metrics::DataSourceMetricsStreamAdapter;
 use futures::future::BoxFuture;
 use futures::{ready, FutureExt, Stream};
 
-use super::PostgresAccessState;
+use super::errors::PostgresError;
+use super::PostgresAccess;
 use crate::common::util::{create_count_record_batch, COUNT_SCHEMA};
 
 #[derive(Debug)]
 pub struct PostgresInsertExec {
     query: String,
-    state: Arc<PostgresAccessState>,
+    access: Arc<PostgresAccess>,
     metrics: ExecutionPlanMetricsSet,
 }
 
 impl PostgresInsertExec {
-    pub fn new(query: String, state: Arc<PostgresAccessState>) -> Self {
+    pub fn new(query: String, access: Arc<PostgresAccess>) -> Self {
         PostgresInsertExec {
             query,
-            state,
+            access,
             metrics: ExecutionPlanMetricsSet::new(),
         }
     }
@@ -86,7 +87,7 @@ impl ExecutionPlan for PostgresInsertExec {
             state: QueryExecState::Idle,
             opener: QueryOpener {
                 query: self.query.clone(),
-                state: self.state.clone(),
+                access: self.access.clone(),
             },
         };
         Ok(Box::pin(DataSourceMetricsStreamAdapter::new(
@@ -114,20 +115,23 @@ impl DisplayAs for PostgresInsertExec {
 #[derive(Clone)]
 struct QueryOpener {
     query: String,
-    state: Arc<PostgresAccessState>,
+    access: Arc<PostgresAccess>,
 }
 
 impl QueryOpener {
-    fn open(&self) -> BoxFuture<'static, Result<u64, tokio_postgres::Error>> {
+    fn open(&self) -> BoxFuture<'static, Result<u64, PostgresError>> {
         let this = self.clone();
-        Box::pin(async move { this.state.client.execute(&this.query, &[]).await })
+        Box::pin(async move {
+            let state = this.access.connect().await?;
+            Ok(state.client.execute(&this.query, &[]).await?)
+        })
     }
 }
 
 enum QueryExecState {
     Idle,
     Open {
-        fut: BoxFuture<'static, Result<u64, tokio_postgres::Error>>,
+        fut: BoxFuture<'static, Result<u64, PostgresError>>,
     },
     Done,
     Error,
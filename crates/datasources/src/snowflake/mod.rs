@@ -7,17 +7,20 @@ use std::sync::{Arc, Mutex};
 use std::task::{Context, Poll};
 
 use async_trait::async_trait;
+use datafusion::arrow::compute::cast;
 use datafusion::arrow::datatypes::{
+    DataType,
     Field,
     Fields,
     Schema as ArrowSchema,
     SchemaRef as ArrowSchemaRef,
 };
-use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::arrow::record_batch::{RecordBatch, RecordBatchOptions};
 use datafusion::datasource::TableProvider;
 use datafusion::error::{DataFusionError, Result as DatafusionResult};
 use datafusion::execution::context::{SessionState, TaskContext};
-use datafusion::logical_expr::{Expr, TableProviderFilterPushDown, TableType};
+use datafusion::logical_expr::expr::{Between, InList, Like};
+use datafusion::logical_expr::{Expr, Operator, TableProviderFilterPushDown, TableType};
 use datafusion::physical_expr::PhysicalSortExpr;
 use datafusion::physical_plan::metrics::{ExecutionPlanMetricsSet, MetricsSet};
 use datafusion::physical_plan::{
@@ -34,15 +37,16 @@ use datafusion_ext::functions::VirtualLister;
 use datafusion_ext::metrics::DataSourceMetricsStreamAdapter;
 use errors::Result;
 use futures::{Stream, StreamExt};
+use protogen::metastore::types::options::CredentialsOptionsSnowflake;
 use snowflake_connector::datatype::SnowflakeDataType;
 use snowflake_connector::{
     snowflake_to_arrow_datatype,
     Connection as SnowflakeConnection,
     QueryBindParameter,
     QueryResult,
-    QueryResultChunkMeta,
 };
 
+use crate::common::query::SqlQueryPushdown;
 use crate::common::util;
 
 #[derive(Debug, Clone)]
@@ -53,6 +57,8 @@ pub struct SnowflakeDbConnection {
     pub database_name: String,
     pub warehouse: String,
     pub role_name: Option<String>,
+    /// Key pair or OAuth credentials, used instead of the password if set.
+    pub credentials: Option<CredentialsOptionsSnowflake>,
 }
 
 #[derive(Debug, Clone)]
@@ -74,10 +80,17 @@ impl SnowflakeAccessor {
     async fn build_conn(conn_params: SnowflakeDbConnection) -> Result<SnowflakeConnection> {
         let mut conn =
             SnowflakeConnection::builder(conn_params.account_name, conn_params.login_name)
-                .password(conn_params.password)
                 .database_name(conn_params.database_name)
                 .warehouse(conn_params.warehouse);
 
+        conn = match conn_params.credentials {
+            Some(CredentialsOptionsSnowflake::KeyPair { private_key }) => {
+                conn.private_key(private_key)
+            }
+            Some(CredentialsOptionsSnowflake::OAuth { token }) => conn.oauth_token(token),
+            None => conn.password(conn_params.password),
+        };
+
         if let Some(role_name) = conn_params.role_name {
             conn = conn.role_name(role_name);
         }
@@ -91,7 +104,7 @@ impl SnowflakeAccessor {
 
         // Validate if the connection is Ok
         let query = "SELECT 1".to_string();
-        accessor.conn.exec_sync(query, Vec::new()).await?;
+        accessor.conn.exec(query, Vec::new()).await?;
 
         Ok(())
     }
@@ -107,7 +120,7 @@ impl SnowflakeAccessor {
             "SELECT * FROM {}.{} WHERE FALSE",
             table_access.schema_name, table_access.table_name
         );
-        let _res = accessor.conn.query_sync(query, vec![]).await?;
+        let _res = accessor.conn.query(query, vec![]).await?;
 
         // Get table schema
         accessor
@@ -124,7 +137,7 @@ impl SnowflakeAccessor {
 
         let res = self
             .conn
-            .query_sync(
+            .query(
                 "
 SELECT
     column_name,
@@ -207,7 +220,7 @@ impl VirtualLister for SnowflakeAccessor {
 
         let res = self
             .conn
-            .query_sync(
+            .query(
                 "SELECT schema_name FROM information_schema.schemata".to_string(),
                 Vec::new(),
             )
@@ -250,7 +263,7 @@ impl VirtualLister for SnowflakeAccessor {
 
         let res = self
             .conn
-            .query_sync(query, bindings)
+            .query(query, bindings)
             .await
             .map_err(|e| ListingErrBoxed(Box::new(e)))?;
 
@@ -338,8 +351,11 @@ impl TableProvider for SnowflakeTableProvider {
             None => Arc::clone(&self.arrow_schema),
         };
 
+        // Only the number of rows matters if nothing is projected, avoid
+        // pulling any columns. The selected constant is dropped when reading
+        // the results.
         let projection_string = if projected_schema.fields().is_empty() {
-            "*".to_string()
+            "1".to_string()
         } else {
             projected_schema
                 .fields
@@ -378,27 +394,87 @@ impl TableProvider for SnowflakeTableProvider {
         let result = self
             .accessor
             .conn
-            .query_sync(query, Vec::new())
+            .query(query, Vec::new())
             .await
             .map_err(|e| DataFusionError::External(Box::new(e)))?;
 
-        let num_partitions = result.num_chunks();
-
         Ok(Arc::new(SnowflakeExec {
             predicate: predicate_string,
             arrow_schema: projected_schema,
-            num_partitions,
-            result: Mutex::new(result),
+            result: Mutex::new(Some(result)),
+            metrics: ExecutionPlanMetricsSet::new(),
+        }))
+    }
+}
+
+#[async_trait]
+impl SqlQueryPushdown for SnowflakeTableProvider {
+    fn dialect(&self) -> util::Datasource {
+        util::Datasource::Snowflake
+    }
+
+    fn table_reference(&self) -> String {
+        // Left unquoted. Quoting would make the names case sensitive, while
+        // tables and columns are otherwise resolved case insensitively (see
+        // `get_table_schema`).
+        format!(
+            "{}.{}",
+            self.table_access.schema_name, self.table_access.table_name
+        )
+    }
+
+    fn column_reference(&self, column: &str) -> String {
+        column.to_string()
+    }
+
+    fn cast_type(&self, datatype: &DataType) -> Option<String> {
+        // Results are cast to the expected schema when read, these just need
+        // to be types that cast cleanly.
+        Some(match datatype {
+            DataType::Boolean => "BOOLEAN".to_string(),
+            DataType::Int8 | DataType::Int16 | DataType::Int32 | DataType::Int64 => {
+                "NUMBER(38,0)".to_string()
+            }
+            DataType::Float32 | DataType::Float64 => "DOUBLE".to_string(),
+            DataType::Decimal128(precision, scale) => format!("NUMBER({precision},{scale})"),
+            DataType::Utf8 => "VARCHAR".to_string(),
+            DataType::Date32 => "DATE".to_string(),
+            _ => return None,
+        })
+    }
+
+    async fn query_exec(
+        &self,
+        query: String,
+        schema: ArrowSchemaRef,
+    ) -> DatafusionResult<Arc<dyn ExecutionPlan>> {
+        let result = self
+            .accessor
+            .conn
+            .query(query, Vec::new())
+            .await
+            .map_err(|e| DataFusionError::External(Box::new(e)))?;
+
+        Ok(Arc::new(SnowflakeExec {
+            predicate: String::new(),
+            arrow_schema: schema,
+            result: Mutex::new(Some(result)),
             metrics: ExecutionPlanMetricsSet::new(),
         }))
     }
 }
 
+/// Number of result chunks to download ahead of the chunk currently being
+/// read.
+const CHUNK_PREFETCH: usize = 4;
+
+/// Reads the result of a query as a single partition, with the result chunks
+/// being downloaded concurrently.
 struct SnowflakeExec {
     predicate: String,
     arrow_schema: ArrowSchemaRef,
-    num_partitions: usize,
-    result: Mutex<QueryResult>,
+    /// Result of the query, taken when executed.
+    result: Mutex<Option<QueryResult>>,
     metrics: ExecutionPlanMetricsSet,
 }
 
@@ -412,7 +488,7 @@ impl ExecutionPlan for SnowflakeExec {
     }
 
     fn output_partitioning(&self) -> Partitioning {
-        Partitioning::UnknownPartitioning(self.num_partitions)
+        Partitioning::UnknownPartitioning(1)
     }
 
     fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
@@ -441,13 +517,15 @@ impl ExecutionPlan for SnowflakeExec {
         partition: usize,
         _ctx: Arc<TaskContext>,
     ) -> DatafusionResult<datafusion::physical_plan::SendableRecordBatchStream> {
-        let chunk = {
-            let mut guard = self.result.lock().unwrap();
-            guard.next().ok_or(DataFusionError::Execution(format!(
-                "missing chunk for partition: {partition}"
-            )))?
-        };
-        let stream = ChunkStream::new(self.schema(), chunk);
+        if partition != 0 {
+            return Err(DataFusionError::Execution(format!(
+                "invalid partition for Snowflake exec: {partition}"
+            )));
+        }
+        let result = self.result.lock().unwrap().take().ok_or_else(|| {
+            DataFusionError::Execution("Snowflake exec already executed".to_string())
+        })?;
+        let stream = ChunkStream::new(self.schema(), result);
         Ok(Box::pin(DataSourceMetricsStreamAdapter::new(
             stream,
             partition,
@@ -495,20 +573,25 @@ struct ChunkStream {
 }
 
 impl ChunkStream {
-    fn new(schema: ArrowSchemaRef, meta: QueryResultChunkMeta) -> Self {
+    fn new(schema: ArrowSchemaRef, result: QueryResult) -> Self {
+        let batch_schema = schema.clone();
         let stream = async_stream::stream! {
-            let chunk = match meta.take_chunk().await {
-                Ok(chunk) => chunk,
-                Err(e) => {
-                    yield Err(DataFusionError::Execution(format!("cannot retrieve chunk: {e}")));
-                    return;
-                },
-            };
-
-            for batch in chunk.into_iter() {
-                let batch = batch?;
-                let batch = util::normalize_batch(&batch)?;
-                yield Ok(batch);
+            let mut chunks = Box::pin(result.into_chunk_stream(CHUNK_PREFETCH));
+            while let Some(chunk) = chunks.next().await {
+                let chunk = match chunk {
+                    Ok(chunk) => chunk,
+                    Err(e) => {
+                        yield Err(DataFusionError::Execution(format!("cannot retrieve chunk: {e}")));
+                        return;
+                    },
+                };
+
+                for batch in chunk.into_iter() {
+                    let batch = batch?;
+                    let batch = util::normalize_batch(&batch)?;
+                    let batch = cast_batch_to_schema(batch, &batch_schema)?;
+                    yield Ok(batch);
+                }
             }
         };
         let inner = Box::pin(stream);
@@ -516,6 +599,42 @@ impl ChunkStream {
     }
 }
 
+/// Cast the batch's columns to the types of the expected schema.
+///
+/// Snowflake returns all integers as NUMBER which we may expect to read as
+/// some other type.
+fn cast_batch_to_schema(
+    batch: RecordBatch,
+    schema: &ArrowSchemaRef,
+) -> DatafusionResult<RecordBatch> {
+    if schema.fields().is_empty() {
+        let options = RecordBatchOptions::new().with_row_count(Some(batch.num_rows()));
+        return Ok(RecordBatch::try_new_with_options(
+            schema.clone(),
+            Vec::new(),
+            &options,
+        )?);
+    }
+
+    let matches = batch
+        .schema()
+        .fields()
+        .iter()
+        .zip(schema.fields())
+        .all(|(have, want)| have.data_type() == want.data_type());
+    if matches {
+        return Ok(batch);
+    }
+
+    let columns = batch
+        .columns()
+        .iter()
+        .zip(schema.fields())
+        .map(|(col, field)| cast(col, field.data_type()))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(RecordBatch::try_new(schema.clone(), columns)?)
+}
+
 impl Stream for ChunkStream {
     type Item = DatafusionResult<RecordBatch>;
 
@@ -530,15 +649,17 @@ impl RecordBatchStream for ChunkStream {
     }
 }
 
-/// Convert filtering expressions to a predicate string usable with Snowflake's
-/// row restriction.
+/// Convert filtering expressions to a predicate string usable in the WHERE
+/// clause of a Snowflake query.
+///
+/// Expressions that can't be converted are skipped, DataFusion will still
+/// apply them after reading.
 fn exprs_to_predicate_string(exprs: &[Expr]) -> Result<String> {
     let mut ss = Vec::new();
-    let mut buf = String::new();
     for expr in exprs {
+        let mut buf = String::new();
         if write_expr(expr, &mut buf)? {
             ss.push(buf);
-            buf = String::new();
         }
     }
 
@@ -546,50 +667,118 @@ fn exprs_to_predicate_string(exprs: &[Expr]) -> Result<String> {
 }
 
 /// Try to write the expression to the string, returning true if it was written.
+///
+/// The buffer may contain a partially written expression if this returns
+/// false.
 fn write_expr(expr: &Expr, buf: &mut String) -> Result<bool> {
     match expr {
         Expr::Column(col) => {
             write!(buf, "{}", col)?;
         }
         Expr::Literal(val) => {
-            util::encode_literal_to_text(util::Datasource::Snowflake, buf, val)?;
+            // Skip literals we can't encode instead of failing the scan.
+            if util::encode_literal_to_text(util::Datasource::Snowflake, buf, val).is_err() {
+                return Ok(false);
+            }
         }
-        Expr::IsNull(expr) => {
-            if write_expr(expr, buf)? {
-                write!(buf, " IS NULL")?;
-            } else {
+        Expr::IsNull(expr) => return write_postfix(expr, " IS NULL", buf),
+        Expr::IsNotNull(expr) => return write_postfix(expr, " IS NOT NULL", buf),
+        Expr::IsTrue(expr) => return write_postfix(expr, " = TRUE", buf),
+        Expr::IsFalse(expr) => return write_postfix(expr, " = FALSE", buf),
+        Expr::Not(expr) => {
+            write!(buf, "(NOT ")?;
+            if !write_expr(expr, buf)? {
                 return Ok(false);
             }
+            write!(buf, ")")?;
         }
-        Expr::IsNotNull(expr) => {
-            if write_expr(expr, buf)? {
-                write!(buf, " IS NOT NULL")?;
-            } else {
+        Expr::BinaryExpr(binary) => {
+            // Division is left out since Snowflake always produces decimals.
+            match binary.op {
+                Operator::Eq
+                | Operator::NotEq
+                | Operator::Lt
+                | Operator::LtEq
+                | Operator::Gt
+                | Operator::GtEq
+                | Operator::Plus
+                | Operator::Minus
+                | Operator::Multiply
+                | Operator::And
+                | Operator::Or => (),
+                _ => return Ok(false),
+            }
+            write!(buf, "(")?;
+            if !write_expr(binary.left.as_ref(), buf)? {
                 return Ok(false);
             }
+            write!(buf, " {} ", binary.op)?;
+            if !write_expr(binary.right.as_ref(), buf)? {
+                return Ok(false);
+            }
+            write!(buf, ")")?;
         }
-        Expr::IsTrue(expr) => {
-            if write_expr(expr, buf)? {
-                write!(buf, " = TRUE")?;
-            } else {
+        Expr::InList(InList {
+            expr,
+            list,
+            negated,
+        }) => {
+            write!(buf, "(")?;
+            if !write_expr(expr, buf)? {
                 return Ok(false);
             }
+            write!(buf, "{} IN (", if *negated { " NOT" } else { "" })?;
+            for (idx, item) in list.iter().enumerate() {
+                if idx > 0 {
+                    write!(buf, ", ")?;
+                }
+                if !write_expr(item, buf)? {
+                    return Ok(false);
+                }
+            }
+            write!(buf, "))")?;
         }
-        Expr::IsFalse(expr) => {
-            if write_expr(expr, buf)? {
-                write!(buf, " = FALSE")?;
-            } else {
+        Expr::Between(Between {
+            expr,
+            negated,
+            low,
+            high,
+        }) => {
+            write!(buf, "(")?;
+            if !write_expr(expr, buf)? {
+                return Ok(false);
+            }
+            write!(buf, "{} BETWEEN ", if *negated { " NOT" } else { "" })?;
+            if !write_expr(low, buf)? {
+                return Ok(false);
+            }
+            write!(buf, " AND ")?;
+            if !write_expr(high, buf)? {
                 return Ok(false);
             }
+            write!(buf, ")")?;
         }
-        Expr::BinaryExpr(binary) => {
-            if !write_expr(binary.left.as_ref(), buf)? {
+        Expr::Like(Like {
+            negated,
+            expr,
+            pattern,
+            escape_char: None,
+            case_insensitive,
+        }) => {
+            write!(buf, "(")?;
+            if !write_expr(expr, buf)? {
                 return Ok(false);
             }
-            write!(buf, " {} ", binary.op)?;
-            if !write_expr(binary.right.as_ref(), buf)? {
+            write!(
+                buf,
+                "{} {} ",
+                if *negated { " NOT" } else { "" },
+                if *case_insensitive { "ILIKE" } else { "LIKE" }
+            )?;
+            if !write_expr(pattern, buf)? {
                 return Ok(false);
             }
+            write!(buf, ")")?;
         }
         _ => {
             // Unsupported.
@@ -599,3 +788,13 @@ fn write_expr(expr: &Expr, buf: &mut String) -> Result<bool> {
 
     Ok(true)
 }
+
+/// Write `expr` followed by `postfix`, e.g. " IS NULL".
+fn write_postfix(expr: &Expr, postfix: &str, buf: &mut String) -> Result<bool> {
+    write!(buf, "(")?;
+    if !write_expr(expr, buf)? {
+        return Ok(false);
+    }
+    write!(buf, "{postfix})")?;
+    Ok(true)
+}
@@ -11,10 +11,12 @@ use std::sync::Arc;
 use async_sqlite::rusqlite::types::Value;
 use async_trait::async_trait;
 use datafusion::arrow::datatypes::{DataType, Field, Fields, Schema, SchemaRef, TimeUnit};
+use datafusion::common::tree_node::{TreeNode, VisitRecursion};
 use datafusion::datasource::TableProvider;
 use datafusion::error::DataFusionError;
 use datafusion::execution::context::SessionState;
 use datafusion::execution::TaskContext;
+use datafusion::logical_expr::expr_rewriter::unnormalize_col;
 use datafusion::logical_expr::{BinaryExpr, TableProviderFilterPushDown, TableType};
 use datafusion::physical_expr::PhysicalSortExpr;
 use datafusion::physical_plan::metrics::{ExecutionPlanMetricsSet, MetricsSet};
@@ -40,8 +42,10 @@ use uuid::Uuid;
 
 use self::errors::{Result, SqliteError};
 use self::wrapper::SqliteAsyncClient;
+use crate::common::dml::{sql_delete_statement, sql_update_statement, ExternalDml};
+use crate::common::query::{quote_table_reference, SqlQueryPushdown};
 use crate::common::url::DatasourceUrl;
-use crate::common::util::{self, COUNT_SCHEMA};
+use crate::common::util::{self, create_count_record_batch, COUNT_SCHEMA};
 use crate::lake::storage_options_into_store_access;
 use crate::object_store::ObjStoreAccessor;
 
@@ -386,22 +390,126 @@ impl TableProvider for SqliteTableProvider {
             return Err(DataFusionError::Execution("cannot overwrite".to_string()));
         }
 
+        self.check_writable()?;
+
+        Ok(Arc::new(SqliteInsertExec {
+            input,
+            table: self.table.to_string(),
+            state: self.state.clone(),
+            schema: self.schema.clone(),
+            metrics: ExecutionPlanMetricsSet::new(),
+        }))
+    }
+}
+
+impl SqliteTableProvider {
+    fn check_writable(&self) -> DataFusionResult<()> {
         if !self.state.is_local_file() {
             return Err(DataFusionError::Execution(
                 "cannot write remote file".to_string(),
             ));
         }
+        Ok(())
+    }
 
-        Ok(Arc::new(SqliteInsertExec {
-            input,
-            table: self.table.to_string(),
+    /// Check that an expression in an UPDATE or DELETE statement only
+    /// compares values of types that sqlite compares the same way we do.
+    fn check_dml_expr(&self, expr: &Expr) -> DataFusionResult<()> {
+        // Columns are qualified with the name of the table in our catalog
+        // which sqlite doesn't know about.
+        let expr = unnormalize_col(expr.clone());
+        let mut supported = true;
+        expr.apply(&mut |expr| {
+            if let Expr::BinaryExpr(binary) = expr {
+                if should_skip_binary_expr(binary, &self.schema)
+                    .map_err(|e| DataFusionError::External(Box::new(e)))?
+                {
+                    supported = false;
+                    return Ok(VisitRecursion::Stop);
+                }
+            }
+            Ok(VisitRecursion::Continue)
+        })?;
+        if !supported {
+            return Err(DataFusionError::Plan(format!(
+                "cannot execute expression in sqlite: {expr}"
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl SqlQueryPushdown for SqliteTableProvider {
+    fn dialect(&self) -> util::Datasource {
+        util::Datasource::Sqlite
+    }
+
+    fn table_reference(&self) -> String {
+        quote_table_reference(self.dialect(), None, &self.table)
+    }
+
+    fn cast_type(&self, datatype: &DataType) -> Option<String> {
+        Some(match datatype {
+            DataType::Int64 => "INTEGER".to_string(),
+            DataType::Float64 => "REAL".to_string(),
+            DataType::Utf8 => "TEXT".to_string(),
+            _ => return None,
+        })
+    }
+
+    async fn query_exec(
+        &self,
+        query: String,
+        schema: SchemaRef,
+    ) -> DataFusionResult<Arc<dyn ExecutionPlan>> {
+        Ok(Arc::new(SqliteQueryExec {
+            query,
             state: self.state.clone(),
-            schema: self.schema.clone(),
+            schema,
             metrics: ExecutionPlanMetricsSet::new(),
         }))
     }
 }
 
+#[async_trait]
+impl ExternalDml for SqliteTableProvider {
+    async fn update(
+        &self,
+        updates: &[(String, Expr)],
+        where_expr: Option<&Expr>,
+    ) -> DataFusionResult<u64> {
+        self.check_writable()?;
+        for (_, expr) in updates {
+            self.check_dml_expr(expr)?;
+        }
+        if let Some(expr) = where_expr {
+            self.check_dml_expr(expr)?;
+        }
+
+        let stmt = sql_update_statement(self, &self.schema, updates, where_expr)?;
+        self.state
+            .client
+            .execute(stmt)
+            .await
+            .map_err(|e| DataFusionError::External(Box::new(e)))
+    }
+
+    async fn delete(&self, where_expr: Option<&Expr>) -> DataFusionResult<u64> {
+        self.check_writable()?;
+        if let Some(expr) = where_expr {
+            self.check_dml_expr(expr)?;
+        }
+
+        let stmt = sql_delete_statement(self, &self.schema, where_expr)?;
+        self.state
+            .client
+            .execute(stmt)
+            .await
+            .map_err(|e| DataFusionError::External(Box::new(e)))
+    }
+}
+
 #[derive(Debug)]
 pub struct SqliteQueryExec {
     query: String,
@@ -535,6 +643,7 @@ fn write_expr(expr: &Expr, schema: &Schema, buf: &mut String) -> Result<bool> {
             if should_skip_binary_expr(binary, schema)? {
                 return Ok(false);
             }
+            buf.push('(');
             if !write_expr(binary.left.as_ref(), schema, buf)? {
                 return Ok(false);
             }
@@ -542,6 +651,7 @@ fn write_expr(expr: &Expr, schema: &Schema, buf: &mut String) -> Result<bool> {
             if !write_expr(binary.right.as_ref(), schema, buf)? {
                 return Ok(false);
             }
+            buf.push(')');
         }
         _ => {
             // Unsupported.
@@ -693,7 +803,15 @@ impl ExecutionPlan for SqliteInsertExec {
                             )?;
                         }
                         write!(&mut stmt, ";")?;
-                        Ok(client.query(COUNT_SCHEMA.clone(), stmt))
+
+                        let client = client.clone();
+                        Ok(futures::stream::once(async move {
+                            let count = client
+                                .execute(stmt)
+                                .await
+                                .map_err(|e| DataFusionError::External(Box::new(e)))?;
+                            Ok(create_count_record_batch(count))
+                        }))
                     })
                     .try_flatten(),
             )
@@ -3,9 +3,11 @@ use std::path::PathBuf;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
+use std::time::Duration;
 
 use async_sqlite::rusqlite;
 use async_sqlite::rusqlite::types::Value;
+use async_sqlite::rusqlite::OpenFlags;
 use datafusion::arrow::datatypes::SchemaRef;
 use datafusion::arrow::record_batch::RecordBatch;
 use datafusion::error::DataFusionError;
@@ -17,10 +19,23 @@ use tokio::sync::mpsc;
 use super::convert::Converter;
 use crate::sqlite::errors::Result;
 
+/// How long to wait for locks held by other connections before erroring.
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Number of read only connections to open for databases in WAL mode.
+const NUM_READERS: usize = 4;
+
 #[derive(Clone)]
 pub struct SqliteAsyncClient {
     path: PathBuf,
+    /// Connection used for writes, and for reads if the database isn't in WAL
+    /// mode.
     inner: async_sqlite::Client,
+    /// Read only connections, only opened if the database is in WAL mode.
+    ///
+    /// In WAL mode readers and the writer don't block each other, so long
+    /// running scans don't hold up writes.
+    readers: Option<async_sqlite::Pool>,
     // we're just tying the lifetime of the tempdir to this connection
     cache: Option<Arc<tempfile::TempDir>>,
 }
@@ -37,8 +52,59 @@ impl SqliteAsyncClient {
             .path(&path)
             .open()
             .await?;
+        inner.conn(|conn| conn.busy_timeout(BUSY_TIMEOUT)).await?;
+
+        let journal_mode: String = inner
+            .conn(|conn| conn.pragma_query_value(None, "journal_mode", |row| row.get(0)))
+            .await?;
+
+        let readers = if journal_mode.eq_ignore_ascii_case("wal") {
+            let pool = async_sqlite::PoolBuilder::new()
+                .path(&path)
+                .flags(
+                    OpenFlags::SQLITE_OPEN_READ_ONLY
+                        | OpenFlags::SQLITE_OPEN_NO_MUTEX
+                        | OpenFlags::SQLITE_OPEN_URI,
+                )
+                .num_conns(NUM_READERS)
+                .open()
+                .await?;
+            // The pool hands out its connections round robin, so this sets the
+            // timeout on each of them once.
+            for _ in 0..NUM_READERS {
+                pool.conn(|conn| conn.busy_timeout(BUSY_TIMEOUT)).await?;
+            }
+            Some(pool)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            path,
+            inner,
+            readers,
+            cache,
+        })
+    }
+
+    /// Run a function against a connection for reading.
+    async fn read<F, T>(&self, func: F) -> Result<T>
+    where
+        F: FnOnce(&rusqlite::Connection) -> Result<T, rusqlite::Error> + Send + 'static,
+        T: Send + 'static,
+    {
+        Ok(match &self.readers {
+            Some(readers) => readers.conn(func).await?,
+            None => self.inner.conn(func).await?,
+        })
+    }
 
-        Ok(Self { path, inner, cache })
+    /// Execute a statement that modifies the database, returning the number
+    /// of changed rows.
+    pub async fn execute(&self, s: impl Into<String>) -> Result<u64> {
+        let s = s.into();
+        let changed = self.inner.conn(move |conn| conn.execute(&s, [])).await?;
+        Ok(changed as u64)
     }
 
     /// Query and return a RecordBatchStream for sqlite data.
@@ -47,12 +113,12 @@ impl SqliteAsyncClient {
 
         let (tx, rx) = mpsc::channel(1);
 
-        let client = self.inner.clone();
+        let client = self.clone();
         let conv = Converter::new(schema.clone());
 
         let handle = Box::pin(async move {
             client
-                .conn(move |conn| {
+                .read(move |conn| {
                     let mut stmt = conn.prepare(&s)?;
                     let mut rows = stmt.query([])?;
                     loop {
@@ -89,39 +155,37 @@ impl SqliteAsyncClient {
     pub async fn query_all(&self, s: impl Into<String>) -> Result<SqliteBatch> {
         let s = s.into();
 
-        Ok(self
-            .inner
-            .conn(move |conn| {
-                let mut stmt = conn.prepare(&s)?;
-
-                let cols = stmt
-                    .column_names()
-                    .into_iter()
-                    .map(|c| Column {
-                        name: c.to_string(),
-                    })
-                    .collect::<Vec<_>>();
-
-                let num_cols = cols.len();
-
-                let data = stmt
-                    .query([])?
-                    .mapped(|r| {
-                        (0..num_cols)
-                            .map(|idx| {
-                                let v = r.get_ref(idx)?;
-                                Ok(Value::from(v))
-                            })
-                            .collect::<Result<Vec<_>, rusqlite::Error>>()
-                    })
-                    .collect::<Result<Vec<_>, rusqlite::Error>>()?;
-
-                Ok(SqliteBatch {
-                    cols: cols.clone(),
-                    data,
+        self.read(move |conn| {
+            let mut stmt = conn.prepare(&s)?;
+
+            let cols = stmt
+                .column_names()
+                .into_iter()
+                .map(|c| Column {
+                    name: c.to_string(),
+                })
+                .collect::<Vec<_>>();
+
+            let num_cols = cols.len();
+
+            let data = stmt
+                .query([])?
+                .mapped(|r| {
+                    (0..num_cols)
+                        .map(|idx| {
+                            let v = r.get_ref(idx)?;
+                            Ok(Value::from(v))
+                        })
+                        .collect::<Result<Vec<_>, rusqlite::Error>>()
                 })
+                .collect::<Result<Vec<_>, rusqlite::Error>>()?;
+
+            Ok(SqliteBatch {
+                cols: cols.clone(),
+                data,
             })
-            .await?)
+        })
+        .await
     }
 
     pub fn is_local_file(&self) -> bool {
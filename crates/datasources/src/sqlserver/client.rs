@@ -17,7 +17,7 @@ use std::task::{Context, Poll};
 
 use futures::{AsyncRead, AsyncWrite, Stream, StreamExt};
 use tiberius::{Column, QueryItem, ResultMetadata, Row};
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, oneshot};
 use tracing::debug;
 
 use super::errors::{Result, SqlServerError};
@@ -81,6 +81,16 @@ where
                         }
                     }
                 }
+                Request::Execute { query, response } => {
+                    let result = self
+                        .client
+                        .execute(query, &[])
+                        .await
+                        .map(|result| result.total())
+                        .map_err(|e| e.into());
+                    // Client may no longer be listening, that's fine.
+                    let _ = response.send(result);
+                }
                 Request::Drop => {
                     debug!("closing SQL Server connection");
                     self.client.close().await?;
@@ -126,6 +136,27 @@ impl Client {
             buffered_rows: VecDeque::new(),
         })
     }
+
+    /// Execute a statement, returning the total number of affected rows.
+    pub async fn execute<'a>(&self, query: impl Into<Cow<'a, str>>) -> Result<u64> {
+        let query = query.into().to_string();
+
+        let (sender, receiver) = oneshot::channel();
+        let req = Request::Execute {
+            query,
+            response: sender,
+        };
+
+        if self.sender.send(req).is_err() {
+            return Err(SqlServerError::String(
+                "connection to SQL Server closed".to_string(),
+            ));
+        }
+
+        receiver
+            .await
+            .map_err(|_| SqlServerError::String("connection to SQL Server closed".to_string()))?
+    }
 }
 
 impl Drop for Client {
@@ -141,6 +172,11 @@ enum Request {
         query: String,
         response: mpsc::Sender<Result<QueryItem>>,
     },
+    /// Execute a statement, sending the number of affected rows.
+    Execute {
+        query: String,
+        response: oneshot::Sender<Result<u64>>,
+    },
     /// Client was dropped, drop the connection.
     Drop,
 }
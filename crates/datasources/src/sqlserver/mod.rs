@@ -5,6 +5,7 @@ mod client;
 use std::any::Any;
 use std::collections::HashMap;
 use std::fmt::{self, Write};
+use std::ops::Deref;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
@@ -45,19 +46,36 @@ use errors::{Result, SqlServerError};
 use futures::future::BoxFuture;
 use futures::stream::BoxStream;
 use futures::{ready, FutureExt, Stream, StreamExt};
-use tiberius::FromSql;
+use once_cell::sync::Lazy;
+use protogen::metastore::types::options::TlsOptions;
+use tiberius::{EncryptionLevel, FromSql};
 use tokio::net::TcpStream;
 use tokio::task::JoinHandle;
 use tokio_util::compat::TokioAsyncWriteCompatExt;
-use tracing::warn;
+use tracing::{debug, warn};
 
+use crate::common::dml::{sql_delete_statement, sql_update_statement, ExternalDml};
+use crate::common::pool::{ConnectionPool, PoolableConnection, PooledConnection};
+use crate::common::query::{quote_table_reference, SqlQueryPushdown};
+use crate::common::tls::{ca_cert_path, client_cert_paths, SslMode};
 use crate::common::util;
 
 /// Timeout when attempting to connecting to the remote server.
 pub const CONNECTION_TIMEOUT: Duration = Duration::from_secs(5);
 
+/// Pool of connections shared by all sql server data sources.
+static SQL_SERVER_POOL: Lazy<Arc<ConnectionPool<SqlServerConnection>>> =
+    Lazy::new(|| Arc::new(ConnectionPool::new("sql_server")));
+
 /// Configuration needed for accessing a sql server instance.
+#[derive(Clone)]
 pub struct SqlServerAccess {
+    /// The connection string the config was created from. Used for keying
+    /// pooled connections.
+    conn_str: String,
+    /// TLS settings applied on top of the connection string. Also used for
+    /// keying pooled connections.
+    tls: TlsOptions,
     config: tiberius::Config,
 }
 
@@ -68,30 +86,83 @@ impl SqlServerAccess {
     /// Example: "server=tcp:localhost,1433;user=SA;password=<YourStrong@Passw0rd>;IntegratedSecurity=true;TrustServerCertificate=true"
     pub fn try_new_from_ado_string(conn_str: &str) -> Result<Self> {
         let config = tiberius::Config::from_ado_string(conn_str)?;
-        Ok(Self { config })
+        Ok(Self {
+            conn_str: conn_str.to_string(),
+            tls: TlsOptions::default(),
+            config,
+        })
+    }
+
+    /// Apply TLS settings, overriding the settings from the connection
+    /// string.
+    ///
+    /// SQL Server doesn't support authenticating with client certificates, so
+    /// providing one is an error.
+    pub fn with_tls(mut self, tls: TlsOptions) -> Result<Self> {
+        if client_cert_paths(&tls)?.is_some() {
+            return Err(SqlServerError::String(
+                "SQL Server does not support client certificates".to_string(),
+            ));
+        }
+
+        match SslMode::from_options(&tls)? {
+            Some(SslMode::Disable) => self.config.encryption(EncryptionLevel::NotSupported),
+            Some(SslMode::Prefer) => self.config.encryption(EncryptionLevel::On),
+            Some(SslMode::Require) => self.config.encryption(EncryptionLevel::Required),
+            None => (),
+        }
+
+        if let Some(path) = ca_cert_path(&tls)? {
+            // Tiberius doesn't allow both trusting all certificates and
+            // trusting a specific CA.
+            let normalized = self.conn_str.to_lowercase().replace(' ', "");
+            if normalized.contains("trustservercertificate=true")
+                || normalized.contains("trustservercertificate=yes")
+            {
+                return Err(SqlServerError::String(
+                    "'ca_cert' cannot be used with 'TrustServerCertificate' in the connection string"
+                        .to_string(),
+                ));
+            }
+            self.config.trust_cert_ca(path.to_string_lossy());
+        }
+
+        self.tls = tls;
+        Ok(self)
     }
 
     /// Validate that we can connect to server.
     pub async fn validate_access(&self) -> Result<()> {
-        let _state = SqlServerAccessState::connect(self.config.clone()).await?;
+        let _state = self.connect().await?;
         Ok(())
     }
 
     /// Validate that we can connect to a specific table.
     pub async fn validate_table_access(&self, schema: &str, table: &str) -> Result<()> {
-        let state = SqlServerAccessState::connect(self.config.clone()).await?;
+        let state = self.connect().await?;
         let _schema = state.get_table_schema(schema, table).await?;
         Ok(())
     }
 
-    /// Connect to the server and return the access state.
+    /// Connect to the server and return the access state, reusing a pooled
+    /// connection if possible.
     pub async fn connect(&self) -> Result<SqlServerAccessState> {
-        SqlServerAccessState::connect(self.config.clone()).await
+        let config = self.config.clone();
+        let key = format!("{}|{:?}", self.conn_str, self.tls);
+        let client = SQL_SERVER_POOL
+            .get(
+                &key,
+                || self.config.get_addr(),
+                || SqlServerConnection::connect(config),
+            )
+            .await?;
+        Ok(SqlServerAccessState { client })
     }
 }
 
+/// An open connection to a sql server instance.
 #[derive(Debug)]
-pub struct SqlServerAccessState {
+struct SqlServerConnection {
     client: Client,
     /// Handle for underlying sql server connection.
     ///
@@ -99,7 +170,7 @@ pub struct SqlServerAccessState {
     _conn_handle: JoinHandle<()>,
 }
 
-impl SqlServerAccessState {
+impl SqlServerConnection {
     async fn connect(config: tiberius::Config) -> Result<Self> {
         let socket =
             match tokio::time::timeout(CONNECTION_TIMEOUT, TcpStream::connect(config.get_addr()))
@@ -122,12 +193,42 @@ impl SqlServerAccessState {
             }
         });
 
-        Ok(SqlServerAccessState {
+        Ok(SqlServerConnection {
             client,
             _conn_handle: handle,
         })
     }
+}
+
+impl Deref for SqlServerConnection {
+    type Target = Client;
 
+    fn deref(&self) -> &Self::Target {
+        &self.client
+    }
+}
+
+#[async_trait]
+impl PoolableConnection for SqlServerConnection {
+    async fn is_healthy(&mut self) -> bool {
+        match self.client.query("SELECT 1").await {
+            Ok(stream) => stream
+                .collect::<Vec<_>>()
+                .await
+                .iter()
+                .all(|row| row.is_ok()),
+            Err(_) => false,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct SqlServerAccessState {
+    /// Client for the connection checked out from the connection pool.
+    client: PooledConnection<SqlServerConnection>,
+}
+
+impl SqlServerAccessState {
     /// Get the arrow schema and sql server schema for a table.
     async fn get_table_schema(
         &self,
@@ -268,21 +369,23 @@ pub struct SqlServerTableProviderConfig {
 pub struct SqlServerTableProvider {
     schema: String,
     table: String,
-    state: Arc<SqlServerAccessState>,
+    /// Access for checking out a connection whenever one's needed.
+    /// Connections aren't held for the lifetime of the provider.
+    access: Arc<SqlServerAccess>,
     arrow_schema: ArrowSchemaRef,
     sql_server_schema: Vec<tiberius::Column>,
 }
 
 impl SqlServerTableProvider {
     pub async fn try_new(conf: SqlServerTableProviderConfig) -> Result<Self> {
-        let state = SqlServerAccessState::connect(conf.access.config).await?;
+        let state = conf.access.connect().await?;
         let (arrow_schema, sql_server_schema) =
             state.get_table_schema(&conf.schema, &conf.table).await?;
 
         Ok(Self {
             schema: conf.schema,
             table: conf.table,
-            state: Arc::new(state),
+            access: Arc::new(conf.access),
             arrow_schema: Arc::new(arrow_schema),
             sql_server_schema,
         })
@@ -358,7 +461,7 @@ impl TableProvider for SqlServerTableProvider {
 
         Ok(Arc::new(SqlServerExec {
             query,
-            state: self.state.clone(),
+            access: self.access.clone(),
             arrow_schema: projected_schema,
             metrics: ExecutionPlanMetricsSet::new(),
         }))
@@ -484,10 +587,84 @@ fn should_skip_binary_expr(
     Ok(is_text_col(&expr.left, dt_map)? || is_text_col(&expr.right, dt_map)?)
 }
 
+#[async_trait]
+impl ExternalDml for SqlServerTableProvider {
+    async fn update(
+        &self,
+        updates: &[(String, Expr)],
+        where_expr: Option<&Expr>,
+    ) -> DatafusionResult<u64> {
+        let stmt = sql_update_statement(self, &self.arrow_schema, updates, where_expr)?;
+        self.execute_dml(stmt).await
+    }
+
+    async fn delete(&self, where_expr: Option<&Expr>) -> DatafusionResult<u64> {
+        let stmt = sql_delete_statement(self, &self.arrow_schema, where_expr)?;
+        self.execute_dml(stmt).await
+    }
+}
+
+impl SqlServerTableProvider {
+    async fn execute_dml(&self, stmt: String) -> DatafusionResult<u64> {
+        debug!(%stmt, "modifying rows in sql server datasource");
+        let state = self
+            .access
+            .connect()
+            .await
+            .map_err(|e| DataFusionError::External(Box::new(e)))?;
+        state
+            .client
+            .execute(stmt)
+            .await
+            .map_err(|e| DataFusionError::External(Box::new(e)))
+    }
+}
+
+#[async_trait]
+impl SqlQueryPushdown for SqlServerTableProvider {
+    fn dialect(&self) -> util::Datasource {
+        util::Datasource::SqlServer
+    }
+
+    fn table_reference(&self) -> String {
+        quote_table_reference(self.dialect(), Some(&self.schema), &self.table)
+    }
+
+    fn cast_type(&self, datatype: &DataType) -> Option<String> {
+        // Note that booleans are left out. Boolean expressions can't be cast
+        // to BIT, and so can't be selected.
+        Some(
+            match datatype {
+                DataType::Int16 => "SMALLINT",
+                DataType::Int32 => "INT",
+                DataType::Int64 => "BIGINT",
+                DataType::Float32 => "REAL",
+                DataType::Float64 => "FLOAT",
+                DataType::Utf8 => "NVARCHAR(MAX)",
+                _ => return None,
+            }
+            .to_string(),
+        )
+    }
+
+    async fn query_exec(
+        &self,
+        query: String,
+        schema: ArrowSchemaRef,
+    ) -> DatafusionResult<Arc<dyn ExecutionPlan>> {
+        Ok(Arc::new(SqlServerExec {
+            query,
+            access: self.access.clone(),
+            arrow_schema: schema,
+            metrics: ExecutionPlanMetricsSet::new(),
+        }))
+    }
+}
+
 /// Execution plan for reading from SQL Server.
 struct SqlServerExec {
     query: String,
-    state: Arc<SqlServerAccessState>,
+    access: Arc<SqlServerAccess>,
     arrow_schema: ArrowSchemaRef,
     metrics: ExecutionPlanMetricsSet,
 }
@@ -539,8 +716,16 @@ impl ExecutionPlan for SqlServerExec {
 
         // Clones to ensure the future is static.
         let query = self.query.clone();
-        let state = self.state.clone();
-        let fut = async move { state.client.query(query).await };
+        let access = self.access.clone();
+        let fut = async move {
+            let state = access.connect().await?;
+            // Rows of an unfinished query would still be sent on the
+            // connection, so it can't be reused if the stream is dropped
+            // early.
+            state.client.begin_operation();
+            let stream = state.client.query(query).await?;
+            Ok((stream, state))
+        };
 
         let stream = RowStream {
             stream_state: RowStreamState::Opening {
@@ -589,11 +774,14 @@ impl fmt::Debug for SqlServerExec {
 enum RowStreamState {
     /// We're still opening the query stream.
     Opening {
-        opening_fut: BoxFuture<'static, Result<QueryStream>>,
+        opening_fut: BoxFuture<'static, Result<(QueryStream, SqlServerAccessState)>>,
     },
     /// Actively streaming from the query stream.
     Scan {
         stream: BoxStream<'static, Vec<Result<tiberius::Row>>>,
+        /// Connection the query is running on, returned to the pool once the
+        /// stream completes.
+        state: SqlServerAccessState,
     },
     /// We finished streaming or hit an error.
     Done,
@@ -617,10 +805,10 @@ impl RowStream {
             match &mut self.stream_state {
                 RowStreamState::Opening { opening_fut } => {
                     match ready!(opening_fut.poll_unpin(cx)) {
-                        Ok(stream) => {
+                        Ok((stream, state)) => {
                             // We have the stream, advance state.
                             let stream = Box::pin(stream.chunks(self.chunk_size));
-                            self.stream_state = RowStreamState::Scan { stream };
+                            self.stream_state = RowStreamState::Scan { stream, state };
                             continue;
                         }
                         Err(e) => {
@@ -630,16 +818,24 @@ impl RowStream {
                     };
                 }
 
-                RowStreamState::Scan { stream } => match ready!(stream.poll_next_unpin(cx)) {
-                    Some(chunk) => match rows_to_record_batch(chunk, self.arrow_schema.clone()) {
-                        Ok(batch) => return Poll::Ready(Some(Ok(batch))),
-                        Err(e) => {
+                RowStreamState::Scan { stream, state } => {
+                    match ready!(stream.poll_next_unpin(cx)) {
+                        Some(chunk) => match rows_to_record_batch(chunk, self.arrow_schema.clone())
+                        {
+                            Ok(batch) => return Poll::Ready(Some(Ok(batch))),
+                            Err(e) => {
+                                self.stream_state = RowStreamState::Done;
+                                return Poll::Ready(Some(Err(DataFusionError::External(
+                                    Box::new(e),
+                                ))));
+                            }
+                        },
+                        None => {
+                            state.client.end_operation();
                             self.stream_state = RowStreamState::Done;
-                            return Poll::Ready(Some(Err(DataFusionError::External(Box::new(e)))));
                         }
-                    },
-                    None => self.stream_state = RowStreamState::Done,
-                },
+                    }
+                }
 
                 RowStreamState::Done => return Poll::Ready(None),
             }
@@ -466,7 +466,9 @@ mod tests {
     ///
     /// The newly created Metastore will have no database data to begin with.
     async fn new_local_metastore() -> MetastoreServiceClient<Channel> {
-        start_inprocess(Arc::new(InMemory::new())).await.unwrap()
+        start_inprocess(Arc::new(InMemory::new()), None)
+            .await
+            .unwrap()
     }
 
     #[tokio::test]
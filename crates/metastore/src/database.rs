@@ -1027,6 +1027,12 @@ impl State {
                 match self.entries.get_mut(oid)?.unwrap() {
                     CatalogEntry::Tunnel(tunnel_entry) => match &mut tunnel_entry.options {
                         TunnelOptions::Ssh(tunnel_options_ssh) => {
+                            if tunnel_options_ssh.user_provided_key {
+                                return Err(MetastoreError::TunnelNotSupportedForAction {
+                                    tunnel: "ssh with a user provided key".to_string(),
+                                    action: "rotating keys",
+                                });
+                            }
                             tunnel_options_ssh.ssh_key = alter_tunnel_rotate_keys.new_ssh_key;
                         }
                         opt => {
@@ -1275,6 +1281,8 @@ impl BuiltinCatalog {
                     },
                     options: TableOptionsInternal {
                         columns: table.columns.clone(),
+                        partition_by: Vec::new(),
+                        view_sql: None,
                     }
                     .into(),
                     tunnel_id: None,
@@ -1446,13 +1454,16 @@ mod tests {
         InternalColumnDefinition,
         TableOptionsDebug,
         TableOptionsInternal,
+        TunnelOptionsSsh,
     };
     use protogen::metastore::types::service::{
         AlterDatabase,
+        AlterTunnelRotateKeys,
         CreateExternalDatabase,
         CreateExternalTable,
         CreateSchema,
         CreateTable,
+        CreateTunnel,
         CreateView,
         DropDatabase,
         DropSchema,
@@ -2052,6 +2063,8 @@ mod tests {
                         nullable: true,
                         arrow_type: DataType::Utf8,
                     }],
+                    partition_by: Vec::new(),
+                    view_sql: None,
                 },
             })],
         )
@@ -2078,10 +2091,52 @@ mod tests {
                         nullable: true,
                         arrow_type: DataType::Utf8,
                     }],
+                    partition_by: Vec::new(),
+                    view_sql: None,
                 },
             })],
         )
         .await
         .unwrap();
     }
+
+    #[tokio::test]
+    async fn rotate_user_provided_tunnel_key() {
+        let db = new_catalog().await;
+
+        let tunnel = |name: &str, user_provided_key| {
+            Mutation::CreateTunnel(CreateTunnel {
+                name: name.to_string(),
+                options: TunnelOptions::Ssh(TunnelOptionsSsh {
+                    connection_string: "ssh://glaredb@bastion".to_string(),
+                    ssh_key: vec![1],
+                    jump_hosts: Vec::new(),
+                    known_hosts: None,
+                    user_provided_key,
+                }),
+                if_not_exists: false,
+            })
+        };
+        let rotate = |name: &str| {
+            Mutation::AlterTunnelRotateKeys(AlterTunnelRotateKeys {
+                name: name.to_string(),
+                if_exists: false,
+                new_ssh_key: vec![2],
+            })
+        };
+
+        db.try_mutate_and_commit(
+            version(&db).await,
+            vec![tunnel("generated", false), tunnel("provided", true)],
+        )
+        .await
+        .unwrap();
+
+        db.try_mutate_and_commit(version(&db).await, vec![rotate("generated")])
+            .await
+            .unwrap();
+        db.try_mutate_and_commit(version(&db).await, vec![rotate("provided")])
+            .await
+            .unwrap_err();
+    }
 }
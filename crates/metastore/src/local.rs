@@ -4,6 +4,7 @@ use std::sync::Arc;
 use object_store::local::LocalFileSystem;
 use object_store::memory::InMemory;
 use object_store::ObjectStore;
+use object_store_util::encryption::KeyProvider;
 use protogen::gen::metastore::service::metastore_service_client::MetastoreServiceClient;
 use protogen::gen::metastore::service::metastore_service_server::MetastoreServiceServer;
 use tonic::transport::{Channel, Endpoint, Server, Uri};
@@ -15,7 +16,7 @@ use crate::srv::Service;
 /// Starts an in-process, in-memory metastore.
 pub async fn start_inprocess_inmemory() -> Result<MetastoreServiceClient<Channel>> {
     info!("Starting in-memory metastore");
-    start_inprocess(Arc::new(InMemory::new())).await
+    start_inprocess(Arc::new(InMemory::new()), None).await
 }
 
 /// Starts an in-process, local persistent metastore.
@@ -25,20 +26,25 @@ pub async fn start_inprocess_local(
     let path = path.as_ref();
     info!(?path, "starting local metastore");
     let local = LocalFileSystem::new_with_prefix(path)?;
-    start_inprocess(Arc::new(local)).await
+    start_inprocess(Arc::new(local), None).await
 }
 
 /// Starts an in-process metastore service, returning a client for the service.
 ///
-/// Useful for tests, as well as when running GlareDB locally.
+/// Useful for tests, as well as when running GlareDB locally. Catalogs are
+/// encrypted if a key provider is given.
 pub async fn start_inprocess(
     store: Arc<dyn ObjectStore>,
+    key_provider: Option<Arc<dyn KeyProvider>>,
 ) -> Result<MetastoreServiceClient<Channel>> {
     let (client, server) = tokio::io::duplex(1024);
 
     tokio::spawn(async move {
         if let Err(e) = Server::builder()
-            .add_service(MetastoreServiceServer::new(Service::new(store)))
+            .add_service(MetastoreServiceServer::new(Service::new(
+                store,
+                key_provider,
+            )))
             .serve_with_incoming(futures::stream::iter(vec![Ok::<_, MetastoreError>(server)]))
             .await
         {
@@ -3,6 +3,7 @@ use std::sync::Arc;
 use async_trait::async_trait;
 use dashmap::DashMap;
 use object_store::ObjectStore;
+use object_store_util::encryption::KeyProvider;
 use protogen::gen::metastore::service::metastore_service_server::MetastoreService;
 use protogen::gen::metastore::service::{
     self,
@@ -38,13 +39,20 @@ pub struct Service {
 }
 
 impl Service {
-    pub fn new(store: Arc<dyn ObjectStore>) -> Service {
+    /// Create a new service persisting catalogs to the store.
+    ///
+    /// Catalogs are encrypted with keys from the key provider if one is
+    /// provided.
+    pub fn new(store: Arc<dyn ObjectStore>, key_provider: Option<Arc<dyn KeyProvider>>) -> Service {
         let process_id = Uuid::new_v4();
-        info!(%process_id, "Creating new Metastore service");
+        info!(%process_id, encrypted = key_provider.is_some(), "Creating new Metastore service");
 
-        let storage = Arc::new(Storage::new(process_id, store));
+        let mut storage = Storage::new(process_id, store);
+        if let Some(key_provider) = key_provider {
+            storage = storage.with_key_provider(key_provider);
+        }
         Service {
-            storage,
+            storage: Arc::new(storage),
             catalogs: DashMap::new(),
         }
     }
@@ -150,7 +158,7 @@ mod tests {
 
     fn new_service() -> Service {
         let store = Arc::new(InMemory::new());
-        Service::new(store)
+        Service::new(store, None)
     }
 
     #[tokio::test]
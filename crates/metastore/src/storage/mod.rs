@@ -61,6 +61,12 @@ pub enum StorageError {
     #[error("Failed to decode protobuf from storage: {0}")]
     ProstDecode(#[from] prost::DecodeError),
 
+    #[error("Catalog is encrypted, but no encryption key was configured")]
+    MissingEncryptionKey,
+
+    #[error(transparent)]
+    Encryption(#[from] object_store_util::encryption::EncryptionError),
+
     #[error(transparent)]
     ObjectStore(#[from] object_store::Error),
 }
@@ -1,8 +1,10 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
-use bytes::BytesMut;
+use bytes::{Bytes, BytesMut};
+use object_store::path::Path as ObjectPath;
 use object_store::{Error as ObjectStoreError, ObjectStore};
+use object_store_util::encryption::{self, KeyProvider};
 use pgrepr::oid::FIRST_AVAILABLE_ID;
 use prost::Message;
 use protogen::gen::metastore::storage;
@@ -38,6 +40,10 @@ pub struct Storage {
     /// The leaser for leasing catalog objects. Leases are only used when making
     /// modifications to the catalog.
     leaser: RemoteLeaser,
+
+    /// Provider for the key used to encrypt catalogs. Catalogs are written
+    /// unencrypted if not set.
+    key_provider: Option<Arc<dyn KeyProvider>>,
 }
 
 impl Storage {
@@ -47,9 +53,19 @@ impl Storage {
             process_id,
             store,
             leaser,
+            key_provider: None,
         }
     }
 
+    /// Encrypt catalogs before writing them using keys from the provider.
+    ///
+    /// Catalogs written before encryption was enabled can still be read, and
+    /// will be encrypted on the next write.
+    pub fn with_key_provider(mut self, key_provider: Arc<dyn KeyProvider>) -> Storage {
+        self.key_provider = Some(key_provider);
+        self
+    }
+
     /// Initialize a new catalog for a database.
     ///
     /// Idempotent via checking if metadata for the catalog exists. If
@@ -88,11 +104,11 @@ impl Storage {
         .try_into()?;
         let mut bs = BytesMut::new();
         first_catalog.encode(&mut bs)?;
+        let catalog_path = PERSISTENT_CATALOG_OBJECT.visible_path(&db_id);
+        let bs = self.encrypt_catalog(&catalog_path, bs.freeze()).await?;
 
         // Write version 0 of the catalog...
-        self.store
-            .put(&PERSISTENT_CATALOG_OBJECT.visible_path(&db_id), bs.freeze())
-            .await?;
+        self.store.put(&catalog_path, bs).await?;
 
         // Initialize lease.
         self.leaser.initialize(&db_id).await?;
@@ -140,6 +156,7 @@ impl Storage {
         // Log we'll want to keep an eye on so we can monitor catalog size.
         debug!(byte_len = %bs.len(), %db_id, "read catalog");
 
+        let bs = self.decrypt_catalog(&path, bs).await?;
         let proto = storage::PersistedCatalog::decode(bs)?;
 
         Ok(proto.try_into()?)
@@ -217,9 +234,13 @@ impl Storage {
         let proto: storage::PersistedCatalog = catalog.try_into()?;
         let mut bs = BytesMut::new();
         proto.encode(&mut bs)?;
+        // Encrypted for the path it'll be visible at, the temporary object is
+        // moved as is.
+        let catalog_path = catalog_obj.visible_path(&db_id);
+        let bs = self.encrypt_catalog(&catalog_path, bs.freeze()).await?;
 
         let tmp_catalog_path = catalog_obj.tmp_path(&db_id, &self.process_id);
-        self.store.put(&tmp_catalog_path, bs.freeze()).await?;
+        self.store.put(&tmp_catalog_path, bs).await?;
 
         let proto: storage::CatalogMetadata = metadata.into();
         let mut bs = BytesMut::new();
@@ -232,9 +253,7 @@ impl Storage {
 
         // Blindly overwrite to prevent subsequent catalog writes from getting
         // stuck if we happen to fail on step 6 or fail the lease check.
-        self.store
-            .rename(&tmp_catalog_path, &catalog_obj.visible_path(&db_id))
-            .await?;
+        self.store.rename(&tmp_catalog_path, &catalog_path).await?;
 
         // Last chance to bail before attempting to make our changes visible.
         if !lease.is_valid() {
@@ -263,11 +282,34 @@ impl Storage {
 
         Ok(proto.try_into()?)
     }
+
+    /// Encrypt an encoded catalog that'll be stored at `path` if a key
+    /// provider is configured.
+    async fn encrypt_catalog(&self, path: &ObjectPath, bs: Bytes) -> Result<Bytes> {
+        match &self.key_provider {
+            Some(provider) => Ok(encryption::encrypt(provider.as_ref(), path, &bs).await?),
+            None => Ok(bs),
+        }
+    }
+
+    /// Decrypt a catalog read from `path`.
+    ///
+    /// Unencrypted catalogs are returned as is.
+    async fn decrypt_catalog(&self, path: &ObjectPath, bs: Bytes) -> Result<Bytes> {
+        if !encryption::is_encrypted(&bs) {
+            return Ok(bs);
+        }
+        match &self.key_provider {
+            Some(provider) => Ok(encryption::decrypt(provider.as_ref(), path, &bs).await?),
+            None => Err(StorageError::MissingEncryptionKey),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use object_store::memory::InMemory;
+    use object_store_util::encryption::local::LocalKeyProvider;
 
     use super::*;
 
@@ -279,6 +321,7 @@ mod tests {
             process_id,
             store,
             leaser,
+            key_provider: None,
         }
     }
 
@@ -327,6 +370,7 @@ mod tests {
             process_id,
             store: storage.store.clone(), // Use the same store (in memory).
             leaser: RemoteLeaser::new(process_id, storage.store.clone()),
+            key_provider: None,
         };
         let lease = different.leaser.acquire(db_id).await.unwrap();
 
@@ -348,4 +392,44 @@ mod tests {
             .await
             .unwrap();
     }
+
+    #[tokio::test]
+    async fn write_encrypted() {
+        let storage = new_storage();
+        let key_provider = Arc::new(LocalKeyProvider::new(&[1; 32]).unwrap());
+        let encrypted = storage.clone().with_key_provider(key_provider);
+
+        // Catalog initialized without encryption.
+        let db_id = Uuid::new_v4();
+        storage.initialize(db_id).await.unwrap();
+
+        // Existing catalogs can still be read with encryption enabled.
+        let mut catalog = encrypted.read_catalog(db_id).await.unwrap();
+
+        catalog.state.version += 1;
+        encrypted
+            .write_catalog(db_id, 0, catalog.clone())
+            .await
+            .unwrap();
+
+        let path = PERSISTENT_CATALOG_OBJECT
+            .with_version(1)
+            .visible_path(&db_id);
+        let bs = storage
+            .store
+            .get(&path)
+            .await
+            .unwrap()
+            .bytes()
+            .await
+            .unwrap();
+        assert!(encryption::is_encrypted(&bs));
+
+        let updated = encrypted.read_catalog(db_id).await.unwrap();
+        assert_eq!(1, updated.state.version);
+
+        // Reading encrypted catalogs requires the key.
+        let err = storage.read_catalog(db_id).await.unwrap_err();
+        assert!(matches!(err, StorageError::MissingEncryptionKey), "{err}");
+    }
 }
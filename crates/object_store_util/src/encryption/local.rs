@@ -0,0 +1,196 @@
+use std::path::Path;
+
+use async_trait::async_trait;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::digest::{digest, SHA256};
+use ring::rand::{SecureRandom, SystemRandom};
+
+use super::{EncryptionError, KeyProvider, Result};
+
+/// Key provider using keys read from a local file.
+///
+/// The file should contain a 256 bit key encoded as 64 hex characters, e.g. as
+/// generated by `openssl rand -hex 32`.
+///
+/// To rotate the key, add the new key as the first line of the file. Any keys
+/// on the following lines are previous keys, which are only used to unwrap the
+/// data keys of objects written before the rotation.
+#[derive(Debug)]
+pub struct LocalKeyProvider {
+    /// The current key followed by previous keys.
+    keys: Vec<LocalKey>,
+}
+
+#[derive(Debug)]
+struct LocalKey {
+    id: String,
+    key: LessSafeKey,
+}
+
+impl LocalKey {
+    fn new(key: &[u8]) -> Result<Self> {
+        let unbound = UnboundKey::new(&AES_256_GCM, key).map_err(|_| {
+            EncryptionError::InvalidKey(format!("expected a 32 byte key, got {} bytes", key.len()))
+        })?;
+
+        // Derive the id from a hash of the key so that objects encrypted with
+        // a different key file can be detected without revealing the key.
+        let hash = digest(&SHA256, key);
+        let id = format!("local:{}", hex::encode(&hash.as_ref()[..8]));
+
+        Ok(LocalKey {
+            id,
+            key: LessSafeKey::new(unbound),
+        })
+    }
+}
+
+impl LocalKeyProvider {
+    /// Create a key provider from a raw 256 bit key.
+    pub fn new(key: &[u8]) -> Result<Self> {
+        Ok(LocalKeyProvider {
+            keys: vec![LocalKey::new(key)?],
+        })
+    }
+
+    /// Add a previous key for unwrapping data keys of existing objects.
+    pub fn with_previous_key(mut self, key: &[u8]) -> Result<Self> {
+        self.keys.push(LocalKey::new(key)?);
+        Ok(self)
+    }
+
+    /// Create a key provider from hex encoded keys, one per line, starting
+    /// with the current key.
+    pub fn from_hex(keys: &str) -> Result<Self> {
+        let decode = |key: &str| {
+            hex::decode(key.trim()).map_err(|e| EncryptionError::InvalidKey(e.to_string()))
+        };
+
+        let mut keys = keys.lines().filter(|line| !line.trim().is_empty());
+        let current = keys
+            .next()
+            .ok_or_else(|| EncryptionError::InvalidKey("no key provided".to_string()))?;
+        keys.try_fold(Self::new(&decode(current)?)?, |provider, key| {
+            provider.with_previous_key(&decode(key)?)
+        })
+    }
+
+    /// Create a key provider from a key file.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let key = std::fs::read_to_string(path)?;
+        Self::from_hex(&key)
+    }
+}
+
+#[async_trait]
+impl KeyProvider for LocalKeyProvider {
+    fn key_id(&self) -> &str {
+        &self.keys[0].id
+    }
+
+    async fn wrap_key(&self, data_key: &[u8]) -> Result<Vec<u8>> {
+        let mut nonce = [0; NONCE_LEN];
+        SystemRandom::new()
+            .fill(&mut nonce)
+            .map_err(|_| EncryptionError::Encrypt)?;
+
+        let current = &self.keys[0];
+        let mut wrapped = data_key.to_vec();
+        current
+            .key
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from(current.id.as_bytes()),
+                &mut wrapped,
+            )
+            .map_err(|_| EncryptionError::Encrypt)?;
+
+        // Nonce is stored in front of the wrapped key.
+        let mut buf = nonce.to_vec();
+        buf.extend(wrapped);
+        Ok(buf)
+    }
+
+    async fn unwrap_key(&self, key_id: &str, wrapped_key: &[u8]) -> Result<Vec<u8>> {
+        let key = self
+            .keys
+            .iter()
+            .find(|key| key.id == key_id)
+            .ok_or_else(|| EncryptionError::UnknownKey(key_id.to_string()))?;
+        if wrapped_key.len() < NONCE_LEN {
+            return Err(EncryptionError::Decrypt);
+        }
+
+        let (nonce, wrapped) = wrapped_key.split_at(NONCE_LEN);
+        let nonce =
+            Nonce::try_assume_unique_for_key(nonce).map_err(|_| EncryptionError::Decrypt)?;
+        let mut wrapped = wrapped.to_vec();
+        let data_key = key
+            .key
+            .open_in_place(nonce, Aad::from(key.id.as_bytes()), &mut wrapped)
+            .map_err(|_| EncryptionError::Decrypt)?;
+
+        Ok(data_key.to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_hex() {
+        let key = "a".repeat(64);
+        let provider = LocalKeyProvider::from_hex(&format!("{key}\n")).unwrap();
+        assert!(provider.key_id().starts_with("local:"));
+
+        LocalKeyProvider::from_hex(&"a".repeat(32)).unwrap_err();
+        LocalKeyProvider::from_hex("not hex").unwrap_err();
+        LocalKeyProvider::from_hex("\n").unwrap_err();
+        LocalKeyProvider::from_hex(&format!("{key}\nnot hex")).unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn rotate() {
+        let old = LocalKeyProvider::from_hex(&"a".repeat(64)).unwrap();
+        let data_key = [2; 32];
+        let wrapped = old.wrap_key(&data_key).await.unwrap();
+
+        // New key first, followed by the previous key.
+        let rotated =
+            LocalKeyProvider::from_hex(&format!("{}\n{}\n", "b".repeat(64), "a".repeat(64)))
+                .unwrap();
+        assert_ne!(old.key_id(), rotated.key_id());
+
+        let unwrapped = rotated.unwrap_key(old.key_id(), &wrapped).await.unwrap();
+        assert_eq!(&data_key[..], &unwrapped[..]);
+
+        // New data keys are wrapped with the new key.
+        let wrapped = rotated.wrap_key(&data_key).await.unwrap();
+        let unwrapped = rotated
+            .unwrap_key(rotated.key_id(), &wrapped)
+            .await
+            .unwrap();
+        assert_eq!(&data_key[..], &unwrapped[..]);
+        old.unwrap_key(rotated.key_id(), &wrapped)
+            .await
+            .unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn wrap_unwrap() {
+        let provider = LocalKeyProvider::new(&[1; 32]).unwrap();
+        let data_key = [2; 32];
+
+        let wrapped = provider.wrap_key(&data_key).await.unwrap();
+        assert_ne!(&data_key[..], &wrapped[..]);
+
+        let unwrapped = provider
+            .unwrap_key(provider.key_id(), &wrapped)
+            .await
+            .unwrap();
+        assert_eq!(&data_key[..], &unwrapped[..]);
+
+        provider.unwrap_key("other", &wrapped).await.unwrap_err();
+    }
+}
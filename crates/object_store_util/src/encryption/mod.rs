@@ -0,0 +1,529 @@
+//! Envelope encryption for objects written to object storage.
+//!
+//! Every object is encrypted with its own randomly generated data key using
+//! AES-256-GCM. The data key is wrapped by a [`KeyProvider`] which holds the
+//! customer managed key, and the wrapped key is stored in the header of the
+//! object. Only the key provider ever sees the customer managed key.
+//!
+//! Encrypted objects have the following layout:
+//!
+//! ```text
+//! header (HEADER_LEN bytes, zero padded):
+//!   magic | version (u8) | key id len (u16) | key id | wrapped key len (u16) | wrapped key
+//! segments:
+//!   ciphertext of SEGMENT_LEN plaintext bytes | tag
+//!   ...
+//!   ciphertext of remaining (possibly zero) plaintext bytes | tag
+//! ```
+//!
+//! Splitting the plaintext into fixed size segments which are sealed
+//! individually lets readers map a plaintext range to the segments containing
+//! it, so range reads (e.g. of a Parquet footer) only need to fetch and decrypt
+//! a few segments instead of the entire object. It also lets writers encrypt
+//! objects as they're streamed, without knowing the final size up front.
+//!
+//! Segment nonces are derived from the segment index, which is safe since
+//! every data key is only used for a single object. Whether or not a segment
+//! is the last one is used as associated data, so segments can't be
+//! reordered, dropped or appended without decryption failing. The plaintext
+//! size is derived from the object size.
+//!
+//! The path of the object is part of the associated data as well, so an
+//! encrypted object can't be swapped for another one. Objects that are copied
+//! or renamed have to be encrypted again for their new path.
+
+pub mod local;
+pub mod store;
+
+use std::fmt::Debug;
+use std::ops::Range;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use object_store::path::Path;
+use object_store::GetRange;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+
+/// Magic bytes at the start of every encrypted object.
+///
+/// 'G' is never a valid first byte of a protobuf message (wire type 7), so
+/// encrypted catalog blobs can be told apart from unencrypted ones.
+pub const MAGIC: &[u8; 8] = b"GLAREENC";
+
+/// Size of the header of encrypted objects.
+///
+/// The header has a fixed size so that plaintext sizes can be computed from
+/// object sizes alone.
+pub const HEADER_LEN: usize = 512;
+
+/// Number of plaintext bytes in every segment (except the last).
+pub const SEGMENT_LEN: usize = 64 * 1024;
+
+const FORMAT_VERSION: u8 = 1;
+
+const TAG_LEN: usize = 16;
+
+const ENCRYPTED_SEGMENT_LEN: usize = SEGMENT_LEN + TAG_LEN;
+
+const DATA_KEY_LEN: usize = 32;
+
+#[derive(Debug, thiserror::Error)]
+pub enum EncryptionError {
+    #[error("Object is not encrypted")]
+    NotEncrypted,
+
+    #[error("Unsupported encryption format version: {0}")]
+    UnsupportedVersion(u8),
+
+    #[error("Invalid encryption header: {0}")]
+    InvalidHeader(&'static str),
+
+    #[error(
+        "Failed to decrypt object, the object is corrupt or was encrypted with a different key"
+    )]
+    Decrypt,
+
+    #[error("Failed to encrypt object")]
+    Encrypt,
+
+    #[error("Unknown encryption key '{0}'")]
+    UnknownKey(String),
+
+    #[error("Invalid encryption key: {0}")]
+    InvalidKey(String),
+
+    #[error("Invalid range {range:?} for object of size {len}")]
+    InvalidRange { range: GetRange, len: usize },
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+pub type Result<T, E = EncryptionError> = std::result::Result<T, E>;
+
+impl From<EncryptionError> for object_store::Error {
+    fn from(e: EncryptionError) -> Self {
+        object_store::Error::Generic {
+            store: "Encrypted",
+            source: Box::new(e),
+        }
+    }
+}
+
+/// Provides the key used for wrapping (encrypting) the data keys of
+/// individual objects.
+///
+/// This mirrors the interface of KMS services, where the key encryption key
+/// never leaves the service and data keys are sent to it for wrapping and
+/// unwrapping.
+#[async_trait]
+pub trait KeyProvider: Debug + Send + Sync {
+    /// Id of the key used for wrapping new data keys.
+    ///
+    /// Stored in the header of encrypted objects so the key can be found again
+    /// when unwrapping.
+    fn key_id(&self) -> &str;
+
+    /// Wrap a data key with the current key.
+    async fn wrap_key(&self, data_key: &[u8]) -> Result<Vec<u8>>;
+
+    /// Unwrap a data key that was wrapped with the key identified by `key_id`.
+    async fn unwrap_key(&self, key_id: &str, wrapped_key: &[u8]) -> Result<Vec<u8>>;
+}
+
+/// Returns if the buffer starts with an encryption header.
+pub fn is_encrypted(buf: &[u8]) -> bool {
+    buf.starts_with(MAGIC)
+}
+
+/// Encrypt the plaintext of the object at `location` with a new data key
+/// wrapped by the provider.
+pub async fn encrypt(
+    provider: &dyn KeyProvider,
+    location: &Path,
+    plaintext: &[u8],
+) -> Result<Bytes> {
+    let data_key = generate_data_key()?;
+    let mut buf = Vec::with_capacity(encrypted_len(plaintext.len()));
+    let key = new_object_key(provider, location, &data_key, &mut buf).await?;
+
+    let count = segment_count(plaintext.len());
+    for idx in 0..count {
+        let start = idx * SEGMENT_LEN;
+        let end = (start + SEGMENT_LEN).min(plaintext.len());
+        key.seal_segment(idx, idx + 1 == count, &plaintext[start..end], &mut buf)?;
+    }
+
+    Ok(buf.into())
+}
+
+/// Decrypt an entire encrypted object read from `location`.
+pub async fn decrypt(
+    provider: &dyn KeyProvider,
+    location: &Path,
+    ciphertext: &[u8],
+) -> Result<Bytes> {
+    let key = open_header(provider, location, ciphertext).await?;
+    let len = plaintext_len(ciphertext.len()).ok_or(EncryptionError::Decrypt)?;
+    let plaintext = key.open_segments(0, segment_count(len), &ciphertext[HEADER_LEN..])?;
+    Ok(plaintext.into())
+}
+
+/// Size of an encrypted object holding `plaintext_len` bytes.
+pub fn encrypted_len(plaintext_len: usize) -> usize {
+    HEADER_LEN + plaintext_len + segment_count(plaintext_len) * TAG_LEN
+}
+
+/// Size of the plaintext held by an encrypted object of the given size.
+///
+/// Returns `None` if no encrypted object can have the given size.
+pub fn plaintext_len(encrypted_len: usize) -> Option<usize> {
+    let segments_len = encrypted_len.checked_sub(HEADER_LEN)?;
+    let full = segments_len / ENCRYPTED_SEGMENT_LEN;
+    match segments_len % ENCRYPTED_SEGMENT_LEN {
+        0 if full > 0 => Some(full * SEGMENT_LEN),
+        // Empty objects still have a single (empty) segment.
+        TAG_LEN if full == 0 => Some(0),
+        rem if rem > TAG_LEN => Some(full * SEGMENT_LEN + rem - TAG_LEN),
+        _ => None,
+    }
+}
+
+/// Number of segments holding `plaintext_len` bytes.
+///
+/// There's always at least one segment so that truncating an object to just
+/// its header can be detected.
+fn segment_count(plaintext_len: usize) -> usize {
+    plaintext_len.div_ceil(SEGMENT_LEN).max(1)
+}
+
+/// Index of the first segment holding the plaintext range, and the range of
+/// the encrypted object holding all segments for the plaintext range.
+///
+/// The plaintext range must not be empty.
+fn segments_for_range(range: &Range<usize>, plaintext_len: usize) -> (usize, Range<usize>) {
+    let first = range.start / SEGMENT_LEN;
+    let last = (range.end - 1) / SEGMENT_LEN;
+
+    let start = HEADER_LEN + first * ENCRYPTED_SEGMENT_LEN;
+    let end = (HEADER_LEN + (last + 1) * ENCRYPTED_SEGMENT_LEN).min(encrypted_len(plaintext_len));
+
+    (first, start..end)
+}
+
+/// Wrap the data key for a new object, appending the header to `buf`.
+async fn new_object_key(
+    provider: &dyn KeyProvider,
+    location: &Path,
+    data_key: &[u8],
+    buf: &mut Vec<u8>,
+) -> Result<DataKey> {
+    let header = Header {
+        key_id: provider.key_id().to_string(),
+        wrapped_key: provider.wrap_key(data_key).await?,
+    };
+    header.encode_into(buf)?;
+    DataKey::new(data_key, location)
+}
+
+/// Generate a data key for a new object.
+fn generate_data_key() -> Result<[u8; DATA_KEY_LEN]> {
+    let mut data_key = [0; DATA_KEY_LEN];
+    SystemRandom::new()
+        .fill(&mut data_key)
+        .map_err(|_| EncryptionError::Encrypt)?;
+    Ok(data_key)
+}
+
+/// Decode the header from the start of an encrypted object and unwrap its
+/// data key.
+async fn open_header(provider: &dyn KeyProvider, location: &Path, buf: &[u8]) -> Result<DataKey> {
+    let header = Header::decode(buf)?;
+    let data_key = provider
+        .unwrap_key(&header.key_id, &header.wrapped_key)
+        .await?;
+    DataKey::new(&data_key, location)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Header {
+    key_id: String,
+    wrapped_key: Vec<u8>,
+}
+
+impl Header {
+    fn encode_into(&self, buf: &mut Vec<u8>) -> Result<()> {
+        let start = buf.len();
+        buf.extend_from_slice(MAGIC);
+        buf.push(FORMAT_VERSION);
+        for field in [self.key_id.as_bytes(), &self.wrapped_key] {
+            let len = u16::try_from(field.len())
+                .map_err(|_| EncryptionError::InvalidHeader("field too long"))?;
+            buf.extend_from_slice(&len.to_be_bytes());
+            buf.extend_from_slice(field);
+        }
+
+        if buf.len() - start > HEADER_LEN {
+            return Err(EncryptionError::InvalidHeader(
+                "key id and wrapped key don't fit in header",
+            ));
+        }
+        buf.resize(start + HEADER_LEN, 0);
+
+        Ok(())
+    }
+
+    fn decode(buf: &[u8]) -> Result<Self> {
+        if buf.len() < HEADER_LEN || !is_encrypted(buf) {
+            return Err(EncryptionError::NotEncrypted);
+        }
+        let buf = &buf[..HEADER_LEN];
+
+        let version = buf[MAGIC.len()];
+        if version != FORMAT_VERSION {
+            return Err(EncryptionError::UnsupportedVersion(version));
+        }
+
+        let mut pos = MAGIC.len() + 1;
+
+        let key_id = String::from_utf8(read_field(buf, &mut pos)?.to_vec())
+            .map_err(|_| EncryptionError::InvalidHeader("key id not utf8"))?;
+        let wrapped_key = read_field(buf, &mut pos)?.to_vec();
+
+        Ok(Header {
+            key_id,
+            wrapped_key,
+        })
+    }
+}
+
+fn read_field<'a>(buf: &'a [u8], pos: &mut usize) -> Result<&'a [u8]> {
+    let truncated = || EncryptionError::InvalidHeader("truncated field");
+    let len_bytes = buf.get(*pos..*pos + 2).ok_or_else(truncated)?;
+    let len = u16::from_be_bytes([len_bytes[0], len_bytes[1]]) as usize;
+    let field = buf.get(*pos + 2..*pos + 2 + len).ok_or_else(truncated)?;
+    *pos += 2 + len;
+    Ok(field)
+}
+
+/// Key used for encrypting the segments of a single object.
+struct DataKey {
+    key: LessSafeKey,
+    /// Path of the object.
+    location: String,
+}
+
+impl DataKey {
+    fn new(key: &[u8], location: &Path) -> Result<Self> {
+        let key = UnboundKey::new(&AES_256_GCM, key)
+            .map_err(|_| EncryptionError::InvalidKey("invalid data key".to_string()))?;
+        Ok(DataKey {
+            key: LessSafeKey::new(key),
+            location: location.to_string(),
+        })
+    }
+
+    fn nonce(idx: usize) -> Nonce {
+        let mut nonce = [0; NONCE_LEN];
+        nonce[NONCE_LEN - 8..].copy_from_slice(&(idx as u64).to_be_bytes());
+        Nonce::assume_unique_for_key(nonce)
+    }
+
+    /// Associated data for a segment.
+    fn aad(&self, last: bool) -> Aad<Vec<u8>> {
+        let mut aad = Vec::with_capacity(1 + self.location.len());
+        aad.push(last as u8);
+        aad.extend_from_slice(self.location.as_bytes());
+        Aad::from(aad)
+    }
+
+    /// Encrypt the segment, appending the ciphertext and tag to `buf`.
+    fn seal_segment(
+        &self,
+        idx: usize,
+        last: bool,
+        segment: &[u8],
+        buf: &mut Vec<u8>,
+    ) -> Result<()> {
+        let start = buf.len();
+        buf.extend_from_slice(segment);
+        let tag = self
+            .key
+            .seal_in_place_separate_tag(Self::nonce(idx), self.aad(last), &mut buf[start..])
+            .map_err(|_| EncryptionError::Encrypt)?;
+        buf.extend_from_slice(tag.as_ref());
+        Ok(())
+    }
+
+    /// Decrypt consecutive segments, starting with the segment at index
+    /// `first` of an object with `count` segments.
+    fn open_segments(&self, first: usize, count: usize, buf: &[u8]) -> Result<Vec<u8>> {
+        let mut plaintext = Vec::with_capacity(buf.len());
+        for (idx, segment) in buf.chunks(ENCRYPTED_SEGMENT_LEN).enumerate() {
+            if segment.len() < TAG_LEN {
+                return Err(EncryptionError::Decrypt);
+            }
+            let idx = first + idx;
+            let mut segment = segment.to_vec();
+            let opened = self
+                .key
+                .open_in_place(Self::nonce(idx), self.aad(idx + 1 == count), &mut segment)
+                .map_err(|_| EncryptionError::Decrypt)?;
+            plaintext.extend_from_slice(opened);
+        }
+        Ok(plaintext)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::local::LocalKeyProvider;
+    use super::*;
+
+    fn test_provider() -> LocalKeyProvider {
+        LocalKeyProvider::new(&[7; 32]).unwrap()
+    }
+
+    fn test_location() -> Path {
+        Path::from("catalog")
+    }
+
+    #[tokio::test]
+    async fn roundtrip() {
+        let provider = test_provider();
+        for len in [
+            0,
+            1,
+            SEGMENT_LEN - 1,
+            SEGMENT_LEN,
+            SEGMENT_LEN + 1,
+            3 * SEGMENT_LEN + 5,
+        ] {
+            let plaintext: Vec<u8> = (0..len).map(|i| i as u8).collect();
+
+            let encrypted = encrypt(&provider, &test_location(), &plaintext)
+                .await
+                .unwrap();
+            assert!(is_encrypted(&encrypted));
+            assert_eq!(encrypted_len(len), encrypted.len(), "len: {len}");
+            assert_eq!(Some(len), plaintext_len(encrypted.len()), "len: {len}");
+
+            let decrypted = decrypt(&provider, &test_location(), &encrypted)
+                .await
+                .unwrap();
+            assert_eq!(plaintext, decrypted.as_ref(), "len: {len}");
+        }
+    }
+
+    #[tokio::test]
+    async fn wrong_key() {
+        let encrypted = encrypt(&test_provider(), &test_location(), b"catalog")
+            .await
+            .unwrap();
+
+        let other = LocalKeyProvider::new(&[8; 32]).unwrap();
+        let err = decrypt(&other, &test_location(), &encrypted)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, EncryptionError::UnknownKey(_)), "{err}");
+    }
+
+    #[tokio::test]
+    async fn tampered() {
+        let provider = test_provider();
+        let plaintext = vec![1; 2 * SEGMENT_LEN];
+        let encrypted = encrypt(&provider, &test_location(), &plaintext)
+            .await
+            .unwrap();
+
+        // Flipped bit in a segment.
+        let mut modified = encrypted.to_vec();
+        modified[HEADER_LEN + 10] ^= 1;
+        let err = decrypt(&provider, &test_location(), &modified)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, EncryptionError::Decrypt), "{err}");
+
+        // Swapped segments.
+        let mut modified = encrypted.to_vec();
+        let (first, second) = modified[HEADER_LEN..].split_at_mut(ENCRYPTED_SEGMENT_LEN);
+        first.swap_with_slice(second);
+        let err = decrypt(&provider, &test_location(), &modified)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, EncryptionError::Decrypt), "{err}");
+
+        // Dropped last segment, or all segments.
+        for len in [HEADER_LEN + ENCRYPTED_SEGMENT_LEN, HEADER_LEN] {
+            let err = decrypt(&provider, &test_location(), &encrypted[..len])
+                .await
+                .unwrap_err();
+            assert!(matches!(err, EncryptionError::Decrypt), "{err}");
+        }
+
+        // Appended copy of a segment.
+        let mut modified = encrypted.to_vec();
+        modified.extend_from_slice(&encrypted[HEADER_LEN..HEADER_LEN + ENCRYPTED_SEGMENT_LEN]);
+        let err = decrypt(&provider, &test_location(), &modified)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, EncryptionError::Decrypt), "{err}");
+    }
+
+    #[tokio::test]
+    async fn moved() {
+        let provider = test_provider();
+        let encrypted = encrypt(&provider, &test_location(), b"catalog")
+            .await
+            .unwrap();
+
+        let err = decrypt(&provider, &Path::from("other"), &encrypted)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, EncryptionError::Decrypt), "{err}");
+    }
+
+    #[tokio::test]
+    async fn rotated_key() {
+        let encrypted = encrypt(&test_provider(), &test_location(), b"catalog")
+            .await
+            .unwrap();
+
+        let rotated = LocalKeyProvider::new(&[8; 32])
+            .unwrap()
+            .with_previous_key(&[7; 32])
+            .unwrap();
+        let decrypted = decrypt(&rotated, &test_location(), &encrypted)
+            .await
+            .unwrap();
+        assert_eq!(b"catalog", decrypted.as_ref());
+    }
+
+    #[tokio::test]
+    async fn not_encrypted() {
+        let err = decrypt(&test_provider(), &test_location(), b"plaintext")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, EncryptionError::NotEncrypted), "{err}");
+    }
+
+    #[test]
+    fn ranges_to_segments() {
+        let len = 3 * SEGMENT_LEN + 100;
+        let encrypted = encrypted_len(len);
+
+        assert_eq!(
+            (0, HEADER_LEN..HEADER_LEN + ENCRYPTED_SEGMENT_LEN),
+            segments_for_range(&(0..10), len)
+        );
+        assert_eq!(
+            (0, HEADER_LEN..HEADER_LEN + 2 * ENCRYPTED_SEGMENT_LEN),
+            segments_for_range(&(SEGMENT_LEN - 1..SEGMENT_LEN + 1), len)
+        );
+        assert_eq!(
+            (3, HEADER_LEN + 3 * ENCRYPTED_SEGMENT_LEN..encrypted),
+            segments_for_range(&(len - 8..len), len)
+        );
+    }
+}
@@ -0,0 +1,577 @@
+use std::ops::Range;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{ready, Context, Poll};
+use std::{fmt, io};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::stream::BoxStream;
+use futures::{StreamExt, TryStreamExt};
+use object_store::path::Path;
+use object_store::{
+    GetOptions,
+    GetRange,
+    GetResult,
+    GetResultPayload,
+    ListResult,
+    MultipartId,
+    ObjectMeta,
+    ObjectStore,
+    PutOptions,
+    PutResult,
+    Result,
+};
+use tokio::io::AsyncWrite;
+use uuid::Uuid;
+
+use super::{
+    decrypt,
+    encrypt,
+    generate_data_key,
+    new_object_key,
+    open_header,
+    plaintext_len,
+    segment_count,
+    segments_for_range,
+    DataKey,
+    EncryptionError,
+    KeyProvider,
+    HEADER_LEN,
+    SEGMENT_LEN,
+};
+
+/// An object store encrypting objects before writing them to the inner store,
+/// and decrypting objects read from the inner store.
+///
+/// Sizes returned from `head` and `list` are the sizes of the plaintext, and
+/// ranges are plaintext ranges. Range reads only fetch and decrypt the segments
+/// containing the range (see the [module docs](super)).
+///
+/// Encrypted objects are bound to their path, so copies and renames read and
+/// encrypt the object again for its new path.
+///
+/// Used for native tables. The Parquet version used by the Delta Lake writer
+/// doesn't support Parquet modular encryption, so table files are encrypted as
+/// whole objects instead.
+///
+/// All objects read through this store are expected to be encrypted, so
+/// encryption should only be enabled for empty locations.
+#[derive(Debug, Clone)]
+pub struct EncryptedObjectStore {
+    inner: Arc<dyn ObjectStore>,
+    provider: Arc<dyn KeyProvider>,
+}
+
+impl EncryptedObjectStore {
+    pub fn new(inner: Arc<dyn ObjectStore>, provider: Arc<dyn KeyProvider>) -> Self {
+        EncryptedObjectStore { inner, provider }
+    }
+
+    async fn get_plaintext_range(
+        &self,
+        location: &Path,
+        range: GetRange,
+        options: GetOptions,
+    ) -> Result<GetResult> {
+        let result = self
+            .inner
+            .get_opts(
+                location,
+                GetOptions {
+                    range: Some((0..HEADER_LEN).into()),
+                    ..options
+                },
+            )
+            .await?;
+        let meta = result.meta.clone();
+        let header = result.bytes().await?;
+        let key = open_header(self.provider.as_ref(), location, &header).await?;
+
+        let len = plaintext_len(meta.size).ok_or(EncryptionError::Decrypt)?;
+        let range = plaintext_range(range, len)?;
+        if range.is_empty() {
+            return Ok(plaintext_result(meta, len, Bytes::new(), range));
+        }
+
+        let (first, encrypted_range) = segments_for_range(&range, len);
+        let segments = self
+            .inner
+            .get_opts(
+                location,
+                GetOptions {
+                    range: Some(encrypted_range.into()),
+                    // Make sure the object wasn't replaced since reading the
+                    // header.
+                    if_match: meta.e_tag.clone(),
+                    ..Default::default()
+                },
+            )
+            .await?
+            .bytes()
+            .await?;
+
+        let offset = range.start - first * SEGMENT_LEN;
+        let plaintext = Bytes::from(key.open_segments(first, segment_count(len), &segments)?)
+            .slice(offset..offset + range.len());
+
+        Ok(plaintext_result(meta, len, plaintext, range))
+    }
+}
+
+impl fmt::Display for EncryptedObjectStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "EncryptedObjectStore({})", self.inner)
+    }
+}
+
+/// Resolve a requested range against the size of the plaintext, following the
+/// same rules as the stores in `object_store`.
+///
+/// Bounded ranges must not be empty, and ranges must start within the
+/// plaintext. Ranges ending past the end of the plaintext are truncated.
+fn plaintext_range(range: GetRange, len: usize) -> Result<Range<usize>, EncryptionError> {
+    match &range {
+        GetRange::Bounded(r) if r.start < r.end && r.start < len => Ok(r.start..r.end.min(len)),
+        GetRange::Offset(offset) if *offset < len => Ok(*offset..len),
+        GetRange::Suffix(n) => Ok(len.saturating_sub(*n)..len),
+        _ => Err(EncryptionError::InvalidRange { range, len }),
+    }
+}
+
+/// Replace the size of an encrypted object with the size of its plaintext.
+///
+/// Sizes of objects that can't be encrypted (e.g. leases written by metastore
+/// next to native tables) are left as is.
+fn plaintext_meta(mut meta: ObjectMeta) -> ObjectMeta {
+    if let Some(size) = plaintext_len(meta.size) {
+        meta.size = size;
+    }
+    meta
+}
+
+fn plaintext_result(
+    mut meta: ObjectMeta,
+    size: usize,
+    bytes: Bytes,
+    range: Range<usize>,
+) -> GetResult {
+    meta.size = size;
+    GetResult {
+        payload: GetResultPayload::Stream(
+            futures::stream::once(async move { Ok::<_, object_store::Error>(bytes) }).boxed(),
+        ),
+        meta,
+        range,
+    }
+}
+
+#[async_trait]
+impl ObjectStore for EncryptedObjectStore {
+    async fn put(&self, location: &Path, bytes: Bytes) -> Result<PutResult> {
+        self.put_opts(location, bytes, PutOptions::default()).await
+    }
+
+    async fn put_opts(&self, location: &Path, bytes: Bytes, opts: PutOptions) -> Result<PutResult> {
+        let encrypted = encrypt(self.provider.as_ref(), location, &bytes).await?;
+        self.inner.put_opts(location, encrypted, opts).await
+    }
+
+    /// Segments are encrypted as they're written and streamed to a multipart
+    /// upload of the inner store. Only the current segment is buffered.
+    async fn put_multipart(
+        &self,
+        location: &Path,
+    ) -> Result<(MultipartId, Box<dyn AsyncWrite + Unpin + Send>)> {
+        let data_key = generate_data_key()?;
+        let mut out = Vec::with_capacity(HEADER_LEN);
+        let key = new_object_key(self.provider.as_ref(), location, &data_key, &mut out).await?;
+
+        let (id, inner) = self.inner.put_multipart(location).await?;
+        let upload = EncryptedUpload {
+            inner,
+            key,
+            segment: Vec::with_capacity(SEGMENT_LEN),
+            idx: 0,
+            out,
+            written: 0,
+            finished: false,
+        };
+        Ok((id, Box::new(upload)))
+    }
+
+    async fn abort_multipart(&self, location: &Path, multipart_id: &MultipartId) -> Result<()> {
+        self.inner.abort_multipart(location, multipart_id).await
+    }
+
+    async fn get_opts(&self, location: &Path, mut options: GetOptions) -> Result<GetResult> {
+        if options.head {
+            let meta = self.inner.get_opts(location, options).await?.meta;
+            let meta = plaintext_meta(meta);
+            let size = meta.size;
+            return Ok(plaintext_result(meta, size, Bytes::new(), 0..size));
+        }
+
+        match options.range.take() {
+            Some(range) => self.get_plaintext_range(location, range, options).await,
+            None => {
+                let result = self.inner.get_opts(location, options).await?;
+                let meta = result.meta.clone();
+                let encrypted = result.bytes().await?;
+                let plaintext = decrypt(self.provider.as_ref(), location, &encrypted).await?;
+                let size = plaintext.len();
+                Ok(plaintext_result(meta, size, plaintext, 0..size))
+            }
+        }
+    }
+
+    async fn head(&self, location: &Path) -> Result<ObjectMeta> {
+        Ok(plaintext_meta(self.inner.head(location).await?))
+    }
+
+    async fn delete(&self, location: &Path) -> Result<()> {
+        self.inner.delete(location).await
+    }
+
+    fn list(&self, prefix: Option<&Path>) -> BoxStream<'_, Result<ObjectMeta>> {
+        self.inner.list(prefix).map_ok(plaintext_meta).boxed()
+    }
+
+    async fn list_with_delimiter(&self, prefix: Option<&Path>) -> Result<ListResult> {
+        let mut result = self.inner.list_with_delimiter(prefix).await?;
+        result.objects = result.objects.into_iter().map(plaintext_meta).collect();
+        Ok(result)
+    }
+
+    async fn copy(&self, from: &Path, to: &Path) -> Result<()> {
+        let bytes = self.get(from).await?.bytes().await?;
+        self.put(to, bytes).await?;
+        Ok(())
+    }
+
+    /// The copy is written to a temporary object first, and then moved into
+    /// place with the inner store's conditional rename. Any mechanism the
+    /// inner store uses to make that atomic still applies.
+    async fn copy_if_not_exists(&self, from: &Path, to: &Path) -> Result<()> {
+        let bytes = self.get(from).await?.bytes().await?;
+        let encrypted = encrypt(self.provider.as_ref(), to, &bytes).await?;
+
+        let tmp = Path::from(format!("{to}.{}.tmp", Uuid::new_v4()));
+        self.inner.put(&tmp, encrypted).await?;
+        let result = self.inner.rename_if_not_exists(&tmp, to).await;
+        if result.is_err() {
+            let _ = self.inner.delete(&tmp).await;
+        }
+        result
+    }
+
+    async fn rename(&self, from: &Path, to: &Path) -> Result<()> {
+        self.copy(from, to).await?;
+        self.delete(from).await
+    }
+
+    async fn rename_if_not_exists(&self, from: &Path, to: &Path) -> Result<()> {
+        self.copy_if_not_exists(from, to).await?;
+        self.delete(from).await
+    }
+}
+
+/// Writer encrypting a multipart upload segment by segment.
+///
+/// A segment is only sealed once more data is written after it, or the writer
+/// is shut down, since the last segment is sealed differently.
+struct EncryptedUpload {
+    inner: Box<dyn AsyncWrite + Unpin + Send>,
+    key: DataKey,
+    /// Plaintext of the current segment.
+    segment: Vec<u8>,
+    /// Index of the current segment.
+    idx: usize,
+    /// Encrypted bytes not yet written to the inner writer.
+    out: Vec<u8>,
+    /// Number of bytes of `out` already written.
+    written: usize,
+    /// If the last segment was sealed.
+    finished: bool,
+}
+
+impl EncryptedUpload {
+    fn seal_segment(&mut self, last: bool) -> io::Result<()> {
+        self.key
+            .seal_segment(self.idx, last, &self.segment, &mut self.out)
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+        self.segment.clear();
+        self.idx += 1;
+        Ok(())
+    }
+
+    /// Write all pending encrypted bytes to the inner writer.
+    fn poll_write_out(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        while self.written < self.out.len() {
+            let n = ready!(Pin::new(&mut self.inner).poll_write(cx, &self.out[self.written..]))?;
+            if n == 0 {
+                return Poll::Ready(Err(io::ErrorKind::WriteZero.into()));
+            }
+            self.written += n;
+        }
+        self.out.clear();
+        self.written = 0;
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl AsyncWrite for EncryptedUpload {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        if self.finished {
+            return Poll::Ready(Err(io::Error::new(
+                io::ErrorKind::Other,
+                "write after shutdown",
+            )));
+        }
+        // Apply backpressure before accepting more data.
+        ready!(self.poll_write_out(cx))?;
+
+        let mut rest = buf;
+        while !rest.is_empty() {
+            if self.segment.len() == SEGMENT_LEN {
+                self.seal_segment(false)?;
+            }
+            let n = rest.len().min(SEGMENT_LEN - self.segment.len());
+            self.segment.extend_from_slice(&rest[..n]);
+            rest = &rest[n..];
+        }
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        // The current segment can't be flushed until it's complete.
+        ready!(self.poll_write_out(cx))?;
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        if !self.finished {
+            self.seal_segment(true)?;
+            self.finished = true;
+        }
+        ready!(self.poll_write_out(cx))?;
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use object_store::memory::InMemory;
+    use tokio::io::AsyncWriteExt;
+
+    use super::*;
+    use crate::encryption::local::LocalKeyProvider;
+    use crate::encryption::{encrypted_len, is_encrypted};
+
+    fn new_store() -> (Arc<InMemory>, EncryptedObjectStore) {
+        let inner = Arc::new(InMemory::new());
+        let provider = Arc::new(LocalKeyProvider::new(&[3; 32]).unwrap());
+        (inner.clone(), EncryptedObjectStore::new(inner, provider))
+    }
+
+    fn test_data(len: usize) -> Bytes {
+        (0..len).map(|i| (i % 251) as u8).collect::<Vec<_>>().into()
+    }
+
+    #[tokio::test]
+    async fn put_get() {
+        let (inner, store) = new_store();
+        let location = Path::from("table/part-0.parquet");
+        let data = test_data(2 * SEGMENT_LEN + 100);
+
+        store.put(&location, data.clone()).await.unwrap();
+
+        let raw = inner.get(&location).await.unwrap().bytes().await.unwrap();
+        assert!(is_encrypted(&raw));
+        assert_eq!(encrypted_len(data.len()), raw.len());
+
+        let got = store.get(&location).await.unwrap().bytes().await.unwrap();
+        assert_eq!(data, got);
+
+        let meta = store.head(&location).await.unwrap();
+        assert_eq!(data.len(), meta.size);
+
+        let listed: Vec<_> = store.list(None).try_collect().await.unwrap();
+        assert_eq!(vec![meta], listed);
+    }
+
+    #[tokio::test]
+    async fn get_ranges() {
+        let (_, store) = new_store();
+        let location = Path::from("data");
+        let len = 3 * SEGMENT_LEN + 100;
+        let data = test_data(len);
+        store.put(&location, data.clone()).await.unwrap();
+
+        let ranges = [
+            0..10,
+            5..SEGMENT_LEN,
+            SEGMENT_LEN - 3..SEGMENT_LEN + 3,
+            SEGMENT_LEN..2 * SEGMENT_LEN,
+            len - 8..len,
+            0..len,
+        ];
+        for range in ranges {
+            let got = store.get_range(&location, range.clone()).await.unwrap();
+            assert_eq!(data.slice(range.clone()), got, "range: {range:?}");
+        }
+
+        // Ranges past the end are truncated.
+        let got = store.get_range(&location, len - 8..len + 1).await.unwrap();
+        assert_eq!(data.slice(len - 8..len), got);
+
+        store.get_range(&location, len..len + 1).await.unwrap_err();
+        store.get_range(&location, 7..7).await.unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn get_offset_and_suffix() {
+        let (_, store) = new_store();
+        let location = Path::from("data");
+        let len = 2 * SEGMENT_LEN + 100;
+        let data = test_data(len);
+        store.put(&location, data.clone()).await.unwrap();
+
+        let get = |range: GetRange| {
+            let store = store.clone();
+            let location = location.clone();
+            async move {
+                let options = GetOptions {
+                    range: Some(range),
+                    ..Default::default()
+                };
+                let result = store.get_opts(&location, options).await?;
+                let range = result.range.clone();
+                Ok::<_, object_store::Error>((range, result.bytes().await?))
+            }
+        };
+
+        for offset in [0, 5, SEGMENT_LEN + 3, len - 1] {
+            let (range, got) = get(GetRange::Offset(offset)).await.unwrap();
+            assert_eq!(offset..len, range);
+            assert_eq!(data.slice(offset..), got, "offset: {offset}");
+        }
+        get(GetRange::Offset(len)).await.unwrap_err();
+
+        for n in [1, 100, SEGMENT_LEN + 1, len] {
+            let (range, got) = get(GetRange::Suffix(n)).await.unwrap();
+            assert_eq!(len - n..len, range);
+            assert_eq!(data.slice(len - n..), got, "suffix: {n}");
+        }
+        // Suffixes longer than the object return the entire object.
+        let (_, got) = get(GetRange::Suffix(len + 10)).await.unwrap();
+        assert_eq!(data, got);
+    }
+
+    #[tokio::test]
+    async fn multipart() {
+        for len in [0, SEGMENT_LEN, SEGMENT_LEN + 1, 3 * SEGMENT_LEN + 7] {
+            let (inner, store) = new_store();
+            let location = Path::from("data");
+            let data = test_data(len);
+
+            let (_, mut writer) = store.put_multipart(&location).await.unwrap();
+            for chunk in data.chunks(1000) {
+                writer.write_all(chunk).await.unwrap();
+            }
+            writer.shutdown().await.unwrap();
+
+            let raw = inner.head(&location).await.unwrap();
+            assert_eq!(encrypted_len(len), raw.size, "len: {len}");
+
+            let got = store.get(&location).await.unwrap().bytes().await.unwrap();
+            assert_eq!(data, got, "len: {len}");
+        }
+    }
+
+    #[tokio::test]
+    async fn multipart_abort() {
+        let (inner, store) = new_store();
+        let location = Path::from("data");
+
+        let (id, mut writer) = store.put_multipart(&location).await.unwrap();
+        writer.write_all(&test_data(100)).await.unwrap();
+        store.abort_multipart(&location, &id).await.unwrap();
+
+        inner.head(&location).await.unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn copy_rename() {
+        let (_, store) = new_store();
+        let data = test_data(100);
+        store.put(&Path::from("a"), data.clone()).await.unwrap();
+
+        store
+            .copy(&Path::from("a"), &Path::from("b"))
+            .await
+            .unwrap();
+        store
+            .rename(&Path::from("b"), &Path::from("c"))
+            .await
+            .unwrap();
+
+        let got = store
+            .get(&Path::from("c"))
+            .await
+            .unwrap()
+            .bytes()
+            .await
+            .unwrap();
+        assert_eq!(data, got);
+        store.head(&Path::from("b")).await.unwrap_err();
+
+        // Encrypted objects are bound to their path.
+        store
+            .copy_if_not_exists(&Path::from("a"), &Path::from("c"))
+            .await
+            .unwrap_err();
+        store
+            .copy_if_not_exists(&Path::from("a"), &Path::from("d"))
+            .await
+            .unwrap();
+        let got = store
+            .get(&Path::from("d"))
+            .await
+            .unwrap()
+            .bytes()
+            .await
+            .unwrap();
+        assert_eq!(data, got);
+        let listed: Vec<_> = store.list(None).try_collect().await.unwrap();
+        assert_eq!(3, listed.len(), "{listed:?}");
+    }
+
+    #[tokio::test]
+    async fn moved_object() {
+        let (inner, store) = new_store();
+        store.put(&Path::from("a"), test_data(100)).await.unwrap();
+
+        // Moved without being encrypted again.
+        inner
+            .rename(&Path::from("a"), &Path::from("b"))
+            .await
+            .unwrap();
+        store.get(&Path::from("b")).await.unwrap_err();
+        store.get_range(&Path::from("b"), 0..10).await.unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn read_unencrypted() {
+        let (inner, store) = new_store();
+        let location = Path::from("plain");
+        inner.put(&location, test_data(1000)).await.unwrap();
+
+        store.get(&location).await.unwrap_err();
+        store.get_range(&location, 0..10).await.unwrap_err();
+    }
+}
@@ -1,4 +1,5 @@
 //! Utilities for the object store crate.
 pub mod conf;
+pub mod encryption;
 pub mod shared;
 pub mod temp;
@@ -9,7 +9,7 @@ pub use sqlparser;
 use sqlparser::ast::{self, ColumnDef, Ident, ObjectName};
 use sqlparser::dialect::GenericDialect;
 use sqlparser::keywords::Keyword;
-use sqlparser::parser::{Parser, ParserError, ParserOptions};
+use sqlparser::parser::{IsOptional, Parser, ParserError, ParserOptions};
 use sqlparser::tokenizer::{Token, Tokenizer, Word};
 
 use self::options::{OptionValue, StatementOptions};
@@ -193,6 +193,80 @@ impl fmt::Display for AlterTableStmtExtension {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OptimizeTableStmt {
+    /// Name of the table to optimize.
+    pub name: ObjectName,
+    /// Columns to cluster the table's data by.
+    pub zorder_by: Vec<Ident>,
+}
+
+impl fmt::Display for OptimizeTableStmt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "OPTIMIZE {}", self.name)?;
+        if !self.zorder_by.is_empty() {
+            write!(f, " ZORDER BY (")?;
+            let mut sep = "";
+            for col in self.zorder_by.iter() {
+                write!(f, "{sep}{col}")?;
+                sep = ", ";
+            }
+            write!(f, ")")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RefreshMaterializedViewStmt {
+    /// Name of the materialized view to refresh.
+    pub name: ObjectName,
+    /// Column used to only append rows newer than the rows already in the
+    /// view. The view's contents are replaced entirely if not provided.
+    ///
+    /// Incremental refreshes are append-only. Rows already in the view are
+    /// never updated or removed, so a full refresh is needed to pick up
+    /// updates and deletes.
+    pub incremental_column: Option<Ident>,
+    /// If the source was declared as append-only (`APPEND ONLY`), meaning
+    /// existing rows are never updated or deleted.
+    pub append_only: bool,
+}
+
+impl fmt::Display for RefreshMaterializedViewStmt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "REFRESH MATERIALIZED VIEW {}", self.name)?;
+        if let Some(col) = &self.incremental_column {
+            write!(f, " INCREMENTALLY USING {col}")?;
+            if self.append_only {
+                write!(f, " APPEND ONLY")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DropMaterializedViewStmt {
+    pub names: Vec<ObjectName>,
+    pub if_exists: bool,
+}
+
+impl fmt::Display for DropMaterializedViewStmt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "DROP MATERIALIZED VIEW ")?;
+        if self.if_exists {
+            write!(f, "IF EXISTS ")?;
+        }
+        let mut sep = "";
+        for name in self.names.iter() {
+            write!(f, "{sep}{name}")?;
+            sep = ", ";
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CreateTunnelStmt {
     /// Name of the tunnel as it exists in GlareDB.
@@ -426,6 +500,12 @@ pub enum StatementWithExtensions {
     AlterDatabase(AlterDatabaseStmt),
     // Alter table extension.
     AlterTableExtension(AlterTableStmtExtension),
+    /// Optimize table extension.
+    OptimizeTable(OptimizeTableStmt),
+    /// Refresh materialized view extension.
+    RefreshMaterializedView(RefreshMaterializedViewStmt),
+    /// Drop materialized view extension.
+    DropMaterializedView(DropMaterializedViewStmt),
     /// Create tunnel extension.
     CreateTunnel(CreateTunnelStmt),
     /// Drop tunnel extension.
@@ -449,6 +529,9 @@ impl fmt::Display for StatementWithExtensions {
             StatementWithExtensions::DropDatabase(stmt) => write!(f, "{}", stmt),
             StatementWithExtensions::AlterDatabase(stmt) => write!(f, "{}", stmt),
             StatementWithExtensions::AlterTableExtension(stmt) => write!(f, "{}", stmt),
+            StatementWithExtensions::OptimizeTable(stmt) => write!(f, "{}", stmt),
+            StatementWithExtensions::RefreshMaterializedView(stmt) => write!(f, "{}", stmt),
+            StatementWithExtensions::DropMaterializedView(stmt) => write!(f, "{}", stmt),
             StatementWithExtensions::CreateTunnel(stmt) => write!(f, "{}", stmt),
             StatementWithExtensions::DropTunnel(stmt) => write!(f, "{}", stmt),
             StatementWithExtensions::AlterTunnel(stmt) => write!(f, "{}", stmt),
@@ -556,6 +639,8 @@ impl<'a> GlareDbParser<'a> {
                     self.parser.next_token();
                     self.parse_copy()
                 }
+                _ if self.consume_token(&Token::make_keyword("OPTIMIZE")) => self.parse_optimize(),
+                _ if self.consume_token(&Token::make_keyword("REFRESH")) => self.parse_refresh(),
                 _ => Ok(StatementWithExtensions::Statement(
                     self.parser.parse_statement()?,
                 )),
@@ -662,6 +747,67 @@ impl<'a> GlareDbParser<'a> {
         }))
     }
 
+    /// Parse an OPTIMIZE statement.
+    ///
+    /// OPTIMIZE [TABLE] <name> [ZORDER BY (<col>, ...)]
+    fn parse_optimize(&mut self) -> Result<StatementWithExtensions, ParserError> {
+        let _ = self.parser.parse_keyword(Keyword::TABLE);
+        let name = self.parser.parse_object_name(false)?;
+        validate_object_name(&name)?;
+
+        let zorder_by = if self.consume_token(&Token::make_keyword("ZORDER")) {
+            self.parser.expect_keyword(Keyword::BY)?;
+            let cols = self
+                .parser
+                .parse_parenthesized_column_list(IsOptional::Mandatory, false)?;
+            for col in cols.iter() {
+                validate_ident(col)?;
+            }
+            cols
+        } else {
+            Vec::new()
+        };
+
+        Ok(StatementWithExtensions::OptimizeTable(OptimizeTableStmt {
+            name,
+            zorder_by,
+        }))
+    }
+
+    /// Parse a REFRESH statement.
+    ///
+    /// REFRESH MATERIALIZED VIEW <name> [INCREMENTALLY USING <col> [APPEND ONLY]]
+    fn parse_refresh(&mut self) -> Result<StatementWithExtensions, ParserError> {
+        self.parser
+            .expect_keywords(&[Keyword::MATERIALIZED, Keyword::VIEW])?;
+        let name = self.parser.parse_object_name(false)?;
+        validate_object_name(&name)?;
+
+        let (incremental_column, append_only) =
+            if self.consume_token(&Token::make_keyword("INCREMENTALLY")) {
+                self.parser.expect_keyword(Keyword::USING)?;
+                let col = self.parser.parse_identifier(false)?;
+                validate_ident(&col)?;
+                let append_only = if self.consume_token(&Token::make_keyword("APPEND")) {
+                    self.parser.expect_keyword(Keyword::ONLY)?;
+                    true
+                } else {
+                    false
+                };
+                (Some(col), append_only)
+            } else {
+                (None, false)
+            };
+
+        Ok(StatementWithExtensions::RefreshMaterializedView(
+            RefreshMaterializedViewStmt {
+                name,
+                incremental_column,
+                append_only,
+            },
+        ))
+    }
+
     /// Report unexpected token.
     fn expected<T>(&self, expected: &str, found: Token) -> Result<T, ParserError> {
         Err(ParserError::ParserError(format!(
@@ -977,6 +1123,12 @@ impl<'a> GlareDbParser<'a> {
         } else if self.consume_token(&Token::make_keyword("CREDENTIALS")) {
             // DROP CREDENTIALS ...
             self.parse_drop_credentials()
+        } else if self
+            .parser
+            .parse_keywords(&[Keyword::MATERIALIZED, Keyword::VIEW])
+        {
+            // DROP MATERIALIZED VIEW ...
+            self.parse_drop_materialized_view()
         } else {
             // Fall back to underlying parser.
             Ok(StatementWithExtensions::Statement(
@@ -1002,6 +1154,22 @@ impl<'a> GlareDbParser<'a> {
         }))
     }
 
+    fn parse_drop_materialized_view(&mut self) -> Result<StatementWithExtensions, ParserError> {
+        let if_exists = self.parser.parse_keywords(&[Keyword::IF, Keyword::EXISTS]);
+
+        let names = self
+            .parser
+            .parse_comma_separated(|parser| parser.parse_object_name(false))?;
+
+        for name in names.iter() {
+            validate_object_name(name)?;
+        }
+
+        Ok(StatementWithExtensions::DropMaterializedView(
+            DropMaterializedViewStmt { names, if_exists },
+        ))
+    }
+
     fn parse_drop_tunnel(&mut self) -> Result<StatementWithExtensions, ParserError> {
         let if_exists = self.parser.parse_keywords(&[Keyword::IF, Keyword::EXISTS]);
 
@@ -1401,6 +1569,44 @@ mod tests {
         }
     }
 
+    #[test]
+    fn optimize_table_roundtrips() {
+        let test_cases = [
+            "OPTIMIZE my_table",
+            "OPTIMIZE my_schema.my_table",
+            "OPTIMIZE my_table ZORDER BY (a)",
+            "OPTIMIZE my_table ZORDER BY (a, b)",
+        ];
+
+        for test_case in test_cases {
+            let stmt = GlareDbParser::parse_sql(test_case)
+                .unwrap()
+                .pop_front()
+                .unwrap();
+            assert_eq!(test_case, stmt.to_string().as_str());
+        }
+    }
+
+    #[test]
+    fn materialized_view_roundtrips() {
+        let test_cases = [
+            "REFRESH MATERIALIZED VIEW my_view",
+            "REFRESH MATERIALIZED VIEW my_schema.my_view",
+            "REFRESH MATERIALIZED VIEW my_view INCREMENTALLY USING updated_at",
+            "REFRESH MATERIALIZED VIEW my_view INCREMENTALLY USING updated_at APPEND ONLY",
+            "DROP MATERIALIZED VIEW my_view",
+            "DROP MATERIALIZED VIEW IF EXISTS my_view, my_schema.other_view",
+        ];
+
+        for test_case in test_cases {
+            let stmt = GlareDbParser::parse_sql(test_case)
+                .unwrap()
+                .pop_front()
+                .unwrap();
+            assert_eq!(test_case, stmt.to_string().as_str());
+        }
+    }
+
     #[test]
     fn copy_to_roundtrips() {
         let test_cases = [
@@ -760,6 +760,7 @@ where
             ExecutionResult::DropCredentials => {
                 Self::command_complete(conn, "DROP CREDENTIALS").await?
             }
+            ExecutionResult::OptimizeTable => Self::command_complete(conn, "OPTIMIZE").await?,
         };
         Ok(())
     }
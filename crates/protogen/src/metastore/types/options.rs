@@ -109,6 +109,8 @@ pub enum DatabaseOptions {
     Clickhouse(DatabaseOptionsClickhouse),
     Cassandra(DatabaseOptionsCassandra),
     Sqlite(DatabaseOptionsSqlite),
+    Oracle(DatabaseOptionsOracle),
+    DuckDb(DatabaseOptionsDuckDb),
 }
 
 impl DatabaseOptions {
@@ -124,6 +126,8 @@ impl DatabaseOptions {
     pub const CLICKHOUSE: &'static str = "clickhouse";
     pub const CASSANDRA: &'static str = "cassandra";
     pub const SQLITE: &'static str = "sqlite";
+    pub const ORACLE: &'static str = "oracle";
+    pub const DUCKDB: &'static str = "duckdb";
 
     pub fn as_str(&self) -> &'static str {
         match self {
@@ -139,6 +143,8 @@ impl DatabaseOptions {
             DatabaseOptions::Clickhouse(_) => Self::CLICKHOUSE,
             DatabaseOptions::Cassandra(_) => Self::CASSANDRA,
             DatabaseOptions::Sqlite(_) => Self::SQLITE,
+            DatabaseOptions::Oracle(_) => Self::ORACLE,
+            DatabaseOptions::DuckDb(_) => Self::DUCKDB,
         }
     }
 }
@@ -181,6 +187,8 @@ impl TryFrom<options::database_options::Options> for DatabaseOptions {
                 DatabaseOptions::Cassandra(v.try_into()?)
             }
             options::database_options::Options::Sqlite(v) => DatabaseOptions::Sqlite(v.try_into()?),
+            options::database_options::Options::Oracle(v) => DatabaseOptions::Oracle(v.try_into()?),
+            options::database_options::Options::Duckdb(v) => DatabaseOptions::DuckDb(v.try_into()?),
         })
     }
 }
@@ -215,6 +223,8 @@ impl From<DatabaseOptions> for options::database_options::Options {
                 options::database_options::Options::Cassandra(v.into())
             }
             DatabaseOptions::Sqlite(v) => options::database_options::Options::Sqlite(v.into()),
+            DatabaseOptions::Oracle(v) => options::database_options::Options::Oracle(v.into()),
+            DatabaseOptions::DuckDb(v) => options::database_options::Options::Duckdb(v.into()),
         }
     }
 }
@@ -309,6 +319,9 @@ impl From<DatabaseOptionsBigQuery> for options::DatabaseOptionsBigQuery {
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct DatabaseOptionsMysql {
     pub connection_string: String,
+    /// TLS settings for the connection.
+    #[serde(default)]
+    pub tls: TlsOptions,
 }
 
 impl TryFrom<options::DatabaseOptionsMysql> for DatabaseOptionsMysql {
@@ -316,6 +329,7 @@ impl TryFrom<options::DatabaseOptionsMysql> for DatabaseOptionsMysql {
     fn try_from(value: options::DatabaseOptionsMysql) -> Result<Self, Self::Error> {
         Ok(DatabaseOptionsMysql {
             connection_string: value.connection_string,
+            tls: value.tls.map(Into::into).unwrap_or_default(),
         })
     }
 }
@@ -324,6 +338,7 @@ impl From<DatabaseOptionsMysql> for options::DatabaseOptionsMysql {
     fn from(value: DatabaseOptionsMysql) -> Self {
         options::DatabaseOptionsMysql {
             connection_string: value.connection_string,
+            tls: Some(value.tls.into()),
         }
     }
 }
@@ -331,6 +346,16 @@ impl From<DatabaseOptionsMysql> for options::DatabaseOptionsMysql {
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct DatabaseOptionsMongoDb {
     pub connection_string: String,
+    /// TLS settings for the connection.
+    #[serde(default)]
+    pub tls: TlsOptions,
+    /// Number of documents to sample when inferring the schema of a
+    /// collection.
+    #[serde(default)]
+    pub sample_size: Option<u64>,
+    /// Read every document when inferring the schema of a collection.
+    #[serde(default)]
+    pub full_scan: bool,
 }
 
 impl TryFrom<options::DatabaseOptionsMongoDb> for DatabaseOptionsMongoDb {
@@ -338,6 +363,9 @@ impl TryFrom<options::DatabaseOptionsMongoDb> for DatabaseOptionsMongoDb {
     fn try_from(value: options::DatabaseOptionsMongoDb) -> Result<Self, Self::Error> {
         Ok(DatabaseOptionsMongoDb {
             connection_string: value.connection_string,
+            tls: value.tls.map(Into::into).unwrap_or_default(),
+            sample_size: value.sample_size,
+            full_scan: value.full_scan,
         })
     }
 }
@@ -346,6 +374,9 @@ impl From<DatabaseOptionsMongoDb> for options::DatabaseOptionsMongoDb {
     fn from(value: DatabaseOptionsMongoDb) -> Self {
         options::DatabaseOptionsMongoDb {
             connection_string: value.connection_string,
+            tls: Some(value.tls.into()),
+            sample_size: value.sample_size,
+            full_scan: value.full_scan,
         }
     }
 }
@@ -353,6 +384,9 @@ impl From<DatabaseOptionsMongoDb> for options::DatabaseOptionsMongoDb {
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct DatabaseOptionsSqlServer {
     pub connection_string: String,
+    /// TLS settings for the connection.
+    #[serde(default)]
+    pub tls: TlsOptions,
 }
 
 impl TryFrom<options::DatabaseOptionsSqlServer> for DatabaseOptionsSqlServer {
@@ -360,6 +394,7 @@ impl TryFrom<options::DatabaseOptionsSqlServer> for DatabaseOptionsSqlServer {
     fn try_from(value: options::DatabaseOptionsSqlServer) -> Result<Self, Self::Error> {
         Ok(DatabaseOptionsSqlServer {
             connection_string: value.connection_string,
+            tls: value.tls.map(Into::into).unwrap_or_default(),
         })
     }
 }
@@ -368,6 +403,7 @@ impl From<DatabaseOptionsSqlServer> for options::DatabaseOptionsSqlServer {
     fn from(value: DatabaseOptionsSqlServer) -> Self {
         options::DatabaseOptionsSqlServer {
             connection_string: value.connection_string,
+            tls: Some(value.tls.into()),
         }
     }
 }
@@ -375,6 +411,9 @@ impl From<DatabaseOptionsSqlServer> for options::DatabaseOptionsSqlServer {
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct DatabaseOptionsClickhouse {
     pub connection_string: String,
+    /// TLS settings for the connection.
+    #[serde(default)]
+    pub tls: TlsOptions,
 }
 
 impl TryFrom<options::DatabaseOptionsClickhouse> for DatabaseOptionsClickhouse {
@@ -382,6 +421,7 @@ impl TryFrom<options::DatabaseOptionsClickhouse> for DatabaseOptionsClickhouse {
     fn try_from(value: options::DatabaseOptionsClickhouse) -> Result<Self, Self::Error> {
         Ok(DatabaseOptionsClickhouse {
             connection_string: value.connection_string,
+            tls: value.tls.map(Into::into).unwrap_or_default(),
         })
     }
 }
@@ -390,9 +430,32 @@ impl From<DatabaseOptionsClickhouse> for options::DatabaseOptionsClickhouse {
     fn from(value: DatabaseOptionsClickhouse) -> Self {
         options::DatabaseOptionsClickhouse {
             connection_string: value.connection_string,
+            tls: Some(value.tls.into()),
         }
     }
 }
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct DatabaseOptionsOracle {
+    pub connection_string: String,
+}
+
+impl TryFrom<options::DatabaseOptionsOracle> for DatabaseOptionsOracle {
+    type Error = ProtoConvError;
+    fn try_from(value: options::DatabaseOptionsOracle) -> Result<Self, Self::Error> {
+        Ok(DatabaseOptionsOracle {
+            connection_string: value.connection_string,
+        })
+    }
+}
+
+impl From<DatabaseOptionsOracle> for options::DatabaseOptionsOracle {
+    fn from(value: DatabaseOptionsOracle) -> Self {
+        options::DatabaseOptionsOracle {
+            connection_string: value.connection_string,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct DatabaseOptionsCassandra {
     pub host: String,
@@ -429,6 +492,8 @@ pub struct DatabaseOptionsSnowflake {
     pub database_name: String,
     pub warehouse: String,
     pub role_name: String,
+    /// Key pair or OAuth credentials, used instead of the password if set.
+    pub credentials: Option<CredentialsOptionsSnowflake>,
 }
 
 impl TryFrom<options::DatabaseOptionsSnowflake> for DatabaseOptionsSnowflake {
@@ -441,6 +506,7 @@ impl TryFrom<options::DatabaseOptionsSnowflake> for DatabaseOptionsSnowflake {
             database_name: value.database_name,
             warehouse: value.warehouse,
             role_name: value.role_name,
+            credentials: value.credentials.optional()?,
         })
     }
 }
@@ -454,6 +520,7 @@ impl From<DatabaseOptionsSnowflake> for options::DatabaseOptionsSnowflake {
             database_name: value.database_name,
             warehouse: value.warehouse,
             role_name: value.role_name,
+            credentials: value.credentials.map(|c| c.into()),
         }
     }
 }
@@ -583,6 +650,45 @@ impl From<StorageOptions> for options::StorageOptions {
     }
 }
 
+/// TLS settings for connecting to an external database.
+///
+/// Certificates and keys are paths to PEM files.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TlsOptions {
+    /// One of 'disable', 'prefer' or 'require'. Uses the connection string's
+    /// setting (or the client default) if not set.
+    pub ssl_mode: Option<String>,
+    /// CA certificates to trust in addition to the default root
+    /// certificates.
+    pub ca_cert: Option<String>,
+    /// Client certificate to present to the server.
+    pub client_cert: Option<String>,
+    /// Private key for the client certificate.
+    pub client_key: Option<String>,
+}
+
+impl From<options::TlsOptions> for TlsOptions {
+    fn from(value: options::TlsOptions) -> Self {
+        TlsOptions {
+            ssl_mode: value.ssl_mode,
+            ca_cert: value.ca_cert,
+            client_cert: value.client_cert,
+            client_key: value.client_key,
+        }
+    }
+}
+
+impl From<TlsOptions> for options::TlsOptions {
+    fn from(value: TlsOptions) -> Self {
+        options::TlsOptions {
+            ssl_mode: value.ssl_mode,
+            ca_cert: value.ca_cert,
+            client_cert: value.client_cert,
+            client_key: value.client_key,
+        }
+    }
+}
+
 impl TryFrom<&mut StatementOptions> for StorageOptions {
     type Error = parser::errors::ParserError;
 
@@ -705,6 +811,8 @@ pub enum TableOptionsV0 {
     Cassandra(TableOptionsCassandra),
     Excel(TableOptionsExcel),
     Sqlite(TableOptionsObjectStore),
+    Oracle(TableOptionsOracle),
+    DuckDb(TableOptionsObjectStore),
 }
 
 impl TableOptionsV0 {
@@ -728,9 +836,15 @@ impl TableOptionsV0 {
     pub const CASSANDRA: &'static str = "cassandra";
     pub const EXCEL: &'static str = "excel";
     pub const SQLITE: &'static str = "sqlite";
+    pub const ORACLE: &'static str = "oracle";
+    pub const DUCKDB: &'static str = "duckdb";
 
     pub const fn new_internal(columns: Vec<InternalColumnDefinition>) -> TableOptionsV0 {
-        TableOptionsV0::Internal(TableOptionsInternal { columns })
+        TableOptionsV0::Internal(TableOptionsInternal {
+            columns,
+            partition_by: Vec::new(),
+            view_sql: None,
+        })
     }
 
     pub fn as_str(&self) -> &'static str {
@@ -755,6 +869,8 @@ impl TableOptionsV0 {
             TableOptionsV0::Cassandra(_) => Self::CASSANDRA,
             TableOptionsV0::Excel(_) => Self::EXCEL,
             TableOptionsV0::Sqlite(_) => Self::SQLITE,
+            TableOptionsV0::Oracle(_) => Self::ORACLE,
+            TableOptionsV0::DuckDb(_) => Self::DUCKDB,
         }
     }
 }
@@ -782,6 +898,8 @@ impl From<TableOptionsV0> for TableOptionsV1 {
             TableOptionsV0::Cassandra(opts) => TableOptionsV1::new(&opts),
             TableOptionsV0::Excel(opts) => TableOptionsV1::new(&opts),
             TableOptionsV0::Sqlite(opts) => TableOptionsV1::new(&opts),
+            TableOptionsV0::Oracle(opts) => TableOptionsV1::new(&opts),
+            TableOptionsV0::DuckDb(opts) => TableOptionsV1::new(&opts),
         }
     }
 }
@@ -802,6 +920,7 @@ impl TryFrom<&TableOptionsV1> for TableOptionsV0 {
                     Self::AZURE => Ok(TableOptionsV0::Azure(obj_store)),
                     Self::LANCE => Ok(TableOptionsV0::Lance(obj_store)),
                     Self::BSON => Ok(TableOptionsV0::Bson(obj_store)),
+                    Self::DUCKDB => Ok(TableOptionsV0::DuckDb(obj_store)),
                     _ => Err(ProtoConvError::UnknownVariant(value.name.to_string())),
                 }
             }
@@ -861,6 +980,10 @@ impl TryFrom<&TableOptionsV1> for TableOptionsV0 {
                 let sqlite: TableOptionsObjectStore = value.extract()?;
                 Ok(TableOptionsV0::Sqlite(sqlite))
             }
+            Self::ORACLE => {
+                let oracle: TableOptionsOracle = value.extract()?;
+                Ok(TableOptionsV0::Oracle(oracle))
+            }
             Self::DEBUG => {
                 let debug: TableOptionsDebug = value.extract()?;
                 Ok(TableOptionsV0::Debug(debug))
@@ -904,6 +1027,8 @@ impl TryFrom<TableOptionsV0> for options::table_options_v0::Options {
             TableOptionsV0::Cassandra(v) => options::table_options_v0::Options::Cassandra(v.into()),
             TableOptionsV0::Excel(v) => options::table_options_v0::Options::Excel(v.into()),
             TableOptionsV0::Sqlite(v) => options::table_options_v0::Options::Sqlite(v.into()),
+            TableOptionsV0::Oracle(v) => options::table_options_v0::Options::Oracle(v.into()),
+            TableOptionsV0::DuckDb(v) => options::table_options_v0::Options::Duckdb(v.into()),
         })
     }
 }
@@ -948,6 +1073,8 @@ impl TryFrom<options::table_options_v0::Options> for TableOptionsV0 {
             }
             options::table_options_v0::Options::Excel(v) => TableOptionsV0::Excel(v.try_into()?),
             options::table_options_v0::Options::Sqlite(v) => TableOptionsV0::Sqlite(v.try_into()?),
+            options::table_options_v0::Options::Oracle(v) => TableOptionsV0::Oracle(v.try_into()?),
+            options::table_options_v0::Options::Duckdb(v) => TableOptionsV0::DuckDb(v.try_into()?),
         })
     }
 }
@@ -1010,6 +1137,13 @@ impl From<TableOptionsDebug> for options::TableOptionsDebug {
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct TableOptionsInternal {
     pub columns: Vec<InternalColumnDefinition>,
+    /// Columns the table's data files are partitioned by.
+    #[serde(default)]
+    pub partition_by: Vec<String>,
+    /// Query the table is materialized from if this table backs a
+    /// materialized view.
+    #[serde(default)]
+    pub view_sql: Option<String>,
 }
 
 impl From<TableOptionsInternal> for TableOptionsV0 {
@@ -1034,6 +1168,8 @@ impl From<DFSchemaRef> for TableOptionsInternal {
                     arrow_type: col.data_type().clone(),
                 })
                 .collect::<Vec<_>>(),
+            partition_by: Vec::new(),
+            view_sql: None,
         }
     }
 }
@@ -1050,6 +1186,8 @@ impl From<SchemaRef> for TableOptionsInternal {
                     arrow_type: col.data_type().clone(),
                 })
                 .collect::<Vec<_>>(),
+            partition_by: Vec::new(),
+            view_sql: None,
         }
     }
 }
@@ -1063,6 +1201,8 @@ impl TryFrom<options::TableOptionsInternal> for TableOptionsInternal {
                 .into_iter()
                 .map(|col| col.try_into())
                 .collect::<Result<_, _>>()?,
+            partition_by: value.partition_by,
+            view_sql: value.view_sql,
         })
     }
 }
@@ -1072,6 +1212,8 @@ impl TryFrom<TableOptionsInternal> for options::TableOptionsInternal {
     fn try_from(value: TableOptionsInternal) -> Result<Self, Self::Error> {
         Ok(options::TableOptionsInternal {
             columns: value.columns.into_iter().map(Into::into).collect(),
+            partition_by: value.partition_by,
+            view_sql: value.view_sql,
         })
     }
 }
@@ -1081,6 +1223,13 @@ pub struct TableOptionsPostgres {
     pub connection_string: String,
     pub schema: String,
     pub table: String,
+    /// Integer or timestamp column to split scans on. Scans are split by ctid
+    /// ranges if not provided.
+    #[serde(default)]
+    pub partition_column: Option<String>,
+    /// Number of partitions to split scans into.
+    #[serde(default)]
+    pub num_partitions: Option<u64>,
 }
 
 impl From<TableOptionsPostgres> for TableOptionsV0 {
@@ -1100,6 +1249,8 @@ impl TryFrom<options::TableOptionsPostgres> for TableOptionsPostgres {
             connection_string: value.connection_string,
             schema: value.schema,
             table: value.table,
+            partition_column: value.partition_column,
+            num_partitions: value.num_partitions,
         })
     }
 }
@@ -1110,6 +1261,8 @@ impl From<TableOptionsPostgres> for options::TableOptionsPostgres {
             connection_string: value.connection_string,
             schema: value.schema,
             table: value.table,
+            partition_column: value.partition_column,
+            num_partitions: value.num_partitions,
         }
     }
 }
@@ -1160,6 +1313,9 @@ pub struct TableOptionsMysql {
     pub connection_string: String,
     pub schema: String,
     pub table: String,
+    /// TLS settings for the connection.
+    #[serde(default)]
+    pub tls: TlsOptions,
 }
 
 impl From<TableOptionsMysql> for TableOptionsV0 {
@@ -1179,6 +1335,7 @@ impl TryFrom<options::TableOptionsMysql> for TableOptionsMysql {
             connection_string: value.connection_string,
             schema: value.schema,
             table: value.table,
+            tls: value.tls.map(Into::into).unwrap_or_default(),
         })
     }
 }
@@ -1189,6 +1346,7 @@ impl From<TableOptionsMysql> for options::TableOptionsMysql {
             connection_string: value.connection_string,
             schema: value.schema,
             table: value.table,
+            tls: Some(value.tls.into()),
         }
     }
 }
@@ -1329,6 +1487,15 @@ pub struct TableOptionsMongoDb {
     pub connection_string: String,
     pub database: String,
     pub collection: String,
+    /// TLS settings for the connection.
+    #[serde(default)]
+    pub tls: TlsOptions,
+    /// Number of documents to sample when inferring the schema.
+    #[serde(default)]
+    pub sample_size: Option<u64>,
+    /// Read every document when inferring the schema.
+    #[serde(default)]
+    pub full_scan: bool,
 }
 
 impl From<TableOptionsMongoDb> for TableOptionsV0 {
@@ -1348,6 +1515,9 @@ impl TryFrom<options::TableOptionsMongo> for TableOptionsMongoDb {
             connection_string: value.connection_string,
             database: value.database,
             collection: value.collection,
+            tls: value.tls.map(Into::into).unwrap_or_default(),
+            sample_size: value.sample_size,
+            full_scan: value.full_scan,
         })
     }
 }
@@ -1358,6 +1528,9 @@ impl From<TableOptionsMongoDb> for options::TableOptionsMongo {
             connection_string: value.connection_string,
             database: value.database,
             collection: value.collection,
+            tls: Some(value.tls.into()),
+            sample_size: value.sample_size,
+            full_scan: value.full_scan,
         }
     }
 }
@@ -1370,6 +1543,13 @@ pub struct TableOptionsExcel {
     pub compression: Option<String>,
     pub sheet_name: Option<String>,
     pub has_header: bool,
+    pub sheet_index: Option<u64>,
+    #[serde(default)]
+    pub all_sheets: bool,
+    pub cell_range: Option<String>,
+    #[serde(default)]
+    pub header_row: u64,
+    pub column_types: Option<String>,
 }
 
 impl From<TableOptionsExcel> for TableOptionsV0 {
@@ -1392,6 +1572,11 @@ impl TryFrom<options::TableOptionsExcel> for TableOptionsExcel {
             compression: value.compression,
             sheet_name: value.sheet_name,
             has_header: value.has_header,
+            sheet_index: value.sheet_index,
+            all_sheets: value.all_sheets,
+            cell_range: value.cell_range,
+            header_row: value.header_row,
+            column_types: value.column_types,
         })
     }
 }
@@ -1405,6 +1590,11 @@ impl From<TableOptionsExcel> for options::TableOptionsExcel {
             compression: value.compression,
             sheet_name: value.sheet_name,
             has_header: value.has_header,
+            sheet_index: value.sheet_index,
+            all_sheets: value.all_sheets,
+            cell_range: value.cell_range,
+            header_row: value.header_row,
+            column_types: value.column_types,
         }
     }
 }
@@ -1414,6 +1604,9 @@ pub struct TableOptionsSqlServer {
     pub connection_string: String,
     pub schema: String,
     pub table: String,
+    /// TLS settings for the connection.
+    #[serde(default)]
+    pub tls: TlsOptions,
 }
 
 impl From<TableOptionsSqlServer> for TableOptionsV0 {
@@ -1433,6 +1626,7 @@ impl TryFrom<options::TableOptionsSqlServer> for TableOptionsSqlServer {
             connection_string: value.connection_string,
             schema: value.schema,
             table: value.table,
+            tls: value.tls.map(Into::into).unwrap_or_default(),
         })
     }
 }
@@ -1443,6 +1637,7 @@ impl From<TableOptionsSqlServer> for options::TableOptionsSqlServer {
             connection_string: value.connection_string,
             schema: value.schema,
             table: value.table,
+            tls: Some(value.tls.into()),
         }
     }
 }
@@ -1452,6 +1647,9 @@ pub struct TableOptionsClickhouse {
     pub connection_string: String,
     pub table: String,
     pub database: Option<String>,
+    /// TLS settings for the connection.
+    #[serde(default)]
+    pub tls: TlsOptions,
 }
 
 impl From<TableOptionsClickhouse> for TableOptionsV0 {
@@ -1471,6 +1669,7 @@ impl TryFrom<options::TableOptionsClickhouse> for TableOptionsClickhouse {
             connection_string: value.connection_string,
             table: value.table,
             database: value.database,
+            tls: value.tls.map(Into::into).unwrap_or_default(),
         })
     }
 }
@@ -1481,6 +1680,45 @@ impl From<TableOptionsClickhouse> for options::TableOptionsClickhouse {
             connection_string: value.connection_string,
             table: value.table,
             database: value.database,
+            tls: Some(value.tls.into()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TableOptionsOracle {
+    pub connection_string: String,
+    pub schema: String,
+    pub table: String,
+}
+
+impl From<TableOptionsOracle> for TableOptionsV0 {
+    fn from(value: TableOptionsOracle) -> Self {
+        TableOptionsV0::Oracle(value)
+    }
+}
+
+impl TableOptionsImpl for TableOptionsOracle {
+    const NAME: &'static str = "oracle";
+}
+
+impl TryFrom<options::TableOptionsOracle> for TableOptionsOracle {
+    type Error = ProtoConvError;
+    fn try_from(value: options::TableOptionsOracle) -> Result<Self, Self::Error> {
+        Ok(TableOptionsOracle {
+            connection_string: value.connection_string,
+            schema: value.schema,
+            table: value.table,
+        })
+    }
+}
+
+impl From<TableOptionsOracle> for options::TableOptionsOracle {
+    fn from(value: TableOptionsOracle) -> Self {
+        options::TableOptionsOracle {
+            connection_string: value.connection_string,
+            schema: value.schema,
+            table: value.table,
         }
     }
 }
@@ -1539,6 +1777,8 @@ pub struct TableOptionsSnowflake {
     pub role_name: String,
     pub schema_name: String,
     pub table_name: String,
+    /// Key pair or OAuth credentials, used instead of the password if set.
+    pub credentials: Option<CredentialsOptionsSnowflake>,
 }
 
 impl From<TableOptionsSnowflake> for TableOptionsV0 {
@@ -1563,6 +1803,7 @@ impl TryFrom<options::TableOptionsSnowflake> for TableOptionsSnowflake {
             role_name: value.role_name,
             schema_name: value.schema_name,
             table_name: value.table_name,
+            credentials: value.credentials.optional()?,
         })
     }
 }
@@ -1578,6 +1819,7 @@ impl From<TableOptionsSnowflake> for options::TableOptionsSnowflake {
             role_name: value.role_name,
             schema_name: value.schema_name,
             table_name: value.table_name,
+            credentials: value.credentials.map(|c| c.into()),
         }
     }
 }
@@ -1607,6 +1849,31 @@ impl From<DatabaseOptionsSqlite> for options::DatabaseOptionsSqlite {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct DatabaseOptionsDuckDb {
+    pub location: String,
+    pub storage_options: Option<StorageOptions>,
+}
+
+impl TryFrom<options::DatabaseOptionsDuckDb> for DatabaseOptionsDuckDb {
+    type Error = ProtoConvError;
+    fn try_from(value: options::DatabaseOptionsDuckDb) -> Result<Self, Self::Error> {
+        Ok(DatabaseOptionsDuckDb {
+            location: value.location,
+            storage_options: value.storage_options.map(|v| v.into()),
+        })
+    }
+}
+
+impl From<DatabaseOptionsDuckDb> for options::DatabaseOptionsDuckDb {
+    fn from(value: DatabaseOptionsDuckDb) -> Self {
+        options::DatabaseOptionsDuckDb {
+            location: value.location,
+            storage_options: value.storage_options.map(|v| v.into()),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct TableOptionsObjectStore {
     pub location: String,
@@ -1747,6 +2014,12 @@ impl From<TunnelOptionsDebug> for options::TunnelOptionsDebug {
 pub struct TunnelOptionsSsh {
     pub connection_string: String,
     pub ssh_key: Vec<u8>,
+    #[serde(default)]
+    pub jump_hosts: Vec<String>,
+    #[serde(default)]
+    pub known_hosts: Option<String>,
+    #[serde(default)]
+    pub user_provided_key: bool,
 }
 
 impl TryFrom<options::TunnelOptionsSsh> for TunnelOptionsSsh {
@@ -1755,6 +2028,9 @@ impl TryFrom<options::TunnelOptionsSsh> for TunnelOptionsSsh {
         Ok(TunnelOptionsSsh {
             connection_string: value.connection_string,
             ssh_key: value.ssh_key,
+            jump_hosts: value.jump_hosts,
+            known_hosts: value.known_hosts,
+            user_provided_key: value.user_provided_key,
         })
     }
 }
@@ -1764,6 +2040,9 @@ impl From<TunnelOptionsSsh> for options::TunnelOptionsSsh {
         options::TunnelOptionsSsh {
             connection_string: value.connection_string,
             ssh_key: value.ssh_key,
+            jump_hosts: value.jump_hosts,
+            known_hosts: value.known_hosts,
+            user_provided_key: value.user_provided_key,
         }
     }
 }
@@ -1774,6 +2053,7 @@ pub enum CredentialsOptions {
     Aws(CredentialsOptionsAws),
     Azure(CredentialsOptionsAzure),
     OpenAI(CredentialsOptionsOpenAI),
+    Snowflake(CredentialsOptionsSnowflake),
 }
 
 impl CredentialsOptions {
@@ -1782,6 +2062,7 @@ impl CredentialsOptions {
     pub const AWS: &'static str = "aws";
     pub const AZURE: &'static str = "azure";
     pub const OPENAI: &'static str = "openai";
+    pub const SNOWFLAKE: &'static str = "snowflake";
 
     pub fn as_str(&self) -> &'static str {
         match self {
@@ -1790,6 +2071,7 @@ impl CredentialsOptions {
             Self::Aws(_) => Self::AWS,
             Self::Azure(_) => Self::AZURE,
             Self::OpenAI(_) => Self::OPENAI,
+            Self::Snowflake(_) => Self::SNOWFLAKE,
         }
     }
 }
@@ -1809,6 +2091,7 @@ impl TryFrom<options::credentials_options::Options> for CredentialsOptions {
             options::credentials_options::Options::Aws(v) => Self::Aws(v.try_into()?),
             options::credentials_options::Options::Azure(v) => Self::Azure(v.try_into()?),
             options::credentials_options::Options::Openai(v) => Self::OpenAI(v.try_into()?),
+            options::credentials_options::Options::Snowflake(v) => Self::Snowflake(v.try_into()?),
         })
     }
 }
@@ -1830,6 +2113,9 @@ impl From<CredentialsOptions> for options::credentials_options::Options {
             CredentialsOptions::OpenAI(v) => {
                 options::credentials_options::Options::Openai(v.into())
             }
+            CredentialsOptions::Snowflake(v) => {
+                options::credentials_options::Options::Snowflake(v.into())
+            }
         }
     }
 }
@@ -1990,6 +2276,49 @@ impl From<CredentialsOptionsOpenAI> for options::CredentialsOptionsOpenAi {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CredentialsOptionsSnowflake {
+    /// PEM encoded RSA private key for key pair (JWT) authentication.
+    KeyPair { private_key: String },
+    /// Access token for OAuth authentication.
+    OAuth { token: String },
+}
+
+impl TryFrom<options::credentials_options_snowflake::Auth> for CredentialsOptionsSnowflake {
+    type Error = ProtoConvError;
+    fn try_from(value: options::credentials_options_snowflake::Auth) -> Result<Self, Self::Error> {
+        Ok(match value {
+            options::credentials_options_snowflake::Auth::PrivateKey(private_key) => {
+                CredentialsOptionsSnowflake::KeyPair { private_key }
+            }
+            options::credentials_options_snowflake::Auth::OauthToken(token) => {
+                CredentialsOptionsSnowflake::OAuth { token }
+            }
+        })
+    }
+}
+
+impl TryFrom<options::CredentialsOptionsSnowflake> for CredentialsOptionsSnowflake {
+    type Error = ProtoConvError;
+    fn try_from(value: options::CredentialsOptionsSnowflake) -> Result<Self, Self::Error> {
+        value.auth.required("auth")
+    }
+}
+
+impl From<CredentialsOptionsSnowflake> for options::CredentialsOptionsSnowflake {
+    fn from(value: CredentialsOptionsSnowflake) -> Self {
+        let auth = match value {
+            CredentialsOptionsSnowflake::KeyPair { private_key } => {
+                options::credentials_options_snowflake::Auth::PrivateKey(private_key)
+            }
+            CredentialsOptionsSnowflake::OAuth { token } => {
+                options::credentials_options_snowflake::Auth::OauthToken(token)
+            }
+        };
+        options::CredentialsOptionsSnowflake { auth: Some(auth) }
+    }
+}
+
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
 pub enum CopyToDestinationOptions {
     Local(CopyToDestinationOptionsLocal),
@@ -2059,6 +2388,7 @@ pub enum CopyToFormatOptions {
     Lance(CopyToFormatOptionsLance),
     Json(CopyToFormatOptionsJson),
     Bson(CopyToFormatOptionsBson),
+    Excel(CopyToFormatOptionsExcel),
 }
 
 impl Default for CopyToFormatOptions {
@@ -2066,6 +2396,7 @@ impl Default for CopyToFormatOptions {
         Self::Csv(CopyToFormatOptionsCsv {
             delim: b',',
             header: true,
+            compression: None,
         })
     }
 }
@@ -2076,6 +2407,7 @@ impl CopyToFormatOptions {
     pub const JSON: &'static str = "json";
     pub const BSON: &'static str = "bson";
     pub const LANCE: &'static str = "lance";
+    pub const EXCEL: &'static str = "xlsx";
 
     pub fn as_str(&self) -> &'static str {
         match self {
@@ -2084,6 +2416,7 @@ impl CopyToFormatOptions {
             Self::Json(_) => Self::JSON,
             Self::Bson(_) => Self::BSON,
             Self::Lance(_) => Self::LANCE,
+            Self::Excel(_) => Self::EXCEL,
         }
     }
 }
@@ -2092,6 +2425,7 @@ impl CopyToFormatOptions {
 pub struct CopyToFormatOptionsCsv {
     pub delim: u8,
     pub header: bool,
+    pub compression: Option<String>,
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
@@ -2102,10 +2436,19 @@ pub struct CopyToFormatOptionsParquet {
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
 pub struct CopyToFormatOptionsJson {
     pub array: bool,
+    pub compression: Option<String>,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct CopyToFormatOptionsBson {
+    pub compression: Option<String>,
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
-pub struct CopyToFormatOptionsBson {}
+pub struct CopyToFormatOptionsExcel {
+    pub sheet_name: Option<String>,
+    pub header: bool,
+}
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
 pub struct CopyToFormatOptionsLance {
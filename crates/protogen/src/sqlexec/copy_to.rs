@@ -64,7 +64,7 @@ pub struct CopyToDestinationOptionsAzure {
 
 #[derive(Clone, PartialEq, Message)]
 pub struct CopyToFormatOptions {
-    #[prost(oneof = "CopyToFormatOptionsEnum", tags = "1, 2, 3")]
+    #[prost(oneof = "CopyToFormatOptionsEnum", tags = "1, 2, 3, 4, 5, 6")]
     pub copy_to_format_options_enum: Option<CopyToFormatOptionsEnum>,
 }
 
@@ -80,6 +80,8 @@ pub enum CopyToFormatOptionsEnum {
     Lance(CopyToFormatOptionsLance),
     #[prost(message, tag = "5")]
     Bson(CopyToFormatOptionsBson),
+    #[prost(message, tag = "6")]
+    Excel(CopyToFormatOptionsExcel),
 }
 
 #[derive(Clone, PartialEq, Message)]
@@ -88,12 +90,16 @@ pub struct CopyToFormatOptionsCsv {
     pub delim: u32,
     #[prost(bool, tag = "2")]
     pub header: bool,
+    #[prost(string, optional, tag = "3")]
+    pub compression: Option<String>,
 }
 
 #[derive(Clone, PartialEq, Message)]
 pub struct CopyToFormatOptionsJson {
     #[prost(bool, tag = "1")]
     pub array: bool,
+    #[prost(string, optional, tag = "2")]
+    pub compression: Option<String>,
 }
 
 #[derive(Clone, PartialEq, Message)]
@@ -115,7 +121,18 @@ pub struct CopyToFormatOptionsLance {
 }
 
 #[derive(Clone, PartialEq, Message)]
-pub struct CopyToFormatOptionsBson {}
+pub struct CopyToFormatOptionsBson {
+    #[prost(string, optional, tag = "1")]
+    pub compression: Option<String>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct CopyToFormatOptionsExcel {
+    #[prost(string, optional, tag = "1")]
+    pub sheet_name: Option<String>,
+    #[prost(bool, tag = "2")]
+    pub header: bool,
+}
 
 impl TryFrom<crate::metastore::types::options::CopyToFormatOptions> for CopyToFormatOptions {
     type Error = crate::errors::ProtoConvError;
@@ -123,8 +140,14 @@ impl TryFrom<crate::metastore::types::options::CopyToFormatOptions> for CopyToFo
         value: crate::metastore::types::options::CopyToFormatOptions,
     ) -> Result<Self, Self::Error> {
         match value {
-            crate::metastore::types::options::CopyToFormatOptions::Bson(_) => {
-                Ok(CopyToFormatOptions::default())
+            crate::metastore::types::options::CopyToFormatOptions::Bson(bson) => {
+                Ok(CopyToFormatOptions {
+                    copy_to_format_options_enum: Some(CopyToFormatOptionsEnum::Bson(
+                        CopyToFormatOptionsBson {
+                            compression: bson.compression,
+                        },
+                    )),
+                })
             }
             crate::metastore::types::options::CopyToFormatOptions::Lance(opts) => {
                 Ok(CopyToFormatOptions {
@@ -144,6 +167,7 @@ impl TryFrom<crate::metastore::types::options::CopyToFormatOptions> for CopyToFo
                         CopyToFormatOptionsCsv {
                             delim: csv.delim as u32,
                             header: csv.header,
+                            compression: csv.compression,
                         },
                     )),
                 })
@@ -151,7 +175,10 @@ impl TryFrom<crate::metastore::types::options::CopyToFormatOptions> for CopyToFo
             crate::metastore::types::options::CopyToFormatOptions::Json(json) => {
                 Ok(CopyToFormatOptions {
                     copy_to_format_options_enum: Some(CopyToFormatOptionsEnum::Json(
-                        CopyToFormatOptionsJson { array: json.array },
+                        CopyToFormatOptionsJson {
+                            array: json.array,
+                            compression: json.compression,
+                        },
                     )),
                 })
             }
@@ -164,6 +191,16 @@ impl TryFrom<crate::metastore::types::options::CopyToFormatOptions> for CopyToFo
                     )),
                 })
             }
+            crate::metastore::types::options::CopyToFormatOptions::Excel(excel) => {
+                Ok(CopyToFormatOptions {
+                    copy_to_format_options_enum: Some(CopyToFormatOptionsEnum::Excel(
+                        CopyToFormatOptionsExcel {
+                            sheet_name: excel.sheet_name,
+                            header: excel.header,
+                        },
+                    )),
+                })
+            }
         }
     }
 }
@@ -184,6 +221,7 @@ impl TryFrom<CopyToFormatOptions> for crate::metastore::types::options::CopyToFo
                     crate::metastore::types::options::CopyToFormatOptionsCsv {
                         delim: csv.delim as u8,
                         header: csv.header,
+                        compression: csv.compression,
                     },
                 ))
             }
@@ -199,12 +237,17 @@ impl TryFrom<CopyToFormatOptions> for crate::metastore::types::options::CopyToFo
             ),
             CopyToFormatOptionsEnum::Json(json) => {
                 Ok(crate::metastore::types::options::CopyToFormatOptions::Json(
-                    crate::metastore::types::options::CopyToFormatOptionsJson { array: json.array },
+                    crate::metastore::types::options::CopyToFormatOptionsJson {
+                        array: json.array,
+                        compression: json.compression,
+                    },
                 ))
             }
-            CopyToFormatOptionsEnum::Bson(_) => {
+            CopyToFormatOptionsEnum::Bson(bson) => {
                 Ok(crate::metastore::types::options::CopyToFormatOptions::Bson(
-                    crate::metastore::types::options::CopyToFormatOptionsBson {},
+                    crate::metastore::types::options::CopyToFormatOptionsBson {
+                        compression: bson.compression,
+                    },
                 ))
             }
             CopyToFormatOptionsEnum::Parquet(parquet) => Ok(
@@ -214,6 +257,14 @@ impl TryFrom<CopyToFormatOptions> for crate::metastore::types::options::CopyToFo
                     },
                 ),
             ),
+            CopyToFormatOptionsEnum::Excel(excel) => Ok(
+                crate::metastore::types::options::CopyToFormatOptions::Excel(
+                    crate::metastore::types::options::CopyToFormatOptionsExcel {
+                        sheet_name: excel.sheet_name,
+                        header: excel.header,
+                    },
+                ),
+            ),
         }
     }
 }
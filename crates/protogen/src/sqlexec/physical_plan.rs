@@ -41,6 +41,10 @@ pub struct CreateTableExec {
     pub or_replace: bool,
     #[prost(message, tag = "5")]
     pub arrow_schema: Option<Schema>,
+    #[prost(string, repeated, tag = "6")]
+    pub partition_by: Vec<String>,
+    #[prost(string, optional, tag = "7")]
+    pub view_sql: Option<String>,
 }
 
 #[derive(Clone, PartialEq, Message)]
@@ -285,6 +289,10 @@ pub struct UpdateExec {
     pub updates: Vec<UpdateSelector>,
     #[prost(message, optional, tag = "3")]
     pub where_expr: Option<LogicalExprNode>,
+    #[prost(bytes, optional, tag = "4")]
+    pub provider_id: Option<Vec<u8>>, // UUID, only set for external tables
+    #[prost(string, optional, tag = "5")]
+    pub dml_datasource: Option<String>, // Only set for external tables
 }
 
 #[derive(Clone, PartialEq, Message)]
@@ -293,12 +301,26 @@ pub struct DeleteExec {
     pub table: Option<TableEntry>,
     #[prost(message, optional, tag = "2")]
     pub where_expr: Option<LogicalExprNode>,
+    #[prost(bytes, optional, tag = "3")]
+    pub provider_id: Option<Vec<u8>>, // UUID, only set for external tables
+    #[prost(string, optional, tag = "4")]
+    pub dml_datasource: Option<String>, // Only set for external tables
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct OptimizeTableExec {
+    #[prost(message, tag = "1")]
+    pub table: Option<TableEntry>,
+    #[prost(string, repeated, tag = "2")]
+    pub zorder_by: Vec<String>,
 }
 
 #[derive(Clone, PartialEq, Message)]
 pub struct InsertExec {
     #[prost(bytes, tag = "1")]
     pub provider_id: Vec<u8>, // UUID
+    #[prost(bool, tag = "2")]
+    pub overwrite: bool,
 }
 
 #[derive(Clone, PartialEq, Message)]
@@ -343,7 +365,7 @@ pub struct AnalyzeExec {
 pub struct ExecutionPlanExtension {
     #[prost(
         oneof = "ExecutionPlanExtensionType",
-        tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31"
+        tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32"
     )]
     pub inner: Option<ExecutionPlanExtensionType>,
 }
@@ -417,4 +439,6 @@ pub enum ExecutionPlanExtensionType {
     DataSourceMetricsExecAdapter(DataSourceMetricsExecAdapter),
     #[prost(message, tag = "31")]
     DescribeTable(DescribeTableExec),
+    #[prost(message, tag = "32")]
+    OptimizeTableExec(OptimizeTableExec),
 }
@@ -10,6 +10,6 @@ pub struct PostgresBinaryCopyConfig {
     pub schema: String,
     #[prost(string, tag = "3")]
     pub table: String,
-    #[prost(string, tag = "4")]
-    pub copy_query: String,
+    #[prost(string, repeated, tag = "4")]
+    pub copy_queries: Vec<String>,
 }
@@ -10,4 +10,8 @@ pub struct PostgresTableProviderConfig {
     pub schema: String,
     #[prost(string, tag = "3")]
     pub table: String,
+    #[prost(string, optional, tag = "4")]
+    pub partition_column: Option<String>,
+    #[prost(uint64, optional, tag = "5")]
+    pub num_partitions: Option<u64>,
 }
@@ -286,6 +286,49 @@ impl Hook for SqliteTestsHook {
     }
 }
 
+static DUCKDB_DB_LOCATION: Lazy<Result<PathBuf>> = Lazy::new(|| {
+    let path = PathBuf::from("testdata/sqllogictests_duckdb/data/db.duckdb");
+    let db = path.to_string_lossy();
+    if path.exists() {
+        info!(%db, "duckdb database exists, skipping setup; to re-create delete the old database file");
+    } else {
+        info!(%db, "creating duckdb database");
+        let output = std::process::Command::new("duckdb")
+            .arg(&path)
+            .arg(".read testdata/sqllogictests_duckdb/data/setup-test-duckdb-db.sql")
+            .output()?;
+
+        if !output.status.success() {
+            return Err(anyhow!(
+                "failed to setup duckdb db (status code: {}):\n  STDOUT: {}\n  STDERR: {}",
+                output.status.code().unwrap_or_default(),
+                String::from_utf8_lossy(&output.stdout),
+                String::from_utf8_lossy(&output.stderr)
+            ));
+        }
+    }
+    Ok(path)
+});
+
+pub struct DuckDbTestsHook;
+
+#[async_trait]
+impl Hook for DuckDbTestsHook {
+    async fn pre(
+        &self,
+        _config: &Config,
+        _client: TestClient,
+        vars: &mut HashMap<String, String>,
+    ) -> Result<bool> {
+        let db_location = match DUCKDB_DB_LOCATION.as_ref() {
+            Ok(path) => path.to_string_lossy().into_owned(),
+            Err(e) => return Err(anyhow!("{e}")),
+        };
+        vars.insert("DUCKDB_DB_LOCATION".to_string(), db_location);
+        Ok(true)
+    }
+}
+
 pub struct IcebergFormatVersionHook(pub usize);
 
 #[async_trait]
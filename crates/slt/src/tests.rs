@@ -2,6 +2,7 @@ use std::collections::HashMap;
 
 use anyhow::{anyhow, Result};
 use async_trait::async_trait;
+use regex::Regex;
 use tokio_postgres::Config;
 use tracing::warn;
 
@@ -151,3 +152,91 @@ impl FnTest for PgBinaryEncoding {
         Ok(())
     }
 }
+
+pub struct ParquetPruningMetrics;
+
+impl ParquetPruningMetrics {
+    /// Get the value of a metric from the output of `EXPLAIN ANALYZE`.
+    fn metric_value(plan: &str, name: &str) -> Result<u64> {
+        let regx = Regex::new(&format!(r"\b{name}=(\d+)")).unwrap();
+        let caps = regx
+            .captures(plan)
+            .ok_or_else(|| anyhow!("metric {name} missing from plan: {plan}"))?;
+        Ok(caps[1].parse()?)
+    }
+}
+
+#[async_trait]
+impl FnTest for ParquetPruningMetrics {
+    async fn run(
+        &self,
+        _config: &Config,
+        client: TestClient,
+        vars: &mut HashMap<String, String>,
+    ) -> Result<()> {
+        let client = match client {
+            TestClient::Pg(client) => client,
+            TestClient::Rpc(_) | TestClient::FlightSql(_) => {
+                warn!("skipping parquet pruning metrics test on rpc");
+                return Ok(());
+            }
+        };
+
+        let tmp = vars
+            .get("TMP")
+            .ok_or_else(|| anyhow!("missing TMP variable"))?;
+        let path = format!("{tmp}/pruning.parquet");
+
+        // Values are wide enough that each row group is split into many pages,
+        // and sorted so that both row groups and pages can be pruned.
+        client
+            .batch_execute(&format!(
+                "
+COPY (
+    SELECT lpad(generate_series::text, 10, '0') || repeat('x', 190) AS v
+        FROM generate_series(1, 300000)
+        ORDER BY generate_series
+) TO '{path}' FORMAT parquet;
+                "
+            ))
+            .await?;
+
+        let needle = "lpad('42', 10, '0') || repeat('x', 190)";
+        let row = client
+            .query_one(
+                &format!("SELECT count(*) FROM read_parquet('{path}') WHERE v = {needle}"),
+                &[],
+            )
+            .await?;
+        let count: i64 = row.get(0);
+        test_assert!(count == 1, anyhow!("expected 1 matching row, got {count}"));
+
+        let rows = client
+            .query(
+                &format!("EXPLAIN ANALYZE SELECT * FROM read_parquet('{path}') WHERE v = {needle}"),
+                &[],
+            )
+            .await?;
+        let plan = rows
+            .iter()
+            .map(|row| row.get::<_, String>(1))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        // 300000 rows are written as 3 row groups, only the first contains the
+        // value.
+        let row_groups_pruned = Self::metric_value(&plan, "row_groups_pruned")?;
+        test_assert!(
+            row_groups_pruned == 2,
+            anyhow!("expected 2 row groups pruned, got {row_groups_pruned}")
+        );
+
+        let pages_pruned = Self::metric_value(&plan, "pages_pruned")?;
+        test_assert!(
+            pages_pruned > 0,
+            anyhow!("expected pages to be pruned using the page index")
+        );
+
+        Ok(())
+    }
+}
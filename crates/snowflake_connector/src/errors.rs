@@ -33,8 +33,24 @@ pub enum SnowflakeError {
     #[error("Invalid connection parameters: {0}")]
     InvalidConnectionParameters(String),
 
+    #[error("Invalid private key: {0}")]
+    InvalidPrivateKey(String),
+
+    #[error("Cannot convert arrow result from '{from}' to '{to}': {reason}")]
+    UnsupportedArrowConversion {
+        from: datafusion::arrow::datatypes::DataType,
+        to: datafusion::arrow::datatypes::DataType,
+        reason: String,
+    },
+
     #[error("Invalid snowflake data-type: {0}")]
     InvalidSnowflakeDataType(String),
+
+    #[error("Invalid value for '{datatype}' in result: '{value}'")]
+    InvalidResultValue {
+        datatype: datafusion::arrow::datatypes::DataType,
+        value: String,
+    },
 }
 
 impl From<SnowflakeError> for datafusion::error::DataFusionError {
@@ -1,4 +1,11 @@
-use crate::auth::{AuthOptions, Authenticator, DefaultAuthenticator, Session};
+use crate::auth::{
+    AuthOptions,
+    Authenticator,
+    DefaultAuthenticator,
+    KeyPairAuthenticator,
+    OAuthAuthenticator,
+    Session,
+};
 use crate::errors::{Result, SnowflakeError};
 use crate::query::Query;
 pub use crate::query::{
@@ -13,6 +20,8 @@ use crate::req::SnowflakeClient;
 mod auth;
 mod query;
 mod req;
+#[cfg(test)]
+mod testutil;
 
 pub mod datatype;
 pub mod errors;
@@ -23,6 +32,9 @@ pub struct ConnectionBuilder {
     login_name: String,
 
     password: Option<String>,
+    private_key: Option<String>,
+    oauth_token: Option<String>,
+    base_url: Option<String>,
     database_name: Option<String>,
     schema_name: Option<String>,
     warehouse: Option<String>,
@@ -45,6 +57,9 @@ impl ConnectionBuilder {
             login_name,
 
             password: None,
+            private_key: None,
+            oauth_token: None,
+            base_url: None,
             database_name: None,
             schema_name: None,
             warehouse: None,
@@ -53,6 +68,12 @@ impl ConnectionBuilder {
     }
 
     builder_fn! {password, String}
+    // PEM encoded RSA private key for key pair authentication.
+    builder_fn! {private_key, String}
+    builder_fn! {oauth_token, String}
+    // Override the URL derived from the account name, e.g. for connecting
+    // through a proxy.
+    builder_fn! {base_url, String}
     builder_fn! {database_name, String}
     builder_fn! {schema_name, String}
     builder_fn! {warehouse, String}
@@ -65,21 +86,36 @@ impl ConnectionBuilder {
             ));
         }
 
-        let url = format!("https://{}.snowflakecomputing.com:443", self.account_name);
+        let url = match self.base_url {
+            Some(url) => url,
+            None => format!("https://{}.snowflakecomputing.com:443", self.account_name),
+        };
         let client = SnowflakeClient::builder().build(url)?;
 
-        let password = self
-            .password
-            .ok_or(SnowflakeError::InvalidConnectionParameters(
-                "password is required for default authentication".to_string(),
-            ))?;
-
-        let auth = DefaultAuthenticator {
-            account_name: self.account_name,
-            login_name: self.login_name,
-            password,
+        // Prefer the stronger authentication methods if multiple were
+        // provided.
+        let auth = match (self.private_key, self.oauth_token, self.password) {
+            (Some(private_key), _, _) => Authenticator::KeyPair(KeyPairAuthenticator {
+                account_name: self.account_name,
+                login_name: self.login_name,
+                private_key,
+            }),
+            (None, Some(token), _) => Authenticator::OAuth(OAuthAuthenticator {
+                account_name: self.account_name,
+                login_name: self.login_name,
+                token,
+            }),
+            (None, None, Some(password)) => Authenticator::Default(DefaultAuthenticator {
+                account_name: self.account_name,
+                login_name: self.login_name,
+                password,
+            }),
+            (None, None, None) => {
+                return Err(SnowflakeError::InvalidConnectionParameters(
+                    "one of password, private key or OAuth token is required".to_string(),
+                ))
+            }
         };
-        let auth = Authenticator::Default(auth);
 
         let auth_options = AuthOptions {
             database_name: self.database_name,
@@ -108,17 +144,22 @@ impl Connection {
         self.session.close(&self.client).await
     }
 
-    pub async fn exec_sync(&self, sql: String, bindings: Vec<QueryBindParameter>) -> Result<()> {
+    /// Execute a statement, ignoring any results.
+    pub async fn exec(&self, sql: String, bindings: Vec<QueryBindParameter>) -> Result<()> {
         let q = Query { sql, bindings };
-        q.exec_sync(&self.client, &self.session).await
+        q.exec(&self.client, &self.session).await
     }
 
-    pub async fn query_sync(
+    /// Execute a query and return its results.
+    ///
+    /// The query is submitted asynchronously and polled until it completes.
+    /// Dropping the returned future before then aborts the query.
+    pub async fn query(
         &self,
         sql: String,
         bindings: Vec<QueryBindParameter>,
     ) -> Result<QueryResult> {
         let q = Query { sql, bindings };
-        q.query_sync(&self.client, &self.session).await
+        q.query(&self.client, &self.session).await
     }
 }
@@ -2,6 +2,7 @@ use std::collections::HashMap;
 use std::fmt::Debug;
 use std::io::{BufReader, Cursor};
 use std::sync::Arc;
+use std::time::Duration;
 use std::vec;
 
 use base64::engine::general_purpose::STANDARD as base64_engine;
@@ -24,19 +25,29 @@ use datafusion::arrow::array::{
     Time64NanosecondBuilder,
     TimestampNanosecondBuilder,
 };
+use datafusion::arrow::compute::cast;
 use datafusion::arrow::datatypes::{DataType, Field, Schema, SchemaRef, TimeUnit};
 use datafusion::arrow::error::ArrowError;
 use datafusion::arrow::ipc::reader::StreamReader;
 use datafusion::arrow::record_batch::{RecordBatch, RecordBatchOptions};
 use datafusion::scalar::ScalarValue;
+use futures::{stream, Stream, StreamExt};
 use serde::{Deserialize, Serialize};
+use tracing::warn;
 
-use crate::auth::Session;
+use crate::auth::{Session, Token};
 use crate::datatype::SnowflakeDataType;
 use crate::errors::{Result, SnowflakeError};
 use crate::req::{EmptySerde, ExecMethod, RequestId, SnowflakeChunkDl, SnowflakeClient};
 
 const QUERY_ENDPOINT: &str = "/queries/v1/query-request";
+const ABORT_ENDPOINT: &str = "/queries/v1/abort-request";
+const MONITORING_ENDPOINT: &str = "/monitoring/queries";
+
+/// Bounds for how long to wait between checking the status of a running
+/// query. The interval doubles with every check.
+const POLL_INITIAL_INTERVAL: Duration = Duration::from_millis(100);
+const POLL_MAX_INTERVAL: Duration = Duration::from_secs(5);
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy)]
 #[serde(rename_all = "lowercase")]
@@ -152,7 +163,6 @@ struct QueryData {
     total: Option<i64>,
     #[allow(unused)]
     returned: Option<i64>,
-    #[allow(unused)]
     query_id: Option<String>,
     // TODO: A lot more other fields...
 }
@@ -173,7 +183,6 @@ struct QueryDataRowType {
 
 #[derive(Debug)]
 pub struct SnowflakeTypeMeta {
-    #[allow(unused)]
     typ: SnowflakeDataType,
     #[allow(unused)]
     precision: u32,
@@ -229,7 +238,7 @@ macro_rules! make_json_column_using {
         for row in $rows.iter() {
             let val = row.get($col_idx).expect("value for column should exist");
             let val = match val {
-                Some(s) => Some($parse_fn(s)),
+                Some(s) => Some($parse_fn(s)?),
                 None => None,
             };
             $arr.append_option(val);
@@ -262,17 +271,25 @@ macro_rules! make_ipc_column {
             let row = row.map($map_fn);
             arr.append_option(row)
         });
-        Arc::new(arr.finish())
+        Ok(Arc::new(arr.finish()))
     }};
 }
 
+/// Convert a scaled timestamp to nanoseconds.
+fn i64_to_timestamp(r: i64, scale: u32) -> i64 {
+    let pow = 10_i64.pow(scale);
+    let sec = r / pow;
+    let nsec = (r % pow) * 10_i64.pow(9 - scale);
+    sec * 1_000_000_000 + nsec
+}
+
 impl RecordBatchIter {
     fn normalize_column(
         expected_field: &Field,
         actual_field: &Field,
         type_meta: &SnowflakeTypeMeta,
         col: &ArrayRef,
-    ) -> ArrayRef {
+    ) -> Result<ArrayRef> {
         match (expected_field.data_type(), actual_field.data_type()) {
             (dt @ DataType::Decimal128(_, _), DataType::Int8) => {
                 make_ipc_column!(col, Int8Array, |r| r as i128, Decimal128Builder, dt)
@@ -287,49 +304,83 @@ impl RecordBatchIter {
                 make_ipc_column!(col, Int64Array, |r| r as i128, Decimal128Builder, dt)
             }
             (dt @ DataType::Time64(TimeUnit::Nanosecond), DataType::Int64) => {
-                make_ipc_column!(col, Int64Array, |r| r * 10_i64.pow(9 - type_meta.scale), Time64NanosecondBuilder, dt)
+                make_ipc_column!(
+                    col,
+                    Int64Array,
+                    |r| r * 10_i64.pow(9 - type_meta.scale),
+                    Time64NanosecondBuilder,
+                    dt
+                )
             }
             (dt @ DataType::Time64(TimeUnit::Nanosecond), DataType::Int32) => {
-                make_ipc_column!(col, Int32Array, |r| r as i64 * 10_i64.pow(9 - type_meta.scale), Time64NanosecondBuilder, dt)
+                make_ipc_column!(
+                    col,
+                    Int32Array,
+                    |r| r as i64 * 10_i64.pow(9 - type_meta.scale),
+                    Time64NanosecondBuilder,
+                    dt
+                )
             }
-            (dt @ DataType::Timestamp(TimeUnit::Nanosecond, _tz), DataType::Struct(_)) => {
+            (dt @ DataType::Timestamp(TimeUnit::Nanosecond, _tz), DataType::Struct(fields)) => {
                 let rows: &StructArray = col.as_any().downcast_ref().unwrap();
-                let seconds = rows
+                let epoch = rows
                     .column(0)
                     .as_any()
                     .downcast_ref::<Int64Array>()
                     .unwrap();
-                let nanos = rows
-                    .column(1)
-                    .as_any()
-                    .downcast_ref::<Int32Array>()
-                    .unwrap();
+                // TIMESTAMP_TZ with a scale <= 7 is sent as (epoch, timezone)
+                // with the epoch being scaled like the non-struct timestamps.
+                // Everything else is sent as (epoch seconds, fraction in
+                // nanoseconds) with TIMESTAMP_TZ having an additional timezone
+                // field. The epoch is always in UTC so the timezone isn't
+                // needed.
+                let fraction = match (type_meta.typ, fields.len()) {
+                    (SnowflakeDataType::TimestampTz, 2) => None,
+                    _ => Some(
+                        rows.column(1)
+                            .as_any()
+                            .downcast_ref::<Int32Array>()
+                            .unwrap(),
+                    ),
+                };
                 let mut arr = TimestampNanosecondBuilder::with_capacity(rows.len())
                     .with_data_type(dt.clone());
                 (0..rows.len()).for_each(|row_idx| {
                     if rows.is_null(row_idx) {
                         arr.append_null();
                     } else {
-                        let t = seconds.value(row_idx) * 1_000_000_000;
-                        let t = t + nanos.value(row_idx) as i64;
+                        let t = match fraction {
+                            Some(fraction) => {
+                                epoch.value(row_idx) * 1_000_000_000
+                                    + fraction.value(row_idx) as i64
+                            }
+                            None => i64_to_timestamp(epoch.value(row_idx), type_meta.scale),
+                        };
                         arr.append_value(t);
                     }
                 });
-                Arc::new(arr.finish())
+                Ok(Arc::new(arr.finish()))
             }
             (dt @ DataType::Timestamp(TimeUnit::Nanosecond, _tz), DataType::Int64) => {
-                fn i64_to_timestamp(r: i64, scale: u32) -> i64 {
-                    let pow = 10_i64.pow(scale);
-                    let sec = r / pow;
-                    let nsec = (r % pow) * 10_i64.pow(9 - scale);
-                    sec * 1_000_000_000 + nsec
-                }
-                make_ipc_column!(col, Int64Array, |r| i64_to_timestamp(r, type_meta.scale), TimestampNanosecondBuilder, dt)
+                make_ipc_column!(
+                    col,
+                    Int64Array,
+                    |r| i64_to_timestamp(r, type_meta.scale),
+                    TimestampNanosecondBuilder,
+                    dt
+                )
+            }
+            (expected, actual) if expected == actual => Ok(Arc::clone(col)),
+            // Semi-structured types (VARIANT, ARRAY, OBJECT) are JSON strings
+            // which may be sent with a different string type. Fall back to
+            // casting for these and anything else we don't explicitly handle.
+            (expected, actual) => {
+                cast(col, expected).map_err(|e| SnowflakeError::UnsupportedArrowConversion {
+                    from: actual.clone(),
+                    to: expected.clone(),
+                    reason: e.to_string(),
+                })
             }
-            (expected, actual) if expected == actual => Arc::clone(col),
-            (expected, actual) => unreachable!(
-                "programming error: conversion from '{actual}' to '{expected}' not supported for snowflake"
-            ),
         }
     }
 
@@ -349,7 +400,7 @@ impl RecordBatchIter {
             .enumerate()
         {
             let col = batch.column(col_idx);
-            let col = Self::normalize_column(expected_field, actual_field, type_meta, col);
+            let col = Self::normalize_column(expected_field, actual_field, type_meta, col)?;
             columns.push(col);
         }
 
@@ -484,6 +535,20 @@ impl QueryResult {
     pub fn num_chunks(&self) -> usize {
         self.num_chunks
     }
+
+    /// Stream the result chunks in order, downloading up to `prefetch` chunks
+    /// concurrently.
+    ///
+    /// The next chunks are only downloaded as earlier ones are taken from the
+    /// stream, so at most `prefetch` chunks are buffered at once.
+    pub fn into_chunk_stream(
+        self,
+        prefetch: usize,
+    ) -> impl Stream<Item = Result<QueryResultChunk>> + Send + Sync {
+        stream::iter(self)
+            .map(QueryResultChunkMeta::take_chunk)
+            .buffered(prefetch.max(1))
+    }
 }
 
 impl Iterator for QueryResult {
@@ -586,17 +651,13 @@ pub struct Query {
 }
 
 impl Query {
-    pub async fn exec_sync(self, client: &SnowflakeClient, session: &Session) -> Result<()> {
-        let _ = self.exec_sync_internal(client, session).await?;
+    pub async fn exec(self, client: &SnowflakeClient, session: &Session) -> Result<()> {
+        let _ = self.exec_internal(client, session).await?;
         Ok(())
     }
 
-    pub async fn query_sync(
-        self,
-        client: &SnowflakeClient,
-        session: &Session,
-    ) -> Result<QueryResult> {
-        let mut data = self.exec_sync_internal(client, session).await?;
+    pub async fn query(self, client: &SnowflakeClient, session: &Session) -> Result<QueryResult> {
+        let mut data = self.exec_internal(client, session).await?;
 
         let rowtype = data.rowtype.expect("rowtype should exist in query result");
         let type_metas: Vec<_> = rowtype.iter().map(SnowflakeTypeMeta::new).collect();
@@ -644,11 +705,13 @@ impl Query {
         })
     }
 
-    async fn exec_sync_internal(
-        self,
-        client: &SnowflakeClient,
-        session: &Session,
-    ) -> Result<QueryData> {
+    /// Submit the query for asynchronous execution and poll for its status
+    /// until it completes.
+    ///
+    /// Long running queries would otherwise keep a single request open for the
+    /// entire duration of the query. If the returned future is dropped before
+    /// the query completes, the query is aborted.
+    async fn exec_internal(self, client: &SnowflakeClient, session: &Session) -> Result<QueryData> {
         if !session.token.is_valid() {
             // TODO: session.refresh_token()
             // For now just let the query go and return the error from the
@@ -670,15 +733,22 @@ impl Query {
             Some(bindings)
         };
 
+        let request_id = RequestId::new();
+        let mut abort = AbortOnDrop(Some(AbortRequest {
+            client: client.clone(),
+            token: session.token.clone(),
+            sql_text: self.sql.clone(),
+            request_id,
+        }));
+
         let mut res: QueryResponse = client
             .execute(
                 ExecMethod::Post,
                 QUERY_ENDPOINT,
-                Some(&QueryParams {
-                    request_id: RequestId::new(),
-                }),
+                Some(&QueryParams { request_id }),
                 &QueryBody {
                     sql_text: self.sql,
+                    async_exec: true,
                     bindings,
                     ..Default::default()
                 },
@@ -686,8 +756,35 @@ impl Query {
             )
             .await?;
 
+        let mut poll_interval = POLL_INITIAL_INTERVAL;
         while res.is_query_in_progress() {
-            let url = res.data.get_result_url.unwrap_or_default();
+            let query_id = res
+                .data
+                .query_id
+                .clone()
+                .ok_or_else(|| SnowflakeError::QueryError {
+                    code: res.code.clone().unwrap_or_default(),
+                    message: "missing query id for query in progress".to_string(),
+                })?;
+
+            match query_status(client, session, &query_id).await? {
+                QueryStatus::Running => {
+                    tokio::time::sleep(poll_interval).await;
+                    poll_interval = (poll_interval * 2).min(POLL_MAX_INTERVAL);
+                    continue;
+                }
+                QueryStatus::Failed { code, message } => {
+                    abort.disarm();
+                    return Err(SnowflakeError::QueryError { code, message });
+                }
+                QueryStatus::Success => (),
+            }
+
+            let url = res
+                .data
+                .get_result_url
+                .take()
+                .unwrap_or_else(|| format!("/queries/{query_id}/result"));
 
             res = client
                 .execute(
@@ -700,6 +797,9 @@ impl Query {
                 .await?;
         }
 
+        // Query completed (either successfully or not), nothing to abort.
+        abort.disarm();
+
         if !res.success {
             return Err(SnowflakeError::QueryError {
                 code: res.code.unwrap_or_default(),
@@ -711,6 +811,165 @@ impl Query {
     }
 }
 
+#[derive(Debug, Deserialize)]
+struct MonitoringResponse {
+    data: Option<MonitoringData>,
+    message: Option<String>,
+    code: Option<String>,
+    success: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct MonitoringData {
+    #[serde(default)]
+    queries: Vec<MonitoringQuery>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MonitoringQuery {
+    status: String,
+    error_code: Option<String>,
+    error_message: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum QueryStatus {
+    Running,
+    Success,
+    Failed { code: String, message: String },
+}
+
+/// Get the status of a submitted query.
+async fn query_status(
+    client: &SnowflakeClient,
+    session: &Session,
+    query_id: &str,
+) -> Result<QueryStatus> {
+    let res: MonitoringResponse = client
+        .execute(
+            ExecMethod::Get,
+            &format!("{MONITORING_ENDPOINT}/{query_id}"),
+            /* params = */ EmptySerde::none(),
+            EmptySerde::new(),
+            Some(&session.token),
+        )
+        .await?;
+
+    if !res.success {
+        return Err(SnowflakeError::QueryError {
+            code: res.code.unwrap_or_default(),
+            message: res.message.unwrap_or_default(),
+        });
+    }
+
+    // The query might not show up right after being submitted.
+    let query = match res.data.and_then(|data| data.queries.into_iter().next()) {
+        Some(query) => query,
+        None => return Ok(QueryStatus::Running),
+    };
+
+    let status = match query.status.as_str() {
+        "SUCCESS" => QueryStatus::Success,
+        "ABORTING"
+        | "ABORTED"
+        | "FAILED_WITH_ERROR"
+        | "FAILED_WITH_INCIDENT"
+        | "DISCONNECTED"
+        | "BLOCKED" => QueryStatus::Failed {
+            code: query.error_code.unwrap_or_default(),
+            message: query
+                .error_message
+                .unwrap_or_else(|| format!("query {}", query.status.to_lowercase())),
+        },
+        // "RUNNING", "QUEUED", "RESUMING_WAREHOUSE", etc.
+        _ => QueryStatus::Running,
+    };
+    Ok(status)
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AbortBody {
+    sql_text: String,
+    request_id: RequestId,
+}
+
+#[derive(Debug, Deserialize)]
+struct AbortResponse {
+    message: Option<String>,
+    code: Option<String>,
+    success: bool,
+}
+
+#[derive(Debug)]
+struct AbortRequest {
+    client: SnowflakeClient,
+    token: Token,
+    sql_text: String,
+    /// Request ID the query was submitted with.
+    request_id: RequestId,
+}
+
+impl AbortRequest {
+    async fn send(self) -> Result<()> {
+        let res: AbortResponse = self
+            .client
+            .execute(
+                ExecMethod::Post,
+                ABORT_ENDPOINT,
+                Some(&QueryParams {
+                    request_id: RequestId::new(),
+                }),
+                &AbortBody {
+                    sql_text: self.sql_text,
+                    request_id: self.request_id,
+                },
+                Some(&self.token),
+            )
+            .await?;
+
+        if !res.success {
+            return Err(SnowflakeError::QueryError {
+                code: res.code.unwrap_or_default(),
+                message: res.message.unwrap_or_default(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Aborts the query when dropped, unless disarmed.
+struct AbortOnDrop(Option<AbortRequest>);
+
+impl AbortOnDrop {
+    fn disarm(&mut self) {
+        self.0 = None;
+    }
+}
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        let req = match self.0.take() {
+            Some(req) => req,
+            None => return,
+        };
+
+        // Can't await in drop, send the request in the background.
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) => {
+                handle.spawn(async move {
+                    let request_id = req.request_id;
+                    if let Err(e) = req.send().await {
+                        warn!(%e, ?request_id, "failed to abort snowflake query");
+                    }
+                });
+            }
+            Err(_) => warn!("not in a runtime, snowflake query will not be aborted"),
+        }
+    }
+}
+
 fn json_to_arrow(schema: SchemaRef, rows: Vec<Vec<Option<String>>>) -> Result<RecordBatchIter> {
     if schema.fields().is_empty() {
         let options = RecordBatchOptions::new().with_row_count(Some(rows.len()));
@@ -722,83 +981,71 @@ fn json_to_arrow(schema: SchemaRef, rows: Vec<Vec<Option<String>>>) -> Result<Re
     for (col_idx, field) in schema.fields.iter().enumerate() {
         let col: ArrayRef = match field.data_type() {
             DataType::Boolean => {
-                fn parse_bool(s: &String) -> bool {
-                    s == "1"
+                fn parse_bool(s: &String) -> Result<bool> {
+                    Ok(s == "1")
                 }
                 make_json_column!(BooleanBuilder, rows, col_idx, parse_bool)
             }
             DataType::Utf8 => {
+                fn parse_str(s: &str) -> Result<&str> {
+                    Ok(s)
+                }
                 let mut arr = StringBuilder::with_capacity(rows.len(), rows.len() * 16);
-                make_json_column_using!(arr, rows, col_idx, String::as_str)
+                make_json_column_using!(arr, rows, col_idx, parse_str)
             }
             DataType::Binary => {
-                fn parse_binary(s: &String) -> Vec<u8> {
-                    hex::decode(s).expect("value should be a valid hex representation")
+                fn parse_binary(s: &String) -> Result<Vec<u8>> {
+                    hex::decode(s).map_err(|_| invalid_value(&DataType::Binary, s))
                 }
                 let mut arr = BinaryBuilder::with_capacity(rows.len(), rows.len() * 16);
                 make_json_column_using!(arr, rows, col_idx, parse_binary)
             }
             DataType::Float64 => {
-                fn parse_float(s: &str) -> f64 {
-                    s.parse()
-                        .expect("value should be a valid floating point string representation")
+                fn parse_float(s: &str) -> Result<f64> {
+                    s.parse().map_err(|_| invalid_value(&DataType::Float64, s))
                 }
                 make_json_column!(Float64Builder, rows, col_idx, parse_float)
             }
             DataType::Int64 => {
-                fn parse_int(s: &str) -> i64 {
-                    s.parse().expect("value should be a valid i64")
+                fn parse_int(s: &str) -> Result<i64> {
+                    s.parse().map_err(|_| invalid_value(&DataType::Int64, s))
                 }
                 make_json_column!(Int64Builder, rows, col_idx, parse_int)
             }
             dt @ DataType::Decimal128(_, scale) => {
-                let parse_decimal = |s: &str| -> i128 {
-                    let mut d: decimal::Decimal128 = s
-                        .parse()
-                        .expect("value should be a valid decimal representation");
+                let parse_decimal = |s: &str| -> Result<i128> {
+                    let mut d: decimal::Decimal128 = s.parse().map_err(|_| invalid_value(dt, s))?;
                     d.rescale(*scale);
-                    d.mantissa()
+                    Ok(d.mantissa())
                 };
                 let mut arr =
                     Decimal128Builder::with_capacity(rows.len()).with_data_type(dt.clone());
                 make_json_column_using!(arr, rows, col_idx, parse_decimal)
             }
             DataType::Date32 => {
-                fn parse_date(s: &str) -> i32 {
-                    s.parse()
-                        .expect("value should be a valid i32 (number of days)")
+                fn parse_date(s: &str) -> Result<i32> {
+                    // Number of days since the epoch.
+                    s.parse().map_err(|_| invalid_value(&DataType::Date32, s))
                 }
                 make_json_column!(Date32Builder, rows, col_idx, parse_date)
             }
             DataType::Time64(TimeUnit::Nanosecond) => {
-                fn parse_time(s: &str) -> i64 {
-                    let times: Vec<i64> = s
-                        .splitn(2, '.')
-                        .map(|v| {
-                            v.parse()
-                                .expect("value should be a valid time of format <seconds>.<nanos>")
-                        })
-                        .collect();
-
-                    let (seconds, nanos) = (times[0], times[1]);
-                    (seconds * 1_000_000_000) + nanos
+                fn parse_time(s: &str) -> Result<i64> {
+                    // Format is "<seconds>.<fraction>".
+                    parse_nanos(s)
+                        .ok_or_else(|| invalid_value(&DataType::Time64(TimeUnit::Nanosecond), s))
                 }
                 make_json_column!(Time64NanosecondBuilder, rows, col_idx, parse_time)
             }
             dt @ DataType::Timestamp(TimeUnit::Nanosecond, _tz) => {
-                fn parse_timestamp(s: &str) -> i64 {
-                    let times: Vec<i64> = s
-                            .splitn(2, '.')
-                            .map(|v| {
-                                v.parse().expect(
-                                    "value should be a valid timestamp of format <seconds since epoch>.<nanos>",
-                                )
-                            })
-                            .collect();
-
-                    let (seconds, nanos) = (times[0], times[1]);
-                    (seconds * 1_000_000_000) + nanos
-                }
+                let parse_timestamp = |s: &str| -> Result<i64> {
+                    // Format is "<seconds since epoch>.<fraction>". TIMESTAMP_TZ
+                    // values have the timezone offset appended
+                    // ("<seconds>.<fraction> <offset>"). The seconds are
+                    // always since the epoch in UTC so we can ignore it.
+                    let epoch = s.split_whitespace().next().unwrap_or_default();
+                    parse_nanos(epoch).ok_or_else(|| invalid_value(dt, s))
+                };
                 let mut arr = TimestampNanosecondBuilder::with_capacity(rows.len())
                     .with_data_type(dt.clone());
                 make_json_column_using!(arr, rows, col_idx, parse_timestamp)
@@ -812,6 +1059,39 @@ fn json_to_arrow(schema: SchemaRef, rows: Vec<Vec<Option<String>>>) -> Result<Re
     Ok(RecordBatchIter::Exact(Some(record_batch)))
 }
 
+fn invalid_value(datatype: &DataType, value: &str) -> SnowflakeError {
+    SnowflakeError::InvalidResultValue {
+        datatype: datatype.clone(),
+        value: value.to_string(),
+    }
+}
+
+/// Parse "<seconds>.<fraction>" into nanoseconds.
+///
+/// The fraction has as many digits as the scale of the column.
+fn parse_nanos(s: &str) -> Option<i64> {
+    let (seconds, fraction) = s.split_once('.').unwrap_or((s, ""));
+    if fraction.len() > 9 {
+        return None;
+    }
+
+    let seconds: i64 = seconds.parse().ok()?;
+    let fraction: i64 = if fraction.is_empty() {
+        0
+    } else {
+        fraction.parse::<i64>().ok()? * 10_i64.pow(9 - fraction.len() as u32)
+    };
+
+    // Fraction applies in the same direction as the seconds, i.e. "-1.5" is
+    // -1.5 seconds.
+    let nanos = if s.starts_with('-') {
+        seconds * 1_000_000_000 - fraction
+    } else {
+        seconds * 1_000_000_000 + fraction
+    };
+    Some(nanos)
+}
+
 fn ipc_to_arrow(
     schema: SchemaRef,
     type_metas: Arc<Vec<SnowflakeTypeMeta>>,
@@ -833,3 +1113,344 @@ fn ipc_to_arrow(
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use datafusion::arrow::array::{LargeStringArray, TimestampNanosecondArray};
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::testutil::{mock_server, test_session, MockServer};
+
+    #[test]
+    fn parse_fraction_nanos() {
+        assert_eq!(Some(1_500_000_000), parse_nanos("1.5"));
+        assert_eq!(Some(1_000_000_123), parse_nanos("1.000000123"));
+        assert_eq!(Some(-1_500_000_000), parse_nanos("-1.5"));
+        assert_eq!(Some(-500_000_000), parse_nanos("-0.5"));
+        assert_eq!(Some(42_000_000_000), parse_nanos("42"));
+        assert_eq!(None, parse_nanos("1.0000000001"));
+        assert_eq!(None, parse_nanos("abc"));
+    }
+
+    #[test]
+    fn json_timestamp_tz() {
+        let schema = Arc::new(Schema::new(vec![Field::new(
+            "ts",
+            DataType::Timestamp(TimeUnit::Nanosecond, Some("UTC".into())),
+            true,
+        )]));
+        let rows = vec![
+            vec![Some("1700000000.123000000 1500".to_string())],
+            vec![None],
+        ];
+        let batch = json_to_arrow(schema, rows)
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap();
+        let col = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<TimestampNanosecondArray>()
+            .unwrap();
+        assert_eq!(1_700_000_000_123_000_000, col.value(0));
+        assert!(col.is_null(1));
+    }
+
+    #[test]
+    fn json_invalid_value() {
+        let schema = Arc::new(Schema::new(vec![Field::new(
+            "t",
+            DataType::Time64(TimeUnit::Nanosecond),
+            true,
+        )]));
+        let rows = vec![vec![Some("not a time".to_string())]];
+        let err = json_to_arrow(schema, rows).err().unwrap();
+        assert!(
+            matches!(err, SnowflakeError::InvalidResultValue { ref value, .. } if value == "not a time"),
+            "unexpected error: {err}"
+        );
+    }
+
+    fn type_meta(typ: SnowflakeDataType, scale: u32) -> SnowflakeTypeMeta {
+        SnowflakeTypeMeta {
+            typ,
+            precision: 0,
+            scale,
+        }
+    }
+
+    #[test]
+    fn arrow_timestamp_tz_struct() {
+        let expected = Field::new(
+            "ts",
+            DataType::Timestamp(TimeUnit::Nanosecond, Some("UTC".into())),
+            true,
+        );
+
+        // Scale <= 7: (scaled epoch, timezone)
+        let col: ArrayRef = Arc::new(StructArray::from(vec![
+            (
+                Arc::new(Field::new("epoch", DataType::Int64, true)),
+                Arc::new(Int64Array::from(vec![17_000_000_001_234])) as ArrayRef,
+            ),
+            (
+                Arc::new(Field::new("timezone", DataType::Int32, true)),
+                Arc::new(Int32Array::from(vec![1500])) as ArrayRef,
+            ),
+        ]));
+        let actual = Field::new("ts", col.data_type().clone(), true);
+        let out = RecordBatchIter::normalize_column(
+            &expected,
+            &actual,
+            &type_meta(SnowflakeDataType::TimestampTz, 4),
+            &col,
+        )
+        .unwrap();
+        let out = out
+            .as_any()
+            .downcast_ref::<TimestampNanosecondArray>()
+            .unwrap();
+        assert_eq!(1_700_000_000_123_400_000, out.value(0));
+
+        // Scale > 7: (epoch seconds, fraction, timezone)
+        let col: ArrayRef = Arc::new(StructArray::from(vec![
+            (
+                Arc::new(Field::new("epoch", DataType::Int64, true)),
+                Arc::new(Int64Array::from(vec![1_700_000_000])) as ArrayRef,
+            ),
+            (
+                Arc::new(Field::new("fraction", DataType::Int32, true)),
+                Arc::new(Int32Array::from(vec![123_456_789])) as ArrayRef,
+            ),
+            (
+                Arc::new(Field::new("timezone", DataType::Int32, true)),
+                Arc::new(Int32Array::from(vec![1500])) as ArrayRef,
+            ),
+        ]));
+        let actual = Field::new("ts", col.data_type().clone(), true);
+        let out = RecordBatchIter::normalize_column(
+            &expected,
+            &actual,
+            &type_meta(SnowflakeDataType::TimestampTz, 9),
+            &col,
+        )
+        .unwrap();
+        let out = out
+            .as_any()
+            .downcast_ref::<TimestampNanosecondArray>()
+            .unwrap();
+        assert_eq!(1_700_000_000_123_456_789, out.value(0));
+    }
+
+    #[test]
+    fn arrow_semi_structured_cast() {
+        let expected = Field::new("v", DataType::Utf8, true);
+        let actual = Field::new("v", DataType::LargeUtf8, true);
+        let col: ArrayRef = Arc::new(LargeStringArray::from(vec![Some(r#"{"a": 1}"#), None]));
+        let out = RecordBatchIter::normalize_column(
+            &expected,
+            &actual,
+            &type_meta(SnowflakeDataType::Variant, 0),
+            &col,
+        )
+        .unwrap();
+        assert_eq!(&DataType::Utf8, out.data_type());
+
+        // Not something snowflake sends, but shouldn't panic either.
+        let expected = Field::new("v", DataType::Date32, true);
+        let col: ArrayRef = Arc::new(StructArray::from(vec![(
+            Arc::new(Field::new("a", DataType::Int64, true)),
+            Arc::new(Int64Array::from(vec![1])) as ArrayRef,
+        )]));
+        let actual = Field::new("v", col.data_type().clone(), true);
+        let err = RecordBatchIter::normalize_column(
+            &expected,
+            &actual,
+            &type_meta(SnowflakeDataType::Variant, 0),
+            &col,
+        )
+        .unwrap_err();
+        assert!(
+            matches!(err, SnowflakeError::UnsupportedArrowConversion { .. }),
+            "{err}"
+        );
+    }
+
+    fn in_progress() -> Value {
+        json!({
+            "data": {
+                "queryId": "query-1",
+                "getResultUrl": "/queries/query-1/result",
+            },
+            "message": "Asynchronous execution in progress.",
+            "code": "333334",
+            "success": true,
+        })
+    }
+
+    fn status(status: &str) -> Value {
+        json!({
+            "data": {
+                "queries": [{
+                    "id": "query-1",
+                    "status": status,
+                    "errorCode": "000604",
+                    "errorMessage": "SQL execution canceled",
+                }],
+            },
+            "message": null,
+            "code": null,
+            "success": true,
+        })
+    }
+
+    fn result(chunks: Value) -> Value {
+        json!({
+            "data": {
+                "queryId": "query-1",
+                "rowtype": [{
+                    "name": "A",
+                    "type": "fixed",
+                    "precision": 38,
+                    "scale": 0,
+                    "nullable": true,
+                }],
+                "rowset": [["1"], ["2"]],
+                "queryResultFormat": "json",
+                "chunks": chunks,
+            },
+            "message": null,
+            "code": null,
+            "success": true,
+        })
+    }
+
+    fn query(sql: &str) -> Query {
+        Query {
+            sql: sql.to_string(),
+            bindings: Vec::new(),
+        }
+    }
+
+    async fn collect_rows(result: QueryResult, prefetch: usize) -> Vec<i128> {
+        let chunks: Vec<_> = result.into_chunk_stream(prefetch).collect().await;
+        let mut rows = Vec::new();
+        for chunk in chunks {
+            for row in chunk.unwrap().into_row_iter() {
+                match row.unwrap().get_column(0).unwrap().unwrap() {
+                    ScalarValue::Decimal128(Some(v), _, _) => rows.push(v),
+                    v => panic!("unexpected value: {v}"),
+                }
+            }
+        }
+        rows
+    }
+
+    #[tokio::test]
+    async fn async_query_polls_status() {
+        let server = MockServer::bind().await;
+        let chunks = json!([
+            { "url": format!("{}/chunk-1", server.url), "rowCount": 2 },
+            { "url": format!("{}/chunk-2", server.url), "rowCount": 1 },
+        ]);
+        let url = server.url.clone();
+        let handle = server.serve(vec![
+            ("POST /queries/v1/query-request", in_progress()),
+            ("GET /monitoring/queries/query-1", status("QUEUED")),
+            ("GET /monitoring/queries/query-1", status("RUNNING")),
+            ("GET /monitoring/queries/query-1", status("SUCCESS")),
+            ("GET /queries/query-1/result", result(chunks)),
+            ("GET /chunk-1", json!([["3"], ["4"]])),
+            ("GET /chunk-2", json!([["5"]])),
+        ]);
+
+        let client = SnowflakeClient::builder().build(url).unwrap();
+        let session = test_session();
+        let result = query("SELECT a FROM t")
+            .query(&client, &session)
+            .await
+            .unwrap();
+        assert_eq!(3, result.num_chunks());
+
+        // Chunks are downloaded concurrently but must be returned in order.
+        assert_eq!(vec![1, 2, 3, 4, 5], collect_rows(result, 2).await);
+
+        let requests = handle.await.unwrap();
+        assert_eq!(json!(true), requests[0].body["asyncExec"]);
+        assert_eq!("SELECT a FROM t", requests[0].body["sqlText"]);
+    }
+
+    #[tokio::test]
+    async fn async_query_failed() {
+        let (url, _handle) = mock_server(vec![
+            ("POST /queries/v1/query-request", in_progress()),
+            (
+                "GET /monitoring/queries/query-1",
+                status("FAILED_WITH_ERROR"),
+            ),
+        ])
+        .await;
+
+        let client = SnowflakeClient::builder().build(url).unwrap();
+        let session = test_session();
+        let err = query("SELECT a FROM t")
+            .exec(&client, &session)
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(&err, SnowflakeError::QueryError { code, .. } if code == "000604"),
+            "{err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn dropped_query_is_aborted() {
+        let server = MockServer::bind().await;
+        let url = server.url.clone();
+        let (handle, mut received) = server.serve_notify(vec![
+            ("POST /queries/v1/query-request", in_progress()),
+            ("GET /monitoring/queries/query-1", status("RUNNING")),
+            (
+                "POST /queries/v1/abort-request",
+                json!({ "data": null, "message": null, "code": null, "success": true }),
+            ),
+        ]);
+
+        let client = SnowflakeClient::builder().build(url).unwrap();
+        let session = test_session();
+
+        // Drop the query once it's been told the query is still running,
+        // while it waits to check the status again.
+        let exec = query("SELECT a FROM t").exec(&client, &session);
+        tokio::pin!(exec);
+        let status_checked = async {
+            while let Some(request_line) = received.recv().await {
+                if request_line.starts_with("GET /monitoring/queries/query-1") {
+                    return;
+                }
+            }
+            panic!("server stopped before the status was checked");
+        };
+        tokio::select! {
+            _ = &mut exec => panic!("query should not complete"),
+            _ = status_checked => (),
+        }
+        drop(exec);
+
+        let requests = handle.await.unwrap();
+        let submit = &requests[0];
+        let abort = &requests[2];
+        assert_eq!("SELECT a FROM t", abort.body["sqlText"]);
+
+        // Abort needs to reference the request the query was submitted with.
+        let submit_request_id = submit
+            .request_line
+            .split("requestId=")
+            .nth(1)
+            .and_then(|s| s.split([' ', '&']).next())
+            .unwrap();
+        assert_eq!(submit_request_id, abort.body["requestId"]);
+    }
+}
@@ -21,7 +21,7 @@ const HEADER_SSE_C_ALGORITHM: &str = "x-amz-server-side-encryption-customer-algo
 const HEADER_SSE_C_AES_VALUE: &str = "AES256";
 const HEADER_SSE_C_KEY: &str = "x-amz-server-side-encryption-customer-key";
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub struct RequestId(Uuid);
 
 impl RequestId {
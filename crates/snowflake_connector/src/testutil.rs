@@ -0,0 +1,142 @@
+//! Utilities for testing the connector against a local mock server.
+
+use serde_json::Value;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+use crate::auth::{Session, Token};
+
+#[derive(Debug)]
+pub struct MockRequest {
+    /// Request line, e.g. "POST /queries/v1/query-request?requestId=... HTTP/1.1".
+    pub request_line: String,
+    /// JSON body, `Null` if the request didn't have a body.
+    pub body: Value,
+}
+
+pub struct MockServer {
+    listener: TcpListener,
+    pub url: String,
+}
+
+impl MockServer {
+    pub async fn bind() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        Self { listener, url }
+    }
+
+    /// Respond to the expected requests, returning a handle resolving to the
+    /// received requests once all expected requests were received.
+    ///
+    /// Each expectation is the start of the request line (method and path) and
+    /// the JSON to respond with. A request gets the response of the first
+    /// matching expectation that wasn't used yet, so requests sent concurrently
+    /// may arrive in any order.
+    pub fn serve(self, expected: Vec<(&'static str, Value)>) -> JoinHandle<Vec<MockRequest>> {
+        self.serve_inner(expected, None)
+    }
+
+    /// Like [`MockServer::serve`], additionally sending the request line of
+    /// every request on the returned channel once it's been responded to.
+    pub fn serve_notify(
+        self,
+        expected: Vec<(&'static str, Value)>,
+    ) -> (
+        JoinHandle<Vec<MockRequest>>,
+        mpsc::UnboundedReceiver<String>,
+    ) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        (self.serve_inner(expected, Some(tx)), rx)
+    }
+
+    fn serve_inner(
+        self,
+        expected: Vec<(&'static str, Value)>,
+        notify: Option<mpsc::UnboundedSender<String>>,
+    ) -> JoinHandle<Vec<MockRequest>> {
+        tokio::spawn(async move {
+            let mut expected: Vec<_> = expected.into_iter().map(Some).collect();
+            let mut requests = Vec::with_capacity(expected.len());
+            for _ in 0..expected.len() {
+                let (mut stream, _) = self.listener.accept().await.unwrap();
+                let req = read_request(&mut stream).await;
+                let (_, response) = expected
+                    .iter_mut()
+                    .find(|exp| matches!(exp, Some((prefix, _)) if req.request_line.starts_with(prefix)))
+                    .and_then(Option::take)
+                    .unwrap_or_else(|| panic!("unexpected request: {}", req.request_line));
+
+                // Close the connection after every response so the client
+                // can't reuse it and every request is a separate accept.
+                let response = response.to_string();
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{response}",
+                    response.len()
+                );
+                stream.write_all(response.as_bytes()).await.unwrap();
+                if let Some(notify) = &notify {
+                    let _ = notify.send(req.request_line.clone());
+                }
+                requests.push(req);
+            }
+            requests
+        })
+    }
+}
+
+/// Start a server responding to the expected requests, returning the URL to
+/// connect to and a handle resolving to the received requests.
+///
+/// See [`MockServer::serve`].
+pub async fn mock_server(
+    expected: Vec<(&'static str, Value)>,
+) -> (String, JoinHandle<Vec<MockRequest>>) {
+    let server = MockServer::bind().await;
+    let url = server.url.clone();
+    (url, server.serve(expected))
+}
+
+async fn read_request(stream: &mut TcpStream) -> MockRequest {
+    let mut buf = Vec::new();
+    loop {
+        let mut chunk = [0; 4096];
+        let n = stream.read(&mut chunk).await.unwrap();
+        assert_ne!(0, n, "connection closed before reading full request");
+        buf.extend_from_slice(&chunk[..n]);
+
+        let Some(idx) = buf.windows(4).position(|w| w == b"\r\n\r\n") else {
+            continue;
+        };
+        let head = String::from_utf8(buf[..idx].to_vec()).unwrap();
+        let content_len = head
+            .lines()
+            .filter_map(|line| line.split_once(':'))
+            .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+            .map(|(_, len)| len.trim().parse::<usize>().unwrap())
+            .unwrap_or(0);
+        let body = &buf[idx + 4..];
+        if body.len() >= content_len {
+            let body = if content_len == 0 {
+                Value::Null
+            } else {
+                serde_json::from_slice(body).unwrap()
+            };
+            return MockRequest {
+                request_line: head.lines().next().unwrap_or_default().to_string(),
+                body,
+            };
+        }
+    }
+}
+
+/// A session that's valid for an hour.
+pub fn test_session() -> Session {
+    let now = chrono::Utc::now();
+    Session {
+        token: Token::new("session-token".to_string(), 3600, now),
+        master_token: Token::new("master-token".to_string(), 14400, now),
+    }
+}
@@ -254,6 +254,42 @@ pub static GLARE_CACHED_EXTERNAL_DATABASE_TABLES: Lazy<BuiltinTable> = Lazy::new
     oid: 16411,
 });
 
+/// Connection pools for external databases.
+///
+/// Pools are shared by all sessions on a node, and only contain the pools on
+/// the node the query runs on.
+pub static GLARE_CONNECTION_POOLS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    schema: INTERNAL_SCHEMA,
+    name: "connection_pools",
+    columns: InternalColumnDefinition::from_tuples([
+        ("datasource", DataType::Utf8, false),
+        // Connection details, without credentials.
+        ("connection", DataType::Utf8, false),
+        ("max_connections", DataType::UInt64, false),
+        ("in_use", DataType::UInt64, false),
+        ("idle", DataType::UInt64, false),
+        ("total_opened", DataType::UInt64, false),
+        ("total_reused", DataType::UInt64, false),
+        ("total_closed", DataType::UInt64, false),
+    ]),
+    oid: 16412,
+});
+
+/// Materialized views, which are stored as native tables.
+pub static GLARE_MATERIALIZED_VIEWS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    schema: INTERNAL_SCHEMA,
+    name: "materialized_views",
+    columns: InternalColumnDefinition::from_tuples([
+        ("oid", DataType::UInt32, false),
+        ("database_oid", DataType::UInt32, false),
+        ("schema_oid", DataType::UInt32, false),
+        ("schema_name", DataType::Utf8, false),
+        ("view_name", DataType::Utf8, false),
+        ("sql", DataType::Utf8, false),
+    ]),
+    oid: 16413,
+});
+
 impl BuiltinTable {
     /// Check if this table matches the provided schema and name.
     pub fn matches(&self, schema: &str, name: &str) -> bool {
@@ -284,6 +320,8 @@ impl BuiltinTable {
             &GLARE_SSH_KEYS,
             &GLARE_DEPLOYMENT_METADATA,
             &GLARE_CACHED_EXTERNAL_DATABASE_TABLES,
+            &GLARE_CONNECTION_POOLS,
+            &GLARE_MATERIALIZED_VIEWS,
         ]
     }
 }
@@ -694,15 +732,16 @@ pub static PG_MATVIEWS: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
     schema: POSTGRES_SCHEMA,
     name: "pg_matviews",
     sql: "
-    SELECT '' as schemaname,
-    '' as matviewname,
+SELECT
+    schema_name as schemaname,
+    view_name as matviewname,
     '' as matviewowner,
     '' as tablespace,
     false as hasindexes,
-    false as ispopulated,
-    '' as definition
-    WHERE false
-    ",
+    true as ispopulated,
+    sql as definition
+FROM glare_catalog.materialized_views;
+",
 });
 
 pub static PG_REWRITE: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
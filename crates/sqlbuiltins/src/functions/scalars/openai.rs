@@ -27,6 +27,7 @@ use datafusion::physical_plan::ColumnarValue;
 use datafusion::scalar::ScalarValue;
 use datafusion::variable::VarProvider;
 use datafusion_ext::vars::CredentialsVarProvider;
+use datasources::common::secrets::SecretReference;
 use once_cell::sync::Lazy;
 use protogen::metastore::types::catalog::FunctionType;
 use tokio::runtime::Handle;
@@ -124,12 +125,14 @@ impl BuiltinScalarUDF for OpenAIEmbed {
         catalog: &catalog::session_catalog::SessionCatalog,
         mut args: Vec<Expr>,
     ) -> datafusion::error::Result<Expr> {
-        let creds_from_arg = |values: Vec<String>| -> Option<OpenAIConfig> {
+        // The API key is kept next to the config since it may be a secret
+        // reference that's only resolved when the function is executed.
+        let creds_from_arg = |values: Vec<String>| -> Option<(OpenAIConfig, String)> {
             let prov = CredentialsVarProvider::new(catalog);
             let scalar = prov.get_value(values);
 
             match scalar.ok()? {
-                ScalarValue::Utf8(v) => Some(OpenAIConfig::new().with_api_key(v.unwrap())),
+                ScalarValue::Utf8(v) => Some((OpenAIConfig::new(), v?)),
                 ScalarValue::Struct(sa) => {
                     let api_key = sa
                         .column_by_name("api_key")
@@ -142,14 +145,14 @@ impl BuiltinScalarUDF for OpenAIEmbed {
                         .map(|c| c.as_string::<i32>().value(0));
 
                     if let Some(api_key) = api_key {
-                        let mut config = OpenAIConfig::new().with_api_key(api_key);
+                        let mut config = OpenAIConfig::new();
                         if let Some(api_base) = api_base {
                             config = config.with_api_base(api_base);
                         }
                         if let Some(org_id) = org_id {
                             config = config.with_org_id(org_id);
                         }
-                        Some(config)
+                        Some((config, api_key.to_string()))
                     } else {
                         None
                     }
@@ -185,7 +188,7 @@ impl BuiltinScalarUDF for OpenAIEmbed {
             3 => {
                 let creds = match args.first() {
                     Some(Expr::Literal(ScalarValue::Utf8(v))) => {
-                        Some(OpenAIConfig::new().with_api_key(v.clone().unwrap()))
+                        v.clone().map(|api_key| (OpenAIConfig::new(), api_key))
                     }
                     Some(Expr::ScalarVariable(_, values)) => creds_from_arg(values.clone()),
                     _ => return Err(DataFusionError::Plan("Invalid argument".to_string())),
@@ -196,7 +199,7 @@ impl BuiltinScalarUDF for OpenAIEmbed {
             }
             _ => return Err(DataFusionError::Plan("Invalid argument count".to_string())),
         };
-        let Some(creds) = creds else {
+        let Some((creds, api_key)) = creds else {
             return Err(DataFusionError::Plan(
                 "No API key or credential provided".to_string(),
             ));
@@ -244,8 +247,6 @@ impl BuiltinScalarUDF for OpenAIEmbed {
                 .build()
                 .unwrap();
 
-            let client = Client::with_config(creds.clone()).with_http_client(reqwest_client);
-            let embed = client.embeddings();
             // We chunk the input into 2000 items per request to avoid hitting token limits
             let reqs = input_chunks
                 .into_iter()
@@ -257,10 +258,25 @@ impl BuiltinScalarUDF for OpenAIEmbed {
                     dimensions: None,
                 });
 
+            let creds = creds.clone();
+            let api_key = api_key.clone();
             // no way around blocking here. Expressions are not async
             let res: datafusion::error::Result<FixedSizeListArray> =
                 task::block_in_place(move || {
                     Handle::current().block_on(async move {
+                        let api_key = match SecretReference::parse(&api_key)
+                            .map_err(|e| DataFusionError::Execution(e.to_string()))?
+                        {
+                            Some(secret) => secret
+                                .resolve()
+                                .await
+                                .map_err(|e| DataFusionError::Execution(e.to_string()))?,
+                            None => api_key,
+                        };
+                        let client = Client::with_config(creds.with_api_key(api_key))
+                            .with_http_client(reqwest_client);
+                        let embed = client.embeddings();
+
                         let values_builder = Float32Builder::new();
                         let mut builder = FixedSizeListBuilder::new(values_builder, model_len);
 
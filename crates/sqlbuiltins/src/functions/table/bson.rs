@@ -2,6 +2,7 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use datafusion::datasource::file_format::file_compression_type::FileCompressionType;
 use datafusion::datasource::TableProvider;
 use datafusion_ext::errors::ExtensionError;
 use datafusion_ext::functions::{FuncParamValue, TableFuncContextProvider};
@@ -62,11 +63,35 @@ impl TableFunc for BsonScan {
             None => 100,
         };
 
-        let (source_url, storage_options) = table_location_and_opts(ctx, args, &mut opts)?;
+        let union_by_name: bool = opts
+            .remove("union_by_name")
+            .map(FuncParamValue::try_into)
+            .transpose()?
+            .unwrap_or(false);
+
+        // Compression is inferred from the extension of each file if not
+        // provided.
+        let compression = opts
+            .remove("compression")
+            .map(|cmp| -> Result<FileCompressionType, ExtensionError> {
+                let cmp: String = cmp.try_into()?;
+                Ok(cmp.parse::<FileCompressionType>()?)
+            })
+            .transpose()?;
+
+        let (source_url, storage_options) = table_location_and_opts(ctx, args, &mut opts).await?;
 
         let store_access = storage_options_into_store_access(&source_url, &storage_options)
             .map_err(ExtensionError::access)?;
 
-        Ok(bson_streaming_table(store_access, source_url, None, Some(sample_size)).await?)
+        Ok(bson_streaming_table(
+            store_access,
+            source_url,
+            None,
+            Some(sample_size),
+            union_by_name,
+            compression,
+        )
+        .await?)
     }
 }
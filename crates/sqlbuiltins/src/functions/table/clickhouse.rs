@@ -10,7 +10,7 @@ use datafusion_ext::functions::{FuncParamValue, TableFuncContextProvider};
 use datasources::clickhouse::{ClickhouseAccess, ClickhouseTableProvider, OwnedClickhouseTableRef};
 use protogen::metastore::types::catalog::{FunctionType, RuntimePreference};
 
-use super::TableFunc;
+use super::{tls_options_from_opts, TableFunc};
 use crate::functions::ConstBuiltinFunction;
 
 #[derive(Debug, Clone, Copy)]
@@ -48,7 +48,7 @@ impl TableFunc for ReadClickhouse {
         &self,
         _: &dyn TableFuncContextProvider,
         args: Vec<FuncParamValue>,
-        _opts: HashMap<String, FuncParamValue>,
+        mut opts: HashMap<String, FuncParamValue>,
     ) -> Result<Arc<dyn TableProvider>> {
         let total_args = args.len();
         let mut args = args.into_iter();
@@ -68,7 +68,8 @@ impl TableFunc for ReadClickhouse {
             _ => return Err(ExtensionError::InvalidNumArgs),
         };
 
-        let access = ClickhouseAccess::new_from_connection_string(conn_string);
+        let tls = tls_options_from_opts(&mut opts)?;
+        let access = ClickhouseAccess::new_from_connection_string(conn_string).with_tls(tls);
 
         let prov = ClickhouseTableProvider::try_new(access, table_ref)
             .await
@@ -47,7 +47,7 @@ impl TableFunc for DeltaScan {
         args: Vec<FuncParamValue>,
         mut opts: HashMap<String, FuncParamValue>,
     ) -> Result<Arc<dyn TableProvider>> {
-        let (source_url, storage_options) = table_location_and_opts(ctx, args, &mut opts)?;
+        let (source_url, storage_options) = table_location_and_opts(ctx, args, &mut opts).await?;
 
         let table = load_table_direct(&source_url.to_string(), storage_options)
             .await
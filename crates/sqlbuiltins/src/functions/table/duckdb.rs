@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use datafusion::arrow::datatypes::DataType;
+use datafusion::datasource::TableProvider;
+use datafusion::logical_expr::{Signature, Volatility};
+use datafusion_ext::errors::{ExtensionError, Result};
+use datafusion_ext::functions::{FuncParamValue, IdentValue, TableFuncContextProvider};
+use datasources::common::url::DatasourceUrl;
+use datasources::duckdb::{DuckDbAccess, DuckDbTableAccess, DuckDbTableProvider};
+use protogen::metastore::types::catalog::{FunctionType, RuntimePreference};
+
+use super::{table_location_and_opts, TableFunc};
+use crate::functions::ConstBuiltinFunction;
+
+#[derive(Debug, Clone, Copy)]
+pub struct ReadDuckDb;
+
+impl ConstBuiltinFunction for ReadDuckDb {
+    const NAME: &'static str = "read_duckdb";
+    const DESCRIPTION: &'static str = "Read a table from a DuckDB database file";
+    const EXAMPLE: &'static str = "SELECT * FROM read_duckdb('/path/to/db.duckdb', 'table')";
+    const FUNCTION_TYPE: FunctionType = FunctionType::TableReturning;
+    fn signature(&self) -> Option<Signature> {
+        Some(Signature::uniform(
+            2,
+            vec![DataType::Utf8],
+            Volatility::Stable,
+        ))
+    }
+}
+
+#[async_trait]
+impl TableFunc for ReadDuckDb {
+    fn detect_runtime(
+        &self,
+        _args: &[FuncParamValue],
+        _parent: RuntimePreference,
+    ) -> Result<RuntimePreference> {
+        Ok(RuntimePreference::Local)
+    }
+
+    async fn create_provider(
+        &self,
+        ctx: &dyn TableFuncContextProvider,
+        mut args: Vec<FuncParamValue>,
+        mut opts: HashMap<String, FuncParamValue>,
+    ) -> Result<Arc<dyn TableProvider>> {
+        // Same semantics as `read_sqlite`: read_duckdb(<path>, [<creds>],
+        // <table>), where the table may be qualified with a schema.
+        match args.len() {
+            0 | 1 => Err(ExtensionError::InvalidNumArgs),
+            2 | 3 => {
+                let table: IdentValue = args.pop().unwrap().try_into()?;
+                let (source_url, mut storage_options) =
+                    table_location_and_opts(ctx, args, &mut opts).await?;
+                let opts = match source_url.clone() {
+                    DatasourceUrl::File(_) => None,
+                    DatasourceUrl::Url(_) => {
+                        let session = ctx.get_session_vars();
+                        storage_options.inner.insert(
+                            "__tmp_prefix".to_string(),
+                            [
+                                session.user_name().as_str(),
+                                &session.database_name(),
+                                &session.connection_id().to_string(),
+                            ]
+                            .join(""),
+                        );
+
+                        Some(storage_options)
+                    }
+                };
+
+                let state = DuckDbAccess::new(source_url, opts).await?.connect().await?;
+                let access = DuckDbTableAccess::from_qualified_name(table.as_str());
+
+                Ok(Arc::new(DuckDbTableProvider::try_new(state, access).await?))
+            }
+            _ => Err(ExtensionError::String("invalid number of args".to_string())),
+        }
+    }
+}
@@ -8,8 +8,15 @@ use datafusion::logical_expr::{Signature, TypeSignature, Volatility};
 use datafusion_ext::errors::{ExtensionError, Result};
 use datafusion_ext::functions::{FuncParamValue, TableFuncContextProvider};
 use datasources::common::url::DatasourceUrl;
-use datasources::excel::read_excel_impl;
-use ioutil::resolve_path;
+use datasources::excel::table::ExcelTableProvider;
+use datasources::excel::{
+    parse_column_types,
+    CellRange,
+    ExcelReadOptions,
+    ExcelTable,
+    SheetSelection,
+};
+use datasources::object_store::local::LocalStoreAccess;
 use protogen::metastore::types::catalog::{FunctionType, RuntimePreference};
 
 use super::{table_location_and_opts, TableFunc};
@@ -29,6 +36,11 @@ impl ConstBuiltinFunction for ExcelScan {
     fn signature(&self) -> Option<Signature> {
         let options: Fields = vec![
             Field::new("sheet_name", DataType::Utf8, true),
+            Field::new("sheet_index", DataType::UInt64, true),
+            Field::new("all_sheets", DataType::Boolean, true),
+            Field::new("range", DataType::Utf8, true),
+            Field::new("header_row", DataType::UInt64, true),
+            Field::new("column_types", DataType::Utf8, true),
             Field::new("infer_rows", DataType::UInt64, true),
             Field::new("has_header", DataType::Boolean, true),
         ]
@@ -60,40 +72,80 @@ impl TableFunc for ExcelScan {
         args: Vec<FuncParamValue>,
         mut opts: HashMap<String, FuncParamValue>,
     ) -> Result<Arc<dyn TableProvider>> {
-        let (source_url, _) = table_location_and_opts(ctx, args, &mut opts)?;
-
-        let url = match source_url {
-            DatasourceUrl::File(path) => path,
-            DatasourceUrl::Url(url) => {
-                return Err(ExtensionError::String(format!(
-                    "Expected file, received url: {}",
-                    url
-                )))
-            }
-        };
+        let (source_url, _) = table_location_and_opts(ctx, args, &mut opts).await?;
+
+        if let DatasourceUrl::Url(url) = &source_url {
+            return Err(ExtensionError::String(format!(
+                "Expected file, received url: {}",
+                url
+            )));
+        }
 
-        let url = resolve_path(&url)?;
         let sheet_name: Option<String> = opts
             .remove("sheet_name")
             .map(FuncParamValue::try_into)
             .transpose()?;
 
+        let sheet_index: Option<usize> = opts
+            .remove("sheet_index")
+            .map(FuncParamValue::try_into)
+            .transpose()?;
+
+        let all_sheets: bool = opts
+            .remove("all_sheets")
+            .map(FuncParamValue::try_into)
+            .transpose()?
+            .unwrap_or(false);
+
+        let cell_range: Option<CellRange> = opts
+            .remove("range")
+            .map(FuncParamValue::try_into)
+            .transpose()?
+            .map(|range: String| range.parse())
+            .transpose()?;
+
+        let header_row: usize = opts
+            .remove("header_row")
+            .map(FuncParamValue::try_into)
+            .transpose()?
+            .unwrap_or(0);
+
+        let column_types = opts
+            .remove("column_types")
+            .map(FuncParamValue::try_into)
+            .transpose()?
+            .map(|types: String| parse_column_types(&types))
+            .transpose()?
+            .unwrap_or_default();
+
         let has_header: bool = opts
             .remove("has_header")
             .map(FuncParamValue::try_into)
             .transpose()?
             .unwrap_or(true);
 
-        let infer_schema_len = opts
+        let infer_rows = opts
             .remove("infer_rows")
             .map(FuncParamValue::try_into)
             .transpose()?
             .unwrap_or(100);
 
-        let table = read_excel_impl(&url, sheet_name.as_deref(), has_header, infer_schema_len)
+        let options = ExcelReadOptions {
+            sheets: SheetSelection::try_new(sheet_name, sheet_index, all_sheets)?,
+            cell_range,
+            header_row,
+            has_header,
+            infer_rows,
+            column_types,
+        };
+
+        let table = ExcelTable::open(Arc::new(LocalStoreAccess), source_url, options)
+            .await
+            .map_err(|e| ExtensionError::Access(Box::new(e)))?;
+        let provider = ExcelTableProvider::try_new(table)
             .await
             .map_err(|e| ExtensionError::Access(Box::new(e)))?;
 
-        Ok(Arc::new(table))
+        Ok(Arc::new(provider))
     }
 }
@@ -44,7 +44,7 @@ impl TableFunc for IcebergDataFiles {
         args: Vec<FuncParamValue>,
         mut opts: HashMap<String, FuncParamValue>,
     ) -> Result<Arc<dyn TableProvider>> {
-        let (loc, opts) = table_location_and_opts(ctx, args, &mut opts)?;
+        let (loc, opts) = table_location_and_opts(ctx, args, &mut opts).await?;
 
         let store =
             storage_options_into_object_store(&loc, &opts).map_err(ExtensionError::access)?;
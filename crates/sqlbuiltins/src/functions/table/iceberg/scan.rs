@@ -42,7 +42,7 @@ impl TableFunc for IcebergScan {
         mut opts: HashMap<String, FuncParamValue>,
     ) -> Result<Arc<dyn TableProvider>> {
         // TODO: Reduce duplication
-        let (loc, opts) = table_location_and_opts(ctx, args, &mut opts)?;
+        let (loc, opts) = table_location_and_opts(ctx, args, &mut opts).await?;
 
         let store =
             storage_options_into_object_store(&loc, &opts).map_err(ExtensionError::access)?;
@@ -54,13 +54,19 @@ impl TableFunc for JsonScan {
         args: Vec<FuncParamValue>,
         mut opts: HashMap<String, FuncParamValue>,
     ) -> Result<Arc<dyn TableProvider>, ExtensionError> {
+        let union_by_name: bool = opts
+            .remove("union_by_name")
+            .map(FuncParamValue::try_into)
+            .transpose()?
+            .unwrap_or(false);
+
         // setup storage access
 
-        let (source_url, storage_options) = table_location_and_opts(ctx, args, &mut opts)?;
+        let (source_url, storage_options) = table_location_and_opts(ctx, args, &mut opts).await?;
 
         let store_access = storage_options_into_store_access(&source_url, &storage_options)
             .map_err(ExtensionError::access)?;
 
-        Ok(json_streaming_table(store_access, source_url, None).await?)
+        Ok(json_streaming_table(store_access, source_url, None, union_by_name).await?)
     }
 }
@@ -0,0 +1,110 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use datafusion::arrow::datatypes::DataType;
+use datafusion::datasource::TableProvider;
+use datafusion::logical_expr::{Signature, Volatility};
+use datafusion_ext::errors::{ExtensionError, Result};
+use datafusion_ext::functions::{FuncParamValue, TableFuncContextProvider};
+use datasources::kafka::errors::KafkaError;
+use datasources::kafka::{
+    kafka_table,
+    BrokerClient,
+    EndOffset,
+    KafkaFormat,
+    KafkaScanOptions,
+    StartOffset,
+};
+use protogen::metastore::types::catalog::{FunctionType, RuntimePreference};
+
+use super::TableFunc;
+use crate::functions::ConstBuiltinFunction;
+
+#[derive(Debug, Clone, Copy)]
+pub struct ReadKafka;
+
+impl ConstBuiltinFunction for ReadKafka {
+    const NAME: &'static str = "read_kafka";
+    const DESCRIPTION: &'static str = "Reads messages from a Kafka topic";
+    const EXAMPLE: &'static str =
+        "SELECT * FROM read_kafka('localhost:9092', 'events', format => 'json', start_offset => 'earliest')";
+    const FUNCTION_TYPE: FunctionType = FunctionType::TableReturning;
+    fn signature(&self) -> Option<Signature> {
+        Some(Signature::uniform(
+            2,
+            vec![DataType::Utf8],
+            Volatility::Volatile,
+        ))
+    }
+}
+
+#[async_trait]
+impl TableFunc for ReadKafka {
+    fn detect_runtime(
+        &self,
+        _args: &[FuncParamValue],
+        _parent: RuntimePreference,
+    ) -> Result<RuntimePreference> {
+        Ok(RuntimePreference::Remote)
+    }
+
+    async fn create_provider(
+        &self,
+        _: &dyn TableFuncContextProvider,
+        args: Vec<FuncParamValue>,
+        mut opts: HashMap<String, FuncParamValue>,
+    ) -> Result<Arc<dyn TableProvider>> {
+        let (brokers, topic): (String, String) = match args.len() {
+            2 => {
+                let mut args = args.into_iter();
+                (
+                    args.next().unwrap().try_into()?,
+                    args.next().unwrap().try_into()?,
+                )
+            }
+            _ => return Err(ExtensionError::InvalidNumArgs),
+        };
+
+        let format: String = opts
+            .remove("format")
+            .map(FuncParamValue::try_into)
+            .transpose()?
+            .unwrap_or_else(|| "json".to_string());
+        let avro_schema: Option<String> = opts
+            .remove("avro_schema")
+            .map(FuncParamValue::try_into)
+            .transpose()?;
+
+        let scan_opts = KafkaScanOptions {
+            format: KafkaFormat::try_new(&format, avro_schema.as_deref())?,
+            start_offset: offset_from_opts(&mut opts, "start_offset")?.unwrap_or_default(),
+            end_offset: offset_from_opts(&mut opts, "end_offset")?.unwrap_or_default(),
+        };
+
+        let client = BrokerClient::connect(&brokers).await?;
+        Ok(kafka_table(Arc::new(client), &topic, scan_opts).await?)
+    }
+}
+
+/// Get an offset that's either provided as an integer or as a string (e.g.
+/// 'earliest').
+fn offset_from_opts<T>(opts: &mut HashMap<String, FuncParamValue>, key: &str) -> Result<Option<T>>
+where
+    T: FromStr<Err = KafkaError>,
+{
+    let val = match opts.remove(key) {
+        Some(val) => val,
+        None => return Ok(None),
+    };
+
+    let offset = if val.is_valid::<i64>() {
+        let offset: i64 = val.try_into()?;
+        offset.to_string().parse()?
+    } else {
+        let offset: String = val.try_into()?;
+        offset.parse()?
+    };
+    Ok(Some(offset))
+}
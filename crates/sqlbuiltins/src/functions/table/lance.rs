@@ -55,7 +55,7 @@ impl TableFunc for LanceScan {
         args: Vec<FuncParamValue>,
         mut opts: HashMap<String, FuncParamValue>,
     ) -> Result<Arc<dyn TableProvider>> {
-        let (source_url, storage_options) = table_location_and_opts(ctx, args, &mut opts)?;
+        let (source_url, storage_options) = table_location_and_opts(ctx, args, &mut opts).await?;
         Ok(Arc::new(
             LanceTable::new(&source_url.to_string(), storage_options)
                 .await
@@ -4,14 +4,17 @@ mod bson;
 mod cassandra;
 mod clickhouse;
 mod delta;
+mod duckdb;
 mod excel;
 mod generate_series;
 mod iceberg;
 mod json;
+mod kafka;
 mod lance;
 mod mongodb;
 mod mysql;
 mod object_store;
+mod oracle;
 mod parquet_metadata;
 mod postgres;
 mod read_blob;
@@ -33,25 +36,29 @@ use async_trait::async_trait;
 use datafusion::datasource::TableProvider;
 use datafusion_ext::errors::{ExtensionError, Result};
 use datafusion_ext::functions::{FuncParamValue, IdentValue, TableFuncContextProvider};
+use datasources::common::secrets::resolve_secrets;
 use datasources::common::url::{DatasourceUrl, DatasourceUrlType};
 use protogen::metastore::types::catalog::RuntimePreference;
-use protogen::metastore::types::options::{CredentialsOptions, StorageOptions};
+use protogen::metastore::types::options::{CredentialsOptions, StorageOptions, TlsOptions};
 
 use self::bigquery::ReadBigQuery;
 use self::bson::BsonScan;
 use self::cassandra::ReadCassandra;
 use self::clickhouse::ReadClickhouse;
 use self::delta::DeltaScan;
+use self::duckdb::ReadDuckDb;
 use self::excel::ExcelScan;
 use self::generate_series::GenerateSeries;
 use self::iceberg::data_files::IcebergDataFiles;
 use self::iceberg::scan::IcebergScan;
 use self::iceberg::snapshots::IcebergSnapshots;
 use self::json::JsonScan;
+use self::kafka::ReadKafka;
 use self::lance::LanceScan;
 use self::mongodb::ReadMongoDb;
 use self::mysql::ReadMysql;
 use self::object_store::{CloudUpload, READ_CSV, READ_JSON, READ_PARQUET};
+use self::oracle::ReadOracle;
 use self::parquet_metadata::ParquetMetadataFunc;
 use self::postgres::ReadPostgres;
 use self::read_blob::READ_BLOB;
@@ -102,8 +109,11 @@ impl BuiltinTableFuncs {
             Arc::new(ReadSnowflake),
             Arc::new(ReadClickhouse),
             Arc::new(ReadSqlite),
+            Arc::new(ReadDuckDb),
             Arc::new(ReadSqlServer),
             Arc::new(ReadCassandra),
+            Arc::new(ReadKafka),
+            Arc::new(ReadOracle),
             // Object store
             Arc::new(READ_BLOB),
             Arc::new(READ_TEXT),
@@ -155,8 +165,25 @@ impl Default for BuiltinTableFuncs {
     }
 }
 
+/// Look up the credentials object in the catalog, resolving any secrets it
+/// references.
+pub async fn resolve_credentials(
+    ctx: &dyn TableFuncContextProvider,
+    creds: &IdentValue,
+) -> Result<CredentialsOptions> {
+    let ent = ctx
+        .get_session_catalog()
+        .resolve_credentials(creds.as_str())
+        .ok_or(ExtensionError::String(format!(
+            "missing credentials object: {creds}"
+        )))?;
+    resolve_secrets(&ent.options)
+        .await
+        .map_err(ExtensionError::access)
+}
+
 // Parse the data lake table location and object store options from the provided function arguments
-pub fn table_location_and_opts(
+pub async fn table_location_and_opts(
     ctx: &dyn TableFuncContextProvider,
     args: Vec<FuncParamValue>,
     opts: &mut HashMap<String, FuncParamValue>,
@@ -177,15 +204,7 @@ pub fn table_location_and_opts(
     // Check if a credentials object has been supplied
     if let Some(func_param) = args.next() {
         let creds: IdentValue = func_param.try_into()?;
-        maybe_cred_opts = Some(
-            ctx.get_session_catalog()
-                .resolve_credentials(creds.as_str())
-                .cloned()
-                .ok_or(ExtensionError::String(format!(
-                    "missing credentials object: {creds}"
-                )))?
-                .options,
-        );
+        maybe_cred_opts = Some(resolve_credentials(ctx, &creds).await?);
     }
 
     let mut storage_options = StorageOptions::default();
@@ -247,6 +266,21 @@ pub fn table_location_and_opts(
     Ok((source_url, storage_options))
 }
 
+/// Get TLS options for connecting to a database from named function
+/// arguments.
+pub fn tls_options_from_opts(opts: &mut HashMap<String, FuncParamValue>) -> Result<TlsOptions> {
+    let mut get_opt = |key: &str| -> Result<Option<String>> {
+        opts.remove(key).map(FuncParamValue::try_into).transpose()
+    };
+
+    Ok(TlsOptions {
+        ssl_mode: get_opt("ssl_mode")?,
+        ca_cert: get_opt("ca_cert")?,
+        client_cert: get_opt("client_cert")?,
+        client_key: get_opt("client_key")?,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -7,10 +7,10 @@ use datafusion::datasource::TableProvider;
 use datafusion::logical_expr::{Signature, Volatility};
 use datafusion_ext::errors::{ExtensionError, Result};
 use datafusion_ext::functions::{FuncParamValue, TableFuncContextProvider};
-use datasources::mongodb::{MongoDbAccessor, MongoDbTableAccessInfo};
+use datasources::mongodb::{MongoDbAccessor, MongoDbTableAccessInfo, SampleSize};
 use protogen::metastore::types::catalog::{FunctionType, RuntimePreference};
 
-use super::TableFunc;
+use super::{tls_options_from_opts, TableFunc};
 use crate::functions::ConstBuiltinFunction;
 
 #[derive(Debug, Clone, Copy)]
@@ -20,7 +20,7 @@ impl ConstBuiltinFunction for ReadMongoDb {
     const NAME: &'static str = "read_mongodb";
     const DESCRIPTION: &'static str = "Reads a MongoDB table";
     const EXAMPLE: &'static str =
-        "SELECT * FROM read_mongodb('mongodb://localhost:27017', 'database', 'collection')";
+        "SELECT * FROM read_mongodb('mongodb://localhost:27017', 'database', 'collection', sample_size => 1000)";
     const FUNCTION_TYPE: FunctionType = FunctionType::TableReturning;
 
     fn signature(&self) -> Option<Signature> {
@@ -46,7 +46,7 @@ impl TableFunc for ReadMongoDb {
         &self,
         _: &dyn TableFuncContextProvider,
         args: Vec<FuncParamValue>,
-        _opts: HashMap<String, FuncParamValue>,
+        mut opts: HashMap<String, FuncParamValue>,
     ) -> Result<Arc<dyn TableProvider>> {
         match args.len() {
             3 => {
@@ -55,7 +55,18 @@ impl TableFunc for ReadMongoDb {
                 let database: String = args.next().unwrap().try_into()?;
                 let collection: String = args.next().unwrap().try_into()?;
 
-                let access = MongoDbAccessor::connect(&conn_str)
+                let tls = tls_options_from_opts(&mut opts)?;
+                let sample_size: Option<usize> = opts
+                    .remove("sample_size")
+                    .map(FuncParamValue::try_into)
+                    .transpose()?;
+                let full_scan: bool = opts
+                    .remove("full_scan")
+                    .map(FuncParamValue::try_into)
+                    .transpose()?
+                    .unwrap_or(false);
+
+                let access = MongoDbAccessor::connect(&conn_str, &tls)
                     .await
                     .map_err(|e| ExtensionError::Access(Box::new(e)))?;
                 let prov = access
@@ -63,6 +74,7 @@ impl TableFunc for ReadMongoDb {
                         database,
                         collection,
                         fields: None, // TODO inject projection with these fields, mostly an arg parsing problem
+                        sample_size: SampleSize::from_options(sample_size, full_scan),
                     })
                     .into_table_provider()
                     .await
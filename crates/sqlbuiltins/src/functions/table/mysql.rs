@@ -10,7 +10,7 @@ use datafusion_ext::functions::{FuncParamValue, TableFuncContextProvider};
 use datasources::mysql::{MysqlAccessor, MysqlTableAccess};
 use protogen::metastore::types::catalog::{FunctionType, RuntimePreference};
 
-use super::TableFunc;
+use super::{tls_options_from_opts, TableFunc};
 use crate::functions::ConstBuiltinFunction;
 
 #[derive(Debug, Clone, Copy)]
@@ -45,7 +45,7 @@ impl TableFunc for ReadMysql {
         &self,
         _: &dyn TableFuncContextProvider,
         args: Vec<FuncParamValue>,
-        _opts: HashMap<String, FuncParamValue>,
+        mut opts: HashMap<String, FuncParamValue>,
     ) -> Result<Arc<dyn TableProvider>> {
         match args.len() {
             3 => {
@@ -54,7 +54,9 @@ impl TableFunc for ReadMysql {
                 let schema: String = args.next().unwrap().try_into()?;
                 let table: String = args.next().unwrap().try_into()?;
 
-                let access = MysqlAccessor::connect(&conn_str, None)
+                let tls = tls_options_from_opts(&mut opts)?;
+
+                let access = MysqlAccessor::connect(&conn_str, None, tls)
                     .await
                     .map_err(|e| ExtensionError::Access(Box::new(e)))?;
                 let prov = access
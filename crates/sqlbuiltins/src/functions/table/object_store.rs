@@ -17,9 +17,10 @@ use datafusion::logical_expr::{Signature, TypeSignature, Volatility};
 use datafusion_ext::errors::{ExtensionError, Result};
 use datafusion_ext::functions::{FuncParamValue, IdentValue, TableFuncContextProvider};
 use datasources::bson::table::bson_streaming_table_from_object;
+use datasources::common::schema::union_schemas_by_name;
 use datasources::common::url::{DatasourceUrl, DatasourceUrlType};
-use datasources::excel::excel_table_from_object;
 use datasources::excel::table::ExcelTableProvider;
+use datasources::excel::{excel_table_from_object, ExcelReadOptions};
 use datasources::json::table::json_streaming_table_from_object;
 use datasources::native::access::NativeTableStorage;
 use datasources::object_store::azure::AzureStoreAccess;
@@ -39,6 +40,7 @@ use object_store::{ObjectMeta, ObjectStore};
 use protogen::metastore::types::catalog::{FunctionType, RuntimePreference};
 use protogen::metastore::types::options::CredentialsOptions;
 
+use crate::functions::table::resolve_credentials;
 use crate::functions::{BuiltinFunction, ConstBuiltinFunction, TableFunc};
 
 #[derive(Debug, Clone, Copy)]
@@ -47,7 +49,12 @@ pub struct ParquetOptionsReader;
 impl OptionReader for ParquetOptionsReader {
     type Format = ParquetFormat;
 
-    const OPTIONS: &'static [(&'static str, DataType)] = &[];
+    const OPTIONS: &'static [(&'static str, DataType)] = &[
+        // Merge schemas of all files by column name. Default: false
+        ("union_by_name", DataType::Boolean),
+    ];
+
+    const SUPPORTS_UNION_BY_NAME: bool = true;
 
     fn read_options(_opts: &HashMap<String, FuncParamValue>) -> Result<Self::Format> {
         Ok(ParquetFormat::default())
@@ -75,6 +82,9 @@ impl OptionReader for CsvOptionReader {
         ("has_header", DataType::Boolean),
     ];
 
+    // Columns in CSV files are read by position, not by name.
+    const SUPPORTS_UNION_BY_NAME: bool = false;
+
     fn read_options(opts: &HashMap<String, FuncParamValue>) -> Result<Self::Format> {
         let mut format = CsvFormat::default().with_schema_infer_max_rec(Some(20480));
 
@@ -113,7 +123,12 @@ pub struct JsonOptionsReader;
 impl OptionReader for JsonOptionsReader {
     type Format = JsonFormat;
 
-    const OPTIONS: &'static [(&'static str, DataType)] = &[];
+    const OPTIONS: &'static [(&'static str, DataType)] = &[
+        // Merge schemas of all files by column name. Default: false
+        ("union_by_name", DataType::Boolean),
+    ];
+
+    const SUPPORTS_UNION_BY_NAME: bool = true;
 
     fn read_options(_opts: &HashMap<String, FuncParamValue>) -> Result<Self::Format> {
         Ok(JsonFormat::default())
@@ -134,6 +149,10 @@ pub trait OptionReader: Sync + Send + Sized {
     /// List of options and their expected data types.
     const OPTIONS: &'static [(&'static str, DataType)];
 
+    /// If the format reads columns by name, allowing schemas from multiple
+    /// files to be merged with the `union_by_name` option.
+    const SUPPORTS_UNION_BY_NAME: bool;
+
     /// Read user provided options, and construct a file format using those options.
     fn read_options(opts: &HashMap<String, FuncParamValue>) -> Result<Self::Format>;
 }
@@ -277,14 +296,30 @@ impl<Opts: OptionReader> TableFunc for ObjScanTableFunc<Opts> {
             format = format.with_compression(compression)?;
         }
 
+        let union_by_name: bool = opts
+            .remove("union_by_name")
+            .map(FuncParamValue::try_into)
+            .transpose()?
+            .unwrap_or(false);
+        if union_by_name && !Opts::SUPPORTS_UNION_BY_NAME {
+            return Err(ExtensionError::String(format!(
+                "'union_by_name' is not supported for {}",
+                self.name
+            )));
+        }
+
         // Optimize creating a table provider for objects by clubbing the same
         // store together.
         let mut fn_registry: HashMap<
             ObjectStoreUrl,
             (Arc<dyn ObjStoreAccess>, Vec<DatasourceUrl>),
         > = HashMap::new();
+        let creds = match &creds_ident {
+            Some(ident) => Some(resolve_credentials(ctx, ident).await?),
+            None => None,
+        };
         for source_url in urls {
-            let access = get_store_access(ctx, &source_url, creds_ident.as_ref(), opts.clone())?;
+            let access = get_store_access(ctx, &source_url, creds.as_ref(), opts.clone())?;
             let base_url = access
                 .base_url()
                 .map_err(|e| ExtensionError::Access(Box::new(e)))?;
@@ -300,6 +335,10 @@ impl<Opts: OptionReader> TableFunc for ObjScanTableFunc<Opts> {
         // all the objects and turn this into a table provider.
 
         let format: Arc<dyn FileFormat> = Arc::new(format);
+        if union_by_name {
+            return get_table_provider_union_by_name(ctx, format, fn_registry.into_values()).await;
+        }
+
         let table = fn_registry
             .into_values()
             .map(|(access, locations)| get_table_provider(ctx, format.clone(), access, locations))
@@ -392,82 +431,119 @@ async fn get_table_provider(
     Ok(prov)
 }
 
+/// Gets a table provider for files across all stores, merging the schemas of
+/// every file by column name.
+///
+/// Files missing columns will have those columns filled with nulls, and
+/// columns with differing types will be widened to a common type.
+async fn get_table_provider_union_by_name(
+    ctx: &dyn TableFuncContextProvider,
+    ft: Arc<dyn FileFormat>,
+    sources: impl IntoIterator<Item = (Arc<dyn ObjStoreAccess>, Vec<DatasourceUrl>)>,
+) -> Result<Arc<dyn TableProvider>> {
+    let state = ctx.get_session_state();
+
+    // Schemas need to be inferred for all files before creating any provider
+    // so that every provider uses the same merged schema.
+    let mut listed = Vec::new();
+    let mut schemas = Vec::new();
+    for (access, locations) in sources {
+        let accessor = ObjStoreAccessor::new(access)?;
+
+        let mut objects = Vec::new();
+        for loc in locations {
+            let objs = accessor.list_globbed(loc.path()).await?;
+            objects.extend(objs.into_iter());
+        }
+
+        let schema = accessor
+            .infer_schema_by_name(&state, ft.as_ref(), &objects)
+            .await?;
+        schemas.push(schema);
+        listed.push((accessor, objects));
+    }
+
+    let schema = Arc::new(union_schemas_by_name(
+        schemas.iter().map(|schema| schema.as_ref()),
+    )?);
+
+    let providers = listed
+        .into_iter()
+        .map(|(accessor, objects)| {
+            accessor.into_table_provider_with_schema(ft.clone(), schema.clone(), objects)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Arc::new(MultiSourceTableProvider::new(providers)))
+}
+
 /// Get's an object store accessor for the provided url.
 ///
-/// If the object store requires credentials, `creds_ident` can be provided to
-/// lookup saved credentials in the catalog. Otherwise individual values (access
-/// keys, etc) will be pulled out of `opts`.
+/// If the object store requires credentials, `creds` can be provided with
+/// saved credentials resolved from the catalog. Otherwise individual values
+/// (access keys, etc) will be pulled out of `opts`.
 fn get_store_access(
     ctx: &dyn TableFuncContextProvider,
     source_url: &DatasourceUrl,
-    creds_ident: Option<&IdentValue>,
+    creds: Option<&CredentialsOptions>,
     mut opts: HashMap<String, FuncParamValue>,
 ) -> Result<Arc<dyn ObjStoreAccess>> {
-    let access: Arc<dyn ObjStoreAccess> = match creds_ident {
-        Some(ident) => {
-            let creds = ctx
-                .get_session_catalog()
-                .resolve_credentials(ident.as_str())
-                .ok_or(ExtensionError::String(format!(
-                    "missing credentials object: {ident}"
-                )))?;
-
-            match source_url.datasource_url_type() {
-                DatasourceUrlType::Gcs => {
-                    let service_account_key = match &creds.options {
-                        CredentialsOptions::Gcp(o) => o.service_account_key.to_owned(),
-                        other => {
-                            return Err(ExtensionError::String(format!(
-                                "invalid credentials for GCS, got {}",
-                                other.as_str()
-                            )))
-                        }
-                    };
-
-                    create_gcs_table_provider(source_url, Some(service_account_key))?
-                }
-                DatasourceUrlType::S3 => {
-                    let (access_key_id, secret_access_key) = match &creds.options {
-                        CredentialsOptions::Aws(o) => {
-                            (o.access_key_id.to_owned(), o.secret_access_key.to_owned())
-                        }
-                        other => {
-                            return Err(ExtensionError::String(format!(
-                                "invalid credentials for S3, got {}",
-                                other.as_str()
-                            )))
-                        }
-                    };
-
-                    create_s3_store_access(
-                        source_url,
-                        &mut opts,
-                        Some(access_key_id),
-                        Some(secret_access_key),
-                    )?
-                }
-                DatasourceUrlType::Azure => {
-                    let (account, access_key) = match &creds.options {
-                        CredentialsOptions::Azure(azure) => {
-                            (azure.account_name.to_owned(), azure.access_key.to_owned())
-                        }
-                        other => {
-                            return Err(ExtensionError::String(format!(
-                                "invalid credentials for Azure, got {}",
-                                other.as_str()
-                            )))
-                        }
-                    };
-
-                    create_azure_store_access(source_url, Some(account), Some(access_key))?
-                }
-                other => {
-                    return Err(ExtensionError::String(format!(
-                        "Cannot get {other} datasource with credentials"
-                    )))
-                }
+    let access: Arc<dyn ObjStoreAccess> = match creds {
+        Some(creds) => match source_url.datasource_url_type() {
+            DatasourceUrlType::Gcs => {
+                let service_account_key = match creds {
+                    CredentialsOptions::Gcp(o) => o.service_account_key.to_owned(),
+                    other => {
+                        return Err(ExtensionError::String(format!(
+                            "invalid credentials for GCS, got {}",
+                            other.as_str()
+                        )))
+                    }
+                };
+
+                create_gcs_table_provider(source_url, Some(service_account_key))?
             }
-        }
+            DatasourceUrlType::S3 => {
+                let (access_key_id, secret_access_key) = match creds {
+                    CredentialsOptions::Aws(o) => {
+                        (o.access_key_id.to_owned(), o.secret_access_key.to_owned())
+                    }
+                    other => {
+                        return Err(ExtensionError::String(format!(
+                            "invalid credentials for S3, got {}",
+                            other.as_str()
+                        )))
+                    }
+                };
+
+                create_s3_store_access(
+                    source_url,
+                    &mut opts,
+                    Some(access_key_id),
+                    Some(secret_access_key),
+                )?
+            }
+            DatasourceUrlType::Azure => {
+                let (account, access_key) = match creds {
+                    CredentialsOptions::Azure(azure) => {
+                        (azure.account_name.to_owned(), azure.access_key.to_owned())
+                    }
+                    other => {
+                        return Err(ExtensionError::String(format!(
+                            "invalid credentials for Azure, got {}",
+                            other.as_str()
+                        )))
+                    }
+                };
+
+                create_azure_store_access(source_url, Some(account), Some(access_key))?
+            }
+            other => {
+                return Err(ExtensionError::String(format!(
+                    "Cannot get {other} datasource with credentials"
+                )))
+            }
+        },
         None => {
             // Raw credentials or No credentials
             match source_url.datasource_url_type() {
@@ -727,8 +803,7 @@ impl TableFunc for CloudUpload {
                 let table = excel_table_from_object(
                     storage.store.inner.as_ref(),
                     meta,
-                    /* sheet_name = */ None,
-                    /* has_header = */ true,
+                    ExcelReadOptions::default(),
                 )
                 .await?;
                 let table = ExcelTableProvider::try_new(table).await?;
@@ -45,7 +45,7 @@ impl TableFunc for ReadPostgres {
         &self,
         _: &dyn TableFuncContextProvider,
         args: Vec<FuncParamValue>,
-        _opts: HashMap<String, FuncParamValue>,
+        mut opts: HashMap<String, FuncParamValue>,
     ) -> Result<Arc<dyn TableProvider>> {
         match args.len() {
             3 => {
@@ -54,11 +54,22 @@ impl TableFunc for ReadPostgres {
                 let schema: String = args.next().unwrap().try_into()?;
                 let table: String = args.next().unwrap().try_into()?;
 
+                let partition_column: Option<String> = opts
+                    .remove("partition_column")
+                    .map(FuncParamValue::try_into)
+                    .transpose()?;
+                let num_partitions: Option<usize> = opts
+                    .remove("num_partitions")
+                    .map(FuncParamValue::try_into)
+                    .transpose()?;
+
                 let access = PostgresAccess::new_from_conn_str(conn_str, None);
                 let prov_conf = PostgresTableProviderConfig {
                     access,
                     schema,
                     table,
+                    partition_column,
+                    num_partitions,
                 };
                 let prov = PostgresTableProvider::try_new(prov_conf)
                     .await
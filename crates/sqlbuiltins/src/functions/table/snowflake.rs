@@ -67,6 +67,7 @@ impl TableFunc for ReadSnowflake {
                     database_name: database,
                     warehouse,
                     role_name: Some(role),
+                    credentials: None,
                 };
                 let access_info = SnowflakeTableAccess {
                     schema_name: schema,
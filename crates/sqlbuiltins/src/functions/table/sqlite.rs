@@ -58,7 +58,7 @@ impl TableFunc for ReadSqlite {
             2 | 3 => {
                 let table: IdentValue = args.pop().unwrap().try_into()?;
                 let (source_url, mut storage_options) =
-                    table_location_and_opts(ctx, args, &mut opts)?;
+                    table_location_and_opts(ctx, args, &mut opts).await?;
                 let opts = match source_url.clone() {
                     DatasourceUrl::File(_) => None,
                     DatasourceUrl::Url(_) => {
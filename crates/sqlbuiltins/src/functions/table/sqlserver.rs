@@ -14,7 +14,7 @@ use datasources::sqlserver::{
 };
 use protogen::metastore::types::catalog::{FunctionType, RuntimePreference};
 
-use super::TableFunc;
+use super::{tls_options_from_opts, TableFunc};
 use crate::functions::ConstBuiltinFunction;
 
 #[derive(Debug, Clone, Copy)]
@@ -49,7 +49,7 @@ impl TableFunc for ReadSqlServer {
         &self,
         _: &dyn TableFuncContextProvider,
         args: Vec<FuncParamValue>,
-        _opts: HashMap<String, FuncParamValue>,
+        mut opts: HashMap<String, FuncParamValue>,
     ) -> Result<Arc<dyn TableProvider>> {
         match args.len() {
             3 => {
@@ -58,7 +58,10 @@ impl TableFunc for ReadSqlServer {
                 let schema: String = args.next().unwrap().try_into()?;
                 let table: String = args.next().unwrap().try_into()?;
 
+                let tls = tls_options_from_opts(&mut opts)?;
+
                 let access = SqlServerAccess::try_new_from_ado_string(&conn_str)
+                    .and_then(|access| access.with_tls(tls))
                     .map_err(ExtensionError::access)?;
                 let prov_conf = SqlServerTableProviderConfig {
                     access,
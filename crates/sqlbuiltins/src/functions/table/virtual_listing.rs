@@ -17,9 +17,12 @@ use datafusion_ext::functions::{
 use datasources::bigquery::BigQueryAccessor;
 use datasources::cassandra::CassandraAccess;
 use datasources::clickhouse::ClickhouseAccess;
+use datasources::common::secrets::resolve_secrets;
 use datasources::debug::DebugVirtualLister;
+use datasources::duckdb::DuckDbAccess;
 use datasources::mongodb::MongoDbAccessor;
 use datasources::mysql::MysqlAccessor;
+use datasources::oracle::OracleAccessor;
 use datasources::postgres::PostgresAccess;
 use datasources::snowflake::{SnowflakeAccessor, SnowflakeDbConnection};
 use datasources::sqlite::SqliteAccess;
@@ -30,8 +33,10 @@ use protogen::metastore::types::options::{
     DatabaseOptionsBigQuery,
     DatabaseOptionsCassandra,
     DatabaseOptionsClickhouse,
+    DatabaseOptionsDuckDb,
     DatabaseOptionsMongoDb,
     DatabaseOptionsMysql,
+    DatabaseOptionsOracle,
     DatabaseOptionsPostgres,
     DatabaseOptionsSnowflake,
     DatabaseOptionsSqlServer,
@@ -298,7 +303,10 @@ pub(crate) async fn get_virtual_lister_for_db<'a, 'b: 'a, 'c: 'b>(
 pub(crate) async fn get_virtual_lister_for_external_db(
     opts: &DatabaseOptions,
 ) -> Result<Box<dyn VirtualLister>> {
-    let lister: Box<dyn VirtualLister> = match opts {
+    let opts = resolve_secrets(opts)
+        .await
+        .map_err(ExtensionError::access)?;
+    let lister: Box<dyn VirtualLister> = match &opts {
         DatabaseOptions::Internal(_) => panic!("attempted to get lister for internal db"),
         DatabaseOptions::Debug(_) => Box::new(DebugVirtualLister),
         DatabaseOptions::Postgres(DatabaseOptionsPostgres { connection_string }) => {
@@ -320,16 +328,21 @@ pub(crate) async fn get_virtual_lister_for_external_db(
                     .map_err(|e| ExtensionError::Access(Box::new(e)))?;
             Box::new(accessor)
         }
-        DatabaseOptions::Mysql(DatabaseOptionsMysql { connection_string }) => {
-            let accessor = MysqlAccessor::connect(connection_string, None)
+        DatabaseOptions::Mysql(DatabaseOptionsMysql {
+            connection_string,
+            tls,
+        }) => {
+            let accessor = MysqlAccessor::connect(connection_string, None, tls.clone())
                 .await
                 .map_err(|e| ExtensionError::Access(Box::new(e)))?;
             Box::new(accessor)
         }
         DatabaseOptions::MongoDb(DatabaseOptionsMongoDb {
-            connection_string, ..
+            connection_string,
+            tls,
+            ..
         }) => {
-            let accessor = MongoDbAccessor::connect(connection_string)
+            let accessor = MongoDbAccessor::connect(connection_string, tls)
                 .await
                 .map_err(|e| ExtensionError::Access(Box::new(e)))?;
             Box::new(accessor)
@@ -341,6 +354,7 @@ pub(crate) async fn get_virtual_lister_for_external_db(
             database_name,
             warehouse,
             role_name,
+            credentials,
         }) => {
             let role_name = if role_name.is_empty() {
                 None
@@ -354,20 +368,29 @@ pub(crate) async fn get_virtual_lister_for_external_db(
                 database_name: database_name.clone(),
                 warehouse: warehouse.clone(),
                 role_name,
+                credentials: credentials.clone(),
             };
             let accessor = SnowflakeAccessor::connect(conn_params)
                 .await
                 .map_err(|e| ExtensionError::Access(Box::new(e)))?;
             Box::new(accessor)
         }
-        DatabaseOptions::SqlServer(DatabaseOptionsSqlServer { connection_string }) => {
+        DatabaseOptions::SqlServer(DatabaseOptionsSqlServer {
+            connection_string,
+            tls,
+        }) => {
             let access = SqlServerAccess::try_new_from_ado_string(connection_string)
+                .and_then(|access| access.with_tls(tls.clone()))
                 .map_err(ExtensionError::access)?;
             let state = access.connect().await.map_err(ExtensionError::access)?;
             Box::new(state)
         }
-        DatabaseOptions::Clickhouse(DatabaseOptionsClickhouse { connection_string }) => {
+        DatabaseOptions::Clickhouse(DatabaseOptionsClickhouse {
+            connection_string,
+            tls,
+        }) => {
             let state = ClickhouseAccess::new_from_connection_string(connection_string.clone())
+                .with_tls(tls.clone())
                 .connect()
                 .await
                 .map_err(ExtensionError::access)?;
@@ -403,6 +426,30 @@ pub(crate) async fn get_virtual_lister_for_external_db(
                 .await?;
             Box::new(state)
         }
+        DatabaseOptions::Oracle(DatabaseOptionsOracle { connection_string }) => {
+            let state = OracleAccessor::connect(connection_string)
+                .await
+                .map_err(ExtensionError::access)?;
+            Box::new(state)
+        }
+        DatabaseOptions::DuckDb(DatabaseOptionsDuckDb {
+            location,
+            storage_options,
+        }) => {
+            let storage_options = match storage_options.clone() {
+                Some(mut opts) => {
+                    opts.inner
+                        .insert("__tmp_prefix".to_string(), Uuid::new_v4().to_string());
+                    Some(opts)
+                }
+                None => None,
+            };
+            let state = DuckDbAccess::new(location.as_str().try_into()?, storage_options)
+                .await?
+                .connect()
+                .await?;
+            Box::new(state)
+        }
         DatabaseOptions::Delta(_) => {
             return Err(ExtensionError::Unimplemented(
                 "deltalake information listing",
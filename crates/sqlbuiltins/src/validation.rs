@@ -86,6 +86,8 @@ pub fn validate_database_creds_support(database: &str, creds: &str) -> Result<()
         (database, creds),
         // Google cloud
         (DatabaseOptions::BIGQUERY, CredentialsOptions::GCP) |
+        // Snowflake
+        (DatabaseOptions::SNOWFLAKE, CredentialsOptions::SNOWFLAKE) |
         // Delta
         (DatabaseOptions::DELTA, CredentialsOptions::GCP | CredentialsOptions::AWS | CredentialsOptions::AZURE)
     ) {
@@ -111,6 +113,8 @@ pub fn validate_table_creds_support(table: &str, creds: &str) -> Result<()> {
         (TableOptionsV0::S3_STORAGE, CredentialsOptions::AWS) |
         // Azure
         (TableOptionsV0::AZURE, CredentialsOptions::AZURE) |
+        // Snowflake
+        (TableOptionsV0::SNOWFLAKE, CredentialsOptions::SNOWFLAKE) |
         // Delta & Iceberg & Lance
         (TableOptionsV0::DELTA | TableOptionsV0::ICEBERG | TableOptionsV0::LANCE, CredentialsOptions::GCP | CredentialsOptions::AWS | CredentialsOptions::AZURE )
     ) {
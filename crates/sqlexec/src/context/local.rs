@@ -38,7 +38,11 @@ use uuid::Uuid;
 use super::{new_datafusion_runtime_env, new_datafusion_session_config_opts};
 use crate::environment::EnvironmentReader;
 use crate::errors::{internal, ExecError, Result};
-use crate::optimizer::DdlInputOptimizationRule;
+use crate::optimizer::{
+    DdlInputOptimizationRule,
+    MongoDbPipelinePushdownRule,
+    SqlQueryPushdownRule,
+};
 use crate::planner::logical_plan::{
     FullObjectReference,
     FullSchemaReference,
@@ -110,6 +114,8 @@ impl LocalSessionContext {
 
         let state = SessionState::new_with_config_rt(conf, Arc::new(runtime))
             .add_optimizer_rule(Arc::new(DdlInputOptimizationRule::new()))
+            .add_optimizer_rule(Arc::new(SqlQueryPushdownRule))
+            .add_optimizer_rule(Arc::new(MongoDbPipelinePushdownRule))
             .add_physical_optimizer_rule(Arc::new(RuntimeGroupPullUp {}));
 
         let df_ctx = DfSessionContext::new_with_state(state);
@@ -164,6 +170,8 @@ impl LocalSessionContext {
 
         let state = SessionState::new_with_config_rt(conf, runtime)
             .add_optimizer_rule(Arc::new(DdlInputOptimizationRule::new()))
+            .add_optimizer_rule(Arc::new(SqlQueryPushdownRule))
+            .add_optimizer_rule(Arc::new(MongoDbPipelinePushdownRule))
             .add_physical_optimizer_rule(Arc::new(RuntimeGroupPullUp {}));
 
         let df_ctx = DfSessionContext::new_with_state(state);
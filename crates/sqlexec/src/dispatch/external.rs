@@ -15,22 +15,25 @@ use datasources::bigquery::{BigQueryAccessor, BigQueryTableAccess};
 use datasources::bson::table::bson_streaming_table;
 use datasources::cassandra::CassandraTableProvider;
 use datasources::clickhouse::{ClickhouseAccess, ClickhouseTableProvider, OwnedClickhouseTableRef};
+use datasources::common::secrets::resolve_secrets;
 use datasources::common::url::DatasourceUrl;
 use datasources::debug::DebugTableType;
+use datasources::duckdb::{DuckDbAccess, DuckDbTableAccess, DuckDbTableProvider};
 use datasources::excel::table::ExcelTableProvider;
-use datasources::excel::ExcelTable;
+use datasources::excel::{parse_column_types, ExcelReadOptions, ExcelTable, SheetSelection};
 use datasources::json::table::json_streaming_table;
 use datasources::lake::delta::access::{load_table_direct, DeltaLakeAccessor};
 use datasources::lake::iceberg::table::IcebergTable;
 use datasources::lake::{storage_options_into_object_store, storage_options_into_store_access};
 use datasources::lance::LanceTable;
-use datasources::mongodb::{MongoDbAccessor, MongoDbTableAccessInfo};
+use datasources::mongodb::{MongoDbAccessor, MongoDbTableAccessInfo, SampleSize};
 use datasources::mysql::{MysqlAccessor, MysqlTableAccess};
 use datasources::object_store::azure::AzureStoreAccess;
 use datasources::object_store::gcs::GcsStoreAccess;
 use datasources::object_store::local::LocalStoreAccess;
 use datasources::object_store::s3::S3StoreAccess;
 use datasources::object_store::{ObjStoreAccess, ObjStoreAccessor};
+use datasources::oracle::{OracleAccessor, OracleTableAccess};
 use datasources::postgres::{PostgresAccess, PostgresTableProvider, PostgresTableProviderConfig};
 use datasources::snowflake::{SnowflakeAccessor, SnowflakeDbConnection, SnowflakeTableAccess};
 use datasources::sqlite::{SqliteAccess, SqliteTableProvider};
@@ -47,8 +50,10 @@ use protogen::metastore::types::options::{
     DatabaseOptionsClickhouse,
     DatabaseOptionsDebug,
     DatabaseOptionsDeltaLake,
+    DatabaseOptionsDuckDb,
     DatabaseOptionsMongoDb,
     DatabaseOptionsMysql,
+    DatabaseOptionsOracle,
     DatabaseOptionsPostgres,
     DatabaseOptionsSnowflake,
     DatabaseOptionsSqlServer,
@@ -64,6 +69,7 @@ use protogen::metastore::types::options::{
     TableOptionsMongoDb,
     TableOptionsMysql,
     TableOptionsObjectStore,
+    TableOptionsOracle,
     TableOptionsPostgres,
     TableOptionsS3,
     TableOptionsSnowflake,
@@ -148,8 +154,9 @@ impl<'a> ExternalDispatcher<'a> {
         name: &str,
     ) -> Result<Arc<dyn TableProvider>> {
         let tunnel = self.get_tunnel_opts(db.tunnel_id)?;
+        let options = resolve_secrets(&db.options).await?;
         // TODO: use the DatasourceRegistry to dispatch instead
-        match &db.options {
+        match &options {
             DatabaseOptions::Debug(DatabaseOptionsDebug {}) => {
                 // Use name of the table as table type here.
                 let provider = DebugTableType::from_str(name)?;
@@ -162,6 +169,8 @@ impl<'a> ExternalDispatcher<'a> {
                     access,
                     schema: schema.to_owned(),
                     table: name.to_owned(),
+                    partition_column: None,
+                    num_partitions: None,
                 };
                 let prov = PostgresTableProvider::try_new(prov_conf).await?;
                 Ok(Arc::new(prov))
@@ -181,23 +190,46 @@ impl<'a> ExternalDispatcher<'a> {
                 let provider = accessor.into_table_provider(table_access, true).await?;
                 Ok(Arc::new(provider))
             }
-            DatabaseOptions::Mysql(DatabaseOptionsMysql { connection_string }) => {
+            DatabaseOptions::Mysql(DatabaseOptionsMysql {
+                connection_string,
+                tls,
+            }) => {
                 let table_access = MysqlTableAccess {
                     schema: schema.to_string(),
                     name: name.to_string(),
                 };
 
-                let accessor = MysqlAccessor::connect(connection_string, tunnel).await?;
+                let accessor =
+                    MysqlAccessor::connect(connection_string, tunnel, tls.clone()).await?;
                 let provider = accessor.into_table_provider(table_access, true).await?;
                 Ok(Arc::new(provider))
             }
-            DatabaseOptions::MongoDb(DatabaseOptionsMongoDb { connection_string }) => {
+            DatabaseOptions::Oracle(DatabaseOptionsOracle { connection_string }) => {
+                let table_access = OracleTableAccess {
+                    schema: schema.to_string(),
+                    name: name.to_string(),
+                };
+
+                let accessor = OracleAccessor::connect(connection_string).await?;
+                let provider = accessor.into_table_provider(table_access, true).await?;
+                Ok(Arc::new(provider))
+            }
+            DatabaseOptions::MongoDb(DatabaseOptionsMongoDb {
+                connection_string,
+                tls,
+                sample_size,
+                full_scan,
+            }) => {
                 let table_info = MongoDbTableAccessInfo {
                     database: schema.to_string(), // A mongodb database is pretty much a schema.
                     collection: name.to_string(),
                     fields: None,
+                    sample_size: SampleSize::from_options(
+                        sample_size.map(|n| n as usize),
+                        *full_scan,
+                    ),
                 };
-                let accessor = MongoDbAccessor::connect(connection_string).await?;
+                let accessor = MongoDbAccessor::connect(connection_string, tls).await?;
                 let table_accessor = accessor.into_table_accessor(table_info);
                 let provider = table_accessor.into_table_provider().await?;
                 Ok(Arc::new(provider))
@@ -209,6 +241,7 @@ impl<'a> ExternalDispatcher<'a> {
                 database_name,
                 warehouse,
                 role_name,
+                credentials,
             }) => {
                 let schema_name = schema.to_string();
                 let table_name = name.to_string();
@@ -225,6 +258,7 @@ impl<'a> ExternalDispatcher<'a> {
                     database_name: database_name.clone(),
                     warehouse: warehouse.clone(),
                     role_name,
+                    credentials: credentials.clone(),
                 };
                 let access_info = SnowflakeTableAccess {
                     schema_name,
@@ -244,8 +278,12 @@ impl<'a> ExternalDispatcher<'a> {
                 let table = accessor.load_table(schema, name).await?;
                 Ok(Arc::new(table))
             }
-            DatabaseOptions::SqlServer(DatabaseOptionsSqlServer { connection_string }) => {
-                let access = SqlServerAccess::try_new_from_ado_string(connection_string)?;
+            DatabaseOptions::SqlServer(DatabaseOptionsSqlServer {
+                connection_string,
+                tls,
+            }) => {
+                let access = SqlServerAccess::try_new_from_ado_string(connection_string)?
+                    .with_tls(tls.clone())?;
                 let table = SqlServerTableProvider::try_new(SqlServerTableProviderConfig {
                     access,
                     schema: schema.to_string(),
@@ -254,9 +292,13 @@ impl<'a> ExternalDispatcher<'a> {
                 .await?;
                 Ok(Arc::new(table))
             }
-            DatabaseOptions::Clickhouse(DatabaseOptionsClickhouse { connection_string }) => {
+            DatabaseOptions::Clickhouse(DatabaseOptionsClickhouse {
+                connection_string,
+                tls,
+            }) => {
                 let access =
-                    ClickhouseAccess::new_from_connection_string(connection_string.clone());
+                    ClickhouseAccess::new_from_connection_string(connection_string.clone())
+                        .with_tls(tls.clone());
                 let table_ref =
                     OwnedClickhouseTableRef::new(Some(schema.to_owned()), name.to_owned());
                 let table = ClickhouseTableProvider::try_new(access, table_ref).await?;
@@ -289,6 +331,22 @@ impl<'a> ExternalDispatcher<'a> {
                 let table = SqliteTableProvider::try_new(state, name).await?;
                 Ok(Arc::new(table))
             }
+            DatabaseOptions::DuckDb(DatabaseOptionsDuckDb {
+                location,
+                storage_options,
+            }) => {
+                let state =
+                    DuckDbAccess::new(location.as_str().try_into()?, storage_options.to_owned())
+                        .await?
+                        .connect()
+                        .await?;
+                let access = DuckDbTableAccess {
+                    schema: schema.to_string(),
+                    name: name.to_string(),
+                };
+                let table = DuckDbTableProvider::try_new(state, access).await?;
+                Ok(Arc::new(table))
+            }
         }
     }
 
@@ -311,7 +369,8 @@ impl<'a> ExternalDispatcher<'a> {
         });
 
 
-        self.dispatch_table_options_v0(&table.options, tunnel, optional_schema)
+        let options = resolve_secrets(&table.options).await?;
+        self.dispatch_table_options_v0(&options, tunnel, optional_schema)
             .await
     }
 
@@ -328,8 +387,9 @@ impl<'a> ExternalDispatcher<'a> {
         // types and not others.
         let compression = compression
             .map(|c| c.parse::<FileCompressionType>())
-            .transpose()?
-            .unwrap_or(FileCompressionType::UNCOMPRESSED);
+            .transpose()?;
+        let bson_compression = compression;
+        let compression = compression.unwrap_or(FileCompressionType::UNCOMPRESSED);
 
         let accessor = ObjStoreAccessor::new(access.clone())?;
 
@@ -359,11 +419,17 @@ impl<'a> ExternalDispatcher<'a> {
                 DatasourceUrl::try_new(path)?,
                 None,
                 Some(128),
+                false,
+                bson_compression,
+            )
+            .await?),
+            "json" => Ok(json_streaming_table(
+                access.clone(),
+                DatasourceUrl::try_new(path)?,
+                None,
+                false,
             )
             .await?),
-            "json" => Ok(
-                json_streaming_table(access.clone(), DatasourceUrl::try_new(path)?, None).await?,
-            ),
             "ndjson" | "jsonl" => Ok(accessor
                 .clone()
                 .into_table_provider(
@@ -439,14 +505,37 @@ impl<'a> ExternalDispatcher<'a> {
                 storage_options,
                 has_header,
                 sheet_name,
+                sheet_index,
+                all_sheets,
+                cell_range,
+                header_row,
+                column_types,
                 ..
             }) => {
                 let source_url = DatasourceUrl::try_new(location)?;
                 let store_access = storage_options_into_store_access(&source_url, storage_options)?;
-                let sheet_name: Option<&str> = sheet_name.as_deref();
 
-                let table =
-                    ExcelTable::open(store_access, source_url, sheet_name, *has_header).await?;
+                let options = ExcelReadOptions {
+                    sheets: SheetSelection::try_new(
+                        sheet_name.clone(),
+                        sheet_index.map(|idx| idx as usize),
+                        *all_sheets,
+                    )?,
+                    cell_range: cell_range
+                        .as_deref()
+                        .map(|range| range.parse())
+                        .transpose()?,
+                    header_row: *header_row as usize,
+                    has_header: *has_header,
+                    column_types: column_types
+                        .as_deref()
+                        .map(parse_column_types)
+                        .transpose()?
+                        .unwrap_or_default(),
+                    ..Default::default()
+                };
+
+                let table = ExcelTable::open(store_access, source_url, options).await?;
                 let provider = ExcelTableProvider::try_new(table).await?;
 
                 Ok(Arc::new(provider))
@@ -456,12 +545,16 @@ impl<'a> ExternalDispatcher<'a> {
                 connection_string,
                 schema,
                 table,
+                partition_column,
+                num_partitions,
             }) => {
                 let access = PostgresAccess::new_from_conn_str(connection_string, tunnel);
                 let prov_conf = PostgresTableProviderConfig {
                     access,
                     schema: schema.to_owned(),
                     table: table.to_owned(),
+                    partition_column: partition_column.clone(),
+                    num_partitions: num_partitions.map(|n| n as usize),
                 };
                 let prov = PostgresTableProvider::try_new(prov_conf).await?;
                 Ok(Arc::new(prov))
@@ -487,13 +580,29 @@ impl<'a> ExternalDispatcher<'a> {
                 connection_string,
                 schema,
                 table,
+                tls,
             }) => {
                 let table_access = MysqlTableAccess {
                     schema: schema.clone(),
                     name: table.clone(),
                 };
 
-                let accessor = MysqlAccessor::connect(connection_string, tunnel).await?;
+                let accessor =
+                    MysqlAccessor::connect(connection_string, tunnel, tls.clone()).await?;
+                let provider = accessor.into_table_provider(table_access, true).await?;
+                Ok(Arc::new(provider))
+            }
+            TableOptionsV0::Oracle(TableOptionsOracle {
+                connection_string,
+                schema,
+                table,
+            }) => {
+                let table_access = OracleTableAccess {
+                    schema: schema.clone(),
+                    name: table.clone(),
+                };
+
+                let accessor = OracleAccessor::connect(connection_string).await?;
                 let provider = accessor.into_table_provider(table_access, true).await?;
                 Ok(Arc::new(provider))
             }
@@ -501,13 +610,22 @@ impl<'a> ExternalDispatcher<'a> {
                 connection_string,
                 database,
                 collection,
+                tls,
+                sample_size,
+                full_scan,
             }) => {
+                // Columns declared for the table take the place of the
+                // inferred schema.
                 let table_info = MongoDbTableAccessInfo {
                     database: database.to_string(),
                     collection: collection.to_string(),
-                    fields: None,
+                    fields: schema.map(|schema| schema.fields.to_vec()),
+                    sample_size: SampleSize::from_options(
+                        sample_size.map(|n| n as usize),
+                        *full_scan,
+                    ),
                 };
-                let accessor = MongoDbAccessor::connect(connection_string).await?;
+                let accessor = MongoDbAccessor::connect(connection_string, tls).await?;
                 let table_accessor = accessor.into_table_accessor(table_info);
                 let provider = table_accessor.into_table_provider().await?;
                 Ok(Arc::new(provider))
@@ -521,6 +639,7 @@ impl<'a> ExternalDispatcher<'a> {
                 role_name,
                 schema_name,
                 table_name,
+                credentials,
             }) => {
                 let role_name = if role_name.is_empty() {
                     None
@@ -535,6 +654,7 @@ impl<'a> ExternalDispatcher<'a> {
                     database_name: database_name.clone(),
                     warehouse: warehouse.clone(),
                     role_name,
+                    credentials: credentials.clone(),
                 };
                 let access_info = SnowflakeTableAccess {
                     schema_name: schema_name.clone(),
@@ -663,8 +783,10 @@ impl<'a> ExternalDispatcher<'a> {
                 connection_string,
                 schema,
                 table,
+                tls,
             }) => {
-                let access = SqlServerAccess::try_new_from_ado_string(connection_string)?;
+                let access = SqlServerAccess::try_new_from_ado_string(connection_string)?
+                    .with_tls(tls.clone())?;
                 let table = SqlServerTableProvider::try_new(SqlServerTableProviderConfig {
                     access,
                     schema: schema.to_string(),
@@ -677,9 +799,11 @@ impl<'a> ExternalDispatcher<'a> {
                 connection_string,
                 database,
                 table,
+                tls,
             }) => {
                 let access =
-                    ClickhouseAccess::new_from_connection_string(connection_string.clone());
+                    ClickhouseAccess::new_from_connection_string(connection_string.clone())
+                        .with_tls(tls.clone());
                 let table_ref = OwnedClickhouseTableRef::new(database.clone(), table.to_owned());
                 let table = ClickhouseTableProvider::try_new(access, table_ref).await?;
                 Ok(Arc::new(table))
@@ -695,15 +819,22 @@ impl<'a> ExternalDispatcher<'a> {
                 location,
                 storage_options,
                 schema_sample_size,
+                compression,
                 ..
             }) => {
                 let source_url = DatasourceUrl::try_new(location)?;
                 let store_access = storage_options_into_store_access(&source_url, storage_options)?;
+                let compression = compression
+                    .as_ref()
+                    .map(|c| c.parse::<FileCompressionType>())
+                    .transpose()?;
                 Ok(bson_streaming_table(
                     store_access,
                     source_url,
                     schema,
                     schema_sample_size.to_owned(),
+                    false,
+                    compression,
                 )
                 .await?)
             }
@@ -746,6 +877,27 @@ impl<'a> ExternalDispatcher<'a> {
 
                 Ok(Arc::new(SqliteTableProvider::try_new(state, table).await?))
             }
+            TableOptionsV0::DuckDb(TableOptionsObjectStore {
+                location,
+                storage_options,
+                name,
+                ..
+            }) => {
+                let mut storage_options = storage_options.to_owned();
+
+                storage_options
+                    .inner
+                    .insert("__tmp_prefix".to_string(), Uuid::new_v4().to_string());
+
+                let table = name.clone().ok_or(DispatchError::MissingTable)?;
+                let state = DuckDbAccess::new(location.as_str().try_into()?, Some(storage_options))
+                    .await?
+                    .connect()
+                    .await?;
+                let access = DuckDbTableAccess::from_qualified_name(&table);
+
+                Ok(Arc::new(DuckDbTableProvider::try_new(state, access).await?))
+            }
         }
     }
 }
@@ -64,6 +64,8 @@ pub enum DispatchError {
     #[error(transparent)]
     MysqlDatasource(#[from] datasources::mysql::errors::MysqlError),
     #[error(transparent)]
+    OracleDatasource(#[from] datasources::oracle::errors::OracleError),
+    #[error(transparent)]
     ObjectStoreDatasource(#[from] datasources::object_store::errors::ObjectStoreSourceError),
     #[error(transparent)]
     MongoDatasource(#[from] datasources::mongodb::errors::MongoDbError),
@@ -94,6 +96,8 @@ pub enum DispatchError {
     #[error(transparent)]
     SqliteDatasource(#[from] datasources::sqlite::errors::SqliteError),
     #[error(transparent)]
+    DuckDbDatasource(#[from] datasources::duckdb::errors::DuckDbError),
+    #[error(transparent)]
     ExcelDatasource(#[from] datasources::excel::errors::ExcelError),
     #[error(transparent)]
     LakeStorageOptions(#[from] datasources::lake::LakeStorageOptionsError),
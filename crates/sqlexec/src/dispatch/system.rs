@@ -1,24 +1,33 @@
 use std::sync::Arc;
 
 use catalog::session_catalog::SessionCatalog;
-use datafusion::arrow::array::{BooleanBuilder, ListBuilder, StringBuilder, UInt32Builder};
+use datafusion::arrow::array::{
+    BooleanBuilder,
+    ListBuilder,
+    StringBuilder,
+    UInt32Builder,
+    UInt64Builder,
+};
 use datafusion::arrow::record_batch::RecordBatch;
 use datafusion::datasource::{MemTable, TableProvider};
 use datafusion::logical_expr::TypeSignature;
+use datasources::common::pool::connection_pool_stats;
 use datasources::common::ssh::key::SshKey;
 use datasources::common::ssh::SshConnectionParameters;
 use datasources::native::access::NativeTableStorage;
 use protogen::metastore::types::catalog::{CatalogEntry, EntryType, SourceAccessMode, TableEntry};
-use protogen::metastore::types::options::TunnelOptions;
+use protogen::metastore::types::options::{TableOptionsV0, TunnelOptions};
 use sqlbuiltins::builtins::{
     BuiltinTable,
     DATABASE_DEFAULT,
     GLARE_CACHED_EXTERNAL_DATABASE_TABLES,
     GLARE_COLUMNS,
+    GLARE_CONNECTION_POOLS,
     GLARE_CREDENTIALS,
     GLARE_DATABASES,
     GLARE_DEPLOYMENT_METADATA,
     GLARE_FUNCTIONS,
+    GLARE_MATERIALIZED_VIEWS,
     GLARE_SCHEMAS,
     GLARE_SSH_KEYS,
     GLARE_TABLES,
@@ -70,6 +79,8 @@ impl<'a> SystemTableDispatcher<'a> {
             Arc::new(self.build_glare_columns())
         } else if GLARE_VIEWS.matches(schema, name) {
             Arc::new(self.build_glare_views())
+        } else if GLARE_MATERIALIZED_VIEWS.matches(schema, name) {
+            Arc::new(self.build_glare_materialized_views())
         } else if GLARE_SCHEMAS.matches(schema, name) {
             Arc::new(self.build_glare_schemas())
         } else if GLARE_FUNCTIONS.matches(schema, name) {
@@ -81,6 +92,8 @@ impl<'a> SystemTableDispatcher<'a> {
         } else if GLARE_CACHED_EXTERNAL_DATABASE_TABLES.matches(schema, name) {
             self.load_persisted_table(&GLARE_CACHED_EXTERNAL_DATABASE_TABLES)
                 .await?
+        } else if GLARE_CONNECTION_POOLS.matches(schema, name) {
+            Arc::new(self.build_glare_connection_pools())
         } else {
             return Err(DispatchError::MissingBuiltinTable {
                 schema: schema.to_string(),
@@ -471,6 +484,66 @@ impl<'a> SystemTableDispatcher<'a> {
         MemTable::try_new(arrow_schema, vec![vec![batch]]).unwrap()
     }
 
+    fn build_glare_materialized_views(&self) -> MemTable {
+        let arrow_schema = Arc::new(GLARE_MATERIALIZED_VIEWS.arrow_schema());
+
+        let mut oid = UInt32Builder::new();
+        let mut database_oid = UInt32Builder::new();
+        let mut schema_oid = UInt32Builder::new();
+        let mut schema_name = StringBuilder::new();
+        let mut view_name = StringBuilder::new();
+        let mut sql = StringBuilder::new();
+
+        for table in self
+            .catalog
+            .iter_entries()
+            .filter(|ent| ent.entry_type() == EntryType::Table)
+        {
+            let view_sql = match table.entry {
+                CatalogEntry::Table(TableEntry {
+                    options: TableOptionsV0::Internal(opts),
+                    ..
+                }) => match &opts.view_sql {
+                    Some(sql) => sql,
+                    None => continue,
+                },
+                _ => continue,
+            };
+
+            oid.append_value(table.oid);
+            database_oid.append_value(
+                table
+                    .parent_entry
+                    .map(|schema| schema.get_meta().parent)
+                    .unwrap_or_default(),
+            );
+            schema_oid.append_value(table.entry.get_meta().parent);
+            schema_name.append_value(
+                table
+                    .parent_entry
+                    .map(|schema| schema.get_meta().name.as_str())
+                    .unwrap_or("<invalid>"),
+            );
+            view_name.append_value(&table.entry.get_meta().name);
+            sql.append_value(view_sql);
+        }
+
+        let batch = RecordBatch::try_new(
+            arrow_schema.clone(),
+            vec![
+                Arc::new(oid.finish()),
+                Arc::new(database_oid.finish()),
+                Arc::new(schema_oid.finish()),
+                Arc::new(schema_name.finish()),
+                Arc::new(view_name.finish()),
+                Arc::new(sql.finish()),
+            ],
+        )
+        .unwrap();
+
+        MemTable::try_new(arrow_schema, vec![vec![batch]]).unwrap()
+    }
+
     fn build_glare_functions(&self) -> MemTable {
         let arrow_schema = Arc::new(GLARE_FUNCTIONS.arrow_schema());
 
@@ -599,6 +672,47 @@ impl<'a> SystemTableDispatcher<'a> {
 
         Ok(MemTable::try_new(arrow_schema, vec![vec![batch]]).unwrap())
     }
+
+    fn build_glare_connection_pools(&self) -> MemTable {
+        let arrow_schema = Arc::new(GLARE_CONNECTION_POOLS.arrow_schema());
+
+        let mut datasource = StringBuilder::new();
+        let mut connection = StringBuilder::new();
+        let mut max_connections = UInt64Builder::new();
+        let mut in_use = UInt64Builder::new();
+        let mut idle = UInt64Builder::new();
+        let mut opened = UInt64Builder::new();
+        let mut reused = UInt64Builder::new();
+        let mut closed = UInt64Builder::new();
+
+        for stats in connection_pool_stats() {
+            datasource.append_value(stats.datasource);
+            connection.append_value(&stats.description);
+            max_connections.append_value(stats.max_connections as u64);
+            in_use.append_value(stats.in_use as u64);
+            idle.append_value(stats.idle as u64);
+            opened.append_value(stats.opened);
+            reused.append_value(stats.reused);
+            closed.append_value(stats.closed);
+        }
+
+        let batch = RecordBatch::try_new(
+            arrow_schema.clone(),
+            vec![
+                Arc::new(datasource.finish()),
+                Arc::new(connection.finish()),
+                Arc::new(max_connections.finish()),
+                Arc::new(in_use.finish()),
+                Arc::new(idle.finish()),
+                Arc::new(opened.finish()),
+                Arc::new(reused.finish()),
+                Arc::new(closed.finish()),
+            ],
+        )
+        .unwrap();
+
+        MemTable::try_new(arrow_schema, vec![vec![batch]]).unwrap()
+    }
 }
 fn sig_to_string_repr(sig: &TypeSignature) -> Vec<String> {
     match sig {
@@ -23,6 +23,9 @@ use object_store::path::Path as ObjectPath;
 use object_store::prefix::PrefixStore;
 use object_store::{Error as ObjectStoreError, ObjectStore};
 use object_store_util::conf::StorageConfig;
+use object_store_util::encryption::local::LocalKeyProvider;
+use object_store_util::encryption::store::EncryptedObjectStore;
+use object_store_util::encryption::KeyProvider;
 use object_store_util::shared::SharedObjectStore;
 use protogen::gen::metastore::service::metastore_service_client::MetastoreServiceClient;
 use protogen::rpcsrv::types::common;
@@ -71,6 +74,10 @@ impl From<common::SessionStorageConfig> for SessionStorageConfig {
 pub struct EngineStorageConfig {
     location: Url,
     conf: StorageConfig,
+    /// Provider for the key used to encrypt native tables and catalogs.
+    ///
+    /// Set through the 'encryption_key_file' storage option.
+    key_provider: Option<Arc<dyn KeyProvider>>,
 }
 
 impl EngineStorageConfig {
@@ -85,18 +92,27 @@ impl EngineStorageConfig {
                 ))
             })?,
             conf: StorageConfig::Local { path },
+            key_provider: None,
         })
     }
     pub fn try_from_options(location: &str, opts: HashMap<String, String>) -> Result<Self> {
+        let key_provider = match opts.get("encryption_key_file") {
+            Some(path) => {
+                Some(Arc::new(LocalKeyProvider::from_file(path)?) as Arc<dyn KeyProvider>)
+            }
+            None => None,
+        };
+
         if location.starts_with("memory://") {
             return Ok(EngineStorageConfig {
                 location: Url::parse(location).map_err(DatasourceCommonError::from)?,
                 conf: StorageConfig::Memory,
+                key_provider,
             });
         }
 
         let datasource_url = DatasourceUrl::try_new(location)?;
-        Ok(match datasource_url {
+        let conf = match datasource_url {
             DatasourceUrl::File(path) => EngineStorageConfig::try_from_path_buf(&path)?,
             DatasourceUrl::Url(ref url) => {
                 let url_type = datasource_url.datasource_url_type();
@@ -124,6 +140,7 @@ impl EngineStorageConfig {
                                 service_account_key,
                                 bucket,
                             },
+                            key_provider: None,
                         }
                     }
                     DatasourceUrlType::S3 | DatasourceUrlType::Http => {
@@ -167,6 +184,7 @@ impl EngineStorageConfig {
                                 endpoint,
                                 bucket,
                             },
+                            key_provider: None,
                         }
                     }
                     DatasourceUrlType::Azure => {
@@ -197,11 +215,17 @@ impl EngineStorageConfig {
                                 access_key,
                                 container_name,
                             },
+                            key_provider: None,
                         }
                     }
                     DatasourceUrlType::File => unreachable!(), // Handled as Datasource::File(_)
                 }
             }
+        };
+
+        Ok(EngineStorageConfig {
+            key_provider,
+            ..conf
         })
     }
 
@@ -231,6 +255,7 @@ impl EngineStorageConfig {
                         service_account_key,
                         bucket: Some(bucket),
                     },
+                    key_provider: self.key_provider.clone(),
                 }
             }
             // Expected gcs config opts for the session.
@@ -242,6 +267,7 @@ impl EngineStorageConfig {
             (_, Some(_)) => EngineStorageConfig {
                 location: Url::parse("memory://").map_err(DatasourceCommonError::from)?,
                 conf: StorageConfig::Memory,
+                key_provider: self.key_provider.clone(),
             },
             _ => self.clone(),
         })
@@ -300,7 +326,10 @@ impl EngineStorageConfig {
         session_conf: &SessionStorageConfig,
     ) -> Result<NativeTableStorage> {
         let conf = self.with_session_config(session_conf)?;
-        let store = conf.new_object_store()?;
+        let mut store = conf.new_object_store()?;
+        if let Some(key_provider) = conf.key_provider {
+            store = Arc::new(EncryptedObjectStore::new(store, key_provider));
+        }
         let native = NativeTableStorage::new(db_id, conf.location, store);
         Ok(native)
     }
@@ -400,9 +429,11 @@ impl Engine {
         // Wrap up the store with a shared one, so that we get to use the non-atomic
         // copy-if-not-exists that is defined there when initializing the lease
         let store = SharedObjectStore::new(store);
-        let client = start_inprocess(Arc::new(store)).await.map_err(|e| {
-            ExecError::String(format!("Failed to start an in-process metastore: {e}"))
-        })?;
+        let client = start_inprocess(Arc::new(store), conf.key_provider.clone())
+            .await
+            .map_err(|e| {
+                ExecError::String(format!("Failed to start an in-process metastore: {e}"))
+            })?;
 
         Engine::new(client, conf, Arc::new(Tracker::Nop), None).await
     }
@@ -635,4 +666,33 @@ mod tests {
         assert_eq!(merged_conf.conf, StorageConfig::Memory,);
         Ok(())
     }
+
+    #[test]
+    fn encryption_key_file_option() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let key_file = dir.path().join("key");
+        std::fs::write(&key_file, format!("{}\n", "ab".repeat(32)))?;
+
+        let opts = HashMap::from_iter([(
+            "encryption_key_file".to_string(),
+            key_file.to_string_lossy().to_string(),
+        )]);
+        let conf = EngineStorageConfig::try_from_options("memory://", opts)?;
+        assert!(conf.key_provider.is_some());
+
+        // Session configs keep the key.
+        let merged_conf = conf.with_session_config(&SessionStorageConfig {
+            gcs_bucket: Some("bucket".to_string()),
+        })?;
+        assert!(merged_conf.key_provider.is_some());
+
+        std::fs::write(&key_file, "not a key")?;
+        let opts = HashMap::from_iter([(
+            "encryption_key_file".to_string(),
+            key_file.to_string_lossy().to_string(),
+        )]);
+        EngineStorageConfig::try_from_options("memory://", opts).unwrap_err();
+
+        Ok(())
+    }
 }
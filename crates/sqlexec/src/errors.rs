@@ -81,6 +81,9 @@ pub enum ExecError {
     #[error(transparent)]
     ObjectStore(#[from] object_store::Error),
 
+    #[error(transparent)]
+    Encryption(#[from] object_store_util::encryption::EncryptionError),
+
     #[error(transparent)]
     VarError(#[from] std::env::VarError),
 
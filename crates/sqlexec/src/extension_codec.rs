@@ -20,6 +20,7 @@ use datafusion_ext::metrics::{
 use datafusion_ext::runtime::runtime_group::RuntimeGroupExec;
 use datafusion_proto::logical_plan::from_proto::parse_expr;
 use datafusion_proto::physical_plan::PhysicalExtensionCodec;
+use datasources::common::dml::DmlDatasource;
 use prost::Message;
 use protogen::metastore::types::catalog::RuntimePreference;
 use uuid::Uuid;
@@ -46,11 +47,13 @@ use crate::planner::physical_plan::drop_tables::DropTablesExec;
 use crate::planner::physical_plan::drop_tunnel::DropTunnelExec;
 use crate::planner::physical_plan::drop_views::DropViewsExec;
 use crate::planner::physical_plan::insert::InsertExec;
+use crate::planner::physical_plan::optimize_table::OptimizeTableExec;
 use crate::planner::physical_plan::remote_scan::{ProviderReference, RemoteScanExec};
 use crate::planner::physical_plan::set_var::SetVarExec;
 use crate::planner::physical_plan::show_var::ShowVarExec;
 use crate::planner::physical_plan::update::UpdateExec;
 use crate::planner::physical_plan::values::ExtValuesExec;
+use crate::planner::physical_plan::DmlTarget;
 use crate::remote::provider_cache::ProviderCache;
 
 pub struct GlareDBExtensionCodec<'a> {
@@ -69,6 +72,49 @@ impl<'a> GlareDBExtensionCodec<'a> {
             table_providers: None,
         }
     }
+
+    /// Get the table to update or delete from. External tables reference a
+    /// provider in the cache on the remote side.
+    fn dml_target(
+        &self,
+        table: Option<protogen::gen::metastore::catalog::TableEntry>,
+        provider_id: Option<Vec<u8>>,
+        dml_datasource: Option<String>,
+    ) -> Result<DmlTarget> {
+        if let Some(id) = provider_id {
+            let datasource: DmlDatasource = dml_datasource
+                .ok_or_else(|| DataFusionError::Internal("missing dml datasource".to_string()))?
+                .parse()?;
+            let provider_id = Uuid::from_slice(&id)
+                .map_err(|e| DataFusionError::Plan(format!("failed to decode provider id: {e}")))?;
+            let prov = self
+                .table_providers
+                .expect("remote context should have provider cache")
+                .get(&provider_id)
+                .ok_or_else(|| {
+                    DataFusionError::Internal(format!("Missing proivder for id: {provider_id}"))
+                })?;
+            return Ok(DmlTarget::External {
+                datasource,
+                provider: ProviderReference::Provider(prov),
+            });
+        }
+
+        let table = table
+            .ok_or_else(|| DataFusionError::Internal("missing table".to_string()))?
+            .try_into()?;
+        Ok(DmlTarget::Native(table))
+    }
+}
+
+/// Get the id of the cached provider to send to the remote side.
+fn remote_provider_id(provider: &ProviderReference) -> Result<Vec<u8>> {
+    match provider {
+        ProviderReference::RemoteReference(id) => Ok(id.into_bytes().to_vec()),
+        ProviderReference::Provider(_) => Err(DataFusionError::Internal(
+            "Unexpectedly got table provider on client side".to_string(),
+        )),
+    }
 }
 
 impl<'a> fmt::Debug for GlareDBExtensionCodec<'a> {
@@ -235,6 +281,8 @@ impl<'a> PhysicalExtensionCodec for GlareDBExtensionCodec<'a> {
                     if_not_exists: ext.if_not_exists,
                     or_replace: ext.or_replace,
                     arrow_schema: Arc::new(schema),
+                    partition_by: ext.partition_by,
+                    view_sql: ext.view_sql,
                     source: inputs.first().cloned(),
                 })
             }
@@ -373,10 +421,7 @@ impl<'a> PhysicalExtensionCodec for GlareDBExtensionCodec<'a> {
                     .map(|expr| parse_expr(&expr, registry))
                     .transpose()?;
                 Arc::new(UpdateExec {
-                    table: ext
-                        .table
-                        .ok_or_else(|| DataFusionError::Internal("missing table".to_string()))?
-                        .try_into()?,
+                    target: self.dml_target(ext.table, ext.provider_id, ext.dml_datasource)?,
                     updates,
                     where_expr,
                 })
@@ -398,6 +443,7 @@ impl<'a> PhysicalExtensionCodec for GlareDBExtensionCodec<'a> {
 
                 Arc::new(InsertExec {
                     provider: ProviderReference::Provider(prov),
+                    overwrite: ext.overwrite,
                     source: Arc::new(WriteOnlyDataSourceMetricsExecAdapter::new(
                         inputs
                             .first()
@@ -414,11 +460,17 @@ impl<'a> PhysicalExtensionCodec for GlareDBExtensionCodec<'a> {
                     .map(|expr| parse_expr(&expr, registry))
                     .transpose()?;
                 Arc::new(DeleteExec {
+                    target: self.dml_target(ext.table, ext.provider_id, ext.dml_datasource)?,
+                    where_expr,
+                })
+            }
+            proto::ExecutionPlanExtensionType::OptimizeTableExec(ext) => {
+                Arc::new(OptimizeTableExec {
                     table: ext
                         .table
                         .ok_or_else(|| DataFusionError::Internal("missing table".to_string()))?
                         .try_into()?,
-                    where_expr,
+                    zorder_by: ext.zorder_by,
                 })
             }
             proto::ExecutionPlanExtensionType::CopyToExec(ext) => Arc::new(CopyToExec {
@@ -553,6 +605,8 @@ impl<'a> PhysicalExtensionCodec for GlareDBExtensionCodec<'a> {
                 if_not_exists: exec.if_not_exists,
                 or_replace: exec.or_replace,
                 arrow_schema: Some(exec.arrow_schema.clone().try_into()?),
+                partition_by: exec.partition_by.clone(),
+                view_sql: exec.view_sql.clone(),
             })
         } else if let Some(exec) = node.as_any().downcast_ref::<CreateTempTableExec>() {
             proto::ExecutionPlanExtensionType::CreateTempTableExec(proto::CreateTempTableExec {
@@ -704,14 +758,27 @@ impl<'a> PhysicalExtensionCodec for GlareDBExtensionCodec<'a> {
                 });
             }
 
+            let (table, provider_id, dml_datasource) = match &exec.target {
+                DmlTarget::Native(table) => (Some(table.clone().into()), None, None),
+                DmlTarget::External {
+                    datasource,
+                    provider,
+                } => (
+                    None,
+                    Some(remote_provider_id(provider)?),
+                    Some(datasource.to_string()),
+                ),
+            };
             proto::ExecutionPlanExtensionType::UpdateExec(proto::UpdateExec {
-                table: Some(exec.table.clone().into()),
+                table,
                 updates,
                 where_expr: exec
                     .where_expr
                     .as_ref()
                     .map(|expr| expr.try_into())
                     .transpose()?,
+                provider_id,
+                dml_datasource,
             })
         } else if let Some(exec) = node.as_any().downcast_ref::<InsertExec>() {
             let id = match exec.provider {
@@ -725,15 +792,34 @@ impl<'a> PhysicalExtensionCodec for GlareDBExtensionCodec<'a> {
 
             proto::ExecutionPlanExtensionType::InsertExec(proto::InsertExec {
                 provider_id: id.into_bytes().to_vec(),
+                overwrite: exec.overwrite,
             })
         } else if let Some(exec) = node.as_any().downcast_ref::<DeleteExec>() {
+            let (table, provider_id, dml_datasource) = match &exec.target {
+                DmlTarget::Native(table) => (Some(table.clone().into()), None, None),
+                DmlTarget::External {
+                    datasource,
+                    provider,
+                } => (
+                    None,
+                    Some(remote_provider_id(provider)?),
+                    Some(datasource.to_string()),
+                ),
+            };
             proto::ExecutionPlanExtensionType::DeleteExec(proto::DeleteExec {
-                table: Some(exec.table.clone().into()),
+                table,
                 where_expr: exec
                     .where_expr
                     .as_ref()
                     .map(|expr| expr.try_into())
                     .transpose()?,
+                provider_id,
+                dml_datasource,
+            })
+        } else if let Some(exec) = node.as_any().downcast_ref::<OptimizeTableExec>() {
+            proto::ExecutionPlanExtensionType::OptimizeTableExec(proto::OptimizeTableExec {
+                table: Some(exec.table.clone().into()),
+                zorder_by: exec.zorder_by.clone(),
             })
         } else if let Some(exec) = node.as_any().downcast_ref::<CopyToExec>() {
             proto::ExecutionPlanExtensionType::CopyToExec(proto::CopyToExec {
@@ -5,6 +5,11 @@ use datafusion::optimizer::OptimizerRule;
 use crate::planner::extension::{ExtensionNode, ExtensionType};
 use crate::planner::logical_plan::{CopyTo, CreateTable, CreateTempTable, Insert};
 
+mod mongodb_pushdown;
+mod sql_pushdown;
+pub(crate) use mongodb_pushdown::MongoDbPipelinePushdownRule;
+pub(crate) use sql_pushdown::SqlQueryPushdownRule;
+
 fn require_downcast_lp<P: 'static>(plan: &dyn UserDefinedLogicalNode) -> &P {
     match plan.as_any().downcast_ref::<P>() {
         Some(p) => p,
@@ -177,6 +182,8 @@ mod test {
                 if_not_exists: false,
                 or_replace: false,
                 schema: schema.clone(),
+                partition_by: Vec::new(),
+                view_sql: None,
                 source: Some(plan),
             }
             .into_extension(),
@@ -215,7 +222,7 @@ mod test {
                 dest: CopyToDestinationOptions::Local(CopyToDestinationOptionsLocal {
                     location: "/tmp".to_string(),
                 }),
-                format: CopyToFormatOptions::Bson(CopyToFormatOptionsBson {}),
+                format: CopyToFormatOptions::Bson(CopyToFormatOptionsBson { compression: None }),
             }
             .into_extension(),
         );
@@ -289,6 +296,7 @@ mod test {
             Insert {
                 source: plan,
                 provider: ProviderReference::RemoteReference(Uuid::nil()),
+                overwrite: false,
                 runtime_preference: RuntimePreference::Unspecified,
             }
             .into_extension(),
@@ -0,0 +1,299 @@
+use std::sync::Arc;
+
+use datafusion::arrow::datatypes::{Field, Schema};
+use datafusion::common::{Column, DFSchema, OwnedTableReference};
+use datafusion::datasource::{DefaultTableSource, TableProvider};
+use datafusion::error::Result;
+use datafusion::logical_expr::expr::Sort as SortExpr;
+use datafusion::logical_expr::{
+    Aggregate,
+    Expr,
+    Filter,
+    Limit,
+    LogicalPlan,
+    Projection,
+    Sort,
+    SubqueryAlias,
+    TableScan,
+};
+use datafusion::optimizer::optimizer::ApplyOrder;
+use datafusion::optimizer::{OptimizerConfig, OptimizerRule};
+use datafusion_ext::runtime::table_provider::RuntimeAwareTableProvider;
+use datasources::mongodb::pipeline::{
+    is_supported_type,
+    is_valid_field_name,
+    MongoDbPipeline,
+    MongoDbPipelineTableProvider,
+    PipelineExprWriter,
+    SortField,
+};
+use datasources::mongodb::MongoDbTableProvider;
+use protogen::metastore::types::catalog::RuntimePreference;
+
+/// Rewrites aggregates, sorts and limits over MongoDB collections into an
+/// aggregation pipeline that's executed by MongoDB.
+///
+/// Only subtrees consisting of a table scan followed by filters, column
+/// projections, a single aggregate with group by columns, sorts and limits are
+/// rewritten. The rewritten subtree is replaced with a scan of the pipeline.
+pub(crate) struct MongoDbPipelinePushdownRule;
+
+impl OptimizerRule for MongoDbPipelinePushdownRule {
+    fn try_optimize(
+        &self,
+        plan: &LogicalPlan,
+        _config: &dyn OptimizerConfig,
+    ) -> Result<Option<LogicalPlan>> {
+        // Plain scans (with filters and limits) are already handled by the
+        // provider.
+        if !matches!(
+            plan,
+            LogicalPlan::Aggregate(_) | LogicalPlan::Sort(_) | LogicalPlan::Limit(_)
+        ) {
+            return Ok(None);
+        }
+
+        let state = match PipelineState::try_from_plan(plan) {
+            Some(state) if state.aggregated || state.sorted => state,
+            _ => return Ok(None),
+        };
+
+        state.into_plan(plan)
+    }
+
+    fn name(&self) -> &str {
+        "MongoDbPipelinePushdownRule"
+    }
+
+    fn apply_order(&self) -> Option<ApplyOrder> {
+        Some(ApplyOrder::TopDown)
+    }
+}
+
+/// State for building up a pipeline while walking a logical plan bottom up.
+struct PipelineState {
+    table_name: OwnedTableReference,
+    /// Provider for the collection being queried. Guaranteed to be a MongoDB
+    /// table provider.
+    provider: Arc<dyn TableProvider>,
+    pipeline: MongoDbPipeline,
+    /// Document field holding each column in the output of the plan we've
+    /// walked so far.
+    fields: Vec<String>,
+    aggregated: bool,
+    sorted: bool,
+}
+
+impl PipelineState {
+    /// Try to build up the pipeline for the plan.
+    ///
+    /// Returns `None` if the plan can't be pushed down.
+    fn try_from_plan(plan: &LogicalPlan) -> Option<Self> {
+        match plan {
+            LogicalPlan::TableScan(scan) => Self::try_from_scan(scan),
+            LogicalPlan::SubqueryAlias(SubqueryAlias { input, .. }) => Self::try_from_plan(input),
+            LogicalPlan::Filter(Filter {
+                predicate, input, ..
+            }) => {
+                let mut state = Self::try_from_plan(input)?;
+                let filter = PipelineExprWriter::new(input.schema(), &state.fields)
+                    .write_filter(predicate)?;
+                state.pipeline.push_match(filter);
+                Some(state)
+            }
+            LogicalPlan::Projection(Projection { expr, input, .. }) => {
+                let mut state = Self::try_from_plan(input)?;
+                let writer = PipelineExprWriter::new(input.schema(), &state.fields);
+                let fields = expr
+                    .iter()
+                    .map(|expr| writer.field(expr).map(|f| f.to_string()))
+                    .collect::<Option<Vec<_>>>()?;
+                state.fields = fields;
+                Some(state)
+            }
+            LogicalPlan::Aggregate(Aggregate {
+                input,
+                group_expr,
+                aggr_expr,
+                ..
+            }) => {
+                let mut state = Self::try_from_plan(input)?;
+                // Without any groups an aggregate still produces a row for
+                // empty input, which `$group` doesn't.
+                if state.aggregated || group_expr.is_empty() {
+                    return None;
+                }
+
+                let writer = PipelineExprWriter::new(input.schema(), &state.fields);
+                let group_fields = group_expr
+                    .iter()
+                    .map(|expr| writer.field(expr).map(|f| f.to_string()))
+                    .collect::<Option<Vec<_>>>()?;
+                let accumulators = aggr_expr
+                    .iter()
+                    .map(|expr| writer.write_accumulator(expr))
+                    .collect::<Option<Vec<_>>>()?;
+
+                state.fields = state.pipeline.push_group(&group_fields, accumulators);
+                state.aggregated = true;
+                Some(state)
+            }
+            LogicalPlan::Sort(Sort { expr, input, fetch }) => {
+                let mut state = Self::try_from_plan(input)?;
+                let writer = PipelineExprWriter::new(input.schema(), &state.fields);
+                let sort_fields = expr
+                    .iter()
+                    .map(|expr| match expr {
+                        Expr::Sort(SortExpr {
+                            expr,
+                            asc,
+                            nulls_first,
+                        }) => Some(SortField {
+                            field: writer.field(expr)?.to_string(),
+                            asc: *asc,
+                            nulls_first: *nulls_first,
+                        }),
+                        _ => None,
+                    })
+                    .collect::<Option<Vec<_>>>()?;
+
+                state.pipeline.push_sort(&sort_fields);
+                if let Some(fetch) = fetch {
+                    state.pipeline.push_limit(*fetch);
+                }
+                state.sorted = true;
+                Some(state)
+            }
+            // Stages are applied in order, so skips and limits can be
+            // followed by any other stage.
+            LogicalPlan::Limit(Limit { skip, fetch, input }) => {
+                let mut state = Self::try_from_plan(input)?;
+                if *skip > 0 {
+                    state.pipeline.push_skip(*skip);
+                }
+                if let Some(fetch) = fetch {
+                    state.pipeline.push_limit(*fetch);
+                }
+                Some(state)
+            }
+            _ => None,
+        }
+    }
+
+    fn try_from_scan(scan: &TableScan) -> Option<Self> {
+        if scan.fetch.is_some() {
+            return None;
+        }
+
+        let source = scan.source.as_any().downcast_ref::<DefaultTableSource>()?;
+        let provider = source.table_provider.clone();
+        let provider = match provider
+            .as_any()
+            .downcast_ref::<RuntimeAwareTableProvider>()
+        {
+            // Remote providers are only stubs, we can't query through them.
+            Some(p) if p.preference == RuntimePreference::Remote => return None,
+            Some(p) => p.provider.clone(),
+            None => provider,
+        };
+        provider.as_any().downcast_ref::<MongoDbTableProvider>()?;
+
+        let table_schema = provider.schema();
+        let table_fields: Vec<String> = table_schema
+            .fields()
+            .iter()
+            .map(|f| f.name().clone())
+            .collect();
+
+        // Filters that were pushed down to the scan are inexact and are
+        // applied again by a filter above the scan, so only the ones we're
+        // able to translate are needed here.
+        let df_schema =
+            DFSchema::try_from_qualified_schema(scan.table_name.clone(), &table_schema).ok()?;
+        let writer = PipelineExprWriter::new(&df_schema, &table_fields);
+        let mut pipeline = MongoDbPipeline::new();
+        for filter in scan
+            .filters
+            .iter()
+            .filter_map(|expr| writer.write_filter(expr))
+        {
+            pipeline.push_match(filter);
+        }
+
+        let fields = scan
+            .projected_schema
+            .fields()
+            .iter()
+            .map(|f| {
+                let name = f.name();
+                is_valid_field_name(name).then(|| name.clone())
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(PipelineState {
+            table_name: scan.table_name.clone(),
+            provider,
+            pipeline,
+            fields,
+            aggregated: false,
+            sorted: false,
+        })
+    }
+
+    /// Create a plan scanning the pushed down pipeline, producing the same
+    /// output as `plan`.
+    fn into_plan(self, plan: &LogicalPlan) -> Result<Option<LogicalPlan>> {
+        let output = plan.schema();
+        if output.fields().len() != self.fields.len() || self.fields.is_empty() {
+            return Ok(None);
+        }
+
+        let mut fields = Vec::with_capacity(self.fields.len());
+        for (idx, field) in output.fields().iter().enumerate() {
+            if !is_supported_type(field.data_type()) {
+                return Ok(None);
+            }
+            fields.push(Field::new(
+                format!("c{idx}"),
+                field.data_type().clone(),
+                true,
+            ));
+        }
+        let schema = Arc::new(Schema::new(fields));
+
+        // Checked when creating the state.
+        let table = self
+            .provider
+            .as_any()
+            .downcast_ref::<MongoDbTableProvider>()
+            .unwrap();
+        let stages = self.pipeline.finish(&self.fields);
+        let provider = MongoDbPipelineTableProvider::new(table, stages, schema);
+        let provider = RuntimeAwareTableProvider::new(RuntimePreference::Local, Arc::new(provider));
+        let scan = TableScan::try_new(
+            self.table_name.clone(),
+            Arc::new(DefaultTableSource::new(Arc::new(provider))),
+            None,
+            Vec::new(),
+            None,
+        )?;
+
+        // Alias the pipeline's fields back to the names the rest of the plan
+        // expects.
+        let exprs = output
+            .fields()
+            .iter()
+            .enumerate()
+            .map(|(idx, field)| {
+                Expr::Column(Column::new(
+                    Some(self.table_name.clone()),
+                    format!("c{idx}"),
+                ))
+                .alias_qualified(field.qualifier().cloned(), field.name())
+            })
+            .collect();
+
+        let projection = Projection::try_new(exprs, Arc::new(LogicalPlan::TableScan(scan)))?;
+        Ok(Some(LogicalPlan::Projection(projection)))
+    }
+}
@@ -0,0 +1,465 @@
+use std::sync::Arc;
+
+use datafusion::arrow::datatypes::{Field, Schema};
+use datafusion::common::{Column, DFSchema, OwnedTableReference};
+use datafusion::datasource::{DefaultTableSource, TableProvider};
+use datafusion::error::Result;
+use datafusion::logical_expr::expr::Sort as SortExpr;
+use datafusion::logical_expr::{
+    Aggregate,
+    Expr,
+    Filter,
+    Join,
+    JoinType,
+    Limit,
+    LogicalPlan,
+    Projection,
+    Sort,
+    SubqueryAlias,
+    TableScan,
+};
+use datafusion::optimizer::optimizer::ApplyOrder;
+use datafusion::optimizer::{OptimizerConfig, OptimizerRule};
+use datafusion_ext::runtime::table_provider::RuntimeAwareTableProvider;
+use datasources::common::query::{
+    as_sql_query_pushdown,
+    SqlExprWriter,
+    SqlOrderBy,
+    SqlQuery,
+    SqlQueryPushdown,
+    SqlQueryTableProvider,
+};
+use protogen::metastore::types::catalog::RuntimePreference;
+
+/// Rewrites aggregates, sorts, limits and joins over external tables into a
+/// query that's executed by the external database.
+///
+/// Only subtrees consisting of table scans, filters, projections, aggregates,
+/// sorts, limits and joins are rewritten, and every expression in the subtree
+/// needs to be expressible in the database's dialect. Joined tables need to be
+/// reachable through the same connection. The rewritten subtree is replaced
+/// with a scan of the pushed down query.
+///
+/// Sorts are only pushed down along with a limit, and the sort is kept on top
+/// of the scan since the scan doesn't report an output ordering. Strings are
+/// only sorted remotely if the database can order them by bytes like we do,
+/// and only grouped or joined on remotely if the database compares them by
+/// bytes.
+pub(crate) struct SqlQueryPushdownRule;
+
+impl OptimizerRule for SqlQueryPushdownRule {
+    fn try_optimize(
+        &self,
+        plan: &LogicalPlan,
+        _config: &dyn OptimizerConfig,
+    ) -> Result<Option<LogicalPlan>> {
+        // Only try to rewrite at nodes where pushing down actually reduces
+        // the data we need to pull. Plain scans (with filters and limits) are
+        // already handled by the providers.
+        if !matches!(
+            plan,
+            LogicalPlan::Aggregate(_)
+                | LogicalPlan::Sort(_)
+                | LogicalPlan::Limit(_)
+                | LogicalPlan::Join(_)
+        ) {
+            return Ok(None);
+        }
+
+        let state = match QueryState::try_from_plan(plan, &mut 0) {
+            Some(state) if state.aggregated || state.sorted || state.joined => state,
+            _ => return Ok(None),
+        };
+
+        state.into_plan(plan)
+    }
+
+    fn name(&self) -> &str {
+        "SqlQueryPushdownRule"
+    }
+
+    fn apply_order(&self) -> Option<ApplyOrder> {
+        Some(ApplyOrder::TopDown)
+    }
+}
+
+/// State for building up a query while walking a logical plan bottom up.
+struct QueryState {
+    /// Name of the (first) table being queried.
+    table_name: OwnedTableReference,
+    /// Provider for the (first) table. Guaranteed to support query pushdown.
+    provider: Arc<dyn TableProvider>,
+    /// SQL for the FROM clause.
+    from: String,
+    /// Qualified column names of all tables being queried.
+    table_columns: Vec<String>,
+    query: SqlQuery,
+    /// SQL for each column in the output of the plan we've walked so far.
+    columns: Vec<String>,
+    aggregated: bool,
+    sorted: bool,
+    limited: bool,
+    joined: bool,
+}
+
+impl QueryState {
+    fn pushdown(&self) -> &dyn SqlQueryPushdown {
+        // Checked when creating the state.
+        as_sql_query_pushdown(self.provider.as_ref()).unwrap()
+    }
+
+    /// Write expressions evaluated against the output of `input` as SQL.
+    fn write_exprs<'a>(
+        &self,
+        input: &LogicalPlan,
+        exprs: impl IntoIterator<Item = &'a Expr>,
+    ) -> Option<Vec<String>> {
+        let writer = SqlExprWriter::new(self.pushdown(), input.schema(), &self.columns);
+        exprs.into_iter().map(|expr| writer.write(expr)).collect()
+    }
+
+    /// Try to build up the query for the plan.
+    ///
+    /// `aliases` is the number of table aliases handed out so far, and is used
+    /// to give every table in the query a unique alias.
+    ///
+    /// Returns `None` if the plan can't be pushed down.
+    fn try_from_plan(plan: &LogicalPlan, aliases: &mut usize) -> Option<Self> {
+        match plan {
+            LogicalPlan::TableScan(scan) => Self::try_from_scan(scan, aliases),
+            // Columns are referenced by position, so aliases don't change
+            // anything.
+            LogicalPlan::SubqueryAlias(SubqueryAlias { input, .. }) => {
+                Self::try_from_plan(input, aliases)
+            }
+            LogicalPlan::Join(join) => {
+                let left = Self::try_from_plan(&join.left, aliases)?;
+                let right = Self::try_from_plan(&join.right, aliases)?;
+                left.try_join(right, join)
+            }
+            LogicalPlan::Filter(Filter {
+                predicate, input, ..
+            }) => {
+                let mut state = Self::try_from_plan(input, aliases)?;
+                if state.sorted || state.limited {
+                    return None;
+                }
+                let predicate = state.write_exprs(input, [predicate])?.pop()?;
+                let filters = if state.aggregated {
+                    &mut state.query.having
+                } else {
+                    &mut state.query.filters
+                };
+                if !filters.contains(&predicate) {
+                    filters.push(predicate);
+                }
+                Some(state)
+            }
+            LogicalPlan::Projection(Projection { expr, input, .. }) => {
+                let mut state = Self::try_from_plan(input, aliases)?;
+                // Sorts are kept local on top of the pushed down query, so
+                // need to be the root of the pushed down plan.
+                if state.sorted {
+                    return None;
+                }
+                state.columns = state.write_exprs(input, expr)?;
+                Some(state)
+            }
+            LogicalPlan::Aggregate(Aggregate {
+                input,
+                group_expr,
+                aggr_expr,
+                ..
+            }) => {
+                let mut state = Self::try_from_plan(input, aliases)?;
+                if state.aggregated || state.sorted || state.limited {
+                    return None;
+                }
+                let writer = SqlExprWriter::new(state.pushdown(), input.schema(), &state.columns);
+                let group_by = group_expr
+                    .iter()
+                    .map(|expr| writer.write_byte_compared(expr))
+                    .collect::<Option<Vec<_>>>()?;
+                let aggs = state.write_exprs(input, aggr_expr)?;
+                state.query.group_by = group_by.clone();
+                state.columns = group_by.into_iter().chain(aggs).collect();
+                state.aggregated = true;
+                Some(state)
+            }
+            LogicalPlan::Sort(Sort { expr, input, fetch }) => {
+                // Sorting remotely without a limit doesn't reduce the rows we
+                // need to pull, and the rows would be sorted again anyways.
+                let fetch = (*fetch)?;
+                let mut state = Self::try_from_plan(input, aliases)?;
+                if state.sorted || state.limited {
+                    return None;
+                }
+                let writer = SqlExprWriter::new(state.pushdown(), input.schema(), &state.columns);
+                let order_by = expr
+                    .iter()
+                    .map(|expr| match expr {
+                        Expr::Sort(SortExpr {
+                            expr,
+                            asc,
+                            nulls_first,
+                        }) => Some(SqlOrderBy {
+                            expr: writer.write_byte_ordered(expr)?,
+                            asc: *asc,
+                            nulls_first: *nulls_first,
+                        }),
+                        _ => None,
+                    })
+                    .collect::<Option<Vec<_>>>()?;
+                state.query.order_by = order_by;
+                state.query.limit = Some(fetch);
+                state.sorted = true;
+                state.limited = true;
+                Some(state)
+            }
+            LogicalPlan::Limit(Limit { skip, fetch, input }) => {
+                let mut state = Self::try_from_plan(input, aliases)?;
+                if state.sorted {
+                    return None;
+                }
+                let fetch = (*fetch)?;
+                if state.limited {
+                    // Only handle a limit on top of another limit without
+                    // offsets.
+                    if *skip != 0 || state.query.offset != 0 {
+                        return None;
+                    }
+                    state.query.limit = state.query.limit.map(|limit| limit.min(fetch));
+                } else {
+                    state.query.limit = Some(fetch);
+                    state.query.offset = *skip;
+                    state.limited = true;
+                }
+                Some(state)
+            }
+            _ => None,
+        }
+    }
+
+    fn try_from_scan(scan: &TableScan, aliases: &mut usize) -> Option<Self> {
+        if scan.fetch.is_some() {
+            return None;
+        }
+
+        let source = scan.source.as_any().downcast_ref::<DefaultTableSource>()?;
+        let provider = source.table_provider.clone();
+        let provider = match provider
+            .as_any()
+            .downcast_ref::<RuntimeAwareTableProvider>()
+        {
+            // Remote providers are only stubs, we can't query through them.
+            Some(p) if p.preference == RuntimePreference::Remote => return None,
+            Some(p) => p.provider.clone(),
+            None => provider,
+        };
+        let pushdown = as_sql_query_pushdown(provider.as_ref())?;
+
+        let alias = format!("t{aliases}");
+        *aliases += 1;
+
+        let table_schema = provider.schema();
+        let table_columns: Vec<String> = table_schema
+            .fields()
+            .iter()
+            .map(|f| format!("{alias}.{}", pushdown.column_reference(f.name())))
+            .collect();
+
+        // Filters that were pushed down to the scan are written against the
+        // full table schema.
+        let df_schema =
+            DFSchema::try_from_qualified_schema(scan.table_name.clone(), &table_schema).ok()?;
+        let writer = SqlExprWriter::new(pushdown, &df_schema, &table_columns);
+        let filters = scan
+            .filters
+            .iter()
+            .map(|expr| writer.write(expr))
+            .collect::<Option<Vec<_>>>()?;
+
+        let columns = scan
+            .projected_schema
+            .fields()
+            .iter()
+            .map(|f| format!("{alias}.{}", pushdown.column_reference(f.name())))
+            .collect();
+
+        let from = format!("{} AS {alias}", pushdown.table_reference());
+
+        Some(QueryState {
+            table_name: scan.table_name.clone(),
+            from,
+            provider,
+            table_columns,
+            query: SqlQuery {
+                filters,
+                ..Default::default()
+            },
+            columns,
+            aggregated: false,
+            sorted: false,
+            limited: false,
+            joined: false,
+        })
+    }
+
+    /// Try to join this query with another query.
+    ///
+    /// Returns `None` if the join can't be pushed down.
+    fn try_join(mut self, right: Self, join: &Join) -> Option<Self> {
+        // Joining on the results of aggregates or limits would require
+        // subqueries.
+        for state in [&self, &right] {
+            if state.aggregated || state.sorted || state.limited {
+                return None;
+            }
+        }
+        if join.null_equals_null {
+            return None;
+        }
+        if !self.pushdown().shares_connection(right.provider.as_ref()) {
+            return None;
+        }
+
+        let join_type = match join.join_type {
+            JoinType::Inner => "INNER JOIN",
+            JoinType::Left => "LEFT JOIN",
+            JoinType::Right => "RIGHT JOIN",
+            JoinType::Full => "FULL JOIN",
+            _ => return None,
+        };
+
+        let mut conditions = Vec::with_capacity(join.on.len());
+        let left_writer = SqlExprWriter::new(self.pushdown(), join.left.schema(), &self.columns);
+        let right_writer =
+            SqlExprWriter::new(right.pushdown(), join.right.schema(), &right.columns);
+        // Join keys are compared for equality, string keys are only joined on
+        // remotely if the database compares them by bytes.
+        for (left_expr, right_expr) in &join.on {
+            conditions.push(format!(
+                "({} = {})",
+                left_writer.write_byte_compared(left_expr)?,
+                right_writer.write_byte_compared(right_expr)?
+            ));
+        }
+
+        let columns: Vec<String> = self
+            .columns
+            .iter()
+            .chain(right.columns.iter())
+            .cloned()
+            .collect();
+        if let Some(filter) = &join.filter {
+            let writer = SqlExprWriter::new(self.pushdown(), &join.schema, &columns);
+            conditions.push(writer.write(filter)?);
+        }
+
+        // Filters on the side of an outer join that isn't preserved need to
+        // be part of the join condition, otherwise they'd remove rows that
+        // should be padded with nulls.
+        let mut left_filters = std::mem::take(&mut self.query.filters);
+        let mut right_filters = right.query.filters;
+        match join.join_type {
+            JoinType::Left => conditions.append(&mut right_filters),
+            JoinType::Right => conditions.append(&mut left_filters),
+            JoinType::Full if !left_filters.is_empty() || !right_filters.is_empty() => return None,
+            _ => (),
+        }
+
+        // Cross joins don't reduce the amount of data we'd need to pull.
+        if conditions.is_empty() {
+            return None;
+        }
+
+        let right_from = if right.joined {
+            format!("({})", right.from)
+        } else {
+            right.from
+        };
+        self.from = format!(
+            "{} {join_type} {right_from} ON {}",
+            self.from,
+            conditions.join(" AND ")
+        );
+        self.query.filters = left_filters.into_iter().chain(right_filters).collect();
+        self.table_columns.extend(right.table_columns);
+        self.columns = columns;
+        self.joined = true;
+
+        Some(self)
+    }
+
+    /// Create a plan scanning the pushed down query, producing the same
+    /// output as `plan`.
+    fn into_plan(mut self, plan: &LogicalPlan) -> Result<Option<LogicalPlan>> {
+        let pushdown = self.pushdown();
+        let dialect = pushdown.dialect();
+        let output = plan.schema();
+
+        let mut select = Vec::with_capacity(self.columns.len());
+        let mut fields = Vec::with_capacity(self.columns.len());
+        for (idx, (sql, field)) in self.columns.iter().zip(output.fields()).enumerate() {
+            let name = format!("c{idx}");
+            // Cast every column so that the database returns exactly the
+            // types we expect. Plain table columns without a cast type are
+            // already the expected type.
+            let sql = match pushdown.cast_type(field.data_type()) {
+                Some(typ) => format!("CAST({sql} AS {typ}) AS {name}"),
+                None if self.table_columns.contains(sql) => format!("{sql} AS {name}"),
+                None => return Ok(None),
+            };
+            select.push(sql);
+            fields.push(Field::new(name, field.data_type().clone(), true));
+        }
+
+        if select.is_empty() {
+            return Ok(None);
+        }
+
+        self.query.select = select;
+        let sql = self.query.to_sql(dialect, &self.from);
+        let schema = Arc::new(Schema::new(fields));
+
+        let provider = SqlQueryTableProvider::try_new(self.provider.clone(), sql, schema)?;
+        let provider = RuntimeAwareTableProvider::new(RuntimePreference::Local, Arc::new(provider));
+        let scan = TableScan::try_new(
+            self.table_name.clone(),
+            Arc::new(DefaultTableSource::new(Arc::new(provider))),
+            None,
+            Vec::new(),
+            None,
+        )?;
+
+        // Alias the query's columns back to the names the rest of the plan
+        // expects.
+        let exprs = output
+            .fields()
+            .iter()
+            .enumerate()
+            .map(|(idx, field)| {
+                Expr::Column(Column::new(
+                    Some(self.table_name.clone()),
+                    format!("c{idx}"),
+                ))
+                .alias_qualified(field.qualifier().cloned(), field.name())
+            })
+            .collect();
+
+        let projection = Projection::try_new(exprs, Arc::new(LogicalPlan::TableScan(scan)))?;
+        let projection = LogicalPlan::Projection(projection);
+
+        // The pushed down query is sorted, but the scan doesn't report its
+        // ordering. Keep the sort to restore the order of the (already
+        // limited) rows.
+        match plan {
+            LogicalPlan::Sort(Sort { expr, fetch, .. }) => Ok(Some(LogicalPlan::Sort(Sort {
+                expr: expr.clone(),
+                input: Arc::new(projection),
+                fetch: *fetch,
+            }))),
+            _ => Ok(Some(projection)),
+        }
+    }
+}
@@ -62,6 +62,9 @@ pub enum PlanError {
     #[error("Invalid number of column aliases for view body; sql: {sql}, aliases: {aliases:?}")]
     InvalidNumberOfAliasesForView { sql: String, aliases: Vec<String> },
 
+    #[error("'{0}' is not a materialized view")]
+    NotAMaterializedView(String),
+
     #[error("An ssh connection is not supported datasource for CREATE EXTERNAL TABLE. An ssh connection must be provided as an optional ssh_tunnel with another connection type")]
     ExternalTableWithSsh,
 
@@ -139,6 +142,7 @@ impl_from_dispatch_variant!(datasources::sqlserver::errors::SqlServerError);
 impl_from_dispatch_variant!(datasources::clickhouse::errors::ClickhouseError);
 impl_from_dispatch_variant!(datasources::cassandra::CassandraError);
 impl_from_dispatch_variant!(datasources::sqlite::errors::SqliteError);
+impl_from_dispatch_variant!(datasources::duckdb::errors::DuckDbError);
 
 #[allow(unused_macros)]
 macro_rules! internal {
@@ -25,6 +25,7 @@ use super::logical_plan::{
     DropTunnel,
     DropViews,
     Insert,
+    OptimizeTable,
     SetVariable,
     ShowVariable,
     Update,
@@ -55,6 +56,7 @@ pub enum ExtensionType {
     DropSchemas,
     DropTunnel,
     DropViews,
+    OptimizeTable,
     SetVariable,
     ShowVariable,
     CopyTo,
@@ -85,6 +87,7 @@ impl FromStr for ExtensionType {
             DropSchemas::EXTENSION_NAME => Self::DropSchemas,
             DropTunnel::EXTENSION_NAME => Self::DropTunnel,
             DropViews::EXTENSION_NAME => Self::DropViews,
+            OptimizeTable::EXTENSION_NAME => Self::OptimizeTable,
             SetVariable::EXTENSION_NAME => Self::SetVariable,
             ShowVariable::EXTENSION_NAME => Self::ShowVariable,
             CopyTo::EXTENSION_NAME => Self::CopyTo,
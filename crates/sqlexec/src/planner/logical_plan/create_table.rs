@@ -13,6 +13,10 @@ pub struct CreateTable {
     pub if_not_exists: bool,
     pub or_replace: bool,
     pub schema: DFSchemaRef,
+    pub partition_by: Vec<String>,
+    /// Query the table is materialized from when creating a materialized
+    /// view.
+    pub view_sql: Option<String>,
     pub source: Option<DfLogicalPlan>,
 }
 
@@ -1,4 +1,4 @@
-use protogen::metastore::types::catalog::TableEntry;
+use protogen::metastore::types::catalog::RuntimePreference;
 
 use super::{
     DfLogicalPlan,
@@ -7,11 +7,13 @@ use super::{
     UserDefinedLogicalNodeCore,
     GENERIC_OPERATION_AND_COUNT_LOGICAL_SCHEMA,
 };
+use crate::planner::physical_plan::DmlTarget;
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Delete {
-    pub table: TableEntry,
+    pub target: DmlTarget,
     pub where_expr: Option<Expr>,
+    pub runtime_preference: RuntimePreference,
 }
 
 impl UserDefinedLogicalNodeCore for Delete {
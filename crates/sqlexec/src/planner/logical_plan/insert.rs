@@ -15,6 +15,8 @@ use crate::planner::physical_plan::remote_scan::ProviderReference;
 pub struct Insert {
     pub source: DfLogicalPlan,
     pub provider: ProviderReference,
+    /// Replace the existing contents of the table instead of appending to it.
+    pub overwrite: bool,
     pub runtime_preference: RuntimePreference,
 }
 
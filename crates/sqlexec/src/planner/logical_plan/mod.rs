@@ -19,6 +19,7 @@ mod drop_tables;
 mod drop_tunnel;
 mod drop_views;
 mod insert;
+mod optimize_table;
 mod set_variable;
 mod show_variable;
 mod update;
@@ -60,6 +61,7 @@ pub use drop_tunnel::*;
 pub use drop_views::*;
 pub use insert::*;
 use once_cell::sync::Lazy;
+pub use optimize_table::*;
 use parser::sqlparser::ast;
 use protogen::metastore::types::options::{
     CopyToDestinationOptions,
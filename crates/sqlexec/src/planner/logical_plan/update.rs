@@ -1,4 +1,4 @@
-use protogen::metastore::types::catalog::TableEntry;
+use protogen::metastore::types::catalog::RuntimePreference;
 
 use super::{
     DfLogicalPlan,
@@ -7,12 +7,14 @@ use super::{
     UserDefinedLogicalNodeCore,
     GENERIC_OPERATION_AND_COUNT_LOGICAL_SCHEMA,
 };
+use crate::planner::physical_plan::DmlTarget;
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Update {
-    pub table: TableEntry,
+    pub target: DmlTarget,
     pub updates: Vec<(String, Expr)>,
     pub where_expr: Option<Expr>,
+    pub runtime_preference: RuntimePreference,
 }
 
 impl UserDefinedLogicalNodeCore for Update {
@@ -5,6 +5,7 @@ use std::sync::Arc;
 
 use datafusion::arrow::datatypes::Schema;
 use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::datasource::file_format::file_compression_type::FileCompressionType;
 use datafusion::error::{DataFusionError, Result as DataFusionResult};
 use datafusion::execution::TaskContext;
 use datafusion::physical_expr::{PhysicalSortExpr, PhysicalSortRequirement};
@@ -21,8 +22,9 @@ use datafusion::physical_plan::{
     SendableRecordBatchStream,
     Statistics,
 };
-use datasources::common::sink::bson::BsonSink;
+use datasources::common::sink::bson::{BsonSink, BsonSinkOpts};
 use datasources::common::sink::csv::{CsvSink, CsvSinkOpts};
+use datasources::common::sink::excel::{ExcelSink, ExcelSinkOpts};
 use datasources::common::sink::json::{JsonSink, JsonSinkOpts};
 use datasources::common::sink::lance::{LanceSink, LanceSinkOpts, LanceWriteParams};
 use datasources::common::sink::parquet::{ParquetSink, ParquetSinkOpts};
@@ -214,6 +216,7 @@ fn get_sink_for_obj(
             CsvSinkOpts {
                 delim: csv_opts.delim,
                 header: csv_opts.header,
+                compression: compression_type(csv_opts.compression.as_deref())?,
             },
         )),
         CopyToFormatOptions::Parquet(parquet_opts) => Box::new(ParquetSink::from_obj_store(
@@ -251,9 +254,32 @@ fn get_sink_for_obj(
             path,
             JsonSinkOpts {
                 array: json_opts.array,
+                compression: compression_type(json_opts.compression.as_deref())?,
+            },
+        )),
+        CopyToFormatOptions::Bson(bson_opts) => Box::new(BsonSink::from_obj_store(
+            store,
+            path,
+            BsonSinkOpts {
+                compression: compression_type(bson_opts.compression.as_deref())?,
+            },
+        )),
+        CopyToFormatOptions::Excel(excel_opts) => Box::new(ExcelSink::from_obj_store(
+            store,
+            path,
+            ExcelSinkOpts {
+                sheet_name: excel_opts.sheet_name,
+                header: excel_opts.header,
             },
         )),
-        CopyToFormatOptions::Bson(_) => Box::new(BsonSink::from_obj_store(store, path)),
     };
     Ok(sink)
 }
+
+/// Parse the compression for a sink, defaulting to uncompressed.
+fn compression_type(compression: Option<&str>) -> DataFusionResult<FileCompressionType> {
+    compression
+        .map(|c| c.parse::<FileCompressionType>())
+        .transpose()
+        .map(|c| c.unwrap_or(FileCompressionType::UNCOMPRESSED))
+}
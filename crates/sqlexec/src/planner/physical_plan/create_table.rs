@@ -21,6 +21,7 @@ use datafusion::physical_plan::{
 };
 use datasources::native::access::{NativeTable, NativeTableStorage, SaveMode};
 use futures::{stream, StreamExt};
+use protogen::metastore::types::options::TableOptionsInternal;
 use protogen::metastore::types::service;
 use protogen::metastore::types::service::Mutation;
 use sqlbuiltins::builtins::DEFAULT_CATALOG;
@@ -37,6 +38,8 @@ pub struct CreateTableExec {
     pub if_not_exists: bool,
     pub or_replace: bool,
     pub arrow_schema: SchemaRef,
+    pub partition_by: Vec<String>,
+    pub view_sql: Option<String>,
     pub source: Option<Arc<dyn ExecutionPlan>>,
 }
 
@@ -74,6 +77,8 @@ impl ExecutionPlan for CreateTableExec {
             if_not_exists: self.if_not_exists,
             or_replace: self.or_replace,
             arrow_schema: self.arrow_schema.clone(),
+            partition_by: self.partition_by.clone(),
+            view_sql: self.view_sql.clone(),
             source: children.first().cloned(),
         }))
     }
@@ -128,13 +133,18 @@ impl CreateTableExec {
         let or_replace = self.or_replace;
         let if_not_exists = self.if_not_exists;
         let catalog_version = self.catalog_version;
+
+        let mut options: TableOptionsInternal = self.arrow_schema.into();
+        options.partition_by = self.partition_by;
+        options.view_sql = self.view_sql;
+
         let state = mutator
             .mutate(
                 catalog_version,
                 [Mutation::CreateTable(service::CreateTable {
                     schema: self.tbl_reference.schema.clone().into_owned(),
                     name: self.tbl_reference.name.clone().into_owned(),
-                    options: self.arrow_schema.into(),
+                    options,
                     if_not_exists,
                     or_replace,
                 })],
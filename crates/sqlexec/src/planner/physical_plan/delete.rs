@@ -17,15 +17,21 @@ use datafusion::physical_plan::{
     Statistics,
 };
 use datafusion::prelude::Expr;
+use datasources::common::dml::DmlDatasource;
 use datasources::native::access::NativeTableStorage;
-use futures::stream;
+use futures::{stream, StreamExt};
 use protogen::metastore::types::catalog::TableEntry;
 
-use super::{new_operation_with_count_batch, GENERIC_OPERATION_AND_COUNT_PHYSICAL_SCHEMA};
+use super::remote_scan::ProviderReference;
+use super::{
+    new_operation_with_count_batch,
+    DmlTarget,
+    GENERIC_OPERATION_AND_COUNT_PHYSICAL_SCHEMA,
+};
 
 #[derive(Debug, Clone)]
 pub struct DeleteExec {
-    pub table: TableEntry,
+    pub target: DmlTarget,
     pub where_expr: Option<Expr>,
 }
 
@@ -74,12 +80,19 @@ impl ExecutionPlan for DeleteExec {
             ));
         }
 
-        let storage = context
-            .session_config()
-            .get_extension::<NativeTableStorage>()
-            .expect("context should have native table storage");
-
-        let stream = stream::once(delete(self.clone(), storage));
+        let stream = match &self.target {
+            DmlTarget::Native(table) => {
+                let storage = context
+                    .session_config()
+                    .get_extension::<NativeTableStorage>()
+                    .expect("context should have native table storage");
+                stream::once(delete(self.clone(), table.clone(), storage)).boxed()
+            }
+            DmlTarget::External {
+                datasource,
+                provider,
+            } => stream::once(delete_external(self.clone(), *datasource, provider.clone())).boxed(),
+        };
 
         Ok(Box::pin(RecordBatchStreamAdapter::new(
             self.schema(),
@@ -100,14 +113,38 @@ impl DisplayAs for DeleteExec {
 
 async fn delete(
     plan: DeleteExec,
+    table: TableEntry,
     storage: impl AsRef<NativeTableStorage>,
 ) -> DataFusionResult<RecordBatch> {
     let storage = storage.as_ref();
 
     let num_deleted = storage
-        .delete_rows_where(&plan.table, plan.where_expr)
+        .delete_rows_where(&table, plan.where_expr)
         .await
         .map_err(|e| DataFusionError::Execution(format!("failed to delete: {e}")))?;
 
     Ok(new_operation_with_count_batch("delete", num_deleted as u64))
 }
+
+async fn delete_external(
+    plan: DeleteExec,
+    datasource: DmlDatasource,
+    provider: ProviderReference,
+) -> DataFusionResult<RecordBatch> {
+    let provider = match provider {
+        ProviderReference::Provider(provider) => provider,
+        ProviderReference::RemoteReference(_) => {
+            return Err(DataFusionError::Internal(
+                "required table provider, found remote reference to delete".to_string(),
+            ))
+        }
+    };
+    let dml = datasource.external_dml(provider.as_ref())?;
+
+    let num_deleted = dml
+        .delete(plan.where_expr.as_ref())
+        .await
+        .map_err(|e| DataFusionError::Execution(format!("failed to delete: {e}")))?;
+
+    Ok(new_operation_with_count_batch("delete", num_deleted))
+}
@@ -29,6 +29,8 @@ use super::{new_operation_with_count_batch, GENERIC_OPERATION_AND_COUNT_PHYSICAL
 #[derive(Debug, Clone)]
 pub struct InsertExec {
     pub provider: ProviderReference,
+    /// Replace the existing contents of the table instead of appending to it.
+    pub overwrite: bool,
     pub source: Arc<WriteOnlyDataSourceMetricsExecAdapter>,
 }
 
@@ -59,6 +61,7 @@ impl ExecutionPlan for InsertExec {
     ) -> DataFusionResult<Arc<dyn ExecutionPlan>> {
         Ok(Arc::new(InsertExec {
             provider: self.provider.clone(),
+            overwrite: self.overwrite,
             source: Arc::new(WriteOnlyDataSourceMetricsExecAdapter::new(
                 children.first().unwrap().clone(),
             )),
@@ -84,7 +87,7 @@ impl ExecutionPlan for InsertExec {
                 )),
                 ProviderReference::Provider(provider) => {
                     // TODO: Add background job to track storage for native tables.
-                    Self::do_insert(provider, this.source, context).await
+                    Self::do_insert(provider, this.source, this.overwrite, context).await
                 }
             }
         });
@@ -110,6 +113,7 @@ impl InsertExec {
     pub async fn do_insert(
         table: Arc<dyn TableProvider>,
         source: Arc<dyn ExecutionPlan>,
+        overwrite: bool,
         context: Arc<TaskContext>,
     ) -> DataFusionResult<RecordBatch> {
         let state = SessionState::new_with_config_rt(
@@ -123,7 +127,7 @@ impl InsertExec {
             source
         };
 
-        let exec = table.insert_into(&state, source, false).await?;
+        let exec = table.insert_into(&state, source, overwrite).await?;
 
         let mut stream = exec.execute(0, context)?;
 
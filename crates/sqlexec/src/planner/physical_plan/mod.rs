@@ -22,6 +22,7 @@ pub mod drop_temp_tables;
 pub mod drop_tunnel;
 pub mod drop_views;
 pub mod insert;
+pub mod optimize_table;
 pub mod remote_exec;
 pub mod remote_scan;
 pub mod send_recv;
@@ -46,8 +47,12 @@ use datafusion::physical_plan::{
     Statistics,
 };
 use datafusion::scalar::ScalarValue;
+use datasources::common::dml::DmlDatasource;
 use futures::{stream, StreamExt};
 use once_cell::sync::Lazy;
+use protogen::metastore::types::catalog::TableEntry;
+
+use self::remote_scan::ProviderReference;
 
 pub static GENERIC_OPERATION_PHYSICAL_SCHEMA: Lazy<Arc<Schema>> = Lazy::new(|| {
     Arc::new(Schema::new(vec![Field::new(
@@ -67,6 +72,18 @@ pub fn new_operation_batch(operation: impl Into<String>) -> RecordBatch {
     .unwrap()
 }
 
+/// Table to update or delete rows from.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum DmlTarget {
+    Native(TableEntry),
+    /// Rows are modified by the external database, through the provider's
+    /// implementation for the datasource.
+    External {
+        datasource: DmlDatasource,
+        provider: ProviderReference,
+    },
+}
+
 /// Arrow schema for dml (excluding select) output streams.
 pub static GENERIC_OPERATION_AND_COUNT_PHYSICAL_SCHEMA: Lazy<Arc<Schema>> = Lazy::new(|| {
     Arc::new(Schema::new(vec![
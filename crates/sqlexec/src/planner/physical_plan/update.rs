@@ -17,15 +17,21 @@ use datafusion::physical_plan::{
     Statistics,
 };
 use datafusion::prelude::Expr;
+use datasources::common::dml::DmlDatasource;
 use datasources::native::access::NativeTableStorage;
-use futures::stream;
+use futures::{stream, StreamExt};
 use protogen::metastore::types::catalog::TableEntry;
 
-use super::{new_operation_with_count_batch, GENERIC_OPERATION_AND_COUNT_PHYSICAL_SCHEMA};
+use super::remote_scan::ProviderReference;
+use super::{
+    new_operation_with_count_batch,
+    DmlTarget,
+    GENERIC_OPERATION_AND_COUNT_PHYSICAL_SCHEMA,
+};
 
 #[derive(Debug, Clone)]
 pub struct UpdateExec {
-    pub table: TableEntry,
+    pub target: DmlTarget,
     pub updates: Vec<(String, Expr)>,
     pub where_expr: Option<Expr>,
 }
@@ -75,12 +81,19 @@ impl ExecutionPlan for UpdateExec {
             ));
         }
 
-        let storage = context
-            .session_config()
-            .get_extension::<NativeTableStorage>()
-            .expect("context should have native table storage");
-
-        let stream = stream::once(update(self.clone(), storage));
+        let stream = match &self.target {
+            DmlTarget::Native(table) => {
+                let storage = context
+                    .session_config()
+                    .get_extension::<NativeTableStorage>()
+                    .expect("context should have native table storage");
+                stream::once(update(self.clone(), table.clone(), storage)).boxed()
+            }
+            DmlTarget::External {
+                datasource,
+                provider,
+            } => stream::once(update_external(self.clone(), *datasource, provider.clone())).boxed(),
+        };
 
         Ok(Box::pin(RecordBatchStreamAdapter::new(
             self.schema(),
@@ -101,14 +114,38 @@ impl DisplayAs for UpdateExec {
 
 async fn update(
     plan: UpdateExec,
+    table: TableEntry,
     storage: impl AsRef<NativeTableStorage>,
 ) -> DataFusionResult<RecordBatch> {
     let storage = storage.as_ref();
 
     let num_updated = storage
-        .update_rows_where(&plan.table, plan.updates, plan.where_expr)
+        .update_rows_where(&table, plan.updates, plan.where_expr)
         .await
         .map_err(|e| DataFusionError::Execution(format!("failed to update: {e}")))?;
 
     Ok(new_operation_with_count_batch("update", num_updated as u64))
 }
+
+async fn update_external(
+    plan: UpdateExec,
+    datasource: DmlDatasource,
+    provider: ProviderReference,
+) -> DataFusionResult<RecordBatch> {
+    let provider = match provider {
+        ProviderReference::Provider(provider) => provider,
+        ProviderReference::RemoteReference(_) => {
+            return Err(DataFusionError::Internal(
+                "required table provider, found remote reference to update".to_string(),
+            ))
+        }
+    };
+    let dml = datasource.external_dml(provider.as_ref())?;
+
+    let num_updated = dml
+        .update(&plan.updates, plan.where_expr.as_ref())
+        .await
+        .map_err(|e| DataFusionError::Execution(format!("failed to update: {e}")))?;
+
+    Ok(new_operation_with_count_batch("update", num_updated))
+}
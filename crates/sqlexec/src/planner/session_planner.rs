@@ -12,8 +12,9 @@ use datafusion::arrow::datatypes::{
     DECIMAL_DEFAULT_SCALE,
 };
 use datafusion::common::parsers::CompressionTypeVariant;
-use datafusion::common::{OwnedSchemaReference, OwnedTableReference, ToDFSchema};
-use datafusion::logical_expr::{cast, col, LogicalPlanBuilder};
+use datafusion::common::{Column, OwnedSchemaReference, OwnedTableReference, ToDFSchema};
+use datafusion::logical_expr::expr_fn::{max, scalar_subquery};
+use datafusion::logical_expr::{cast, col, Expr, LogicalPlan as DfLogicalPlan, LogicalPlanBuilder};
 use datafusion::sql::planner::{object_name_to_table_reference, PlannerContext};
 use datafusion::sql::TableReference;
 use datafusion_ext::conversion::convert;
@@ -22,10 +23,14 @@ use datafusion_ext::{AsyncContextProvider, IdentNormalizer};
 use datasources::bigquery::{BigQueryAccessor, BigQueryTableAccess};
 use datasources::cassandra::{CassandraAccess, CassandraAccessState};
 use datasources::clickhouse::{ClickhouseAccess, ClickhouseTableRef};
-use datasources::common::ssh::key::SshKey;
+use datasources::common::dml::DmlDatasource;
+use datasources::common::secrets::{resolve_secrets, validate_secret_references};
+use datasources::common::ssh::key::{known_hosts_entry, validate_known_hosts, SshKey};
 use datasources::common::ssh::{SshConnection, SshConnectionParameters};
+use datasources::common::tls::validate_tls_options;
 use datasources::common::url::{DatasourceUrl, DatasourceUrlType};
 use datasources::debug::DebugTableType;
+use datasources::duckdb::{DuckDbAccess, DuckDbTableAccess};
 use datasources::lake::delta::access::{load_table_direct, DeltaLakeAccessor};
 use datasources::lake::iceberg::table::IcebergTable;
 use datasources::lake::storage_options_into_object_store;
@@ -37,7 +42,13 @@ use datasources::object_store::gcs::GcsStoreAccess;
 use datasources::object_store::local::LocalStoreAccess;
 use datasources::object_store::s3::S3StoreAccess;
 use datasources::object_store::{file_type_from_path, ObjStoreAccess, ObjStoreAccessor};
-use datasources::postgres::{PostgresAccess, PostgresDbConnection};
+use datasources::oracle::{OracleAccessor, OracleDbConnection, OracleTableAccess};
+use datasources::postgres::{
+    PostgresAccess,
+    PostgresDbConnection,
+    PostgresTableProvider,
+    PostgresTableProviderConfig,
+};
 use datasources::snowflake::{SnowflakeAccessor, SnowflakeDbConnection, SnowflakeTableAccess};
 use datasources::sqlserver::SqlServerAccess;
 use object_store::aws::AmazonS3ConfigKey;
@@ -70,7 +81,10 @@ use parser::{
     CreateTunnelStmt,
     DropCredentialsStmt,
     DropDatabaseStmt,
+    DropMaterializedViewStmt,
     DropTunnelStmt,
+    OptimizeTableStmt,
+    RefreshMaterializedViewStmt,
     StatementWithExtensions,
 };
 use protogen::metastore::types::catalog::{
@@ -89,6 +103,7 @@ use protogen::metastore::types::options::{
     CopyToFormatOptions,
     CopyToFormatOptionsBson,
     CopyToFormatOptionsCsv,
+    CopyToFormatOptionsExcel,
     CopyToFormatOptionsJson,
     CopyToFormatOptionsLance,
     CopyToFormatOptionsParquet,
@@ -98,14 +113,17 @@ use protogen::metastore::types::options::{
     CredentialsOptionsDebug,
     CredentialsOptionsGcp,
     CredentialsOptionsOpenAI,
+    CredentialsOptionsSnowflake,
     DatabaseOptions,
     DatabaseOptionsBigQuery,
     DatabaseOptionsCassandra,
     DatabaseOptionsClickhouse,
     DatabaseOptionsDebug,
     DatabaseOptionsDeltaLake,
+    DatabaseOptionsDuckDb,
     DatabaseOptionsMongoDb,
     DatabaseOptionsMysql,
+    DatabaseOptionsOracle,
     DatabaseOptionsPostgres,
     DatabaseOptionsSnowflake,
     DatabaseOptionsSqlServer,
@@ -123,11 +141,13 @@ use protogen::metastore::types::options::{
     TableOptionsMongoDb,
     TableOptionsMysql,
     TableOptionsObjectStore,
+    TableOptionsOracle,
     TableOptionsPostgres,
     TableOptionsS3,
     TableOptionsSnowflake,
     TableOptionsSqlServer,
     TableOptionsV0,
+    TlsOptions,
     TunnelOptions,
     TunnelOptionsDebug,
     TunnelOptionsInternal,
@@ -148,6 +168,7 @@ use tracing::debug;
 use super::context_builder::PartialContextProvider;
 use super::extension::ExtensionNode;
 use super::physical_plan::remote_scan::ProviderReference;
+use super::physical_plan::DmlTarget;
 use crate::context::local::LocalSessionContext;
 use crate::planner::errors::{internal, PlanError, Result};
 use crate::planner::logical_plan::{
@@ -174,6 +195,7 @@ use crate::planner::logical_plan::{
     FullObjectReference,
     Insert,
     LogicalPlan,
+    OptimizeTable,
     SetVariable,
     ShowVariable,
     TransactionPlan,
@@ -251,6 +273,13 @@ impl<'a> SessionPlanner<'a> {
             StatementWithExtensions::AlterTableExtension(stmt) => {
                 self.plan_alter_table_extension(stmt)
             }
+            StatementWithExtensions::OptimizeTable(stmt) => self.plan_optimize_table(stmt),
+            StatementWithExtensions::RefreshMaterializedView(stmt) => {
+                self.plan_refresh_materialized_view(stmt).await
+            }
+            StatementWithExtensions::DropMaterializedView(stmt) => {
+                self.plan_drop_materialized_view(stmt)
+            }
             StatementWithExtensions::CreateTunnel(stmt) => self.plan_create_tunnel(stmt),
             StatementWithExtensions::DropTunnel(stmt) => self.plan_drop_tunnel(stmt),
             StatementWithExtensions::AlterTunnel(stmt) => self.plan_alter_tunnel(stmt),
@@ -315,11 +344,14 @@ impl<'a> SessionPlanner<'a> {
 
                 let project_id: String = m.remove_required("project_id")?;
 
-                BigQueryAccessor::validate_external_database(&service_account_key, &project_id)
-                    .await
-                    .map_err(|e| PlanError::InvalidExternalDatabase {
-                        source: Box::new(e),
-                    })?;
+                BigQueryAccessor::validate_external_database(
+                    &resolve_secrets(&service_account_key).await?,
+                    &project_id,
+                )
+                .await
+                .map_err(|e| PlanError::InvalidExternalDatabase {
+                    source: Box::new(e),
+                })?;
 
                 DatabaseOptions::BigQuery(DatabaseOptionsBigQuery {
                     service_account_key,
@@ -328,37 +360,59 @@ impl<'a> SessionPlanner<'a> {
             }
             DatabaseOptions::MYSQL => {
                 let connection_string = get_mysql_conn_str(m)?;
-                MysqlAccessor::validate_external_database(&connection_string, tunnel_options)
+                let tls = get_tls_options(m)?;
+                MysqlAccessor::validate_external_database(&connection_string, tunnel_options, &tls)
+                    .await
+                    .map_err(|e| PlanError::InvalidExternalDatabase {
+                        source: Box::new(e),
+                    })?;
+                DatabaseOptions::Mysql(DatabaseOptionsMysql {
+                    connection_string,
+                    tls,
+                })
+            }
+            DatabaseOptions::ORACLE => {
+                let connection_string = get_oracle_conn_str(m)?;
+                OracleAccessor::validate_external_database(&connection_string)
                     .await
                     .map_err(|e| PlanError::InvalidExternalDatabase {
                         source: Box::new(e),
                     })?;
-                DatabaseOptions::Mysql(DatabaseOptionsMysql { connection_string })
+                DatabaseOptions::Oracle(DatabaseOptionsOracle { connection_string })
             }
             DatabaseOptions::MONGODB => {
                 let connection_string = get_mongodb_conn_str(m)?;
+                let tls = get_tls_options(m)?;
                 // Validate the accessor
-                MongoDbAccessor::validate_external_database(connection_string.as_str())
+                MongoDbAccessor::validate_external_database(connection_string.as_str(), &tls)
                     .await
                     .map_err(|e| PlanError::InvalidExternalDatabase {
                         source: Box::new(e),
                     })?;
-                DatabaseOptions::MongoDb(DatabaseOptionsMongoDb { connection_string })
+                let (sample_size, full_scan) = get_mongodb_sample_options(m)?;
+                DatabaseOptions::MongoDb(DatabaseOptionsMongoDb {
+                    connection_string,
+                    tls,
+                    sample_size,
+                    full_scan,
+                })
             }
             DatabaseOptions::SNOWFLAKE => {
                 let account_name: String = m.remove_required("account")?;
                 let login_name: String = m.remove_required("username")?;
-                let password: String = m.remove_required("password")?;
+                let credentials = snowflake_credentials(creds_options);
+                let password = get_snowflake_password(m, &credentials)?;
                 let database_name: String = m.remove_required("database")?;
                 let warehouse: String = m.remove_required("warehouse")?;
                 let role_name: Option<String> = m.remove_optional("role")?;
                 SnowflakeAccessor::validate_external_database(SnowflakeDbConnection {
                     account_name: account_name.clone(),
                     login_name: login_name.clone(),
-                    password: password.clone(),
+                    password: resolve_secrets(&password).await?,
                     database_name: database_name.clone(),
                     warehouse: warehouse.clone(),
                     role_name: role_name.clone(),
+                    credentials: resolve_secrets(&credentials).await?,
                 })
                 .await
                 .map_err(|e| PlanError::InvalidExternalDatabase {
@@ -371,6 +425,7 @@ impl<'a> SessionPlanner<'a> {
                     database_name,
                     warehouse,
                     role_name: role_name.unwrap_or_default(),
+                    credentials,
                 })
             }
             DatabaseOptions::DELTA => {
@@ -389,7 +444,7 @@ impl<'a> SessionPlanner<'a> {
                 }
 
                 // Try connecting to validate.
-                DeltaLakeAccessor::connect(&catalog, storage_options.clone())
+                DeltaLakeAccessor::connect(&catalog, resolve_secrets(&storage_options).await?)
                     .await
                     .map_err(|e| PlanError::InvalidExternalDatabase {
                         source: Box::new(e),
@@ -402,22 +457,32 @@ impl<'a> SessionPlanner<'a> {
             }
             DatabaseOptions::SQL_SERVER => {
                 let connection_string: String = m.remove_required("connection_string")?;
+                let tls = get_tls_options(m)?;
 
                 // Validate
-                let access = SqlServerAccess::try_new_from_ado_string(&connection_string)?;
+                let access = SqlServerAccess::try_new_from_ado_string(&connection_string)?
+                    .with_tls(tls.clone())?;
                 access.validate_access().await?;
 
-                DatabaseOptions::SqlServer(DatabaseOptionsSqlServer { connection_string })
+                DatabaseOptions::SqlServer(DatabaseOptionsSqlServer {
+                    connection_string,
+                    tls,
+                })
             }
             DatabaseOptions::CLICKHOUSE => {
                 let connection_string: String = m.remove_required("connection_string")?;
+                let tls = get_tls_options(m)?;
 
                 // Validate
                 let access =
-                    ClickhouseAccess::new_from_connection_string(connection_string.clone());
+                    ClickhouseAccess::new_from_connection_string(connection_string.clone())
+                        .with_tls(tls.clone());
                 access.validate_access().await?;
 
-                DatabaseOptions::Clickhouse(DatabaseOptionsClickhouse { connection_string })
+                DatabaseOptions::Clickhouse(DatabaseOptionsClickhouse {
+                    connection_string,
+                    tls,
+                })
             }
             DatabaseOptions::CASSANDRA => {
                 let host: String = m.remove_required("host")?;
@@ -444,6 +509,29 @@ impl<'a> SessionPlanner<'a> {
                     storage_options: Some(storage_options),
                 })
             }
+            DatabaseOptions::DUCKDB => {
+                let location: String = m.remove_required("location")?;
+                let mut storage_options = StorageOptions::try_from(m)?;
+                if let Some(creds) = creds_options {
+                    storage_options_with_credentials(&mut storage_options, creds);
+                }
+
+                let url: DatasourceUrl = location.as_str().try_into()?;
+                if url.datasource_url_type() == DatasourceUrlType::File {
+                    DuckDbAccess::new(url, None)
+                        .await?
+                        .validate_access()
+                        .await
+                        .map_err(|e| PlanError::InvalidExternalDatabase {
+                            source: Box::new(e),
+                        })?;
+                }
+
+                DatabaseOptions::DuckDb(DatabaseOptionsDuckDb {
+                    location,
+                    storage_options: Some(storage_options),
+                })
+            }
             DatabaseOptions::DEBUG => {
                 datasources::debug::validate_tunnel_connections(tunnel_options.as_ref())?;
                 DatabaseOptions::Debug(DatabaseOptionsDebug {})
@@ -451,6 +539,10 @@ impl<'a> SessionPlanner<'a> {
             other => return Err(internal!("unsupported datasource: {}", other)),
         };
 
+        // Options are stored with secret references, connections are
+        // validated above with the resolved secrets.
+        validate_secret_references(&db_options)?;
+
         let database_name = normalize_ident(stmt.name);
 
         let plan = CreateExternalDatabase {
@@ -493,6 +585,8 @@ impl<'a> SessionPlanner<'a> {
                 let connection_string = get_pg_conn_str(m)?;
                 let schema: String = m.remove_required("schema")?;
                 let table: String = m.remove_required("table")?;
+                let partition_column: Option<String> = m.remove_optional("partition_column")?;
+                let num_partitions: Option<usize> = m.remove_optional("num_partitions")?;
 
                 let access =
                     PostgresAccess::new_from_conn_str(connection_string.clone(), tunnel_options);
@@ -503,10 +597,28 @@ impl<'a> SessionPlanner<'a> {
                         source: Box::new(e),
                     })?;
 
+                if partition_column.is_some() || num_partitions.is_some() {
+                    // Creating the provider validates the partitioning
+                    // options against the table's schema.
+                    PostgresTableProvider::try_new(PostgresTableProviderConfig {
+                        access,
+                        schema: schema.clone(),
+                        table: table.clone(),
+                        partition_column: partition_column.clone(),
+                        num_partitions,
+                    })
+                    .await
+                    .map_err(|e| PlanError::InvalidExternalTable {
+                        source: Box::new(e),
+                    })?;
+                }
+
                 TableOptionsPostgres {
                     connection_string,
                     schema,
                     table,
+                    partition_column,
+                    num_partitions: num_partitions.map(|n| n as u64),
                 }
                 .into()
             }
@@ -528,11 +640,15 @@ impl<'a> SessionPlanner<'a> {
                     table_id,
                 };
 
-                BigQueryAccessor::validate_table_access(&service_account_key, &project_id, &access)
-                    .await
-                    .map_err(|e| PlanError::InvalidExternalTable {
-                        source: Box::new(e),
-                    })?;
+                BigQueryAccessor::validate_table_access(
+                    &resolve_secrets(&service_account_key).await?,
+                    &project_id,
+                    &access,
+                )
+                .await
+                .map_err(|e| PlanError::InvalidExternalTable {
+                    source: Box::new(e),
+                })?;
 
                 TableOptionsBigQuery {
                     service_account_key,
@@ -546,19 +662,49 @@ impl<'a> SessionPlanner<'a> {
                 let connection_string = get_mysql_conn_str(m)?;
                 let schema = m.remove_required("schema")?;
                 let table = m.remove_required("table")?;
+                let tls = get_tls_options(m)?;
 
                 let access = MysqlTableAccess {
                     schema,
                     name: table,
                 };
 
-                MysqlAccessor::validate_table_access(&connection_string, &access, tunnel_options)
+                MysqlAccessor::validate_table_access(
+                    &connection_string,
+                    &access,
+                    tunnel_options,
+                    &tls,
+                )
+                .await
+                .map_err(|e| PlanError::InvalidExternalTable {
+                    source: Box::new(e),
+                })?;
+
+                TableOptionsMysql {
+                    connection_string,
+                    schema: access.schema,
+                    table: access.name,
+                    tls,
+                }
+                .into()
+            }
+            TableOptionsV0::ORACLE => {
+                let connection_string = get_oracle_conn_str(m)?;
+                let schema = m.remove_required("schema")?;
+                let table = m.remove_required("table")?;
+
+                let access = OracleTableAccess {
+                    schema,
+                    name: table,
+                };
+
+                OracleAccessor::validate_table_access(&connection_string, &access)
                     .await
                     .map_err(|e| PlanError::InvalidExternalTable {
                         source: Box::new(e),
                     })?;
 
-                TableOptionsMysql {
+                TableOptionsOracle {
                     connection_string,
                     schema: access.schema,
                     table: access.name,
@@ -569,18 +715,24 @@ impl<'a> SessionPlanner<'a> {
                 let connection_string = get_mongodb_conn_str(m)?;
                 let database = m.remove_required("database")?;
                 let collection = m.remove_required("collection")?;
+                let tls = get_tls_options(m)?;
+                let (sample_size, full_scan) = get_mongodb_sample_options(m)?;
 
                 TableOptionsMongoDb {
                     connection_string,
                     database,
                     collection,
+                    tls,
+                    sample_size,
+                    full_scan,
                 }
                 .into()
             }
             TableOptionsV0::SNOWFLAKE => {
                 let account_name: String = m.remove_required("account")?;
                 let login_name: String = m.remove_required("username")?;
-                let password: String = m.remove_required("password")?;
+                let credentials = snowflake_credentials(creds_options);
+                let password = get_snowflake_password(m, &credentials)?;
                 let database_name: String = m.remove_required("database")?;
                 let warehouse: String = m.remove_required("warehouse")?;
                 let role_name: Option<String> = m.remove_optional("role")?;
@@ -590,10 +742,11 @@ impl<'a> SessionPlanner<'a> {
                 let conn_params = SnowflakeDbConnection {
                     account_name: account_name.clone(),
                     login_name: login_name.clone(),
-                    password: password.clone(),
+                    password: resolve_secrets(&password).await?,
                     database_name: database_name.clone(),
                     warehouse: warehouse.clone(),
                     role_name: role_name.clone(),
+                    credentials: resolve_secrets(&credentials).await?,
                 };
 
                 let access_info = SnowflakeTableAccess {
@@ -616,6 +769,7 @@ impl<'a> SessionPlanner<'a> {
                     role_name: role_name.unwrap_or_default(),
                     schema_name: access_info.schema_name,
                     table_name: access_info.table_name,
+                    credentials,
                 }
                 .into()
             }
@@ -623,9 +777,11 @@ impl<'a> SessionPlanner<'a> {
                 let connection_string: String = m.remove_required("connection_string")?;
                 let schema_name: String = m.remove_required("schema")?;
                 let table_name: String = m.remove_required("table")?;
+                let tls = get_tls_options(m)?;
 
                 // Validate
-                let access = SqlServerAccess::try_new_from_ado_string(&connection_string)?;
+                let access = SqlServerAccess::try_new_from_ado_string(&connection_string)?
+                    .with_tls(tls.clone())?;
                 access
                     .validate_table_access(&schema_name, &table_name)
                     .await?;
@@ -634,6 +790,7 @@ impl<'a> SessionPlanner<'a> {
                     connection_string,
                     schema: schema_name,
                     table: table_name,
+                    tls,
                 }
                 .into()
             }
@@ -644,10 +801,12 @@ impl<'a> SessionPlanner<'a> {
                 // You can optionally provide a database name in clickhouse.
                 // This is similar to schema in other databases such as PG.
                 let database_name: Option<String> = m.remove_optional("database")?;
+                let tls = get_tls_options(m)?;
 
                 // Validate
                 let access =
-                    ClickhouseAccess::new_from_connection_string(connection_string.clone());
+                    ClickhouseAccess::new_from_connection_string(connection_string.clone())
+                        .with_tls(tls.clone());
 
                 let table_ref =
                     ClickhouseTableRef::new(database_name.as_ref(), table_name.as_str());
@@ -658,6 +817,7 @@ impl<'a> SessionPlanner<'a> {
                     connection_string,
                     table: table_name,
                     database: database_name,
+                    tls,
                 }
                 .into()
             }
@@ -698,6 +858,34 @@ impl<'a> SessionPlanner<'a> {
                     schema_sample_size: None,
                 })
             }
+            TableOptionsV0::DUCKDB => {
+                let location: String = m.remove_required("location")?;
+                let table: String = m.remove_required("table")?;
+                let mut storage_options = StorageOptions::try_from(m)?;
+                if let Some(creds) = creds_options {
+                    storage_options_with_credentials(&mut storage_options, creds);
+                }
+
+                let url: DatasourceUrl = location.as_str().try_into()?;
+                if url.datasource_url_type() == DatasourceUrlType::File {
+                    DuckDbAccess::new(url, None)
+                        .await?
+                        .validate_table_access(&DuckDbTableAccess::from_qualified_name(&table))
+                        .await
+                        .map_err(|e| PlanError::InvalidExternalTable {
+                            source: Box::new(e),
+                        })?;
+                }
+
+                TableOptionsV0::DuckDb(TableOptionsObjectStore {
+                    location,
+                    storage_options,
+                    name: table.into(),
+                    file_type: Some(TableOptionsV0::DUCKDB.to_string()),
+                    compression: None,
+                    schema_sample_size: None,
+                })
+            }
             TableOptionsV0::LOCAL => {
                 let location: String = m.remove_required("location")?;
 
@@ -726,7 +914,7 @@ impl<'a> SessionPlanner<'a> {
 
                 let access = Arc::new(GcsStoreAccess {
                     bucket: bucket.clone(),
-                    service_account_key: service_account_key.clone(),
+                    service_account_key: resolve_secrets(&service_account_key).await?,
                     opts: HashMap::new(),
                 });
                 let (file_type, compression) =
@@ -767,8 +955,8 @@ impl<'a> SessionPlanner<'a> {
                 let access = Arc::new(S3StoreAccess {
                     bucket: bucket.clone(),
                     region: Some(region.clone()),
-                    access_key_id: access_key_id.clone(),
-                    secret_access_key: secret_access_key.clone(),
+                    access_key_id: resolve_secrets(&access_key_id).await?,
+                    secret_access_key: resolve_secrets(&secret_access_key).await?,
                     opts: HashMap::new(),
                 });
                 let (file_type, compression) =
@@ -806,8 +994,8 @@ impl<'a> SessionPlanner<'a> {
 
                 let access = Arc::new(AzureStoreAccess {
                     container,
-                    account_name: Some(account_name.clone()),
-                    access_key: Some(access_key.clone()),
+                    account_name: Some(resolve_secrets(&account_name).await?),
+                    access_key: Some(resolve_secrets(&access_key).await?),
                     opts: HashMap::new(),
                 });
 
@@ -847,7 +1035,9 @@ impl<'a> SessionPlanner<'a> {
                 }
 
                 if datasource == TableOptionsV0::DELTA {
-                    let _table = load_table_direct(&location, storage_options.clone()).await?;
+                    let _table =
+                        load_table_direct(&location, resolve_secrets(&storage_options).await?)
+                            .await?;
 
                     TableOptionsV0::Delta(TableOptionsObjectStore {
                         location,
@@ -859,7 +1049,10 @@ impl<'a> SessionPlanner<'a> {
                     })
                 } else {
                     let url = DatasourceUrl::try_new(&location)?;
-                    let store = storage_options_into_object_store(&url, &storage_options)?;
+                    let store = storage_options_into_object_store(
+                        &url,
+                        &resolve_secrets(&storage_options).await?,
+                    )?;
                     let _table = IcebergTable::open(url, store).await?;
 
                     TableOptionsV0::Iceberg(TableOptionsObjectStore {
@@ -879,7 +1072,8 @@ impl<'a> SessionPlanner<'a> {
                     storage_options_with_credentials(&mut storage_options, creds);
                 }
                 // Validate that the table exists.
-                let _table = LanceTable::new(&location, storage_options.clone()).await?;
+                let _table =
+                    LanceTable::new(&location, resolve_secrets(&storage_options).await?).await?;
                 TableOptionsV0::Lance(TableOptionsObjectStore {
                     location,
                     storage_options,
@@ -891,6 +1085,10 @@ impl<'a> SessionPlanner<'a> {
             }
             TableOptionsV0::BSON => {
                 let location: String = m.remove_required("location")?;
+                // Inferred from the file extension when not provided.
+                let compression = m
+                    .remove_optional::<parser::options::CompressionTypeVariant>("compression")?
+                    .map(|c| convert::<_, CompressionTypeVariant>(c).to_string());
                 let mut storage_options = StorageOptions::try_from(m)?;
                 if let Some(creds) = creds_options {
                     storage_options_with_credentials(&mut storage_options, creds);
@@ -907,7 +1105,7 @@ impl<'a> SessionPlanner<'a> {
                     storage_options,
                     name: None,
                     file_type: None,
-                    compression: None,
+                    compression,
                     schema_sample_size,
                 })
             }
@@ -928,6 +1126,37 @@ impl<'a> SessionPlanner<'a> {
                     .map(|val| val.parse::<bool>().unwrap_or(true))
                     .unwrap_or_default();
 
+                let sheet_index = storage_options
+                    .inner
+                    .get("sheet_index")
+                    .map(|val| val.parse::<u64>())
+                    .transpose()?;
+
+                let all_sheets = storage_options
+                    .inner
+                    .get("all_sheets")
+                    .map(|val| {
+                        val.parse::<bool>().map_err(|e| {
+                            PlanError::String(format!("invalid value for 'all_sheets': {e}"))
+                        })
+                    })
+                    .transpose()?
+                    .unwrap_or_default();
+
+                let cell_range = storage_options.inner.get("range").map(|val| val.to_owned());
+
+                let header_row = storage_options
+                    .inner
+                    .get("header_row")
+                    .map(|val| val.parse::<u64>())
+                    .transpose()?
+                    .unwrap_or_default();
+
+                let column_types = storage_options
+                    .inner
+                    .get("column_types")
+                    .map(|val| val.to_owned());
+
                 if let DatasourceUrl::File(p) = DatasourceUrl::try_new(&location)? {
                     if !p.exists() {
                         return Err(PlanError::String(
@@ -943,6 +1172,11 @@ impl<'a> SessionPlanner<'a> {
                     compression: None,
                     sheet_name,
                     has_header,
+                    sheet_index,
+                    all_sheets,
+                    cell_range,
+                    header_row,
+                    column_types,
                 }
                 .into()
             }
@@ -1012,6 +1246,9 @@ impl<'a> SessionPlanner<'a> {
         let external_table_options = self
             .get_tbl_opts_from_v0(datasource.as_str(), m, creds_options, tunnel_options)
             .await?;
+        // Options are stored with secret references, connections are
+        // validated with the resolved secrets.
+        validate_secret_references(&external_table_options)?;
 
         let table_name = object_name_to_table_ref(stmt.name)?;
 
@@ -1037,11 +1274,57 @@ impl<'a> SessionPlanner<'a> {
             TunnelOptions::DEBUG => TunnelOptions::Debug(TunnelOptionsDebug {}),
             TunnelOptions::SSH => {
                 let connection_string = get_ssh_conn_str(m)?;
-                let ssh_key = SshKey::generate_random()?;
+
+                // Use the user's key if provided, otherwise generate one. The
+                // public key of generated keys is available in
+                // `glare_catalog.ssh_keys`.
+                //
+                // User provided keys may be used outside of GlareDB, so are
+                // only ever stored encrypted.
+                let (ssh_key, user_provided_key) =
+                    match m.remove_optional::<String>("private_key")? {
+                        Some(private_key) => {
+                            let passphrase: Option<String> =
+                                m.remove_optional("private_key_passphrase")?;
+                            let key = SshKey::from_openssh(&private_key, passphrase.as_deref())?;
+                            (key.to_encrypted_bytes()?, true)
+                        }
+                        None => (SshKey::generate_random()?.to_bytes()?, false),
+                    };
+
+                let jump_hosts = m
+                    .remove_optional::<Vec<String>>("jump_hosts")?
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|host| {
+                        let params: SshConnectionParameters = host.trim().parse()?;
+                        Ok(SshConnection::Parameters(params).connection_string())
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+
+                // Host keys may either be provided as `known_hosts` entries
+                // (covering jump hosts as well), or as a key pinned for the
+                // target host.
+                let mut known_hosts = Vec::new();
+                if let Some(entries) = m.remove_optional::<String>("known_hosts")? {
+                    known_hosts.push(validate_known_hosts(&entries)?);
+                }
+                if let Some(host_key) = m.remove_optional::<String>("host_key")? {
+                    let params: SshConnectionParameters = connection_string.parse()?;
+                    known_hosts.push(known_hosts_entry(&params, &host_key)?);
+                }
+                let known_hosts = if known_hosts.is_empty() {
+                    None
+                } else {
+                    Some(known_hosts.join("\n"))
+                };
 
                 TunnelOptions::Ssh(TunnelOptionsSsh {
                     connection_string,
-                    ssh_key: ssh_key.to_bytes()?,
+                    ssh_key,
+                    jump_hosts,
+                    known_hosts,
+                    user_provided_key,
                 })
             }
             other => return Err(internal!("unsupported tunnel: {other}")),
@@ -1103,8 +1386,28 @@ impl<'a> SessionPlanner<'a> {
                     org_id,
                 })
             }
+            CredentialsOptions::SNOWFLAKE => {
+                let private_key: Option<String> = m.remove_optional("private_key")?;
+                let oauth_token: Option<String> = m.remove_optional("oauth_token")?;
+
+                let creds = match (private_key, oauth_token) {
+                    (Some(private_key), None) => {
+                        CredentialsOptionsSnowflake::KeyPair { private_key }
+                    }
+                    (None, Some(token)) => CredentialsOptionsSnowflake::OAuth { token },
+                    _ => {
+                        return Err(PlanError::String(
+                            "expected one of 'private_key' or 'oauth_token'".to_string(),
+                        ))
+                    }
+                };
+                CredentialsOptions::Snowflake(creds)
+            }
             other => return Err(internal!("unsupported credentials provider: {other}")),
         };
+        // Secrets are resolved when the credentials are used, only check that
+        // the references are well formed.
+        validate_secret_references(&options)?;
 
         let name = normalize_ident(stmt.name);
 
@@ -1204,6 +1507,7 @@ impl<'a> SessionPlanner<'a> {
                 columns,
                 query,
                 temporary,
+                partition_by,
                 ..
             } => {
                 validate_object_name(&name)?;
@@ -1263,7 +1567,18 @@ impl<'a> SessionPlanner<'a> {
                     (None, arrow_cols)
                 };
 
+                let partition_by = match partition_by {
+                    Some(expr) => partition_columns(*expr, &arrow_cols)?,
+                    None => Vec::new(),
+                };
+
                 if temporary {
+                    if !partition_by.is_empty() {
+                        return Err(PlanError::UnsupportedFeature(
+                            "PARTITION BY with temporary tables",
+                        ));
+                    }
+
                     let table_name = match table_name {
                         TableReference::Bare { table } => table.into_owned(),
                         _ => return Err(internal!("cannot specify schema with temporary tables")),
@@ -1290,6 +1605,8 @@ impl<'a> SessionPlanner<'a> {
                     let create_table = CreateTable {
                         tbl_reference: self.ctx.resolve_table_ref(table_name)?,
                         schema: df_schema,
+                        partition_by,
+                        view_sql: None,
                         if_not_exists,
                         or_replace,
                         source,
@@ -1298,6 +1615,68 @@ impl<'a> SessionPlanner<'a> {
                 }
             }
 
+            // Materialized views, stored as native tables.
+            ast::Statement::CreateView {
+                or_replace,
+                materialized: true,
+                name,
+                columns,
+                query,
+                options,
+                ..
+            } => {
+                validate_object_name(&name)?;
+                let table_name = object_name_to_table_ref(name)?;
+
+                if !matches!(options, ast::CreateTableOptions::None) {
+                    return Err(PlanError::UnsupportedFeature("materialized view options"));
+                }
+
+                let query_string = query.to_string();
+
+                let mut planner = SqlQueryPlanner::new(&mut context_provider);
+                let source = planner.query_to_plan(*query).await?;
+
+                let columns: Vec<_> = columns
+                    .into_iter()
+                    .map(|col| normalize_ident(col.name))
+                    .collect();
+                if !columns.is_empty() && source.schema().fields().len() != columns.len() {
+                    return Err(PlanError::InvalidNumberOfAliasesForView {
+                        sql: query_string,
+                        aliases: columns,
+                    });
+                }
+
+                let mut columns = columns.into_iter();
+                let fields: Vec<_> = source
+                    .schema()
+                    .fields()
+                    .iter()
+                    .map(|df_field| {
+                        let field = df_field.field().as_ref().clone();
+                        match columns.next() {
+                            Some(name) => field.with_name(name),
+                            None => field,
+                        }
+                    })
+                    .collect();
+
+                let source = project_onto_fields(source, &fields)?;
+                let df_schema = Schema::new(fields).to_dfschema_ref()?;
+
+                Ok(CreateTable {
+                    tbl_reference: self.ctx.resolve_table_ref(table_name)?,
+                    if_not_exists: false,
+                    or_replace,
+                    schema: df_schema,
+                    partition_by: Vec::new(),
+                    view_sql: Some(query_string),
+                    source: Some(source),
+                }
+                .into_logical_plan())
+            }
+
             // Views
             ast::Statement::CreateView {
                 or_replace,
@@ -1398,31 +1777,12 @@ impl<'a> SessionPlanner<'a> {
                     ));
                 }
 
-                let state = self.ctx.df_ctx().state();
-                let mut ctx_provider = PartialContextProvider::new(self.ctx, &state)?;
-
-                let provider = ctx_provider.table_provider(table_name).await?;
-
-                let (runtime_preference, provider) = match (
-                    provider.preference,
-                    provider
-                        .provider
-                        .as_any()
-                        .downcast_ref::<StubRemoteTableProvider>(),
-                ) {
-                    (RuntimePreference::Remote, Some(stub)) => (
-                        RuntimePreference::Remote,
-                        ProviderReference::RemoteReference(stub.id()),
-                    ),
-                    _ => (
-                        RuntimePreference::Local,
-                        ProviderReference::Provider(provider.provider),
-                    ),
-                };
+                let (runtime_preference, provider) = self.write_target(table_name).await?;
 
                 Ok(Insert {
                     source,
                     provider,
+                    overwrite: false,
                     runtime_preference,
                 }
                 .into_logical_plan())
@@ -1623,18 +1983,14 @@ impl<'a> SessionPlanner<'a> {
                     None
                 };
 
-                let resolver = EntryResolver::from_context(self.ctx);
-                let ent = resolver
-                    .resolve_entry_from_reference(table_name)?
-                    .try_into_table_entry()?;
-                // External deletes not supported yet.
-                if ent.meta.external {
-                    return Err(PlanError::UnsupportedFeature("DELETE with external tables"));
-                }
+                let (target, runtime_preference) = self
+                    .dml_target(table_name, "DELETE with external tables")
+                    .await?;
 
                 Ok(Delete {
-                    table: ent,
+                    target,
                     where_expr,
+                    runtime_preference,
                 }
                 .into_logical_plan())
             }
@@ -1690,19 +2046,15 @@ impl<'a> SessionPlanner<'a> {
                     None
                 };
 
-                let resolver = EntryResolver::from_context(self.ctx);
-                let ent = resolver
-                    .resolve_entry_from_reference(table_name)?
-                    .try_into_table_entry()?;
-                // External updates not supported yet.
-                if ent.meta.external {
-                    return Err(PlanError::UnsupportedFeature("UPDATE with external tables"));
-                }
+                let (target, runtime_preference) = self
+                    .dml_target(table_name, "UPDATE with external tables")
+                    .await?;
 
                 Ok(Update {
-                    table: ent,
+                    target,
                     updates,
                     where_expr,
+                    runtime_preference,
                 }
                 .into_logical_plan())
             }
@@ -1762,6 +2114,18 @@ impl<'a> SessionPlanner<'a> {
 
         let plan = match stmt.action {
             AlterTunnelAction::RotateKeys => {
+                // Replacing a user's key with a generated one would silently
+                // break their access setup on the remote host.
+                let tunnel = self.ctx.get_session_catalog().resolve_tunnel(&name);
+                if let Some(TunnelOptions::Ssh(opts)) = tunnel.map(|ent| &ent.options) {
+                    if opts.user_provided_key {
+                        return Err(PlanError::InvalidTunnel {
+                            tunnel: name,
+                            reason: "keys provided by the user can't be rotated, recreate the tunnel with a new private key".to_string(),
+                        });
+                    }
+                }
+
                 let new_ssh_key = SshKey::generate_random()?;
                 let new_ssh_key = new_ssh_key.to_bytes()?;
                 AlterTunnelRotateKeys {
@@ -1818,6 +2182,259 @@ impl<'a> SessionPlanner<'a> {
         .into_logical_plan())
     }
 
+    fn plan_optimize_table(&self, stmt: OptimizeTableStmt) -> Result<LogicalPlan> {
+        validate_object_name(&stmt.name)?;
+        let table_name = object_name_to_table_ref(stmt.name)?;
+
+        let resolver = EntryResolver::from_context(self.ctx);
+        let ent = resolver
+            .resolve_entry_from_reference(table_name)?
+            .try_into_table_entry()?;
+
+        let opts = match &ent.options {
+            TableOptionsV0::Internal(opts) if !ent.meta.is_temp && !ent.meta.builtin => opts,
+            _ => {
+                return Err(PlanError::UnsupportedFeature(
+                    "OPTIMIZE with external, temporary or builtin tables",
+                ))
+            }
+        };
+
+        let zorder_by: Vec<_> = stmt.zorder_by.into_iter().map(normalize_ident).collect();
+        validate_column_names(&zorder_by, opts.columns.iter().map(|c| c.name.as_str()))?;
+        if let Some(col) = zorder_by.iter().find(|col| opts.partition_by.contains(col)) {
+            return Err(PlanError::String(format!(
+                "cannot ZORDER BY partition column '{col}'"
+            )));
+        }
+
+        Ok(OptimizeTable {
+            table: ent,
+            zorder_by,
+        }
+        .into_logical_plan())
+    }
+
+    async fn plan_refresh_materialized_view(
+        &self,
+        stmt: RefreshMaterializedViewStmt,
+    ) -> Result<LogicalPlan> {
+        validate_object_name(&stmt.name)?;
+        let table_name = object_name_to_table_ref(stmt.name)?;
+
+        let resolver = EntryResolver::from_context(self.ctx);
+        let ent = resolver
+            .resolve_entry_from_reference(table_name.clone())?
+            .try_into_table_entry()?;
+
+        let (opts, sql) = match &ent.options {
+            TableOptionsV0::Internal(opts) => match &opts.view_sql {
+                Some(sql) => (opts, sql),
+                None => return Err(PlanError::NotAMaterializedView(ent.meta.name)),
+            },
+            _ => return Err(PlanError::NotAMaterializedView(ent.meta.name)),
+        };
+
+        let query = match parser::parse_sql(sql)?.pop_front() {
+            Some(StatementWithExtensions::Statement(ast::Statement::Query(q))) => *q,
+            _ => return Err(internal!("invalid materialized view query: {sql}")),
+        };
+
+        let state = self.ctx.df_ctx().state();
+        let mut context_provider = PartialContextProvider::new(self.ctx, &state)?;
+        let mut planner = SqlQueryPlanner::new(&mut context_provider);
+        let source = planner.query_to_plan(query).await?;
+
+        let fields: Vec<_> = opts
+            .columns
+            .iter()
+            .map(|col| Field::new(&col.name, col.arrow_type.clone(), col.nullable))
+            .collect();
+        if source.schema().fields().len() != fields.len() {
+            return Err(PlanError::String(format!(
+                "query for materialized view '{}' returns {} columns, expected {}",
+                ent.meta.name,
+                source.schema().fields().len(),
+                fields.len(),
+            )));
+        }
+        let source = project_onto_fields(source, &fields)?;
+
+        // A full refresh replaces the contents of the view. An incremental
+        // refresh only appends rows where the column is greater than the
+        // current max value of the column in the view (the watermark).
+        //
+        // There's no key to merge rows on, a row that was updated with a newer
+        // value would be appended again next to its previous version. So
+        // incremental refreshes require the source to be declared append-only.
+        let (source, overwrite) = match stmt.incremental_column {
+            Some(column) => {
+                let column = normalize_ident(column);
+                validate_column_names(
+                    std::slice::from_ref(&column),
+                    fields.iter().map(|f| f.name().as_str()),
+                )?;
+
+                if !stmt.append_only {
+                    return Err(PlanError::String(format!(
+                        "incremental refresh of materialized view '{}' requires an append-only source, \
+                         use 'INCREMENTALLY USING {column} APPEND ONLY' if rows are never updated or deleted, \
+                         or a full refresh otherwise",
+                        ent.meta.name,
+                    )));
+                }
+
+                let view_source = context_provider
+                    .get_table_source(table_name.clone())
+                    .await?;
+                let watermark = LogicalPlanBuilder::scan(table_name.clone(), view_source, None)?
+                    .aggregate(
+                        Vec::<Expr>::new(),
+                        vec![max(Expr::Column(Column::new_unqualified(&column)))],
+                    )?
+                    .build()?;
+                let watermark = Arc::new(watermark);
+
+                let source = LogicalPlanBuilder::from(source)
+                    .filter(
+                        scalar_subquery(watermark.clone())
+                            .is_null()
+                            .or(Expr::Column(Column::new_unqualified(column))
+                                .gt(scalar_subquery(watermark))),
+                    )?
+                    .build()?;
+
+                (source, false)
+            }
+            None => (source, true),
+        };
+
+        let (runtime_preference, provider) = self.write_target(table_name).await?;
+
+        Ok(Insert {
+            source,
+            provider,
+            overwrite,
+            runtime_preference,
+        }
+        .into_logical_plan())
+    }
+
+    fn plan_drop_materialized_view(&self, stmt: DropMaterializedViewStmt) -> Result<LogicalPlan> {
+        let resolver = EntryResolver::from_context(self.ctx);
+
+        let mut refs = Vec::with_capacity(stmt.names.len());
+        for name in stmt.names.into_iter() {
+            validate_object_name(&name)?;
+            let r = object_name_to_table_ref(name)?;
+
+            // Make sure we're not dropping a regular table. Missing entries
+            // are handled when executing the drop.
+            if let Ok(ResolvedEntry::Entry(CatalogEntry::Table(ent))) =
+                resolver.resolve_entry_from_reference(r.clone())
+            {
+                if !matches!(&ent.options, TableOptionsV0::Internal(opts) if opts.view_sql.is_some())
+                {
+                    return Err(PlanError::NotAMaterializedView(ent.meta.name));
+                }
+            }
+
+            refs.push(self.ctx.resolve_table_ref(r)?);
+        }
+
+        Ok(DropTables {
+            if_exists: stmt.if_exists,
+            tbl_references: refs,
+        }
+        .into_logical_plan())
+    }
+
+    /// Get the table to update or delete rows from along with where the
+    /// statement should run.
+    ///
+    /// Errors with `unsupported` if rows in the table can't be modified.
+    async fn dml_target(
+        &self,
+        table_name: OwnedTableReference,
+        unsupported: &'static str,
+    ) -> Result<(DmlTarget, RuntimePreference)> {
+        let resolver = EntryResolver::from_context(self.ctx);
+        let datasource = match resolver.resolve_entry_from_reference(table_name.clone())? {
+            // Native tables are always modified on the remote side.
+            ResolvedEntry::Entry(CatalogEntry::Table(ent)) if !ent.meta.external => {
+                return Ok((DmlTarget::Native(ent), RuntimePreference::Remote));
+            }
+            ResolvedEntry::Entry(CatalogEntry::Table(ent)) => match ent.options {
+                TableOptionsV0::Sqlite(_) => Some(DmlDatasource::Sqlite),
+                TableOptionsV0::Postgres(_) => Some(DmlDatasource::Postgres),
+                TableOptionsV0::Mysql(_) => Some(DmlDatasource::Mysql),
+                TableOptionsV0::SqlServer(_) => Some(DmlDatasource::SqlServer),
+                _ => None,
+            },
+            ResolvedEntry::NeedsExternalResolution { db_ent, .. } => match db_ent.options {
+                DatabaseOptions::Sqlite(_) => Some(DmlDatasource::Sqlite),
+                DatabaseOptions::Postgres(_) => Some(DmlDatasource::Postgres),
+                DatabaseOptions::Mysql(_) => Some(DmlDatasource::Mysql),
+                DatabaseOptions::SqlServer(_) => Some(DmlDatasource::SqlServer),
+                _ => None,
+            },
+            ResolvedEntry::Entry(ent) => {
+                return Err(PlanError::String(format!(
+                    "{} is not a table",
+                    ent.get_meta().name
+                )))
+            }
+        };
+        let datasource = datasource.ok_or(PlanError::UnsupportedFeature(unsupported))?;
+
+        let access_mode = self
+            .get_access_mode(table_name.clone())?
+            .unwrap_or(SourceAccessMode::ReadOnly);
+        if !access_mode.has_write_access() {
+            return Err(PlanError::ObjectNotAllowedToWriteInto(table_name));
+        }
+
+        let (runtime_preference, provider) = self.write_target(table_name).await?;
+        Ok((
+            DmlTarget::External {
+                datasource,
+                provider,
+            },
+            runtime_preference,
+        ))
+    }
+
+    /// Get the provider to write to for a table along with where the write
+    /// should run.
+    async fn write_target(
+        &self,
+        table_name: OwnedTableReference,
+    ) -> Result<(RuntimePreference, ProviderReference)> {
+        let state = self.ctx.df_ctx().state();
+        let mut ctx_provider = PartialContextProvider::new(self.ctx, &state)?;
+
+        let provider = ctx_provider.table_provider(table_name).await?;
+
+        Ok(
+            match (
+                provider.preference,
+                provider
+                    .provider
+                    .as_any()
+                    .downcast_ref::<StubRemoteTableProvider>(),
+            ) {
+                (RuntimePreference::Remote, Some(stub)) => (
+                    RuntimePreference::Remote,
+                    ProviderReference::RemoteReference(stub.id()),
+                ),
+                _ => (
+                    RuntimePreference::Local,
+                    ProviderReference::Provider(provider.provider),
+                ),
+            },
+        )
+    }
+
     async fn plan_copy_to(&self, stmt: CopyToStmt) -> Result<LogicalPlan> {
         let query = match stmt.source {
             CopyToSource::Table(table) => {
@@ -1890,6 +2507,7 @@ impl<'a> SessionPlanner<'a> {
                 },
             )?;
         }
+        let creds_options = resolve_secrets(&creds_options).await?;
 
         fn get_bucket(
             m: &mut StatementOptions,
@@ -1996,11 +2614,25 @@ impl<'a> SessionPlanner<'a> {
 
         let loc = dest.location();
         let loc = Path::new(loc);
-        let ext = loc
+        let mut ext = loc
             .extension()
             .and_then(|ext| ext.to_str())
             .map(|ext| ext.to_lowercase());
 
+        // Compressed outputs (e.g. `data.csv.gz`) use the extension before the
+        // compression extension to determine the format.
+        let ext_compression = ext
+            .as_deref()
+            .and_then(|ext| ext.parse::<CompressionTypeVariant>().ok())
+            .filter(|compression| compression.is_compressed());
+        if ext_compression.is_some() {
+            ext = loc
+                .file_stem()
+                .and_then(|stem| Path::new(stem).extension())
+                .and_then(|ext| ext.to_str())
+                .map(|ext| ext.to_lowercase());
+        }
+
         let format = match stmt
             .format
             .as_ref()
@@ -2015,9 +2647,11 @@ impl<'a> SessionPlanner<'a> {
             Some(CopyToFormatOptions::CSV) => {
                 let delim = m.remove_optional::<char>("delimeter")?.unwrap_or(',');
                 let header = m.remove_optional::<bool>("header")?.unwrap_or(true);
+                let compression = copy_to_compression(&mut m, ext_compression)?;
                 CopyToFormatOptions::Csv(CopyToFormatOptionsCsv {
                     delim: delim as u8,
                     header,
+                    compression,
                 })
             }
             Some(CopyToFormatOptions::PARQUET) => {
@@ -2028,10 +2662,17 @@ impl<'a> SessionPlanner<'a> {
             }
             Some(CopyToFormatOptions::JSON) => {
                 let array = m.remove_optional::<bool>("array")?.unwrap_or(false);
-                CopyToFormatOptions::Json(CopyToFormatOptionsJson { array })
+                let compression = copy_to_compression(&mut m, ext_compression)?;
+                CopyToFormatOptions::Json(CopyToFormatOptionsJson { array, compression })
             }
             Some(CopyToFormatOptions::BSON) => {
-                CopyToFormatOptions::Bson(CopyToFormatOptionsBson {})
+                let compression = copy_to_compression(&mut m, ext_compression)?;
+                CopyToFormatOptions::Bson(CopyToFormatOptionsBson { compression })
+            }
+            Some(CopyToFormatOptions::EXCEL) => {
+                let sheet_name = m.remove_optional::<String>("sheet_name")?;
+                let header = m.remove_optional::<bool>("header")?.unwrap_or(true);
+                CopyToFormatOptions::Excel(CopyToFormatOptionsExcel { sheet_name, header })
             }
             Some(CopyToFormatOptions::LANCE) => {
                 CopyToFormatOptions::Lance(CopyToFormatOptionsLance {
@@ -2213,6 +2854,86 @@ async fn validate_and_get_file_type_and_compression(
     Ok((file_type, compression))
 }
 
+/// Get the compression to use for COPY TO outputs, falling back to the
+/// compression inferred from the file extension.
+fn copy_to_compression(
+    m: &mut StatementOptions,
+    inferred: Option<CompressionTypeVariant>,
+) -> Result<Option<String>> {
+    let compression = m
+        .remove_optional::<parser::options::CompressionTypeVariant>("compression")?
+        .map(convert::<_, CompressionTypeVariant>)
+        .or(inferred)
+        .filter(|compression| compression.is_compressed())
+        .map(|compression| compression.to_string());
+    Ok(compression)
+}
+
+/// Get the column names from a `PARTITION BY` expression, accepting either a
+/// single column or a parenthesized list of columns.
+fn partition_columns(expr: ast::Expr, fields: &[Field]) -> Result<Vec<String>> {
+    let exprs = match expr {
+        ast::Expr::Tuple(exprs) => exprs,
+        ast::Expr::Nested(expr) => vec![*expr],
+        expr => vec![expr],
+    };
+
+    let mut columns = Vec::with_capacity(exprs.len());
+    for expr in exprs {
+        let ident = match expr {
+            ast::Expr::Identifier(ident) => ident,
+            other => {
+                return Err(PlanError::String(format!(
+                    "PARTITION BY only supports column names, got: {other}"
+                )))
+            }
+        };
+        validate_ident(&ident)?;
+        columns.push(normalize_ident(ident));
+    }
+
+    validate_column_names(&columns, fields.iter().map(|f| f.name().as_str()))?;
+    Ok(columns)
+}
+
+/// Project the output of `source` onto `fields` by position, casting and
+/// renaming columns as needed.
+fn project_onto_fields(source: DfLogicalPlan, fields: &[Field]) -> Result<DfLogicalPlan> {
+    let exprs: Vec<_> = fields
+        .iter()
+        .zip(source.schema().fields().iter())
+        .map(|(field, df_field)| {
+            cast(
+                Expr::Column(df_field.qualified_column()),
+                field.data_type().clone(),
+            )
+            .alias(field.name())
+        })
+        .collect();
+
+    Ok(LogicalPlanBuilder::from(source).project(exprs)?.build()?)
+}
+
+/// Check that all `columns` exist in the table and are only listed once.
+fn validate_column_names<'a>(
+    columns: &[String],
+    table_columns: impl Iterator<Item = &'a str> + Clone,
+) -> Result<()> {
+    for (idx, column) in columns.iter().enumerate() {
+        if !table_columns.clone().any(|name| name == column) {
+            return Err(PlanError::String(format!(
+                "column '{column}' does not exist in table"
+            )));
+        }
+        if columns[..idx].contains(column) {
+            return Err(PlanError::String(format!(
+                "column '{column}' specified more than once"
+            )));
+        }
+    }
+    Ok(())
+}
+
 /// Resolves an ident (unquoted -> lowercase else case sensitive).
 fn normalize_ident(ident: Ident) -> String {
     let normalizer = IdentNormalizer::new(/* normalize = */ true);
@@ -2421,6 +3142,28 @@ fn get_mysql_conn_str(m: &mut StatementOptions) -> Result<String> {
     Ok(conn.connection_string())
 }
 
+fn get_oracle_conn_str(m: &mut StatementOptions) -> Result<String> {
+    let conn = match m.remove_optional("connection_string")? {
+        Some(conn_str) => OracleDbConnection::ConnectionString(conn_str),
+        None => {
+            let host = m.remove_required("host")?;
+            let port = m.remove_optional("port")?;
+            let user = m.remove_required("user")?;
+            let password = m.remove_optional("password")?;
+            let service_name = m.remove_required("service_name")?;
+            OracleDbConnection::Parameters {
+                host,
+                port,
+                user,
+                password,
+                service_name,
+            }
+        }
+    };
+
+    Ok(conn.connection_string())
+}
+
 fn get_mongodb_conn_str(m: &mut StatementOptions) -> Result<String> {
     let conn = match m.remove_optional("connection_string")? {
         Some(conn_str) => MongoDbConnection::ConnectionString(conn_str),
@@ -2443,6 +3186,27 @@ fn get_mongodb_conn_str(m: &mut StatementOptions) -> Result<String> {
     Ok(conn.connection_string())
 }
 
+/// Get the options for how many documents to read when inferring the schema
+/// of a MongoDB collection.
+fn get_mongodb_sample_options(m: &mut StatementOptions) -> Result<(Option<u64>, bool)> {
+    let sample_size: Option<usize> = m.remove_optional("sample_size")?;
+    let full_scan: Option<bool> = m.remove_optional("full_scan")?;
+    Ok((sample_size.map(|n| n as u64), full_scan.unwrap_or(false)))
+}
+
+/// Get the TLS options for a connection, checking that any referenced
+/// certificates can be loaded.
+fn get_tls_options(m: &mut StatementOptions) -> Result<TlsOptions> {
+    let tls = TlsOptions {
+        ssl_mode: m.remove_optional("ssl_mode")?,
+        ca_cert: m.remove_optional("ca_cert")?,
+        client_cert: m.remove_optional("client_cert")?,
+        client_key: m.remove_optional("client_key")?,
+    };
+    validate_tls_options(&tls)?;
+    Ok(tls)
+}
+
 fn get_ssh_conn_str(m: &mut StatementOptions) -> Result<String> {
     let conn = match m.remove_optional("connection_string")? {
         Some(conn_str) => SshConnection::ConnectionString(conn_str),
@@ -2456,14 +3220,37 @@ fn get_ssh_conn_str(m: &mut StatementOptions) -> Result<String> {
     Ok(conn.connection_string())
 }
 
+/// Get the snowflake specific credentials, if any.
+///
+/// Credentials of other providers are rejected during validation.
+fn snowflake_credentials(creds: Option<CredentialsOptions>) -> Option<CredentialsOptionsSnowflake> {
+    match creds {
+        Some(CredentialsOptions::Snowflake(creds)) => Some(creds),
+        _ => None,
+    }
+}
+
+/// Get the password for connecting to snowflake. The password is only required
+/// if no key pair or OAuth credentials are provided.
+fn get_snowflake_password(
+    m: &mut StatementOptions,
+    credentials: &Option<CredentialsOptionsSnowflake>,
+) -> Result<String> {
+    Ok(match credentials {
+        Some(_) => m.remove_optional("password")?.unwrap_or_default(),
+        None => m.remove_required("password")?,
+    })
+}
+
 /// Update storage options with the provided credentials object contents
 fn storage_options_with_credentials(
     storage_options: &mut StorageOptions,
     creds: CredentialsOptions,
 ) {
     match creds {
-        CredentialsOptions::Debug(_) => {}  // Nothing to do here
+        CredentialsOptions::Debug(_) => {}     // Nothing to do here
         CredentialsOptions::OpenAI(_) => {} // Nothing to do here. OpenAI is not a storage backend
+        CredentialsOptions::Snowflake(_) => {} // Nothing to do here. Snowflake is not a storage backend
         CredentialsOptions::Gcp(creds) => {
             storage_options.inner.insert(
                 GoogleConfigKey::ServiceAccountKey.as_ref().to_string(),
@@ -43,6 +43,7 @@ use crate::planner::logical_plan::{
     DropTunnel,
     DropViews,
     Insert,
+    OptimizeTable,
     SetVariable,
     ShowVariable,
     Update,
@@ -71,6 +72,7 @@ use crate::planner::physical_plan::drop_temp_tables::DropTempTablesExec;
 use crate::planner::physical_plan::drop_tunnel::DropTunnelExec;
 use crate::planner::physical_plan::drop_views::DropViewsExec;
 use crate::planner::physical_plan::insert::InsertExec;
+use crate::planner::physical_plan::optimize_table::OptimizeTableExec;
 use crate::planner::physical_plan::remote_exec::RemoteExecutionExec;
 use crate::planner::physical_plan::remote_scan::ProviderReference;
 use crate::planner::physical_plan::send_recv::SendRecvJoinExec;
@@ -181,6 +183,8 @@ impl ExtensionPlanner for DDLExtensionPlanner {
                     if_not_exists: lp.if_not_exists,
                     or_replace: lp.or_replace,
                     arrow_schema: Arc::new(lp.schema.as_ref().into()),
+                    partition_by: lp.partition_by.clone(),
+                    view_sql: lp.view_sql.clone(),
                     source: physical_inputs.first().cloned(),
                 };
                 RuntimeGroupExec::new(RuntimePreference::Remote, Arc::new(exec))
@@ -329,6 +333,14 @@ impl ExtensionPlanner for DDLExtensionPlanner {
                 };
                 RuntimeGroupExec::new(RuntimePreference::Remote, Arc::new(exec))
             }
+            ExtensionType::OptimizeTable => {
+                let lp = require_downcast_lp::<OptimizeTable>(node);
+                let exec = OptimizeTableExec {
+                    table: lp.table.clone(),
+                    zorder_by: lp.zorder_by.clone(),
+                };
+                RuntimeGroupExec::new(RuntimePreference::Remote, Arc::new(exec))
+            }
             ExtensionType::SetVariable => {
                 let lp = require_downcast_lp::<SetVariable>(node);
                 let exec = SetVarExec {
@@ -362,11 +374,11 @@ impl ExtensionPlanner for DDLExtensionPlanner {
             ExtensionType::Update => {
                 let lp = require_downcast_lp::<Update>(node);
                 let exec = UpdateExec {
-                    table: lp.table.clone(),
+                    target: lp.target.clone(),
                     updates: lp.updates.clone(),
                     where_expr: lp.where_expr.clone(),
                 };
-                RuntimeGroupExec::new(RuntimePreference::Remote, Arc::new(exec))
+                RuntimeGroupExec::new(lp.runtime_preference, Arc::new(exec))
             }
             ExtensionType::Insert => {
                 let lp = require_downcast_lp::<Insert>(node);
@@ -380,6 +392,7 @@ impl ExtensionPlanner for DDLExtensionPlanner {
                 };
                 let exec = Arc::new(InsertExec {
                     provider,
+                    overwrite: lp.overwrite,
                     source: Arc::new(WriteOnlyDataSourceMetricsExecAdapter::new(
                         physical_inputs.first().unwrap().clone(),
                     )),
@@ -389,10 +402,10 @@ impl ExtensionPlanner for DDLExtensionPlanner {
             ExtensionType::Delete => {
                 let lp = require_downcast_lp::<Delete>(node);
                 let exec = DeleteExec {
-                    table: lp.table.clone(),
+                    target: lp.target.clone(),
                     where_expr: lp.where_expr.clone(),
                 };
-                RuntimeGroupExec::new(RuntimePreference::Remote, Arc::new(exec))
+                RuntimeGroupExec::new(lp.runtime_preference, Arc::new(exec))
             }
         };
 
@@ -117,6 +117,8 @@ pub enum ExecutionResult {
     DropTunnel,
     /// Credentials are dropped.
     DropCredentials,
+    /// A table's data files were optimized.
+    OptimizeTable,
 }
 // this just makes the `prepare_statement` method a bit more ergonomic.
 pub struct PrepareStatementArg {
@@ -237,6 +239,7 @@ impl ExecutionResult {
             ExecutionResult::DropDatabase => "drop_database",
             ExecutionResult::DropTunnel => "drop_tunnel",
             ExecutionResult::DropCredentials => "drop_credentials",
+            ExecutionResult::OptimizeTable => "optimize_table",
         }
     }
 
@@ -298,6 +301,7 @@ impl ExecutionResult {
             "drop_database" => ExecutionResult::DropDatabase,
             "drop_tunnel" => ExecutionResult::DropTunnel,
             "drop_credentials" => ExecutionResult::DropCredentials,
+            "optimize_table" => ExecutionResult::OptimizeTable,
             _ => return None,
         })
     }
@@ -355,6 +359,7 @@ impl fmt::Display for ExecutionResult {
             ExecutionResult::DropDatabase => write!(f, "Database(s) dropped"),
             ExecutionResult::DropTunnel => write!(f, "Tunnel(s) dropped"),
             ExecutionResult::DropCredentials => write!(f, "Credentials dropped"),
+            ExecutionResult::OptimizeTable => write!(f, "Table optimized"),
         }
     }
 }
@@ -732,6 +737,8 @@ impl Session {
                             metrics.elapsed_compute_ns = Some(agg_metrics.elapsed_compute_ns);
                             metrics.bytes_read = Some(agg_metrics.bytes_read);
                             metrics.bytes_written = agg_metrics.bytes_written;
+                            metrics.row_groups_pruned = Some(agg_metrics.row_groups_pruned);
+                            metrics.pages_pruned = Some(agg_metrics.pages_pruned);
                             self.ctx.get_metrics_handler().push_metric(metrics);
                             write_result
                         }
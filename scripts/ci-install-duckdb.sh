@@ -0,0 +1,14 @@
+#!/usr/bin/env bash
+
+# Script for installing the duckdb cli in CI. This script is meant to be
+# `source`d.
+#
+# Usage: source ./script/ci-install-duckdb.sh
+
+curl -sSL -o duckdb_cli.zip \
+     https://github.com/duckdb/duckdb/releases/download/v0.10.0/duckdb_cli-linux-amd64.zip
+mkdir -p ${HOME}/bin
+unzip -qo duckdb_cli.zip -d ${HOME}/bin
+rm duckdb_cli.zip
+
+export PATH=$HOME/bin:$PATH
@@ -0,0 +1,16 @@
+#!/usr/bin/env bash
+
+# Script for installing the Oracle Instant Client in CI. The oracle datasource
+# loads the client libraries at runtime. This script is meant to be `source`d.
+#
+# Usage: source ./script/ci-install-oracle-client.sh
+
+curl -sSL -o instantclient.zip \
+     https://download.oracle.com/otn_software/linux/instantclient/instantclient-basiclite-linuxx64.zip
+mkdir -p ${HOME}/oracle
+unzip -qo instantclient.zip -d ${HOME}/oracle
+rm instantclient.zip
+
+sudo apt-get install -y libaio1 > /dev/null
+
+export LD_LIBRARY_PATH=$(echo ${HOME}/oracle/instantclient_*):$LD_LIBRARY_PATH
@@ -86,6 +86,13 @@ clickhouse client \
 clickhouse client \
   --query "INSERT INTO datatypes(_id) VALUES (2)"
 
+# Nested datatypes
+clickhouse client \
+  --query "INSERT INTO nested_datatypes VALUES
+      (1, [1, 2, 3], ['a', NULL], {'k1': 1, 'k2': 2}, (1, 'one'), (2, 'two'), [(1, 'x1'), (2, 'x2')], 'low', 'card', 'red', ['l1', 'l2']),
+      (2, [], [], {}, (3, 'three'), (4, NULL), [], 'low', NULL, 'blue, light', []),
+      (3, [4], [NULL], {'k3': 3}, (5, 'five'), (6, 'six'), [(3, 'x3')], 'high', 'card', 'red', ['l1'])"
+
 clickhouse client \
     --query="INSERT INTO bikeshare_stations FORMAT CSVWithNames" < ./testdata/sqllogictests_datasources_common/data/bikeshare_stations.csv
 clickhouse client \
@@ -6,4 +6,9 @@ print("--- insert_test fixture ---");
 db.insert_test.drop();
 db.insert_test.insertOne({"a":0,"b":0,"c":0});
 printjson(db.insert_test.find());
+print("--- sparse_test fixture ---");
+db.sparse_test.drop();
+db.sparse_test.insertMany(Array.from({ length: 1000 }, (_, i) => ({ i: i })));
+db.sparse_test.insertOne({ i: 1000, rare: "found" });
+printjson(db.sparse_test.countDocuments());
 print("---");
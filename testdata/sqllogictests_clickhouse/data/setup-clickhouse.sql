@@ -35,6 +35,25 @@ CREATE OR REPLACE TABLE datatypes (
 ) ENGINE MergeTree
   ORDER BY _id;
 
+-- nested_datatypes table for testing arrays, maps, tuples, nested, enum and low
+-- cardinality columns. Nested columns are kept as arrays of tuples.
+SET flatten_nested = 0;
+
+CREATE OR REPLACE TABLE nested_datatypes (
+    _id Int32,
+    c1  Array(Int32),
+    c2  Array(Nullable(String)),
+    c3  Map(String, Int64),
+    c4  Tuple(Int32, String),
+    c5  Tuple(a Int32, b Nullable(String)),
+    c6  Nested(x Int32, y String),
+    c7  LowCardinality(String),
+    c8  LowCardinality(Nullable(String)),
+    c9  Enum8('red' = 1, 'green' = 2, 'blue, light' = 3),
+    c10 Array(LowCardinality(String))
+) ENGINE MergeTree
+  ORDER BY _id;
+
 -- bikeshare_stations table for testing datasources.
 CREATE OR REPLACE TABLE bikeshare_stations (
     station_id        Int32,
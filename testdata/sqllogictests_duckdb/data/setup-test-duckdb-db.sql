@@ -0,0 +1,92 @@
+-- Create datatypes table
+CREATE TABLE IF NOT EXISTS datatypes (
+    -- Booleans
+    c1 BOOLEAN,
+
+    -- Integers
+    c2 TINYINT,
+    c3 SMALLINT,
+    c4 INTEGER,
+    c5 BIGINT,
+    c6 UBIGINT,
+
+    -- Floats and decimals
+    c7 FLOAT,
+    c8 DOUBLE,
+    c9 DECIMAL(10, 2),
+
+    -- Strings
+    c10 VARCHAR,
+
+    -- Binary
+    c11 BLOB,
+
+    -- Dates and times
+    c12 DATE,
+    c13 TIME,
+    c14 TIMESTAMP,
+    c15 TIMESTAMPTZ
+);
+
+SET TimeZone = 'UTC';
+
+-- Insert data into datatypes
+INSERT INTO datatypes VALUES (
+    -- Booleans
+    true,
+
+    -- Integers
+    -1,
+    -12,
+    -1234,
+    -12345678,
+    12345678,
+
+    -- Floats and decimals
+    1.25,
+    -34.625,
+    123.45,
+
+    -- Strings
+    'abc',
+
+    -- Binary
+    'abc'::BLOB,
+
+    -- Dates and times
+    '1999-09-30',
+    '16:32:24.123',
+    '1999-09-30 16:32:34.123456',
+    '1999-09-30 16:32:34.123456+00'
+);
+
+-- Insert nulls
+INSERT INTO datatypes (c1) VALUES (NULL);
+
+-- Create bikeshare_stations table
+CREATE TABLE IF NOT EXISTS bikeshare_stations (
+    station_id        INTEGER,
+    name              VARCHAR,
+    status            VARCHAR,
+    address           VARCHAR,
+    alternate_name    VARCHAR,
+    city_asset_number INTEGER,
+    property_type     VARCHAR,
+    number_of_docks   INTEGER,
+    power_type        VARCHAR,
+    footprint_length  INTEGER,
+    footprint_width   DOUBLE,
+    notes             VARCHAR,
+    council_district  INTEGER,
+    modified_date     TIMESTAMP
+);
+
+COPY bikeshare_stations FROM 'testdata/sqllogictests_datasources_common/data/bikeshare_stations.csv' (HEADER);
+
+-- Tables outside of the default ("main") schema.
+CREATE SCHEMA IF NOT EXISTS analytics;
+
+CREATE TABLE IF NOT EXISTS analytics.station_status AS
+    SELECT status, count(*) AS num_stations
+    FROM bikeshare_stations
+    GROUP BY status;
@@ -114,6 +114,15 @@ VALUES (
     DEFAULT
 );
 
+-- dml_test table for testing updates and deletes. Rows are inserted by the
+-- tests.
+CREATE TABLE IF NOT EXISTS glaredb_test.dml_test (
+    id         INT,
+    name       TEXT,
+    amount     INT,
+    created_at DATETIME
+);
+
 -- Enable loading local data onto server.
 SET @@GLOBAL.local_infile = 1;
 
@@ -0,0 +1,48 @@
+-- Tables used by the oracle sqllogictests. Run as the 'glaredb_test' user.
+
+CREATE TABLE datatypes (
+    c1  NUMBER(10),
+    c2  NUMBER(38, 2),
+    c3  NUMBER,
+    c4  BINARY_FLOAT,
+    c5  BINARY_DOUBLE,
+    c6  VARCHAR2(100),
+    c7  CHAR(3),
+    c8  CLOB,
+    c9  RAW(16),
+    c10 DATE,
+    c11 TIMESTAMP,
+    c12 TIMESTAMP WITH TIME ZONE
+);
+
+INSERT INTO datatypes VALUES (
+    1,
+    123.45,
+    6.5,
+    1.5,
+    -2.25,
+    'abc',
+    'def',
+    'a longer piece of text',
+    HEXTORAW('DEADBEEF'),
+    DATE '1999-09-30',
+    TIMESTAMP '1999-09-30 16:32:34.123456',
+    TIMESTAMP '1999-09-30 16:32:34.123456 +05:30'
+);
+
+INSERT INTO datatypes (c1) VALUES (2);
+
+CREATE TABLE numbers (
+    id   NUMBER(10) NOT NULL,
+    name VARCHAR2(20)
+);
+
+INSERT INTO numbers VALUES (1, 'one');
+INSERT INTO numbers VALUES (2, 'two');
+INSERT INTO numbers VALUES (3, 'three');
+INSERT INTO numbers VALUES (4, 'four');
+INSERT INTO numbers VALUES (5, 'five');
+
+CREATE VIEW numbers_view AS SELECT * FROM numbers WHERE id > 2;
+
+COMMIT;
@@ -59,6 +59,30 @@ INSERT INTO datatypes (
     '0001-01-01 00:00:00 UTC'
 );
 
+-- dml_test table for testing updates and deletes. Rows are inserted by the
+-- tests.
+CREATE TABLE IF NOT EXISTS dml_test (
+    id         INT,
+    name       TEXT,
+    amount     INT,
+    created_at TIMESTAMP
+);
+
+-- Table with identifiers that need quoting for testing updates and deletes.
+CREATE TABLE IF NOT EXISTS "DML Mixed Case" (
+    "Id"    INT,
+    "Value" TEXT
+);
+
+-- Table with identifiers that need quoting for testing partitioned scans.
+CREATE TABLE IF NOT EXISTS "Mixed Case" (
+    "Id"    INT,
+    "Value" TEXT
+);
+
+INSERT INTO "Mixed Case"
+    SELECT i, 'value ' || i FROM generate_series(1, 100) AS i;
+
 -- bikeshare_stations table for testing datasources.
 CREATE TABLE IF NOT EXISTS bikeshare_stations (
     station_id        INT,
@@ -1,3 +1,6 @@
+-- Use WAL mode so that scans use separate read only connections.
+PRAGMA journal_mode = WAL;
+
 -- Create datatypes table
 CREATE TABLE IF NOT EXISTS datatypes (
     -- Booleans
@@ -97,6 +100,14 @@ UPDATE bikeshare_stations
     SET city_asset_number = NULL
     WHERE city_asset_number = '';
 
+-- Create dml_test table for testing updates and deletes. Tests populate it
+-- themselves since the database is reused between runs.
+CREATE TABLE IF NOT EXISTS dml_test (
+    id     INT,
+    name   TEXT,
+    amount INT
+);
+
 -- Create bikeshare_trips table
 CREATE TABLE IF NOT EXISTS bikeshare_trips (
     trip_id            BIGINT,
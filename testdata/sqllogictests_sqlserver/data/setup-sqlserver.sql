@@ -83,6 +83,24 @@ VALUES (
 
 INSERT INTO datatypes(c1) VALUES (NULL); -- inserts nulls
 
+IF OBJECT_ID('dbo.dml_test', 'u') IS NOT NULL
+   DROP TABLE dml_test;
+GO
+
+-- dml_test table for testing updates and deletes.
+CREATE TABLE dml_test (
+    id         INT,
+    name       VARCHAR(255),
+    amount     INT,
+    created_at DATETIME
+);
+
+INSERT INTO dml_test VALUES
+    (1, 'a', 10, '2020-01-01 00:00:00'),
+    (2, 'b', 20, '2020-01-01 00:00:00'),
+    (3, 'c', 30, '2999-01-01 00:00:00');
+GO
+
 IF OBJECT_ID('dbo.bikeshare_stations', 'u') IS NOT NULL
    DROP TABLE bikeshare_stations;
 GO
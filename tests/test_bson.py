@@ -126,3 +126,33 @@ def test_null_handling(
         curr.execute(f"select count(*) from '{data_path}'")
         r = curr.fetchone()
         assert r[0] == 110
+
+
+def test_read_bson_union_by_name(
+    glaredb_connection: psycopg2.extensions.connection,
+    tmp_path_factory: pytest.TempPathFactory,
+):
+    tmp_dir = tmp_path_factory.mktemp(basename="union_by_name", numbered=True)
+
+    with open(tmp_dir.joinpath("a.bson"), "wb") as f:
+        for i in range(10):
+            f.write(bson.encode({"id": i, "name": f"name-{i}"}))
+
+    with open(tmp_dir.joinpath("b.bson"), "wb") as f:
+        for i in range(10, 20):
+            f.write(bson.encode({"score": i / 2, "id": i}))
+
+    with glaredb_connection.cursor(cursor_factory=psycopg2.extras.RealDictCursor) as curr:
+        curr.execute(
+            f"select * from read_bson('{tmp_dir}/*.bson', union_by_name => true, schema_sample_size => 5) order by id"
+        )
+        rows = curr.fetchall()
+        assert len(rows) == 20
+        for row in rows:
+            assert set(row.keys()) == {"id", "name", "score"}
+            if row["id"] < 10:
+                assert row["name"] == f"name-{row['id']}"
+                assert row["score"] is None
+            else:
+                assert row["name"] is None
+                assert row["score"] == row["id"] / 2